@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+
+use super::{extract_rules, Rule};
+use crate::parse::{section_at_line, sections, Section};
+
+/// Where a rule of a collected grammar is defined in its document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleLocation {
+    pub name: String,
+    /// Line of the rule in the document (0-based)
+    pub line: usize,
+    /// Label of the innermost section defining the rule (e.g., "Section 5.6.2")
+    pub section: Option<String>,
+    /// Line of the rule in the collected grammar (0-based)
+    pub grammar_line: usize,
+}
+
+/// All ABNF rules of a document as a single grammar, without imports resolved
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectedGrammar {
+    /// The rules as ABNF text, one rule per line with continuation lines indented
+    pub abnf: String,
+    /// Location of every rule, in grammar order
+    pub locations: Vec<RuleLocation>,
+}
+
+impl CollectedGrammar {
+    /// Find the rule at a line of the grammar, e.g. to map an error of an ABNF tool back to the document
+    pub fn locate(&self, grammar_line: usize) -> Option<&RuleLocation> {
+        self.locations
+            .iter()
+            .take_while(|l| l.grammar_line <= grammar_line)
+            .last()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+}
+
+/// Collect the ABNF rules of a document into one grammar
+///
+/// Rules repeated verbatim, such as those of a "Collected ABNF" appendix,
+/// are included once, at their first definition.
+pub fn collect_grammar(text: &str) -> CollectedGrammar {
+    let found = sections(text);
+    let mut kept: Vec<Rule> = Vec::new();
+    let mut grammar = CollectedGrammar::default();
+
+    for rule in extract_rules(text) {
+        let repeated = kept.iter().any(|k| {
+            k.is_named(&rule.name)
+                && k.incremental == rule.incremental
+                && normalize(&k.definition) == normalize(&rule.definition)
+        });
+        if repeated {
+            continue;
+        }
+
+        let op = if rule.incremental { "=/" } else { "=" };
+        let definition = rule.definition.replace('\n', "\n    ");
+        grammar.locations.push(RuleLocation {
+            name: rule.name.clone(),
+            line: rule.line,
+            section: section_at_line(&found, rule.line).map(Section::label),
+            grammar_line: grammar.abnf.lines().count(),
+        });
+        grammar
+            .abnf
+            .push_str(&format!("{} {} {}\n", rule.name, op, definition));
+        kept.push(rule);
+    }
+    grammar
+}
+
+/// Collapse whitespace and drop comments, so reformatted copies of a rule compare equal
+fn normalize(definition: &str) -> String {
+    definition
+        .lines()
+        .map(strip_comment)
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Remove a `;` comment from a line, leaving semicolons in quoted strings
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ';' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "\
+5.  Fields
+
+5.1.  Field Names
+
+     field-name = token
+     token      = 1*tchar
+                  ; at least one character
+
+Appendix A.  Collected ABNF
+
+   field-name = token
+
+   token = 1*tchar
+
+   tchar = \"!\" / \";\" / DIGIT
+";
+
+    #[test]
+    fn test_collect_grammar() {
+        let grammar = collect_grammar(TEXT);
+
+        assert_eq!(
+            grammar.abnf,
+            "field-name = token\ntoken = 1*tchar\n    ; at least one character\n\
+             tchar = \"!\" / \";\" / DIGIT\n"
+        );
+        let names: Vec<&str> = grammar.locations.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, vec!["field-name", "token", "tchar"]);
+        assert_eq!(grammar.locations[1].line, 5);
+        assert_eq!(grammar.locations[1].section.as_deref(), Some("Section 5.1"));
+        assert_eq!(grammar.locations[2].section.as_deref(), Some("Appendix A"));
+        assert_eq!(grammar.locations[2].grammar_line, 3);
+
+        // The comment line belongs to the rule before it
+        assert_eq!(grammar.locate(2).unwrap().name, "token");
+        assert_eq!(grammar.locate(3).unwrap().line, 14);
+    }
+}
@@ -0,0 +1,50 @@
+use super::{extract_rules, Rule};
+use crate::models::DocumentType;
+
+/// The core rules of RFC 5234, Appendix B.1
+const CORE_RULES: &str = r#"
+         ALPHA          =  %x41-5A / %x61-7A   ; A-Z / a-z
+         BIT            =  "0" / "1"
+         CHAR           =  %x01-7F
+         CR             =  %x0D
+         CRLF           =  CR LF
+         CTL            =  %x00-1F / %x7F
+         DIGIT          =  %x30-39
+         DQUOTE         =  %x22
+         HEXDIG         =  DIGIT / "A" / "B" / "C" / "D" / "E" / "F"
+         HTAB           =  %x09
+         LF             =  %x0A
+         LWSP           =  *(WSP / CRLF WSP)
+         OCTET          =  %x00-FF
+         SP             =  %x20
+         VCHAR          =  %x21-7E
+         WSP            =  SP / HTAB
+"#;
+
+/// Get the ABNF core rules (ALPHA, DIGIT, CRLF, ...) defined by RFC 5234
+pub fn core_rules() -> Vec<Rule> {
+    extract_rules(CORE_RULES)
+        .into_iter()
+        .map(|mut rule| {
+            rule.origin = Some(DocumentType::Rfc(5234));
+            rule
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_core_rules() {
+        let rules = core_rules();
+
+        assert_eq!(rules.len(), 16);
+        assert!(rules
+            .iter()
+            .all(|r| r.origin == Some(DocumentType::Rfc(5234))));
+        let alpha = rules.iter().find(|r| r.name == "ALPHA").unwrap();
+        assert_eq!(alpha.definition, "%x41-5A / %x61-7A   ; A-Z / a-z");
+    }
+}
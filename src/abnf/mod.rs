@@ -0,0 +1,11 @@
+mod collect;
+mod core;
+mod resolve;
+mod rules;
+mod validate;
+
+pub use self::core::core_rules;
+pub use collect::{collect_grammar, CollectedGrammar, RuleLocation};
+pub use resolve::{resolve, resolve_grammar, FailedImport, Grammar};
+pub use rules::{extract_rules, Rule};
+pub use validate::{check_syntax, validate, Diagnostic, DiagnosticKind};
@@ -0,0 +1,297 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::{core_rules, extract_rules, Rule};
+use crate::api::DocumentFetcher;
+use crate::models::DocumentType;
+
+/// Maximum number of documents fetched while resolving imports
+const MAX_IMPORTED_DOCUMENTS: usize = 16;
+
+/// A grammar with imported rules resolved
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Grammar {
+    /// Rules of the document followed by the rules imported for it
+    pub rules: Vec<Rule>,
+    /// Referenced rule names that could not be resolved
+    pub unresolved: Vec<String>,
+    /// Imported RFCs that could not be fetched; their rules are missing
+    #[serde(default)]
+    pub failed_imports: Vec<FailedImport>,
+}
+
+/// An RFC whose rules could not be imported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailedImport {
+    pub number: u32,
+    /// Why fetching the RFC failed
+    pub error: String,
+}
+
+impl Grammar {
+    /// Check if every referenced rule is defined
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.unresolved.is_empty()
+    }
+
+    /// Look up a rule by name, ignoring case
+    pub fn rule(&self, name: &str) -> Option<&Rule> {
+        self.rules.iter().find(|r| r.is_named(name))
+    }
+
+    /// Render the grammar as ABNF text, one rule per line
+    pub fn to_abnf(&self) -> String {
+        let mut out = String::new();
+        let mut origin = None;
+
+        for rule in &self.rules {
+            if rule.origin != origin {
+                if let Some(doc) = &rule.origin {
+                    out.push_str(&format!("\n; imported from {}\n", doc));
+                }
+                origin = rule.origin.clone();
+            }
+            let op = if rule.incremental { "=/" } else { "=" };
+            let definition = rule.definition.replace('\n', "\n    ");
+            out.push_str(&format!("{} {} {}\n", rule.name, op, definition));
+        }
+
+        out.trim_start().to_string()
+    }
+}
+
+/// Resolve the rules of a document against rules of other RFCs and the core rules
+///
+/// Rules whose definition is a prose pointer like `<OWS, see [RFC7230]>` are
+/// replaced by the definition from that RFC. Remaining undefined references are
+/// looked up in `imports` (in the given order) and finally in the RFC 5234 core rules.
+pub fn resolve(own: Vec<Rule>, imports: &[(u32, Vec<Rule>)]) -> Grammar {
+    let sources: HashMap<u32, &[Rule]> = imports.iter().map(|(n, r)| (*n, r.as_slice())).collect();
+    let core = core_rules();
+
+    let mut rules: Vec<Rule> = Vec::new();
+    let mut pending: Vec<(String, Option<u32>)> = Vec::new();
+
+    for rule in own {
+        match rule.prose_import() {
+            Some(number) => pending.push((rule.name.clone(), Some(number))),
+            None => rules.push(rule),
+        }
+    }
+    pending.extend(undefined_references(&rules).into_iter().map(|n| (n, None)));
+
+    let mut unresolved: Vec<String> = Vec::new();
+    while let Some((name, preferred)) = pending.pop() {
+        if rules.iter().any(|r| r.is_named(&name))
+            || unresolved.iter().any(|n| n.eq_ignore_ascii_case(&name))
+        {
+            continue;
+        }
+
+        let candidates = preferred
+            .into_iter()
+            .chain(imports.iter().map(|(n, _)| *n))
+            .filter_map(|n| sources.get(&n).map(|rules| (n, *rules)));
+
+        let mut found: Vec<Rule> = Vec::new();
+        for (number, source) in candidates {
+            found = source
+                .iter()
+                .filter(|r| r.is_named(&name) && r.prose_import().is_none())
+                .cloned()
+                .map(|mut r| {
+                    r.origin.get_or_insert(DocumentType::Rfc(number));
+                    r
+                })
+                .collect();
+            if !found.is_empty() {
+                break;
+            }
+        }
+        if found.is_empty() {
+            found = core.iter().filter(|r| r.is_named(&name)).cloned().collect();
+        }
+        if found.is_empty() {
+            unresolved.push(name);
+            continue;
+        }
+
+        // Imported rules may in turn reference rules of their own document
+        let origin = found[0].origin.as_ref().and_then(rfc_number);
+        for rule in &found {
+            pending.extend(rule.references().into_iter().map(|n| (n, origin)));
+        }
+        rules.extend(found);
+    }
+
+    unresolved.sort_by_key(|n| n.to_lowercase());
+    Grammar {
+        rules,
+        unresolved,
+        failed_imports: Vec::new(),
+    }
+}
+
+/// Extract the ABNF of a document and resolve imports by fetching referenced RFCs
+///
+/// RFCs named in prose pointers are fetched automatically; `imports` lists
+/// additional RFCs whose rules are included by reference. RFCs that can't be
+/// fetched are listed in [`Grammar::failed_imports`] and the other imports are
+/// still resolved.
+pub async fn resolve_grammar(
+    text: &str,
+    fetcher: &DocumentFetcher,
+    imports: &[u32],
+) -> Result<Grammar> {
+    let own = extract_rules(text);
+
+    let mut pending: Vec<u32> = imports.iter().rev().copied().collect();
+    pending.extend(own.iter().filter_map(Rule::prose_import));
+
+    let mut sources: Vec<(u32, Vec<Rule>)> = Vec::new();
+    let mut failed_imports: Vec<FailedImport> = Vec::new();
+    while let Some(number) = pending.pop() {
+        if number == 5234
+            || sources.iter().any(|(n, _)| *n == number)
+            || failed_imports.iter().any(|f| f.number == number)
+        {
+            continue;
+        }
+        if sources.len() >= MAX_IMPORTED_DOCUMENTS {
+            break;
+        }
+
+        let fetched = fetcher
+            .fetch(&DocumentType::Rfc(number))
+            .await
+            .and_then(|(content, format)| crate::render::to_text(content, format, 80));
+        let content = match fetched {
+            Ok(content) => content,
+            Err(e) => {
+                failed_imports.push(FailedImport {
+                    number,
+                    error: format!("{:#}", e),
+                });
+                continue;
+            }
+        };
+        let rules = extract_rules(&content);
+        pending.extend(rules.iter().filter_map(Rule::prose_import));
+        sources.push((number, rules));
+    }
+
+    // Explicit imports take precedence over documents found through prose pointers
+    sources.sort_by_key(|(n, _)| imports.iter().position(|i| i == n).unwrap_or(usize::MAX));
+    let mut grammar = resolve(own, &sources);
+    grammar.failed_imports = failed_imports;
+    Ok(grammar)
+}
+
+/// Names referenced by the given rules but not defined by them
+fn undefined_references(rules: &[Rule]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for rule in rules {
+        for name in rule.references() {
+            let defined = rules.iter().any(|r| r.is_named(&name));
+            if !defined && !names.iter().any(|n| n.eq_ignore_ascii_case(&name)) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// Get the RFC number of a document, if it is an RFC
+fn rfc_number(doc: &DocumentType) -> Option<u32> {
+    match doc {
+        DocumentType::Rfc(number) => Some(*number),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::OfflineMode;
+    use crate::cache::CacheManager;
+    use crate::models::Format;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_core_rules() {
+        let own = extract_rules("   token = 1*tchar\n   tchar = ALPHA / DIGIT / \"!\"\n");
+        let grammar = resolve(own, &[]);
+
+        assert!(grammar.is_complete());
+        assert_eq!(grammar.rules.len(), 4);
+        assert_eq!(
+            grammar.rule("alpha").unwrap().origin,
+            Some(DocumentType::Rfc(5234))
+        );
+    }
+
+    #[test]
+    fn test_resolve_prose_import() {
+        let own = extract_rules(
+            "   field = token OWS value\n   OWS = <OWS, see [RFC7230], Section 3.2.3>\n",
+        );
+        let http = extract_rules("   OWS = *( SP / HTAB )\n   token = 1*tchar\n   tchar = ALPHA\n");
+        let grammar = resolve(own, &[(7230, http)]);
+
+        let ows = grammar.rule("OWS").unwrap();
+        assert_eq!(ows.definition, "*( SP / HTAB )");
+        assert_eq!(ows.origin, Some(DocumentType::Rfc(7230)));
+
+        // "token" is found through the explicit import, "value" is missing
+        assert_eq!(
+            grammar.rule("token").unwrap().origin,
+            Some(DocumentType::Rfc(7230))
+        );
+        assert!(grammar.rule("tchar").is_some());
+        assert_eq!(grammar.unresolved, vec!["value".to_string()]);
+        assert!(!grammar.is_complete());
+    }
+
+    #[test]
+    fn test_failed_imports() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        cache
+            .store_document(
+                &DocumentType::Rfc(7230),
+                Format::Text,
+                "   OWS = *( SP / HTAB )\n",
+            )
+            .unwrap();
+        let fetcher = DocumentFetcher::new()
+            .unwrap()
+            .with_cache(cache)
+            .with_offline_mode(OfflineMode::Offline);
+        let text = "   field = OWS token\n   token = <token, see [RFC9110]>\n\
+                    OWS = <OWS, see [RFC7230]>\n";
+
+        // RFC 9110 isn't cached; RFC 7230 is still imported
+        let grammar = tokio_test::block_on(resolve_grammar(text, &fetcher, &[])).unwrap();
+        assert_eq!(
+            grammar.rule("OWS").unwrap().origin,
+            Some(DocumentType::Rfc(7230))
+        );
+        assert_eq!(grammar.failed_imports.len(), 1);
+        assert_eq!(grammar.failed_imports[0].number, 9110);
+        assert_eq!(grammar.unresolved, vec!["token".to_string()]);
+    }
+
+    #[test]
+    fn test_to_abnf() {
+        let own = extract_rules("   greeting = \"hello\" SP name\n   name = 1*ALPHA\n");
+        let grammar = resolve(own, &[]);
+        let abnf = grammar.to_abnf();
+
+        assert!(abnf.starts_with("greeting = \"hello\" SP name\nname = 1*ALPHA\n"));
+        assert!(abnf.contains("; imported from RFC 5234\n"));
+        assert!(abnf.contains("SP = %x20"));
+    }
+}
@@ -0,0 +1,255 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::DocumentType;
+use crate::parse::is_page_artifact;
+
+/// A single ABNF rule definition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rule {
+    /// Rule name as written (rule names are case-insensitive)
+    pub name: String,
+    /// Right-hand side of the rule, with continuation lines joined
+    pub definition: String,
+    /// Whether this is an incremental alternative (`=/`)
+    pub incremental: bool,
+    /// Line index of the rule in its document
+    pub line: usize,
+    /// Document the rule was imported from; None for rules of the document itself
+    pub origin: Option<DocumentType>,
+}
+
+impl Rule {
+    /// Check if this rule has the given name, ignoring case
+    #[must_use]
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+    }
+
+    /// Names of the rules referenced by this rule's definition
+    pub fn references(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        let mut chars = self.definition.char_indices().peekable();
+
+        while let Some((start, c)) = chars.next() {
+            match c {
+                // Skip quoted strings, prose values and comments
+                '"' => skip_until(&mut chars, '"'),
+                '<' => skip_until(&mut chars, '>'),
+                ';' => skip_until(&mut chars, '\n'),
+                // Skip numeric values like %x41-5A
+                '%' => {
+                    while chars
+                        .next_if(|(_, c)| !c.is_whitespace() && !is_delimiter(*c))
+                        .is_some()
+                    {}
+                }
+                c if c.is_ascii_alphabetic() => {
+                    let mut end = start + 1;
+                    while let Some((i, _)) =
+                        chars.next_if(|(_, c)| c.is_ascii_alphanumeric() || *c == '-')
+                    {
+                        end = i + 1;
+                    }
+                    let name = &self.definition[start..end];
+                    if !names.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+                        names.push(name.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        names
+    }
+
+    /// If the definition only points at another document, e.g.
+    /// `<OWS, see [RFC7230], Section 3.2.3>`, get that RFC's number
+    pub fn prose_import(&self) -> Option<u32> {
+        let definition = self.definition.trim();
+        let prose = definition.strip_prefix('<')?.strip_suffix('>')?;
+        let start = prose.find("RFC")? + 3;
+        let digits: String = prose[start..]
+            .trim_start()
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect();
+        digits.parse().ok()
+    }
+}
+
+/// Skip characters up to and including `end`
+fn skip_until(chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>, end: char) {
+    for (_, c) in chars.by_ref() {
+        if c == end {
+            break;
+        }
+    }
+}
+
+/// Characters that separate ABNF elements
+fn is_delimiter(c: char) -> bool {
+    matches!(c, '(' | ')' | '[' | ']' | '/')
+}
+
+/// Extract all ABNF rule definitions from a plain text document
+///
+/// Rules are recognized in artwork: a rule has to start a block, be indented
+/// further than the prose before it, or follow another rule or a comment, so
+/// formulas like "x = y + 1" within a paragraph are not taken for rules.
+pub fn extract_rules(text: &str) -> Vec<Rule> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut rules = Vec::new();
+    let mut i = 0;
+    // Indentation of the prose line before, if a rule can't start right here
+    let mut prose_indent: Option<usize> = None;
+
+    while i < lines.len() {
+        let line = lines[i];
+        if is_page_artifact(line) || line.trim_start().starts_with('\u{c}') {
+            i += 1;
+            continue;
+        }
+        let rule = parse_rule_start(line)
+            .filter(|(indent, ..)| prose_indent.is_none_or(|prose| *indent > prose));
+        let Some((indent, name, incremental, first)) = rule else {
+            let trimmed = line.trim_start();
+            prose_indent = (!trimmed.is_empty() && !trimmed.starts_with(';'))
+                .then(|| line.len() - trimmed.len());
+            i += 1;
+            continue;
+        };
+
+        let start = i;
+        let mut definition = first.trim().to_string();
+        i += 1;
+
+        // Continuation lines are indented further than the rule name
+        while i < lines.len() {
+            let line = lines[i];
+            if is_page_artifact(line) || line.trim_start().starts_with('\u{c}') {
+                i += 1;
+                continue;
+            }
+            let line_indent = line.len() - line.trim_start().len();
+            if line.trim().is_empty() || line_indent <= indent {
+                break;
+            }
+            definition.push('\n');
+            definition.push_str(line.trim());
+            i += 1;
+        }
+
+        rules.push(Rule {
+            name: name.to_string(),
+            definition,
+            incremental,
+            line: start,
+            origin: None,
+        });
+        prose_indent = None;
+    }
+
+    rules
+}
+
+/// Parse the first line of a rule into (indent, name, incremental, rest)
+fn parse_rule_start(line: &str) -> Option<(usize, &str, bool, &str)> {
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+
+    let eq = trimmed.find('=')?;
+    let name = trimmed[..eq].trim_end();
+    if !is_rule_name(name) {
+        return None;
+    }
+
+    let rest = &trimmed[eq + 1..];
+    let (incremental, rest) = match rest.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    // Reject comparisons ("==") and empty definitions
+    if rest.starts_with('=') || rest.trim().is_empty() {
+        return None;
+    }
+
+    Some((indent, name, incremental, rest))
+}
+
+/// Check for a valid ABNF rule name: ALPHA *(ALPHA / DIGIT / "-")
+fn is_rule_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r##"
+   The grammar uses the core rules of [RFC5234].
+
+     URI-reference = URI / relative-ref
+     URI           = scheme ":" hier-part [ "?" query ]
+                     [ "#" fragment ]
+     scheme        = ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )
+     OWS           = <OWS, see [RFC7230], Section 3.2.3>
+     scheme        =/ "urn"   ; added later
+
+   In C, int x = 5; is not a rule, and neither is a == b.
+"##;
+
+    #[test]
+    fn test_extract_rules() {
+        let rules = extract_rules(SAMPLE);
+        let names: Vec<&str> = rules.iter().map(|r| r.name.as_str()).collect();
+
+        assert_eq!(
+            names,
+            vec!["URI-reference", "URI", "scheme", "OWS", "scheme"]
+        );
+        assert_eq!(
+            rules[1].definition,
+            "scheme \":\" hier-part [ \"?\" query ]\n[ \"#\" fragment ]"
+        );
+        assert!(rules[4].incremental);
+        assert_eq!(rules[0].line, 3);
+    }
+
+    #[test]
+    fn test_rules_only_in_artwork() {
+        let text = "   The length is computed as\n   len = size + 1\n   bytes.  The syntax is:\n\
+                    \x20    length = 1*DIGIT\n   ; comment\n   unit = \"B\"\n";
+        let names: Vec<String> = extract_rules(text).into_iter().map(|r| r.name).collect();
+
+        assert_eq!(names, vec!["length", "unit"]);
+    }
+
+    #[test]
+    fn test_references() {
+        let rules = extract_rules(SAMPLE);
+
+        assert_eq!(
+            rules[1].references(),
+            vec!["scheme", "hier-part", "query", "fragment"]
+        );
+        assert_eq!(rules[2].references(), vec!["ALPHA", "DIGIT"]);
+        assert!(rules[3].references().is_empty());
+        assert!(rules[4].references().is_empty());
+    }
+
+    #[test]
+    fn test_numeric_values_not_references() {
+        let rule = extract_rules("   ALPHA = %x41-5A / %x61-7A ; A-Z\n").remove(0);
+        assert!(rule.references().is_empty());
+    }
+
+    #[test]
+    fn test_prose_import() {
+        let rules = extract_rules(SAMPLE);
+
+        assert_eq!(rules[3].prose_import(), Some(7230));
+        assert_eq!(rules[0].prose_import(), None);
+    }
+}
@@ -0,0 +1,367 @@
+use serde::{Deserialize, Serialize};
+
+use super::{core_rules, Grammar, Rule};
+use crate::models::DocumentType;
+
+/// Kind of problem found in a grammar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiagnosticKind {
+    /// The definition does not follow RFC 5234 syntax
+    Syntax,
+    /// A referenced rule is not defined
+    UndefinedRule,
+    /// A rule is defined more than once with `=`
+    DuplicateRule,
+    /// An incremental alternative (`=/`) extends a rule that is never defined
+    OrphanIncrement,
+}
+
+/// A problem found while validating a grammar
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Kind of problem
+    pub kind: DiagnosticKind,
+    /// Name of the rule in which the problem was found
+    pub rule: String,
+    /// Line index of that rule in its document
+    pub line: usize,
+    /// Document the rule was imported from; None for rules of the validated document
+    #[serde(default)]
+    pub origin: Option<DocumentType>,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl Grammar {
+    /// Validate the syntax of every rule and check that all references are defined
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        validate(&self.rules)
+    }
+}
+
+/// Validate a set of rules without resolving imports
+///
+/// References to the RFC 5234 core rules are accepted even if the core rules
+/// are not part of `rules`.
+pub fn validate(rules: &[Rule]) -> Vec<Diagnostic> {
+    let core = core_rules();
+    let mut diagnostics = Vec::new();
+
+    for (i, rule) in rules.iter().enumerate() {
+        let diagnostic = |kind, message| Diagnostic {
+            kind,
+            rule: rule.name.clone(),
+            line: rule.line,
+            origin: rule.origin.clone(),
+            message,
+        };
+
+        if let Err(message) = check_syntax(&rule.definition) {
+            diagnostics.push(diagnostic(DiagnosticKind::Syntax, message));
+        }
+
+        let earlier = &rules[..i];
+        if !rule.incremental
+            && earlier
+                .iter()
+                .any(|r| r.is_named(&rule.name) && !r.incremental)
+        {
+            diagnostics.push(diagnostic(
+                DiagnosticKind::DuplicateRule,
+                format!(
+                    "'{}' is already defined; use '=/' to add alternatives",
+                    rule.name
+                ),
+            ));
+        }
+        if rule.incremental
+            && !rules
+                .iter()
+                .any(|r| r.is_named(&rule.name) && !r.incremental)
+        {
+            diagnostics.push(diagnostic(
+                DiagnosticKind::OrphanIncrement,
+                format!("'{}' is extended with '=/' but never defined", rule.name),
+            ));
+        }
+
+        for name in rule.references() {
+            let defined = rules.iter().chain(core.iter()).any(|r| r.is_named(&name));
+            if !defined {
+                diagnostics.push(diagnostic(
+                    DiagnosticKind::UndefinedRule,
+                    format!("reference to undefined rule '{}'", name),
+                ));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// Check a rule definition against the RFC 5234 grammar
+pub fn check_syntax(definition: &str) -> Result<(), String> {
+    let mut parser = Parser {
+        input: definition.as_bytes(),
+        pos: 0,
+    };
+    parser.skip_whitespace();
+    parser.alternation()?;
+    parser.skip_whitespace();
+
+    match parser.peek() {
+        None => Ok(()),
+        Some(c) => Err(parser.error(&format!("unexpected '{}'", c as char))),
+    }
+}
+
+/// Recursive-descent parser for ABNF element syntax
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("{} at offset {}", message, self.pos)
+    }
+
+    /// Skip whitespace, newlines and comments
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            match c {
+                b' ' | b'\t' | b'\r' | b'\n' => self.pos += 1,
+                b';' => {
+                    while self.peek().is_some_and(|c| c != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn alternation(&mut self) -> Result<(), String> {
+        self.concatenation()?;
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'/') {
+                return Ok(());
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            self.concatenation()?;
+        }
+    }
+
+    fn concatenation(&mut self) -> Result<(), String> {
+        self.repetition()?;
+        loop {
+            let before = self.pos;
+            self.skip_whitespace();
+            match self.peek() {
+                Some(c) if self.pos > before && starts_element(c) => self.repetition()?,
+                _ => {
+                    self.pos = before;
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    fn repetition(&mut self) -> Result<(), String> {
+        self.digits();
+        if self.peek() == Some(b'*') {
+            self.pos += 1;
+            self.digits();
+        }
+        self.element()
+    }
+
+    fn element(&mut self) -> Result<(), String> {
+        match self.peek() {
+            Some(c) if c.is_ascii_alphabetic() => {
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || c == b'-')
+                {
+                    self.pos += 1;
+                }
+                Ok(())
+            }
+            Some(b'(') => self.group(b')'),
+            Some(b'[') => self.group(b']'),
+            Some(b'"') => self.quoted(b'"', "string"),
+            Some(b'<') => self.quoted(b'>', "prose value"),
+            Some(b'%') => self.numeric_or_case(),
+            Some(c) => Err(self.error(&format!("expected element, found '{}'", c as char))),
+            None => Err(self.error("expected element, found end of definition")),
+        }
+    }
+
+    fn group(&mut self, close: u8) -> Result<(), String> {
+        self.pos += 1;
+        self.skip_whitespace();
+        self.alternation()?;
+        self.skip_whitespace();
+        if self.peek() != Some(close) {
+            return Err(self.error(&format!("expected '{}'", close as char)));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn quoted(&mut self, close: u8, what: &str) -> Result<(), String> {
+        let start = self.pos;
+        self.pos += 1;
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            if c == close {
+                return Ok(());
+            }
+            if c == b'\n' {
+                break;
+            }
+        }
+        self.pos = start;
+        Err(self.error(&format!("unterminated {}", what)))
+    }
+
+    /// Parse `%x41-5A`, `%d13.10`, `%b1` or the RFC 7405 `%s"..."` / `%i"..."`
+    fn numeric_or_case(&mut self) -> Result<(), String> {
+        self.pos += 1;
+        let radix = match self.peek().map(|c| c.to_ascii_lowercase()) {
+            Some(b's') | Some(b'i') => {
+                self.pos += 1;
+                return match self.peek() {
+                    Some(b'"') => self.quoted(b'"', "string"),
+                    _ => Err(self.error("expected string after case prefix")),
+                };
+            }
+            Some(b'b') => 2,
+            Some(b'd') => 10,
+            Some(b'x') => 16,
+            _ => return Err(self.error("expected 'b', 'd' or 'x' after '%'")),
+        };
+        self.pos += 1;
+
+        self.value(radix)?;
+        match self.peek() {
+            Some(b'-') => {
+                self.pos += 1;
+                self.value(radix)
+            }
+            Some(b'.') => {
+                while self.peek() == Some(b'.') {
+                    self.pos += 1;
+                    self.value(radix)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn value(&mut self, radix: u32) -> Result<(), String> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| (c as char).is_digit(radix)) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(self.error("expected numeric value"));
+        }
+        Ok(())
+    }
+
+    fn digits(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+    }
+}
+
+/// Check if a character may start a repetition
+fn starts_element(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, b'*' | b'(' | b'[' | b'"' | b'<' | b'%')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abnf::extract_rules;
+
+    #[test]
+    fn test_valid_syntax() {
+        for definition in [
+            "ALPHA *( ALPHA / DIGIT / \"+\" / \"-\" / \".\" )",
+            "1*3DIGIT [ \".\" 1*DIGIT ]",
+            "%x41-5A / %x61-7A ; letters",
+            "%d13.10",
+            "%s\"GET\" / %i\"post\"",
+            "<a prose description>",
+            "scheme \":\" hier-part\n[ \"#\" fragment ] ; comment",
+        ] {
+            assert_eq!(check_syntax(definition), Ok(()), "{}", definition);
+        }
+    }
+
+    #[test]
+    fn test_invalid_syntax() {
+        for definition in [
+            "( ALPHA / DIGIT",
+            "[ ALPHA",
+            "\"unterminated",
+            "ALPHA /",
+            "%q41",
+            "%x",
+            "ALPHA )",
+            "<prose",
+        ] {
+            assert!(check_syntax(definition).is_err(), "{}", definition);
+        }
+    }
+
+    #[test]
+    fn test_validate_references() {
+        let rules = extract_rules("   a = b / ALPHA\n   b = c\n");
+        let diagnostics = validate(&rules);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::UndefinedRule);
+        assert_eq!(diagnostics[0].rule, "b");
+        assert_eq!(diagnostics[0].line, 1);
+    }
+
+    #[test]
+    fn test_imported_rule_location() {
+        let own = extract_rules("   field = value\n   value = <value, see [RFC7230]>\n");
+        let http = extract_rules("   ; comment\n\n   value = ( ALPHA\n");
+        let grammar = crate::abnf::resolve(own, &[(7230, http)]);
+        let diagnostics = grammar.validate();
+
+        // The syntax error is reported at its line in RFC 7230
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::Syntax);
+        assert_eq!(diagnostics[0].origin, Some(DocumentType::Rfc(7230)));
+        assert_eq!(diagnostics[0].line, 2);
+        assert_eq!(validate(&extract_rules("   a = ( b\n"))[0].origin, None);
+    }
+
+    #[test]
+    fn test_validate_duplicates() {
+        let rules = extract_rules("   a = \"x\"\n   A = \"y\"\n   a =/ \"z\"\n   b =/ \"w\"\n");
+        let kinds: Vec<DiagnosticKind> = validate(&rules).iter().map(|d| d.kind).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                DiagnosticKind::DuplicateRule,
+                DiagnosticKind::OrphanIncrement
+            ]
+        );
+    }
+}
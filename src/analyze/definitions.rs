@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::DocumentType;
+use crate::parse::{is_page_artifact, sections};
+
+/// Title words of sections that define terms
+const DEFINITION_SECTIONS: &[&str] = &["terminology", "definitions", "terms", "conventions"];
+
+/// A term definition found in a document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Definition {
+    /// The term as written in the document
+    pub term: String,
+    /// Definition text, joined into a single paragraph
+    pub definition: String,
+    /// Document the definition comes from
+    pub document: DocumentType,
+    /// Label of the defining section (e.g., "Section 1.2")
+    pub section: String,
+    /// Line of the term (0-based)
+    pub line: usize,
+}
+
+/// Look up a term in the Terminology/Definitions sections of a set of documents
+///
+/// Recognizes the usual layouts (`Term:  definition`, `o  Term: definition`,
+/// and a term on its own line followed by an indented definition).
+pub fn define<'a, I>(term: &str, scope: I) -> Vec<Definition>
+where
+    I: IntoIterator<Item = (&'a DocumentType, &'a str)>,
+{
+    let term = term.trim();
+    let mut found = Vec::new();
+    if term.is_empty() {
+        return found;
+    }
+
+    for (doc, text) in scope {
+        let lines: Vec<&str> = text.lines().collect();
+        for section in sections(text).iter().filter(|s| {
+            let title = s.title.to_ascii_lowercase();
+            DEFINITION_SECTIONS.iter().any(|w| title.contains(w))
+        }) {
+            for i in section.start_line + 1..section.end_line.min(lines.len()) {
+                let Some((written, first)) = match_term(lines[i], term) else {
+                    continue;
+                };
+                let definition = collect_definition(&lines[..section.end_line], i, first);
+                if definition.is_empty() {
+                    continue;
+                }
+                found.push(Definition {
+                    term: written,
+                    definition,
+                    document: doc.clone(),
+                    section: section.label(),
+                    line: i,
+                });
+                break;
+            }
+        }
+    }
+
+    found
+}
+
+/// Check if a line starts a definition of `term`, returning the term as written
+/// and the definition text on the same line
+fn match_term<'l>(line: &'l str, term: &str) -> Option<(String, &'l str)> {
+    let content = line.trim_start();
+    if content.len() == line.len() {
+        return None;
+    }
+    let content = ["o ", "* ", "- "]
+        .iter()
+        .find_map(|b| content.strip_prefix(b))
+        .unwrap_or(content)
+        .trim_start();
+    let head = content.get(..term.len())?;
+    if !head.eq_ignore_ascii_case(term) {
+        return None;
+    }
+
+    let rest = &content[term.len()..];
+    let rest = rest.strip_prefix('"').unwrap_or(rest);
+    if let Some(after) = rest.strip_prefix(':') {
+        return Some((head.to_string(), after.trim()));
+    }
+    if rest.trim().is_empty() {
+        return Some((head.to_string(), ""));
+    }
+    // "Term - definition" and "Term   definition" (aligned columns)
+    if let Some(after) = rest.strip_prefix(" - ").or_else(|| rest.strip_prefix("  ")) {
+        return Some((head.to_string(), after.trim()));
+    }
+    None
+}
+
+/// Join the definition text starting at the term line with its continuation lines
+fn collect_definition(lines: &[&str], start: usize, first: &str) -> String {
+    let indent = |l: &str| l.len() - l.trim_start().len();
+    let term_indent = indent(lines[start]);
+    let mut parts: Vec<&str> = Vec::new();
+    if !first.is_empty() {
+        parts.push(first);
+    }
+
+    for line in lines.iter().skip(start + 1) {
+        if is_page_artifact(line) {
+            continue;
+        }
+        if line.trim().is_empty() {
+            if parts.is_empty() {
+                continue;
+            }
+            break;
+        }
+        if indent(line) <= term_indent {
+            break;
+        }
+        parts.push(line.trim());
+    }
+
+    parts.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUIC: &str = "1.  Overview\n\n   Endpoint: not a definition.\n\n\
+1.2.  Terms and Definitions\n\n   \
+Endpoint:  An entity that can participate in a QUIC connection by\n      \
+generating, receiving, and processing QUIC packets.\n\n   \
+Client:  The endpoint that initiates a QUIC connection.\n\n\
+2.  Streams\n\n   Text.\n";
+
+    const TLS: &str = "1.1.  Terminology\n\n   \
+o  endpoint: Either the client or server of the connection.\n\n   \
+sender\n      An endpoint that is transmitting records.\n";
+
+    #[test]
+    fn test_define() {
+        let quic = DocumentType::Rfc(9000);
+        let tls = DocumentType::Rfc(8446);
+        let defs = define("endpoint", [(&quic, QUIC), (&tls, TLS)]);
+
+        assert_eq!(defs.len(), 2);
+        assert_eq!(defs[0].term, "Endpoint");
+        assert_eq!(
+            defs[0].definition,
+            "An entity that can participate in a QUIC connection by generating, receiving, and processing QUIC packets."
+        );
+        assert_eq!(defs[0].section, "Section 1.2");
+        assert_eq!(defs[0].line, 6);
+        assert_eq!(defs[1].document, tls);
+        assert_eq!(
+            defs[1].definition,
+            "Either the client or server of the connection."
+        );
+
+        let sender = define("Sender", [(&tls, TLS)]);
+        assert_eq!(
+            sender[0].definition,
+            "An endpoint that is transmitting records."
+        );
+    }
+
+    #[test]
+    fn test_define_no_match() {
+        let quic = DocumentType::Rfc(9000);
+        assert!(define("stream", [(&quic, QUIC)]).is_empty());
+        assert!(define("end", [(&quic, QUIC)]).is_empty());
+        assert!(define(" ", [(&quic, QUIC)]).is_empty());
+    }
+}
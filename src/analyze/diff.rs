@@ -0,0 +1,296 @@
+use serde::{Deserialize, Serialize};
+
+use crate::parse::{is_page_artifact, sections, Section};
+
+/// A section as it appears in one of the compared documents
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionRef {
+    /// Section number; None for unnumbered sections like "Abstract"
+    pub number: Option<String>,
+    /// Section title
+    pub title: String,
+}
+
+impl SectionRef {
+    pub(super) fn of(section: &Section) -> Self {
+        Self {
+            number: section.number.clone(),
+            title: section.title.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for SectionRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.number {
+            Some(number) => write!(f, "{} {}", number, self.title),
+            None => write!(f, "{}", self.title),
+        }
+    }
+}
+
+/// How a section differs between two documents
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SectionChange {
+    /// The section only exists in the new document
+    Added(SectionRef),
+    /// The section only exists in the old document
+    Removed(SectionRef),
+    /// The section was renumbered or retitled, and possibly modified
+    Renamed {
+        old: SectionRef,
+        new: SectionRef,
+        modified: bool,
+    },
+    /// The section kept its heading but its text changed
+    Modified(SectionRef),
+}
+
+/// A section's heading and its own text, without subsections
+struct Entry {
+    section: Section,
+    body: String,
+}
+
+/// Compare two documents section by section
+///
+/// Sections are matched by title first (so renumbered sections are found),
+/// then by number (so retitled sections are found). Text is compared with
+/// whitespace and page breaks normalized, so reflowed paragraphs don't count
+/// as changes. Changes follow the order of the new document, with removed
+/// sections last.
+pub fn diff_sections(old: &str, new: &str) -> Vec<SectionChange> {
+    let old = entries(old);
+    let new = entries(new);
+    let mut matches: Vec<Option<usize>> = vec![None; new.len()];
+    let mut used = vec![false; old.len()];
+
+    // Same title, preferring the same number when a title repeats
+    for (i, entry) in new.iter().enumerate() {
+        let candidates = || {
+            old.iter().enumerate().filter(|(j, o)| {
+                !used[*j] && o.section.title.eq_ignore_ascii_case(&entry.section.title)
+            })
+        };
+        let found = candidates()
+            .find(|(_, o)| o.section.number == entry.section.number)
+            .or_else(|| candidates().next())
+            .map(|(j, _)| j);
+        if let Some(j) = found {
+            used[j] = true;
+            matches[i] = Some(j);
+        }
+    }
+
+    // Same number, different title
+    for (i, entry) in new.iter().enumerate() {
+        if matches[i].is_some() || entry.section.number.is_none() {
+            continue;
+        }
+        let found = old
+            .iter()
+            .enumerate()
+            .find(|(j, o)| !used[*j] && o.section.number == entry.section.number)
+            .map(|(j, _)| j);
+        if let Some(j) = found {
+            used[j] = true;
+            matches[i] = Some(j);
+        }
+    }
+
+    let mut changes = Vec::new();
+    for (entry, matched) in new.iter().zip(&matches) {
+        let new_ref = SectionRef::of(&entry.section);
+        let Some(j) = *matched else {
+            changes.push(SectionChange::Added(new_ref));
+            continue;
+        };
+        let old_ref = SectionRef::of(&old[j].section);
+        let modified = old[j].body != entry.body;
+        if old_ref != new_ref {
+            changes.push(SectionChange::Renamed {
+                old: old_ref,
+                new: new_ref,
+                modified,
+            });
+        } else if modified {
+            changes.push(SectionChange::Modified(new_ref));
+        }
+    }
+    changes.extend(
+        old.iter()
+            .zip(&used)
+            .filter(|(_, used)| !**used)
+            .map(|(entry, _)| SectionChange::Removed(SectionRef::of(&entry.section))),
+    );
+    changes
+}
+
+fn entries(text: &str) -> Vec<Entry> {
+    let lines: Vec<&str> = text.lines().collect();
+    let sections = sections(text);
+    let starts: Vec<usize> = sections.iter().map(|s| s.start_line).collect();
+
+    sections
+        .into_iter()
+        .enumerate()
+        .map(|(i, section)| {
+            // A section's own text ends where its first subsection begins
+            let end = starts
+                .get(i + 1)
+                .map_or(section.end_line, |&next| next.min(section.end_line))
+                .min(lines.len());
+            let body = lines[(section.start_line + 1).min(end)..end]
+                .iter()
+                .filter(|line| !is_page_artifact(line))
+                .flat_map(|line| line.split_whitespace())
+                .collect::<Vec<_>>()
+                .join(" ");
+            Entry { section, body }
+        })
+        .collect()
+}
+
+/// Show the lines that differ between two texts as a single unified diff hunk
+///
+/// The hunk spans from the first to the last differing line, which is enough
+/// to see how a copy was altered; at most `max_lines` removed and added
+/// lines are shown. Returns an empty string if the lines are the same.
+pub fn diff_lines(old: &str, new: &str, max_lines: usize) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let removed = &old[prefix..old.len() - suffix];
+    let added = &new[prefix..new.len() - suffix];
+    if removed.is_empty() && added.is_empty() {
+        return String::new();
+    }
+
+    let mut out = format!(
+        "@@ -{},{} +{},{} @@\n",
+        prefix + 1,
+        removed.len(),
+        prefix + 1,
+        added.len()
+    );
+    for (sign, lines) in [('-', removed), ('+', added)] {
+        for line in lines.iter().take(max_lines) {
+            out.push(sign);
+            out.push_str(line);
+            out.push('\n');
+        }
+        if lines.len() > max_lines {
+            out.push_str(&format!(
+                "{} ({} more lines)\n",
+                sign,
+                lines.len() - max_lines
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "one\ntwo\nthree\nfour\n";
+        let new = "one\n2\nthree\nfour\nfive\n";
+        assert_eq!(diff_lines(old, old, 10), "");
+        assert_eq!(
+            diff_lines(old, new, 10),
+            "@@ -2,3 +2,4 @@\n-two\n-three\n-four\n+2\n+three\n+four\n+five\n"
+        );
+        assert_eq!(
+            diff_lines(old, "one\n", 1),
+            "@@ -2,3 +2,0 @@\n-two\n- (2 more lines)\n"
+        );
+    }
+
+    const OLD: &str = "\
+Abstract
+
+   This document describes a protocol.
+
+1.  Introduction
+
+   The protocol is fast.
+
+2.  Terminology
+
+   Terms are defined here.
+
+3.  Framing
+
+   Frames are sent.
+
+4.  Legacy Mode
+
+   Old peers are supported.
+";
+
+    const NEW: &str = "\
+Abstract
+
+   This document describes
+   a protocol.
+
+1.  Introduction
+
+   The protocol is fast and secure.
+
+2.  Conventions and Definitions
+
+   Terms are defined here.
+
+3.  Streams
+
+   Streams are ordered.
+
+4.  Framing
+
+   Frames are sent.
+";
+
+    fn heading(number: &str, title: &str) -> SectionRef {
+        SectionRef {
+            number: Some(number.to_string()),
+            title: title.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diff_sections() {
+        assert_eq!(
+            diff_sections(OLD, NEW),
+            vec![
+                SectionChange::Modified(heading("1", "Introduction")),
+                SectionChange::Renamed {
+                    old: heading("2", "Terminology"),
+                    new: heading("2", "Conventions and Definitions"),
+                    modified: false,
+                },
+                SectionChange::Added(heading("3", "Streams")),
+                SectionChange::Renamed {
+                    old: heading("3", "Framing"),
+                    new: heading("4", "Framing"),
+                    modified: false,
+                },
+                SectionChange::Removed(heading("4", "Legacy Mode")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_identical_documents() {
+        assert!(diff_sections(OLD, OLD).is_empty());
+    }
+}
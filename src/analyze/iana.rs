@@ -0,0 +1,388 @@
+use serde::{Deserialize, Serialize};
+
+use crate::parse::{is_page_artifact, sections, tables, Section};
+
+/// Registration policies defined by RFC 8126, Section 4
+const POLICIES: &[&str] = &[
+    "Private Use",
+    "Experimental Use",
+    "Hierarchical Allocation",
+    "First Come First Served",
+    "Expert Review",
+    "Specification Required",
+    "RFC Required",
+    "IETF Review",
+    "Standards Action",
+    "IESG Approval",
+];
+
+/// Phrases used to state that a document asks nothing of IANA
+const NO_ACTION_PHRASES: &[&str] = &[
+    "no iana actions",
+    "no actions for iana",
+    "no requests of iana",
+    "no requests to iana",
+    "no iana considerations",
+    "not require any iana actions",
+];
+
+/// What a document asks IANA to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActionKind {
+    /// Create a new registry
+    CreateRegistry,
+    /// Register values in an existing registry
+    Register,
+    /// Update existing registrations or registry policies
+    Update,
+    /// Anything else (e.g., informational text)
+    Other,
+}
+
+/// An entry to be added to a registry, as heading/value pairs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    pub fields: Vec<(String, String)>,
+}
+
+impl RegistryEntry {
+    /// Get a field value by heading, ignoring case
+    pub fn get(&self, field: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(field))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// A single action requested of IANA
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IanaAction {
+    /// Number of the section describing the action
+    pub section: Option<String>,
+    /// Title of that section
+    pub title: String,
+    /// Kind of action
+    pub kind: ActionKind,
+    /// Name of the affected registry
+    pub registry: Option<String>,
+    /// Registration policy (RFC 8126 terminology)
+    pub policy: Option<String>,
+    /// Entries to be registered
+    pub entries: Vec<RegistryEntry>,
+}
+
+/// The structured content of a document's IANA Considerations section
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IanaConsiderations {
+    /// Number of the IANA Considerations section
+    pub section: Option<String>,
+    /// Whether the document explicitly states it has no IANA actions
+    pub no_actions: bool,
+    /// Requested actions, one per subsection
+    pub actions: Vec<IanaAction>,
+}
+
+/// Extract the IANA Considerations section of a plain text document
+/// Returns None if the document has no such section
+pub fn iana_considerations(text: &str) -> Option<IanaConsiderations> {
+    let lines: Vec<&str> = text.lines().collect();
+    let sections = sections(text);
+    let iana = sections
+        .iter()
+        .find(|s| s.title.to_lowercase().contains("iana considerations"))?;
+
+    let children: Vec<&Section> = sections
+        .iter()
+        .filter(|s| {
+            s.level == iana.level + 1
+                && s.start_line > iana.start_line
+                && s.end_line <= iana.end_line
+        })
+        .collect();
+
+    let full_text = prose(&lines[iana.start_line + 1..iana.end_line]).to_lowercase();
+    let no_actions = NO_ACTION_PHRASES.iter().any(|p| full_text.contains(p));
+
+    let blocks: Vec<&Section> = if children.is_empty() {
+        vec![iana]
+    } else {
+        children
+    };
+
+    let actions = if no_actions {
+        Vec::new()
+    } else {
+        blocks
+            .into_iter()
+            .map(|section| parse_action(section, &lines))
+            .filter(|a| a.kind != ActionKind::Other || !a.entries.is_empty())
+            .collect()
+    };
+
+    Some(IanaConsiderations {
+        section: iana.number.clone(),
+        no_actions,
+        actions,
+    })
+}
+
+/// Parse one (sub)section of the IANA Considerations into an action
+fn parse_action(section: &Section, lines: &[&str]) -> IanaAction {
+    let body = &lines[section.start_line + 1..section.end_line];
+    let text = prose(body);
+    let lower = text.to_lowercase();
+
+    let kind = if [
+        "new registry",
+        "new sub-registry",
+        "added a registry",
+        "create",
+        "establish",
+    ]
+    .iter()
+    .any(|w| lower.contains(w))
+    {
+        ActionKind::CreateRegistry
+    } else if ["register", "added", "add ", "assign", "allocat"]
+        .iter()
+        .any(|w| lower.contains(w))
+    {
+        ActionKind::Register
+    } else if ["update", "modif", "change"]
+        .iter()
+        .any(|w| lower.contains(w))
+    {
+        ActionKind::Update
+    } else {
+        ActionKind::Other
+    };
+
+    let registry = registry_name(&text).or_else(|| {
+        section
+            .title
+            .to_lowercase()
+            .contains("regist")
+            .then(|| section.title.clone())
+    });
+
+    let policy = POLICIES
+        .iter()
+        .filter_map(|p| lower.find(&p.to_lowercase()).map(|pos| (pos, *p)))
+        .min_by_key(|(pos, _)| *pos)
+        .map(|(_, p)| p.to_string());
+
+    let mut entries: Vec<RegistryEntry> = tables(body, section.start_line + 1)
+        .into_iter()
+        .flat_map(|table| {
+            let header = table.header.clone();
+            table.rows.into_iter().map(move |row| RegistryEntry {
+                fields: header.iter().cloned().zip(row).collect(),
+            })
+        })
+        .collect();
+    if entries.is_empty() {
+        entries = template_entries(body);
+    }
+
+    IanaAction {
+        section: section.number.clone(),
+        title: section.title.clone(),
+        kind,
+        registry,
+        policy,
+        entries,
+    }
+}
+
+/// Join body lines into running text, dropping pagination
+fn prose(lines: &[&str]) -> String {
+    lines
+        .iter()
+        .filter(|l| !is_page_artifact(l))
+        .flat_map(|l| l.split_whitespace())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Find a quoted registry name like `the "QUIC Frame Types" registry`
+fn registry_name(text: &str) -> Option<String> {
+    let mut rest = text;
+    let mut before_len = 0;
+
+    while let Some(open) = rest.find('"') {
+        let after_open = &rest[open + 1..];
+        let close = after_open.find('"')?;
+        let name = &after_open[..close];
+        let after = after_open[close + 1..].trim_start().to_lowercase();
+        let before = text[..before_len + open].to_lowercase();
+        let before = before.trim_end();
+
+        let names_registry = after.starts_with("registry")
+            || after.starts_with("sub-registry")
+            || after.starts_with("subregistry")
+            || [
+                "registry entitled",
+                "registry named",
+                "registry called",
+                "registry for",
+                "registry",
+            ]
+            .iter()
+            .any(|p| before.ends_with(p));
+        if names_registry && !name.is_empty() {
+            return Some(name.to_string());
+        }
+
+        let consumed = open + close + 2;
+        before_len += consumed;
+        rest = &rest[consumed..];
+    }
+
+    None
+}
+
+/// Parse registration templates made of "Field: value" lines
+fn template_entries(lines: &[&str]) -> Vec<RegistryEntry> {
+    let mut entries = Vec::new();
+    let mut fields: Vec<(String, String)> = Vec::new();
+    let mut field_indent = 0;
+
+    let mut flush = |fields: &mut Vec<(String, String)>| {
+        if fields.len() >= 2 {
+            entries.push(RegistryEntry {
+                fields: std::mem::take(fields),
+            });
+        }
+        fields.clear();
+    };
+
+    for line in lines.iter().filter(|l| !is_page_artifact(l)) {
+        let indent = line.len() - line.trim_start().len();
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            continue;
+        }
+        if let Some((key, value)) = template_field(trimmed) {
+            // A repeated field starts a new entry
+            if fields.iter().any(|(k, _)| k == key) {
+                flush(&mut fields);
+            }
+            fields.push((key.to_string(), value.to_string()));
+            field_indent = indent;
+        } else if let Some((_, value)) = fields.last_mut().filter(|_| indent > field_indent) {
+            if !value.is_empty() {
+                value.push(' ');
+            }
+            value.push_str(trimmed);
+        } else {
+            flush(&mut fields);
+        }
+    }
+    flush(&mut fields);
+
+    entries
+}
+
+/// Parse a "Field Name: value" line
+fn template_field(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once(':')?;
+    let key_ok = key.len() <= 40
+        && key.starts_with(|c: char| c.is_ascii_uppercase())
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '/' | '(' | ')'));
+    key_ok.then(|| (key.trim(), value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+22.  IANA Considerations
+
+   This document establishes several registries for the management of
+   codepoints in QUIC.
+
+22.1.  QUIC Frame Types Registry
+
+   IANA has added a registry for "QUIC Frame Types" under a "QUIC"
+   heading.  New values are assigned via Specification Required.
+
+   +-------+------------+---------------+
+   | Value | Frame Name | Specification |
+   +=======+============+===============+
+   | 0x00  | PADDING    | Section 19.1  |
+   +-------+------------+---------------+
+   | 0x01  | PING       | Section 19.2  |
+   +-------+------------+---------------+
+
+22.2.  Media Type Registration
+
+   IANA is asked to register the following in the "Media Types" registry:
+
+      Type name: application
+
+      Subtype name: example
+
+      Security considerations: See Section 9 of this document, which
+         covers all of it.
+
+23.  References
+"#;
+
+    #[test]
+    fn test_iana_actions() {
+        let iana = iana_considerations(SAMPLE).unwrap();
+
+        assert_eq!(iana.section.as_deref(), Some("22"));
+        assert!(!iana.no_actions);
+        assert_eq!(iana.actions.len(), 2);
+
+        let frames = &iana.actions[0];
+        assert_eq!(frames.kind, ActionKind::CreateRegistry);
+        assert_eq!(frames.registry.as_deref(), Some("QUIC Frame Types"));
+        assert_eq!(frames.policy.as_deref(), Some("Specification Required"));
+        assert_eq!(frames.entries.len(), 2);
+        assert_eq!(frames.entries[1].get("frame name"), Some("PING"));
+
+        let media = &iana.actions[1];
+        assert_eq!(media.kind, ActionKind::Register);
+        assert_eq!(media.registry.as_deref(), Some("Media Types"));
+        assert_eq!(media.entries.len(), 1);
+        assert_eq!(media.entries[0].get("Subtype name"), Some("example"));
+        assert_eq!(
+            media.entries[0].get("Security considerations"),
+            Some("See Section 9 of this document, which covers all of it.")
+        );
+    }
+
+    #[test]
+    fn test_no_actions() {
+        let text = "8.  IANA Considerations\n\n   This document has no IANA actions.\n";
+        let iana = iana_considerations(text).unwrap();
+
+        assert!(iana.no_actions);
+        assert!(iana.actions.is_empty());
+    }
+
+    #[test]
+    fn test_missing_section() {
+        assert!(iana_considerations("1.  Introduction\n\n   Text.\n").is_none());
+    }
+
+    #[test]
+    fn test_registry_name() {
+        assert_eq!(
+            registry_name(r#"IANA has created a new registry entitled "Foo Parameters"."#),
+            Some("Foo Parameters".to_string())
+        );
+        assert_eq!(
+            registry_name(r#"values in the "TLS ExtensionType Values" registry"#),
+            Some("TLS ExtensionType Values".to_string())
+        );
+        assert_eq!(registry_name(r#"a "quoted" word"#), None);
+    }
+}
@@ -0,0 +1,11 @@
+mod definitions;
+mod diff;
+mod iana;
+mod references;
+mod requirements;
+
+pub use definitions::{define, Definition};
+pub use diff::{diff_lines, diff_sections, SectionChange, SectionRef};
+pub use iana::{iana_considerations, ActionKind, IanaAction, IanaConsiderations, RegistryEntry};
+pub use references::{check_references, ReferenceIssue, ReferenceIssueKind};
+pub use requirements::{requirements, Requirement, RequirementLevel};
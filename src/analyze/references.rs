@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use crate::parse::{citations, references};
+
+/// Kind of mismatch between citations and the References sections
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReferenceIssueKind {
+    /// Cited in the body but not listed in the References
+    Unlisted,
+    /// Listed in the References but never cited in the body
+    Uncited,
+}
+
+/// A citation or reference entry without its counterpart
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferenceIssue {
+    pub kind: ReferenceIssueKind,
+    /// Anchor without brackets (e.g., "RFC8446")
+    pub anchor: String,
+    /// Line of the first citation, or of the reference entry (0-based)
+    pub line: usize,
+}
+
+/// Check that every citation appears in the References and every reference is cited
+pub fn check_references(text: &str) -> Vec<ReferenceIssue> {
+    let listed = references(text);
+    let cited = citations(text);
+    let mut issues: Vec<ReferenceIssue> = Vec::new();
+
+    for citation in &cited {
+        if !listed.iter().any(|r| r.anchor == citation.anchor)
+            && !issues.iter().any(|i| i.anchor == citation.anchor)
+        {
+            issues.push(ReferenceIssue {
+                kind: ReferenceIssueKind::Unlisted,
+                anchor: citation.anchor.clone(),
+                line: citation.line,
+            });
+        }
+    }
+
+    // Entries with other anchors (e.g., "[QUIC-TLS]") are matched textually
+    let body: Vec<&str> = text
+        .lines()
+        .enumerate()
+        .filter(|(i, _)| !listed.iter().any(|r| r.line == *i))
+        .map(|(_, line)| line)
+        .collect();
+    for reference in &listed {
+        let bracketed = format!("[{}]", reference.anchor);
+        let is_cited = cited.iter().any(|c| c.anchor == reference.anchor)
+            || body.iter().any(|line| line.contains(&bracketed));
+        if !is_cited {
+            issues.push(ReferenceIssue {
+                kind: ReferenceIssueKind::Uncited,
+                anchor: reference.anchor.clone(),
+                line: reference.line,
+            });
+        }
+    }
+
+    issues.sort_by_key(|i| i.line);
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_references() {
+        let text =
+            "1.  Introduction\n\n   Uses [RFC8446], [QUIC-TLS] and [I-D.ietf-quic-qlog].\n\n\
+2.  Normative References\n\n\
+   [RFC8446]  Rescorla, E., \"TLS 1.3\", RFC 8446.\n\n\
+   [RFC2119]  Bradner, S., \"Key words\", BCP 14, RFC 2119.\n\n\
+   [QUIC-TLS] Thomson, M., \"Using TLS to Secure QUIC\", RFC 9001.\n";
+        let issues = check_references(text);
+
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].kind, ReferenceIssueKind::Unlisted);
+        assert_eq!(issues[0].anchor, "I-D.ietf-quic-qlog");
+        assert_eq!(issues[0].line, 2);
+        assert_eq!(issues[1].kind, ReferenceIssueKind::Uncited);
+        assert_eq!(issues[1].anchor, "RFC2119");
+        assert_eq!(issues[1].line, 8);
+    }
+}
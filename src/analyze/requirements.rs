@@ -0,0 +1,262 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::SectionRef;
+use crate::lint::BCP14_KEYWORDS;
+use crate::parse::{is_page_artifact, section_at_line, sections, Section};
+
+/// Words ending in a full stop that don't end a sentence
+const ABBREVIATIONS: &[&str] = &["e.g.", "i.e.", "cf.", "al.", "vs.", "Ed.", "Eds."];
+
+/// Strength of a requirement, after the keywords of BCP 14 (RFC 2119, RFC 8174)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequirementLevel {
+    /// MAY, OPTIONAL
+    May,
+    /// SHOULD NOT, NOT RECOMMENDED
+    ShouldNot,
+    /// SHOULD, RECOMMENDED
+    Should,
+    /// MUST NOT, SHALL NOT
+    MustNot,
+    /// MUST, REQUIRED, SHALL
+    Must,
+}
+
+impl RequirementLevel {
+    /// Get the level of a BCP 14 keyword
+    pub fn of(keyword: &str) -> Option<Self> {
+        match keyword {
+            "MUST" | "REQUIRED" | "SHALL" => Some(Self::Must),
+            "MUST NOT" | "SHALL NOT" => Some(Self::MustNot),
+            "SHOULD" | "RECOMMENDED" => Some(Self::Should),
+            "SHOULD NOT" | "NOT RECOMMENDED" => Some(Self::ShouldNot),
+            "MAY" | "OPTIONAL" => Some(Self::May),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RequirementLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Must => "MUST",
+            Self::MustNot => "MUST NOT",
+            Self::Should => "SHOULD",
+            Self::ShouldNot => "SHOULD NOT",
+            Self::May => "MAY",
+        })
+    }
+}
+
+/// A sentence using BCP 14 keywords
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Requirement {
+    /// The strongest requirement of the sentence
+    pub level: RequirementLevel,
+    /// Keywords as written, in order (e.g., ["MUST", "MAY"])
+    pub keywords: Vec<String>,
+    /// The sentence, joined into a single line
+    pub sentence: String,
+    /// Innermost section containing the sentence
+    pub section: Option<SectionRef>,
+    /// Line the sentence starts on (0-based)
+    pub line: usize,
+}
+
+/// Find every sentence that uses BCP 14 keywords, with its section
+///
+/// Sentences are read across line and page breaks. The requirements
+/// language paragraph, which quotes every keyword, is skipped.
+pub fn requirements(text: &str) -> Vec<Requirement> {
+    let found = sections(text);
+    let mut requirements = Vec::new();
+
+    for paragraph in paragraphs(text, &found) {
+        for (line, sentence) in split_sentences(&paragraph.lines) {
+            if sentence.contains("are to be interpreted as described in") {
+                continue;
+            }
+            let keywords = keywords(&sentence);
+            let Some(level) = keywords
+                .iter()
+                .filter_map(|k| RequirementLevel::of(k))
+                .max()
+            else {
+                continue;
+            };
+            requirements.push(Requirement {
+                level,
+                keywords,
+                sentence,
+                section: section_at_line(&found, line).map(SectionRef::of),
+                line,
+            });
+        }
+    }
+
+    requirements
+}
+
+/// Lines of a paragraph, with their line numbers
+struct Paragraph<'a> {
+    lines: Vec<(usize, &'a str)>,
+}
+
+/// Split the body of a document into paragraphs, joining those broken by a page break
+fn paragraphs<'a>(text: &'a str, sections: &[Section]) -> Vec<Paragraph<'a>> {
+    let lines: Vec<&str> = text.lines().collect();
+    let is_break = |line: &str| is_page_artifact(line) || line.trim() == "\u{c}";
+    let mut paragraphs = Vec::new();
+    let mut current: Vec<(usize, &str)> = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        if is_break(line) {
+            continue;
+        }
+        let heading = sections.iter().any(|s| s.start_line == i);
+        if !heading && !line.trim().is_empty() {
+            current.push((i, line.trim()));
+            continue;
+        }
+        // Blank lines around a page break don't end the paragraph
+        let next = lines[i + 1..].iter().find(|l| !l.trim().is_empty());
+        let previous = lines[..i].iter().rev().find(|l| !l.trim().is_empty());
+        if !heading && (next.is_some_and(|l| is_break(l)) || previous.is_some_and(|l| is_break(l)))
+        {
+            continue;
+        }
+        if !current.is_empty() {
+            paragraphs.push(Paragraph {
+                lines: std::mem::take(&mut current),
+            });
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(Paragraph { lines: current });
+    }
+    paragraphs
+}
+
+/// Split the lines of a paragraph into sentences, each with the line it starts on
+fn split_sentences(lines: &[(usize, &str)]) -> Vec<(usize, String)> {
+    let mut sentences = Vec::new();
+    let mut sentence = String::new();
+    let mut start = 0;
+
+    for &(i, line) in lines {
+        for word in line.split_whitespace() {
+            if sentence.is_empty() {
+                start = i;
+            } else {
+                sentence.push(' ');
+            }
+            sentence.push_str(word);
+            if ends_sentence(word) {
+                sentences.push((start, std::mem::take(&mut sentence)));
+            }
+        }
+    }
+    if !sentence.is_empty() {
+        sentences.push((start, sentence));
+    }
+    sentences
+}
+
+fn ends_sentence(word: &str) -> bool {
+    let word = word.trim_end_matches(['"', '\'', ')']);
+    word.ends_with(['.', '?', '!']) && !ABBREVIATIONS.contains(&word)
+}
+
+/// Get the BCP 14 keywords of a sentence in order, preferring "MUST NOT" over "MUST"
+fn keywords(sentence: &str) -> Vec<String> {
+    let words: Vec<&str> = sentence
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let mut found = Vec::new();
+    let mut i = 0;
+    while i < words.len() {
+        if let Some(pair) = words
+            .get(i + 1)
+            .map(|next| format!("{} {}", words[i], next))
+        {
+            if BCP14_KEYWORDS.contains(&pair.as_str()) {
+                found.push(pair);
+                i += 2;
+                continue;
+            }
+        }
+        if BCP14_KEYWORDS.contains(&words[i]) {
+            found.push(words[i].to_string());
+        }
+        i += 1;
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "\
+1.  Introduction
+
+   The key words \"MUST\", \"MUST NOT\", \"REQUIRED\", \"SHALL\", \"SHALL NOT\",
+   \"SHOULD\", \"SHOULD NOT\", \"RECOMMENDED\", \"NOT RECOMMENDED\", \"MAY\", and
+   \"OPTIONAL\" in this document are to be interpreted as described in
+   BCP 14 [RFC2119] [RFC8174] when, and only when, they appear in all
+   capitals, as shown here.
+
+2.  Streams
+
+2.1.  Stream Types
+
+   Endpoints MUST NOT send data on a closed stream.  A receiver MAY,
+   e.g. after a timeout, close the stream; it SHOULD
+
+Iyengar & Thomson            Standards Track                    [Page 7]
+\u{c}
+RFC 9000                    QUIC Transport                      May 2021
+
+
+   send a RESET_STREAM frame first.  Stream IDs are 62 bits.
+
+3.  Security Considerations
+
+   Use of TLS is NOT RECOMMENDED to be skipped.
+";
+
+    #[test]
+    fn test_requirements() {
+        let found = requirements(TEXT);
+
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].level, RequirementLevel::MustNot);
+        assert_eq!(
+            found[0].sentence,
+            "Endpoints MUST NOT send data on a closed stream."
+        );
+        assert_eq!(found[0].line, 12);
+        let section = found[0].section.as_ref().unwrap();
+        assert_eq!(section.number.as_deref(), Some("2.1"));
+        assert_eq!(section.title, "Stream Types");
+
+        // Sentences continue across abbreviations and page breaks
+        assert_eq!(
+            found[1].sentence,
+            "A receiver MAY, e.g. after a timeout, close the stream; it SHOULD send \
+             a RESET_STREAM frame first."
+        );
+        assert_eq!(found[1].keywords, vec!["MAY", "SHOULD"]);
+        assert_eq!(found[1].level, RequirementLevel::Should);
+
+        assert_eq!(found[2].keywords, vec!["NOT RECOMMENDED"]);
+        assert_eq!(
+            found[2].section.as_ref().unwrap().to_string(),
+            "3 Security Considerations"
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use anyhow::{anyhow, Context, Result};
+
+use super::retry::status_error;
+use super::DocumentFetcher;
+use crate::cache::CacheManager;
+use crate::models::DocumentType;
+use crate::profile::{self, Phase};
+use crate::Error;
+
+/// Get the file name the bibxml service publishes a document's reference
+/// under (e.g., "reference.RFC.9000.xml", "reference.I-D.ietf-quic-transport.xml")
+///
+/// Drafts without a revision get the reference to their latest revision;
+/// with one, the reference to that revision. Subseries and external
+/// specifications have no reference.
+pub fn bibxml_file_name(doc: &DocumentType) -> crate::Result<String> {
+    match doc {
+        DocumentType::Rfc(number) => Ok(format!("reference.RFC.{}.xml", number)),
+        DocumentType::Draft(name) if doc.draft_revision().is_some() => {
+            Ok(format!("reference.I-D.{}.xml", name))
+        }
+        DocumentType::Draft(name) => Ok(format!(
+            "reference.I-D.{}.xml",
+            name.strip_prefix("draft-").unwrap_or(name)
+        )),
+        _ => Err(anyhow!("{} has no bibxml reference", doc).into()),
+    }
+}
+
+/// Get the path of a document's reference on the bibxml service
+fn bibxml_path(doc: &DocumentType) -> Result<String> {
+    let directory = match doc {
+        DocumentType::Draft(_) => "bibxml3",
+        _ => "bibxml",
+    };
+    Ok(format!("/{}/{}", directory, bibxml_file_name(doc)?))
+}
+
+impl DocumentFetcher {
+    /// Fetch a document's xml2rfc `<reference>` from the IETF bibxml service,
+    /// for including in a draft
+    pub async fn bibxml_reference(&self, doc: &DocumentType) -> crate::Result<String> {
+        let url = self.sources.bibxml_url(&bibxml_path(doc)?);
+        let response = self
+            .send(&url, self.client.get(&url))
+            .await
+            .context("Failed to query the bibxml service")?;
+
+        if !response.status().is_success() {
+            return Err(status_error(&url, &response, Some(doc)));
+        }
+
+        let xml = profile::time_async(Phase::Network, response.text())
+            .await
+            .context("Failed to read bibxml reference")?;
+        if !xml.contains("<reference") {
+            return Err(Error::Parse(anyhow!("{} is not an xml2rfc reference", url)));
+        }
+        Ok(xml)
+    }
+
+    /// Get a document's bibxml reference from the cache, fetching and caching it if missing
+    ///
+    /// The reference of a draft without a revision points to whatever
+    /// revision is the latest, so it is always fetched and never cached.
+    pub async fn bibxml_reference_cached(
+        &self,
+        doc: &DocumentType,
+        cache: &CacheManager,
+    ) -> crate::Result<String> {
+        let latest = matches!(doc, DocumentType::Draft(_)) && doc.draft_revision().is_none();
+        if latest {
+            return self.bibxml_reference(doc).await;
+        }
+        if let Some(xml) = cache.get_bibxml(doc) {
+            return Ok(xml);
+        }
+        let xml = self.bibxml_reference(doc).await?;
+        cache.store_bibxml(doc, &xml)?;
+        Ok(xml)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Subseries;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bibxml_path() {
+        assert_eq!(
+            bibxml_path(&DocumentType::Rfc(9000)).unwrap(),
+            "/bibxml/reference.RFC.9000.xml"
+        );
+        assert_eq!(
+            bibxml_path(&DocumentType::Draft(
+                "draft-ietf-quic-transport".to_string()
+            ))
+            .unwrap(),
+            "/bibxml3/reference.I-D.ietf-quic-transport.xml"
+        );
+        assert_eq!(
+            bibxml_file_name(&DocumentType::Draft(
+                "draft-ietf-quic-transport-34".to_string()
+            ))
+            .unwrap(),
+            "reference.I-D.draft-ietf-quic-transport-34.xml"
+        );
+        assert!(bibxml_file_name(&DocumentType::Subseries(Subseries::Bcp, 14)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cached_reference_is_not_fetched() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let doc = DocumentType::Rfc(9000);
+        let xml = "<reference anchor=\"RFC9000\"/>";
+        cache.store_bibxml(&doc, xml).unwrap();
+
+        let fetcher = DocumentFetcher::new()
+            .unwrap()
+            .with_sources(crate::api::SourceConfig::default().with_bibxml("http://127.0.0.1:9"));
+        assert_eq!(
+            fetcher.bibxml_reference_cached(&doc, &cache).await.unwrap(),
+            xml
+        );
+
+        // The reference to the latest revision of a draft goes stale, so it is always fetched
+        let latest = DocumentType::Draft("draft-ietf-quic-qlog".to_string());
+        cache.store_bibxml(&latest, "<reference/>").unwrap();
+        assert!(fetcher
+            .bibxml_reference_cached(&latest, &cache)
+            .await
+            .is_err());
+    }
+}
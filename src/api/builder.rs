@@ -0,0 +1,219 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use reqwest::{Certificate, Client, NoProxy, Proxy};
+
+use super::{DataTrackerClient, DocumentFetcher};
+
+/// Timeout of every request unless configured otherwise
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Proxy schemes that need the `socks` feature
+const SOCKS_SCHEMES: [&str; 4] = ["socks4://", "socks4a://", "socks5://", "socks5h://"];
+
+/// How the HTTP clients reach the network: proxies, trusted certificates and timeouts
+///
+/// By default requests go through the proxies in `HTTP_PROXY`, `HTTPS_PROXY`
+/// and `ALL_PROXY`, except for hosts listed in `NO_PROXY`, and are verified
+/// against the system's root certificates.
+#[derive(Debug, Clone)]
+pub struct FetcherBuilder {
+    /// Proxy for every request, overriding the environment
+    proxy: Option<String>,
+    /// Ignore the proxy environment variables
+    direct: bool,
+    /// PEM files of additional trusted root certificates
+    root_certificates: Vec<PathBuf>,
+    accept_invalid_certs: bool,
+    timeout: Duration,
+}
+
+impl Default for FetcherBuilder {
+    fn default() -> Self {
+        Self {
+            proxy: None,
+            direct: false,
+            root_certificates: Vec::new(),
+            accept_invalid_certs: false,
+            timeout: TIMEOUT,
+        }
+    }
+}
+
+impl FetcherBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Read the configuration from the environment
+    ///
+    /// `RFC_PROXY` overrides the standard proxy variables, `RFC_CA_CERT` names
+    /// a PEM file of additional root certificates, and `RFC_INSECURE_TLS=1`
+    /// disables certificate verification.
+    pub fn from_env() -> Self {
+        let mut builder = Self::new();
+        if let Some(proxy) = env_value("RFC_PROXY") {
+            builder = builder.with_proxy(proxy);
+        }
+        if let Some(path) = env_value("RFC_CA_CERT") {
+            builder = builder.with_root_certificate(path);
+        }
+        if env_value("RFC_INSECURE_TLS").is_some_and(|v| matches!(v.as_str(), "1" | "true" | "yes"))
+        {
+            builder = builder.danger_accept_invalid_certs();
+        }
+        builder
+    }
+
+    /// Send every request through a proxy (e.g., "http://proxy:3128" or "socks5h://localhost:1080")
+    ///
+    /// Hosts listed in `NO_PROXY` are still reached directly. SOCKS proxies
+    /// need the `socks` feature.
+    pub fn with_proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self.direct = false;
+        self
+    }
+
+    /// Connect directly, ignoring the proxy environment variables
+    pub fn without_proxy(mut self) -> Self {
+        self.proxy = None;
+        self.direct = true;
+        self
+    }
+
+    /// Trust the root certificates in a PEM file in addition to the system's
+    pub fn with_root_certificate(mut self, path: impl Into<PathBuf>) -> Self {
+        self.root_certificates.push(path.into());
+        self
+    }
+
+    /// Accept any server certificate, e.g. behind a TLS-intercepting corporate proxy
+    ///
+    /// This makes every connection open to interception; prefer trusting the
+    /// proxy's certificate with [`with_root_certificate`](Self::with_root_certificate).
+    pub fn danger_accept_invalid_certs(mut self) -> Self {
+        self.accept_invalid_certs = true;
+        self
+    }
+
+    /// Give up on requests taking longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Build the configured HTTP client
+    pub(super) fn client(&self) -> Result<Client> {
+        let mut builder = Client::builder()
+            .user_agent(concat!("rfc-cli/", env!("CARGO_PKG_VERSION")))
+            .timeout(self.timeout)
+            .danger_accept_invalid_certs(self.accept_invalid_certs);
+        if let Some(url) = &self.proxy {
+            builder = builder.proxy(proxy(url)?);
+        } else if self.direct {
+            builder = builder.no_proxy();
+        }
+        for path in &self.root_certificates {
+            for certificate in root_certificates(path)? {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        builder.build().context("Failed to create HTTP client")
+    }
+
+    /// Build a client for the RFC Editor
+    pub fn build(&self) -> crate::Result<DocumentFetcher> {
+        Ok(DocumentFetcher::from_client(self.client()?))
+    }
+
+    /// Build a client for the Datatracker API
+    pub fn build_datatracker(&self) -> crate::Result<DataTrackerClient> {
+        Ok(DataTrackerClient::from_client(self.client()?))
+    }
+}
+
+/// Get a non-empty environment variable
+fn env_value(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Parse a proxy URL, keeping the hosts excluded by `NO_PROXY`
+fn proxy(url: &str) -> Result<Proxy> {
+    let lower = url.to_ascii_lowercase();
+    if cfg!(not(feature = "socks")) && SOCKS_SCHEMES.iter().any(|s| lower.starts_with(s)) {
+        anyhow::bail!(
+            "SOCKS proxy '{}' needs rfc built with the `socks` feature",
+            url
+        );
+    }
+    let proxy = Proxy::all(url).with_context(|| format!("Invalid proxy '{}'", url))?;
+    Ok(proxy.no_proxy(NoProxy::from_env()))
+}
+
+/// Read the certificates of a PEM file
+fn root_certificates(path: &Path) -> Result<Vec<Certificate>> {
+    let pem = std::fs::read(path)
+        .with_context(|| format!("Failed to read certificates {}", path.display()))?;
+    let certificates = Certificate::from_pem_bundle(&pem)
+        .with_context(|| format!("Invalid certificates in {}", path.display()))?;
+    if certificates.is_empty() {
+        anyhow::bail!("No certificates found in {}", path.display());
+    }
+    Ok(certificates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_proxies() {
+        assert!(FetcherBuilder::new()
+            .with_proxy("http://proxy.example:3128")
+            .build()
+            .is_ok());
+        assert!(FetcherBuilder::new().without_proxy().build().is_ok());
+        assert!(FetcherBuilder::new()
+            .with_proxy("http://[bad")
+            .build_datatracker()
+            .is_err());
+
+        let socks = FetcherBuilder::new()
+            .with_proxy("socks5h://localhost:1080")
+            .build();
+        #[cfg(feature = "socks")]
+        assert!(socks.is_ok());
+        #[cfg(not(feature = "socks"))]
+        assert!(socks.err().unwrap().to_string().contains("`socks` feature"));
+    }
+
+    #[test]
+    fn test_root_certificates() {
+        let temp = TempDir::new().unwrap();
+        let missing = temp.path().join("missing.pem");
+        let err = FetcherBuilder::new()
+            .with_root_certificate(&missing)
+            .build()
+            .err()
+            .unwrap();
+        assert!(err.to_string().starts_with("Failed to read certificates"));
+
+        let empty = temp.path().join("empty.pem");
+        std::fs::write(&empty, "not a certificate\n").unwrap();
+        assert!(FetcherBuilder::new()
+            .with_root_certificate(&empty)
+            .build()
+            .is_err());
+
+        assert!(FetcherBuilder::new()
+            .danger_accept_invalid_certs()
+            .build()
+            .is_ok());
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use reqwest::{RequestBuilder, Response};
+
+/// Consecutive failures after which a host is skipped
+const FAILURE_THRESHOLD: u32 = 3;
+/// How long a host is skipped before it is tried again
+const COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Failure bookkeeping for one host
+#[derive(Debug, Default, Clone, Copy)]
+struct HostState {
+    failures: u32,
+    open_until: Option<Instant>,
+}
+
+/// Per-host circuit breaker shared by the HTTP clients
+///
+/// After `threshold` consecutive connection errors or server errors (5xx),
+/// requests to the host fail immediately until the cooldown has passed. The
+/// next request after the cooldown is let through as a probe; a success
+/// closes the circuit, a failure opens it again.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    hosts: Mutex<HashMap<String, HostState>>,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(FAILURE_THRESHOLD, COOLDOWN)
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold: threshold.max(1),
+            cooldown,
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the process-wide breaker used by default by all clients
+    pub fn shared() -> Arc<CircuitBreaker> {
+        static SHARED: OnceLock<Arc<CircuitBreaker>> = OnceLock::new();
+        SHARED.get_or_init(Default::default).clone()
+    }
+
+    /// Fail fast if the host of a URL is currently skipped
+    pub fn check(&self, url: &str) -> crate::Result<()> {
+        let host = host_of(url);
+        let hosts = self.hosts.lock().unwrap();
+        if let Some(open_until) = hosts.get(&host).and_then(|s| s.open_until) {
+            let now = Instant::now();
+            if now < open_until {
+                return Err(crate::Error::Network(anyhow::anyhow!(
+                    "{} is unavailable after {} consecutive failures; not retrying for {}s",
+                    host,
+                    hosts[&host].failures,
+                    (open_until - now).as_secs() + 1
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Record a successful response from the host of a URL
+    pub fn record_success(&self, url: &str) {
+        self.hosts.lock().unwrap().remove(&host_of(url));
+    }
+
+    /// Record a failed request to the host of a URL
+    pub fn record_failure(&self, url: &str) {
+        let mut hosts = self.hosts.lock().unwrap();
+        let state = hosts.entry(host_of(url)).or_default();
+        state.failures += 1;
+        if state.failures >= self.threshold {
+            state.open_until = Some(Instant::now() + self.cooldown);
+        }
+    }
+
+    /// Record a response from the host of a URL: a server error is a failure
+    pub fn record_response(&self, url: &str, response: &Response) {
+        if response.status().is_server_error() {
+            self.record_failure(url);
+        } else {
+            self.record_success(url);
+        }
+    }
+
+    /// Send a request through the breaker, recording its outcome
+    pub async fn send(&self, url: &str, request: RequestBuilder) -> crate::Result<Response> {
+        self.check(url)?;
+        match request.send().await {
+            Ok(response) => {
+                self.record_response(url, &response);
+                Ok(response)
+            }
+            Err(e) => {
+                self.record_failure(url);
+                Err(crate::Error::Network(e.into()))
+            }
+        }
+    }
+}
+
+/// Get the host of a URL, or the URL itself if it can't be parsed
+pub(super) fn host_of(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_string))
+        .unwrap_or_else(|| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+        let url = "https://www.rfc-editor.org/rfc/rfc9000.txt";
+
+        breaker.record_failure(url);
+        assert!(breaker.check(url).is_ok());
+        breaker.record_failure("https://www.rfc-editor.org/rfc/rfc8446.txt");
+
+        let err = breaker.check(url).unwrap_err().to_string();
+        assert!(err.starts_with("www.rfc-editor.org is unavailable after 2 consecutive failures"));
+        // Other hosts are unaffected
+        assert!(breaker
+            .check("https://www.ietf.org/rfc/rfc9000.txt")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_success_resets() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+        let url = "https://datatracker.ietf.org/doc/rfc9000/doc.json";
+
+        breaker.record_failure(url);
+        breaker.record_success(url);
+        breaker.record_failure(url);
+        assert!(breaker.check(url).is_ok());
+    }
+
+    #[test]
+    fn test_cooldown_expires() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        let url = "https://www.rfc-editor.org/errata.json";
+
+        breaker.record_failure(url);
+        assert!(breaker.check(url).is_ok());
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::Context;
+
+use super::DocumentFetcher;
+use crate::models::{Cluster, DocumentType};
+
+/// Listing of every cluster with its member documents, on the RFC Editor
+const CLUSTERS_PATH: &str = "/all_clusters.php";
+
+impl DocumentFetcher {
+    /// Fetch all RFC Editor clusters
+    pub async fn clusters(&self) -> crate::Result<Vec<Cluster>> {
+        let url = self.sources.rfc_editor_url(CLUSTERS_PATH);
+        let html = self
+            .fetch_content(&url)
+            .await
+            .context("Failed to fetch cluster list")?;
+        Ok(parse_clusters(&html))
+    }
+
+    /// Find the cluster a document was (or is being) published in
+    /// Returns None if the document isn't part of a cluster
+    pub async fn cluster_of(&self, doc: &DocumentType) -> crate::Result<Option<Cluster>> {
+        Ok(self
+            .clusters()
+            .await?
+            .into_iter()
+            .find(|cluster| cluster.contains(doc)))
+    }
+}
+
+/// Extract clusters from the cluster listing page
+///
+/// Every cluster ID ("C310") starts a cluster; the RFCs ("RFC 9000",
+/// "rfc9000") and drafts mentioned after it, up to the next ID, are its members.
+fn parse_clusters(html: &str) -> Vec<Cluster> {
+    let tokens: Vec<&str> = html
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    let mut clusters: Vec<Cluster> = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        i += 1;
+
+        if is_cluster_id(token) {
+            // IDs appear both in links and as link text
+            if clusters.last().is_none_or(|c| c.id != token) {
+                clusters.push(Cluster {
+                    id: token.to_string(),
+                    documents: Vec::new(),
+                });
+            }
+            continue;
+        }
+        let Some(cluster) = clusters.last_mut() else {
+            continue;
+        };
+
+        let lower = token.to_ascii_lowercase();
+        let doc = if lower.starts_with("draft-") {
+            Some(DocumentType::Draft(lower.trim_end_matches('-').to_string()))
+        } else if let Some(number) = lower.strip_prefix("rfc").filter(|n| !n.is_empty()) {
+            number.parse().ok().map(DocumentType::Rfc)
+        } else if lower == "rfc" {
+            let number = tokens.get(i).and_then(|n| n.parse().ok());
+            if number.is_some() {
+                i += 1;
+            }
+            number.map(DocumentType::Rfc)
+        } else {
+            None
+        };
+        if let Some(doc) = doc {
+            if !cluster.documents.contains(&doc) {
+                cluster.documents.push(doc);
+            }
+        }
+    }
+    clusters
+}
+
+/// Check if a token is a cluster ID ("C" followed by digits)
+fn is_cluster_id(token: &str) -> bool {
+    token
+        .strip_prefix('C')
+        .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LISTING: &str = r#"<html><body><h1>All Clusters</h1>
+<table>
+<tr><td><a href="cluster_info.php?cid=C310">C310</a></td>
+<td><a href="/info/rfc8999">RFC 8999</a><br>
+<a href="/info/rfc9000">RFC 9000</a><br>
+<a href="https://datatracker.ietf.org/doc/draft-ietf-quic-http/">draft-ietf-quic-http</a></td></tr>
+<tr><td><a href="cluster_info.php?cid=C311">C311</a></td>
+<td><a href="/info/rfc9110">rfc9110</a> <a href="/info/rfc9110">RFC 9110</a></td></tr>
+</table></body></html>"#;
+
+    #[test]
+    fn test_parse_clusters() {
+        let clusters = parse_clusters(LISTING);
+
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].id, "C310");
+        assert_eq!(
+            clusters[0].documents,
+            vec![
+                DocumentType::Rfc(8999),
+                DocumentType::Rfc(9000),
+                DocumentType::Draft("draft-ietf-quic-http".to_string()),
+            ]
+        );
+        assert_eq!(clusters[1].documents, vec![DocumentType::Rfc(9110)]);
+    }
+
+    #[test]
+    fn test_ignores_text_before_first_cluster() {
+        let clusters = parse_clusters("See RFC 2026 for the process. C1: RFC 1 RFC 2");
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(
+            clusters[0].documents,
+            vec![DocumentType::Rfc(1), DocumentType::Rfc(2)]
+        );
+    }
+}
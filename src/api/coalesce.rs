@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::OnceCell;
+
+use crate::{Error, Result};
+
+/// The shared outcome of one download
+type Outcome = std::result::Result<Arc<Vec<u8>>, Arc<Error>>;
+
+/// Deduplicates concurrent downloads of the same URL
+///
+/// The first caller for a key performs the request; callers that arrive while
+/// it is in flight wait for it and receive the same bytes (or the same error).
+/// Once a request completes the key is forgotten, so later calls fetch again.
+#[derive(Debug, Default)]
+pub struct Coalescer {
+    inflight: Mutex<HashMap<String, Arc<OnceCell<Outcome>>>>,
+}
+
+impl Coalescer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Run `fetch` for `key`, unless a fetch for the same key is already running
+    pub async fn run<F, Fut>(&self, key: &str, fetch: F) -> Result<Arc<Vec<u8>>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = anyhow::Result<Vec<u8>>>,
+    {
+        let cell = self
+            .inflight
+            .lock()
+            .unwrap()
+            .entry(key.to_string())
+            .or_default()
+            .clone();
+
+        let outcome = cell
+            .get_or_init(|| async { fetch().await.map(Arc::new).map_err(|e| Arc::new(e.into())) })
+            .await
+            .clone();
+
+        // Whoever finishes first forgets the key; if the leading caller was
+        // cancelled, a waiting caller has taken over the request
+        let mut inflight = self.inflight.lock().unwrap();
+        if inflight.get(key).is_some_and(|c| Arc::ptr_eq(c, &cell)) {
+            inflight.remove(key);
+        }
+        drop(inflight);
+        outcome.map_err(|e| e.replicate())
+    }
+
+    /// Number of requests currently in flight
+    pub fn in_flight(&self) -> usize {
+        self.inflight.lock().unwrap().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_concurrent_fetches_share_one_request() {
+        let coalescer = Arc::new(Coalescer::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let coalescer = coalescer.clone();
+                let calls = calls.clone();
+                tokio::spawn(async move {
+                    coalescer
+                        .run("https://example.org/rfc9000.txt", || async move {
+                            calls.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                            Ok(b"QUIC".to_vec())
+                        })
+                        .await
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            assert_eq!(task.await.unwrap().unwrap().as_slice(), b"QUIC");
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(coalescer.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_shared_and_not_cached() {
+        let coalescer = Coalescer::new();
+
+        let failed = coalescer
+            .run("key", || async { anyhow::bail!("HTTP 503") })
+            .await;
+        assert_eq!(failed.unwrap_err().to_string(), "HTTP 503");
+
+        let retried = coalescer.run("key", || async { Ok(vec![1]) }).await;
+        assert_eq!(retried.unwrap().as_slice(), &[1]);
+    }
+}
@@ -1,58 +1,178 @@
-use std::time::Duration;
+use std::sync::Arc;
 
 use anyhow::{Context, Result};
-use reqwest::Client;
+use futures::stream::{self, TryStreamExt};
+use reqwest::{Client, RequestBuilder, Response};
 use serde::Deserialize;
 
-use crate::models::{Document, DocumentType, SearchFilter, SearchResult};
+use super::retry::status_error;
+use super::schema::{self, Page};
+use super::{
+    CircuitBreaker, FetcherBuilder, OfflineError, OfflineMode, RateLimiter, RetryPolicy,
+    SourceConfig,
+};
+use crate::models::{
+    Author, Document, DocumentMetadata, DocumentType, SearchFilter, SearchResult, Stream,
+};
+use crate::profile::{self, Phase};
+use crate::Error;
 
 pub const DATATRACKER_BASE_URL: &str = "https://datatracker.ietf.org";
 
+/// Name of the document API in schema errors
+const DOCUMENT_ENDPOINT: &str = "document";
+/// Name of the per-document `doc.json` endpoint in schema errors
+pub(super) const DOC_JSON_ENDPOINT: &str = "doc.json";
+
+/// Maximum number of documents requested by one bulk query
+const BULK_BATCH_SIZE: usize = 50;
+
+/// Number of documents requested per page by `search_all`
+const SEARCH_PAGE_SIZE: u32 = 100;
+
+/// Maximum number of result pages followed when listing every document of a group
+const MAX_PAGES: usize = 20;
+
 /// Client for the IETF Datatracker API
 pub struct DataTrackerClient {
     client: Client,
+    breaker: Arc<CircuitBreaker>,
+    limiter: Arc<RateLimiter>,
+    retry: RetryPolicy,
+    sources: SourceConfig,
+    offline: OfflineMode,
 }
 
-/// Response from the Datatracker document search API
-#[derive(Debug, Deserialize)]
-struct SearchResponse {
-    meta: SearchMeta,
-    objects: Vec<ApiDocument>,
-}
-
+/// Document as returned by the Datatracker API
+///
+/// Every field but the name is optional and read leniently, so records keep
+/// working when upstream adds fields or changes how values are written.
 #[derive(Debug, Deserialize)]
-struct SearchMeta {
-    #[serde(default)]
-    next: Option<String>,
+struct ApiDocument {
+    name: String,
+    #[serde(default, deserialize_with = "schema::lenient_text")]
+    title: String,
+    #[serde(
+        rename = "abstract",
+        default,
+        deserialize_with = "schema::lenient_string"
+    )]
+    abstract_text: Option<String>,
+    #[serde(default, deserialize_with = "schema::lenient_u32")]
+    pages: Option<u32>,
+    #[serde(default, deserialize_with = "schema::lenient_string")]
+    time: Option<String>,
+    #[serde(default, deserialize_with = "schema::lenient_string")]
+    std_level: Option<String>,
+    #[serde(default, deserialize_with = "schema::lenient_string")]
+    stream: Option<String>,
+    #[serde(default, deserialize_with = "schema::lenient_names")]
+    authors: Vec<String>,
+    #[serde(default, deserialize_with = "schema::lenient_string")]
+    rev: Option<String>,
 }
 
-/// Document as returned by the Datatracker API
+/// Document as returned by the Datatracker `doc.json` endpoint
 #[derive(Debug, Deserialize)]
-struct ApiDocument {
+struct DocJson {
     name: String,
+    #[serde(default, deserialize_with = "schema::lenient_text")]
     title: String,
-    #[serde(rename = "abstract")]
+    #[serde(
+        rename = "abstract",
+        default,
+        deserialize_with = "schema::lenient_string"
+    )]
     abstract_text: Option<String>,
+    #[serde(default, deserialize_with = "schema::lenient_u32")]
     pages: Option<u32>,
-    #[serde(rename = "time")]
+    #[serde(default, deserialize_with = "schema::lenient_string")]
     time: Option<String>,
-    #[serde(rename = "std_level")]
+    #[serde(default, deserialize_with = "schema::lenient_string")]
+    expires: Option<String>,
+    #[serde(default, deserialize_with = "schema::lenient_string")]
     std_level: Option<String>,
+    #[serde(default, deserialize_with = "schema::lenient_string")]
+    intended_std_level: Option<String>,
+    #[serde(default, deserialize_with = "schema::lenient_string")]
     stream: Option<String>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "schema::lenient_string")]
+    state: Option<String>,
+    #[serde(default, deserialize_with = "schema::lenient_string")]
+    rev: Option<String>,
+    #[serde(default, deserialize_with = "lenient_group")]
+    group: Option<DocJsonGroup>,
+    #[serde(default, deserialize_with = "schema::lenient_names")]
     authors: Vec<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct DocJsonGroup {
+    acronym: String,
+    #[serde(default, deserialize_with = "schema::lenient_string")]
+    name: Option<String>,
+    #[serde(rename = "type", default, deserialize_with = "schema::lenient_string")]
+    group_type: Option<String>,
+}
+
+/// Read the group of a `doc.json`, treating a group of another shape as missing
+fn lenient_group<'de, D: serde::Deserializer<'de>>(
+    de: D,
+) -> Result<Option<DocJsonGroup>, D::Error> {
+    Ok(serde_json::from_value(serde_json::Value::deserialize(de)?).ok())
+}
+
 impl DataTrackerClient {
     /// Create a new DataTracker API client
-    pub fn new() -> Result<Self> {
-        Ok(Self {
-            client: Client::builder()
-                .user_agent(concat!("rfc-cli/", env!("CARGO_PKG_VERSION")))
-                .timeout(Duration::from_secs(30))
-                .build()
-                .context("Failed to create HTTP client")?,
-        })
+    ///
+    /// Proxies and certificates are configured as in [`FetcherBuilder::from_env`].
+    pub fn new() -> crate::Result<Self> {
+        FetcherBuilder::from_env().build_datatracker()
+    }
+
+    /// Create a client sending its requests with `client`
+    pub(super) fn from_client(client: Client) -> Self {
+        Self {
+            client,
+            breaker: CircuitBreaker::shared(),
+            limiter: RateLimiter::shared(),
+            retry: RetryPolicy::from_env(),
+            sources: SourceConfig::from_env(),
+            offline: OfflineMode::from_env(),
+        }
+    }
+
+    /// Fail every query with an [`OfflineError`] instead of using the network
+    pub fn with_offline_mode(mut self, mode: OfflineMode) -> Self {
+        self.offline = mode;
+        self
+    }
+
+    /// Query the Datatracker at the base URL of `sources`
+    pub fn with_sources(mut self, sources: SourceConfig) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Limit requests with `limiter` instead of the process-wide limiter
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Retry 429 and 503 responses as `policy` allows instead of failing
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Send a request within the rate limit, through the circuit breaker, with retries
+    async fn send(&self, url: &str, request: RequestBuilder) -> Result<Response> {
+        if self.offline.is_offline() {
+            return Err(OfflineError::new(url, None).into());
+        }
+        let sent = super::retry::send(&self.breaker, &self.limiter, &self.retry, url, request);
+        profile::time_async(Phase::Network, sent).await
     }
 
     /// Search for documents matching the query
@@ -62,61 +182,308 @@ impl DataTrackerClient {
         query: &str,
         filter: SearchFilter,
         limit: u32,
-    ) -> Result<SearchResult> {
+    ) -> crate::Result<SearchResult> {
+        self.search_by_author(query, None, filter, limit).await
+    }
+
+    /// Search for documents matching the query, written by an author if given
+    ///
+    /// The author is matched against the names of the people listed on the
+    /// document; an empty query returns all documents of that author.
+    pub async fn search_by_author(
+        &self,
+        query: &str,
+        author: Option<&str>,
+        filter: SearchFilter,
+        limit: u32,
+    ) -> crate::Result<SearchResult> {
+        self.search_page(query, author, filter, limit, 0).await
+    }
+
+    /// Get one page of search results, starting at `offset`
+    ///
+    /// Offsets count unfiltered Datatracker records, so pass the
+    /// `next_offset` of the previous page rather than a number of documents.
+    /// Results come without section matches; add them for cached documents
+    /// with [`SearchResult::with_cached_sections`].
+    pub async fn search_page(
+        &self,
+        query: &str,
+        author: Option<&str>,
+        filter: SearchFilter,
+        limit: u32,
+        offset: u64,
+    ) -> crate::Result<SearchResult> {
+        let (result, timings) =
+            profile::measured(self.fetch_page(query, author, filter, limit, offset)).await;
+        let mut result = result?;
+        result.timings = timings;
+        Ok(result)
+    }
+
+    async fn fetch_page(
+        &self,
+        query: &str,
+        author: Option<&str>,
+        filter: SearchFilter,
+        limit: u32,
+        offset: u64,
+    ) -> crate::Result<SearchResult> {
+        let url = self.search_url(query, author, filter, limit, offset);
+
+        let response = self
+            .send(&url, self.client.get(&url))
+            .await
+            .context("Failed to send search request")?;
+
+        if !response.status().is_success() {
+            return Err(status_error(&url, &response, None));
+        }
+
+        let body = profile::time_async(Phase::Network, response.text())
+            .await
+            .context("Failed to read search response")?;
+        let page = schema::parse_list(DOCUMENT_ENDPOINT, &body)?;
+
+        let (documents, next_offset) = self.take_page(page, limit as usize, offset);
+        Ok(SearchResult {
+            documents,
+            has_more: next_offset.is_some(),
+            query: query.to_string(),
+            filter,
+            offset,
+            next_offset,
+            timings: None,
+        })
+    }
+
+    /// Stream every matching document, requesting further pages as needed
+    pub fn search_all<'a>(
+        &'a self,
+        query: &'a str,
+        author: Option<&'a str>,
+        filter: SearchFilter,
+    ) -> impl futures::Stream<Item = crate::Result<Document>> + 'a {
+        stream::try_unfold(Some(0), move |offset| async move {
+            let Some(offset) = offset else {
+                return Ok::<_, Error>(None);
+            };
+            let page = self
+                .search_page(query, author, filter, SEARCH_PAGE_SIZE, offset)
+                .await?;
+            let documents = stream::iter(page.documents.into_iter().map(Ok));
+            Ok(Some((documents, page.next_offset)))
+        })
+        .try_flatten()
+    }
+
+    /// Keep up to `limit` RFCs and drafts of a response, and find where the next page starts
+    fn take_page(
+        &self,
+        page: Page<ApiDocument>,
+        limit: usize,
+        offset: u64,
+    ) -> (Vec<Document>, Option<u64>) {
+        // Offsets count every record, including those that couldn't be read
+        let mut next_record = None;
+        let mut documents = Vec::new();
+        for (doc, position) in page.objects.into_iter().zip(page.positions) {
+            if documents.len() == limit {
+                next_record = Some(position);
+                break;
+            }
+            if Self::is_rfc_or_draft(&doc.name) {
+                documents.push(self.convert_api_document(doc));
+            }
+        }
+
+        // Records left over in this response come first on the next page
+        let next_offset = if let Some(position) = next_record {
+            Some(offset + position as u64)
+        } else if page.next.is_some() && page.records > 0 {
+            Some(offset + page.records as u64)
+        } else {
+            None
+        };
+        (documents, next_offset)
+    }
+
+    /// Build the URL of a document search
+    fn search_url(
+        &self,
+        query: &str,
+        author: Option<&str>,
+        filter: SearchFilter,
+        limit: u32,
+        offset: u64,
+    ) -> String {
         // Request more results than needed since we filter locally
         // The API returns many document types we don't want (slides, reviews, etc.)
         let api_limit = limit.saturating_mul(5);
 
         // Search by title (not name) since that's where keywords like "bgp" appear
-        let mut url = format!(
-            "{}/api/v1/doc/document/?title__icontains={}&limit={}&format=json",
-            DATATRACKER_BASE_URL,
-            urlencoding::encode(query),
+        let mut url = self.sources.datatracker_url(&format!(
+            "/api/v1/doc/document/?limit={}&format=json",
             api_limit
-        );
+        ));
+        if offset > 0 {
+            url.push_str(&format!("&offset={}", offset));
+        }
+        if !query.is_empty() || author.is_none() {
+            url.push_str(&format!("&title__icontains={}", urlencoding::encode(query)));
+        }
+        if let Some(author) = author {
+            url.push_str(&format!(
+                "&authors__person__name__icontains={}",
+                urlencoding::encode(author)
+            ));
+        }
 
         // Add type filter if specified
         if let Some(type_param) = filter.api_param() {
             url.push_str(&format!("&type={}", type_param));
         }
+        url
+    }
+
+    /// List the RFCs and drafts of an IRTF research group (e.g., "cfrg")
+    pub async fn rg_documents(&self, acronym: &str, limit: u32) -> crate::Result<Vec<Document>> {
+        let mut documents = self.group_documents(acronym, limit).await?;
+        // Fill in the stream for records that don't carry one
+        for doc in &mut documents {
+            doc.stream.get_or_insert(Stream::Irtf);
+        }
+        Ok(documents)
+    }
+
+    /// List the active drafts and published RFCs of a working group (e.g., "quic")
+    ///
+    /// Drafts come first, sorted by name and carrying their latest revision,
+    /// followed by the RFCs, newest first. States are "Active" and "RFC".
+    pub async fn wg_documents(&self, acronym: &str) -> crate::Result<Vec<Document>> {
+        let group = urlencoding::encode(&acronym.to_lowercase()).into_owned();
+        let drafts = self
+            .all_documents(&format!(
+                "group__acronym={}&type=draft&states__slug=active",
+                group
+            ))
+            .await?;
+        let rfcs = self
+            .all_documents(&format!("group__acronym={}&type=rfc", group))
+            .await?;
+
+        let convert = |docs: Vec<ApiDocument>| -> Vec<Document> {
+            docs.into_iter()
+                .filter(|doc| Self::is_rfc_or_draft(&doc.name))
+                .map(|doc| self.convert_api_document(doc))
+                .collect()
+        };
+        Ok(Self::pipeline(acronym, convert(drafts), convert(rfcs)))
+    }
+
+    /// Order a group's drafts and RFCs, filling in their group and state
+    fn pipeline(acronym: &str, drafts: Vec<Document>, rfcs: Vec<Document>) -> Vec<Document> {
+        let mut drafts: Vec<Document> = drafts
+            .into_iter()
+            .filter(|d| matches!(d.doc_type, DocumentType::Draft(_)))
+            .collect();
+        drafts.sort_by(|a, b| a.name.cmp(&b.name));
+        let mut rfcs: Vec<Document> = rfcs
+            .into_iter()
+            .filter(|d| matches!(d.doc_type, DocumentType::Rfc(_)))
+            .collect();
+        rfcs.sort_by_key(|d| match d.doc_type {
+            DocumentType::Rfc(number) => std::cmp::Reverse(number),
+            _ => std::cmp::Reverse(0),
+        });
+
+        for (doc, state) in drafts
+            .iter_mut()
+            .map(|d| (d, "Active"))
+            .chain(rfcs.iter_mut().map(|d| (d, "RFC")))
+        {
+            doc.wg.get_or_insert_with(|| acronym.to_lowercase());
+            doc.state = Some(state.to_string());
+        }
+        drafts.extend(rfcs);
+        drafts
+    }
+
+    /// Fetch every page of a document query (e.g., "group__acronym=quic")
+    async fn all_documents(&self, query: &str) -> Result<Vec<ApiDocument>> {
+        let mut url = self.sources.datatracker_url(&format!(
+            "/api/v1/doc/document/?{}&limit=100&format=json",
+            query
+        ));
+        let mut documents = Vec::new();
+
+        for _ in 0..MAX_PAGES {
+            let response = self
+                .send(&url, self.client.get(&url))
+                .await
+                .context("Failed to send group documents request")?;
+            if !response.status().is_success() {
+                anyhow::bail!(
+                    "Group documents request to {} failed: HTTP {}",
+                    url,
+                    response.status()
+                );
+            }
+            let body = profile::time_async(Phase::Network, response.text())
+                .await
+                .context("Failed to read group documents response")?;
+            let page: Page<ApiDocument> = schema::parse_list(DOCUMENT_ENDPOINT, &body)?;
+            documents.extend(page.objects);
+            match page.next {
+                // Tastypie links to the next page by path, other APIs by URL
+                Some(next) if next.starts_with("http") => url = next,
+                Some(next) => url = self.sources.datatracker_url(&next),
+                None => break,
+            }
+        }
+        Ok(documents)
+    }
+
+    /// List the RFCs and drafts belonging to a group
+    async fn group_documents(&self, acronym: &str, limit: u32) -> Result<Vec<Document>> {
+        let url = self.sources.datatracker_url(&format!(
+            "/api/v1/doc/document/?group__acronym={}&limit={}&format=json",
+            urlencoding::encode(&acronym.to_lowercase()),
+            limit.saturating_mul(5)
+        ));
 
         let response = self
-            .client
-            .get(&url)
-            .send()
+            .send(&url, self.client.get(&url))
             .await
-            .context("Failed to send search request")?;
+            .context("Failed to send group documents request")?;
 
         if !response.status().is_success() {
             anyhow::bail!(
-                "Search request to {} failed: HTTP {}",
+                "Group documents request to {} failed: HTTP {}",
                 url,
                 response.status()
             );
         }
 
-        let search_response: SearchResponse = response
-            .json()
+        let body = profile::time_async(Phase::Network, response.text())
             .await
-            .context("Failed to parse search response")?;
+            .context("Failed to read group documents response")?;
+        let page: Page<ApiDocument> = schema::parse_list(DOCUMENT_ENDPOINT, &body)?;
 
-        // Filter to only RFCs and drafts, then take up to the requested limit
-        let documents: Vec<Document> = search_response
+        Ok(page
             .objects
             .into_iter()
             .filter(|doc| Self::is_rfc_or_draft(&doc.name))
             .map(|doc| self.convert_api_document(doc))
             .take(limit as usize)
-            .collect();
-
-        let returned_count = documents.len() as u32;
+            .collect())
+    }
 
-        Ok(SearchResult {
-            documents,
-            has_more: search_response.meta.next.is_some() || returned_count == limit,
-            query: query.to_string(),
-            filter,
-        })
+    /// Parse an API stream value, falling back to the stream implied by the draft name
+    fn parse_stream(stream: Option<&str>, name: &str) -> Option<Stream> {
+        stream
+            .and_then(Stream::parse)
+            .or_else(|| Stream::from_draft_name(name))
     }
 
     /// Check if a document name is an RFC or Internet-Draft
@@ -141,9 +508,135 @@ impl DataTrackerClient {
             pages: doc.pages,
             published,
             status: doc.std_level,
-            authors: doc.authors,
-            stream: doc.stream,
+            authors: doc.authors.into_iter().map(Author::new).collect(),
+            stream: Self::parse_stream(doc.stream.as_deref(), &doc.name),
             wg: None,
+            rev: doc.rev,
+            state: None,
+            matches: Vec::new(),
+        }
+    }
+
+    /// Fetch metadata for many documents with one query per batch of names
+    ///
+    /// Results are in the order given; documents the Datatracker doesn't know
+    /// are None. Draft revisions are ignored, so the latest revision is returned.
+    pub async fn documents(&self, docs: &[DocumentType]) -> crate::Result<Vec<Option<Document>>> {
+        let names: Vec<String> = docs.iter().map(Self::tracker_name).collect();
+        let mut found: Vec<Document> = Vec::new();
+
+        for batch in names.chunks(BULK_BATCH_SIZE) {
+            let url = self.bulk_url(batch);
+            let response = self
+                .send(&url, self.client.get(&url))
+                .await
+                .context("Failed to query document info")?;
+            if !response.status().is_success() {
+                return Err(status_error(&url, &response, None));
+            }
+            let body = profile::time_async(Phase::Network, response.text())
+                .await
+                .context("Failed to read document info")?;
+            let page: Page<ApiDocument> = schema::parse_list(DOCUMENT_ENDPOINT, &body)?;
+            found.extend(
+                page.objects
+                    .into_iter()
+                    .map(|d| self.convert_api_document(d)),
+            );
+        }
+
+        Ok(names
+            .iter()
+            .map(|name| found.iter().find(|d| &d.name == name).cloned())
+            .collect())
+    }
+
+    /// Name of a document in the Datatracker, without a draft revision
+    fn tracker_name(doc: &DocumentType) -> String {
+        match doc.draft_revision() {
+            Some((base, _)) => base.to_string(),
+            None => doc.name(),
+        }
+    }
+
+    /// Build the URL of a bulk document query
+    fn bulk_url(&self, names: &[String]) -> String {
+        self.sources.datatracker_url(&format!(
+            "/api/v1/doc/document/?name__in={}&limit={}&format=json",
+            urlencoding::encode(&names.join(",")),
+            names.len()
+        ))
+    }
+
+    /// Fetch metadata for a single document from its `doc.json` endpoint
+    ///
+    /// If `doc.json` can no longer be read, the document API is asked instead.
+    pub async fn document(&self, doc: &DocumentType) -> crate::Result<Document> {
+        match self.metadata(doc).await {
+            Err(e @ Error::Schema { .. }) => self
+                .documents(std::slice::from_ref(doc))
+                .await?
+                .pop()
+                .flatten()
+                .ok_or(e),
+            result => result.map(Document::from),
+        }
+    }
+
+    /// Fetch the full metadata of a document from its `doc.json` endpoint
+    pub async fn metadata(&self, doc: &DocumentType) -> crate::Result<DocumentMetadata> {
+        let url = self
+            .sources
+            .datatracker_url(&format!("/doc/{}/doc.json", doc.name()));
+
+        let response = self
+            .send(&url, self.client.get(&url))
+            .await
+            .context("Failed to query document info")?;
+
+        if !response.status().is_success() {
+            return Err(status_error(&url, &response, Some(doc)));
+        }
+
+        let body = profile::time_async(Phase::Network, response.text())
+            .await
+            .context("Failed to read document info")?;
+        let doc_json: DocJson = schema::parse_object(DOC_JSON_ENDPOINT, &body)?;
+
+        Ok(self.convert_metadata(doc_json))
+    }
+
+    /// Convert a `doc.json` response to our DocumentMetadata model
+    fn convert_metadata(&self, doc: DocJson) -> DocumentMetadata {
+        let doc_type = self.parse_doc_type(&doc.name);
+        // doc.json uses "YYYY-MM-DD HH:MM:SS" rather than RFC 3339
+        let parse_time = |t: &String| {
+            chrono::NaiveDateTime::parse_from_str(t, "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .map(|dt| dt.and_utc())
+        };
+        // Ignore pseudo-groups like "none" used for individual submissions
+        let group = doc
+            .group
+            .filter(|g| g.group_type.as_deref() != Some("individ") && g.acronym != "none");
+        let stream = Self::parse_stream(doc.stream.as_deref(), &doc.name);
+
+        DocumentMetadata {
+            published: doc.time.as_ref().and_then(parse_time),
+            expires: doc.expires.as_ref().and_then(parse_time),
+            name: doc.name,
+            doc_type,
+            title: doc.title,
+            authors: doc.authors.into_iter().map(Author::new).collect(),
+            abstract_text: doc.abstract_text,
+            pages: doc.pages,
+            rev: doc.rev,
+            stream,
+            std_level: doc.std_level,
+            intended_std_level: doc.intended_std_level,
+            state: doc.state,
+            wg_name: group.as_ref().and_then(|g| g.name.clone()),
+            wg: group.map(|g| g.acronym),
         }
     }
 
@@ -171,4 +664,240 @@ mod tests {
             DocumentType::Draft("draft-ietf-quic-transport-34".to_string())
         );
     }
+
+    #[test]
+    fn test_bulk_url() {
+        let names: Vec<String> = [
+            DocumentType::Rfc(9000),
+            DocumentType::Draft("draft-ietf-quic-qlog-main-schema-09".to_string()),
+        ]
+        .iter()
+        .map(DataTrackerClient::tracker_name)
+        .collect();
+        let client = DataTrackerClient::new().unwrap();
+        assert_eq!(
+            client.bulk_url(&names),
+            "https://datatracker.ietf.org/api/v1/doc/document/\
+             ?name__in=rfc9000%2Cdraft-ietf-quic-qlog-main-schema&limit=2&format=json"
+        );
+
+        let internal = client.with_sources(
+            SourceConfig::default().with_datatracker("https://tracker.corp.example/"),
+        );
+        assert!(internal
+            .bulk_url(&names)
+            .starts_with("https://tracker.corp.example/api/v1/doc/document/?name__in="));
+    }
+
+    #[test]
+    fn test_search_url() {
+        let client = DataTrackerClient::new().unwrap();
+        assert_eq!(
+            client.search_url("quic", None, SearchFilter::RfcsOnly, 10, 0),
+            "https://datatracker.ietf.org/api/v1/doc/document/\
+             ?limit=50&format=json&title__icontains=quic&type=rfc"
+        );
+        assert_eq!(
+            client.search_url("quic", None, SearchFilter::Both, 10, 150),
+            "https://datatracker.ietf.org/api/v1/doc/document/\
+             ?limit=50&format=json&offset=150&title__icontains=quic"
+        );
+        assert_eq!(
+            client.search_url("", Some("Martin Thomson"), SearchFilter::Both, 10, 0),
+            "https://datatracker.ietf.org/api/v1/doc/document/\
+             ?limit=50&format=json&authors__person__name__icontains=Martin%20Thomson"
+        );
+    }
+
+    #[test]
+    fn test_take_page() {
+        let client = DataTrackerClient::new().unwrap();
+        let response = |next: bool| -> Page<ApiDocument> {
+            let objects: Vec<String> = ["rfc9000", "slides-111-quic", "rfc9001", "rfc9002"]
+                .iter()
+                .map(|name| format!(r#"{{"name": "{}", "title": "{}"}}"#, name, name))
+                .collect();
+            let next = if next {
+                r#""/api/v1/doc/document/?offset=4""#
+            } else {
+                "null"
+            };
+            let json = format!(
+                r#"{{"meta": {{"next": {}}}, "objects": [{}]}}"#,
+                next,
+                objects.join(",")
+            );
+            schema::parse_list(DOCUMENT_ENDPOINT, &json).unwrap()
+        };
+
+        // Stops after the second RFC; the next page starts at the third record
+        let (documents, next) = client.take_page(response(false), 2, 100);
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[1].name, "rfc9001");
+        assert_eq!(next, Some(103));
+
+        let (documents, next) = client.take_page(response(true), 10, 100);
+        assert_eq!(documents.len(), 3);
+        assert_eq!(next, Some(104));
+
+        let (_, next) = client.take_page(response(false), 10, 100);
+        assert_eq!(next, None);
+
+        // Records that can't be read still count towards the offset
+        let json = r#"{"meta": {"next": "/api/v1/doc/document/?offset=5"}, "objects": [
+            {"title": "no name"}, {"name": "rfc9000"}, {"title": "no name"},
+            {"name": "rfc9001"}, {"name": "rfc9002"}]}"#;
+        let page = || schema::parse_list(DOCUMENT_ENDPOINT, json).unwrap();
+        let (documents, next) = client.take_page(page(), 1, 100);
+        assert_eq!(documents[0].name, "rfc9000");
+        assert_eq!(next, Some(103));
+        let (documents, next) = client.take_page(page(), 2, 100);
+        assert_eq!(documents[1].name, "rfc9001");
+        assert_eq!(next, Some(104));
+        let (documents, next) = client.take_page(page(), 10, 100);
+        assert_eq!(documents.len(), 3);
+        assert_eq!(next, Some(105));
+    }
+
+    #[test]
+    fn test_pipeline() {
+        let doc = |name: &str| {
+            let client = DataTrackerClient::new().unwrap();
+            Document::new(name.to_string(), String::new(), client.parse_doc_type(name))
+        };
+        let documents = DataTrackerClient::pipeline(
+            "QUIC",
+            vec![
+                doc("draft-ietf-quic-qlog"),
+                doc("draft-ietf-quic-ack-frequency"),
+            ],
+            vec![doc("rfc8999"), doc("rfc9369"), doc("rfc9000")],
+        );
+
+        let names: Vec<&str> = documents.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "draft-ietf-quic-ack-frequency",
+                "draft-ietf-quic-qlog",
+                "rfc9369",
+                "rfc9000",
+                "rfc8999"
+            ]
+        );
+        assert_eq!(documents[0].state.as_deref(), Some("Active"));
+        assert_eq!(documents[2].state.as_deref(), Some("RFC"));
+        assert!(documents.iter().all(|d| d.wg.as_deref() == Some("quic")));
+    }
+
+    #[test]
+    fn test_parse_stream() {
+        assert_eq!(
+            DataTrackerClient::parse_stream(Some("/api/v1/name/streamname/irtf/"), "rfc9180"),
+            Some(Stream::Irtf)
+        );
+        assert_eq!(
+            DataTrackerClient::parse_stream(None, "draft-irtf-cfrg-hpke-12"),
+            Some(Stream::Irtf)
+        );
+        assert_eq!(
+            DataTrackerClient::parse_stream(None, "draft-smith-foo"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_convert_doc_json() {
+        let client = DataTrackerClient::new().unwrap();
+        let json = r#"{
+            "name": "draft-ietf-quic-transport",
+            "rev": "34",
+            "pages": 207,
+            "time": "2021-01-15 12:00:00",
+            "group": {"name": "QUIC", "type": "wg", "acronym": "quic"},
+            "title": "QUIC: A UDP-Based Multiplexed and Secure Transport",
+            "abstract": "This document defines the core of the QUIC transport protocol.",
+            "state": "RFC",
+            "intended_std_level": "Proposed Standard",
+            "std_level": null,
+            "stream": "IETF",
+            "authors": [{"name": "Jana Iyengar", "email": null, "affiliation": "Fastly"}]
+        }"#;
+        let doc_json: DocJson = serde_json::from_str(json).unwrap();
+        let doc = Document::from(client.convert_metadata(doc_json));
+
+        assert_eq!(
+            doc.doc_type,
+            DocumentType::Draft("draft-ietf-quic-transport".to_string())
+        );
+        assert_eq!(doc.rev.as_deref(), Some("34"));
+        assert_eq!(doc.state.as_deref(), Some("RFC"));
+        assert_eq!(doc.status.as_deref(), Some("Proposed Standard"));
+        assert_eq!(doc.wg.as_deref(), Some("quic"));
+        assert_eq!(doc.authors, vec![Author::new("Jana Iyengar")]);
+        assert_eq!(doc.stream, Some(Stream::Ietf));
+        assert!(doc.published.is_some());
+    }
+
+    #[test]
+    fn test_doc_json_schema_drift() {
+        let client = DataTrackerClient::new().unwrap();
+        // Values written in other shapes than today, plus fields added upstream
+        let json = r#"{
+            "name": "rfc9000",
+            "pages": "151",
+            "group": "/api/v1/group/group/2161/",
+            "title": {"name": "QUIC"},
+            "stream": {"slug": "ietf", "name": "IETF"},
+            "authors": ["Jana Iyengar", {"name": "Martin Thomson", "person": 1}],
+            "ballot": {"positions": []}
+        }"#;
+        let doc_json: DocJson = schema::parse_object(DOC_JSON_ENDPOINT, json).unwrap();
+        let meta = client.convert_metadata(doc_json);
+        assert_eq!(meta.pages, Some(151));
+        assert_eq!(meta.title, "QUIC");
+        assert_eq!(meta.wg, None);
+        assert_eq!(meta.stream, Some(Stream::Ietf));
+        assert_eq!(meta.authors.len(), 2);
+
+        let err =
+            schema::parse_object::<DocJson>(DOC_JSON_ENDPOINT, r#"{"title": "x"}"#).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<schema::SchemaError>().unwrap().endpoint,
+            "doc.json"
+        );
+    }
+
+    #[test]
+    fn test_convert_metadata() {
+        let client = DataTrackerClient::new().unwrap();
+        let json = r#"{
+            "name": "rfc9000",
+            "rev": null,
+            "pages": 151,
+            "time": "2021-05-27 09:30:00",
+            "expires": null,
+            "group": {"name": "QUIC", "type": "wg", "acronym": "quic"},
+            "title": "QUIC: A UDP-Based Multiplexed and Secure Transport",
+            "abstract": "This document defines the core of the QUIC transport protocol.",
+            "state": "Published",
+            "intended_std_level": "Proposed Standard",
+            "std_level": "Proposed Standard",
+            "stream": "IETF",
+            "authors": [{"name": "Jana Iyengar"}, {"name": "Martin Thomson"}]
+        }"#;
+        let meta = client.convert_metadata(serde_json::from_str(json).unwrap());
+
+        assert_eq!(meta.doc_type, DocumentType::Rfc(9000));
+        assert_eq!(meta.pages, Some(151));
+        assert_eq!(meta.status(), Some("Proposed Standard"));
+        assert_eq!(meta.wg.as_deref(), Some("quic"));
+        assert_eq!(meta.wg_name.as_deref(), Some("QUIC"));
+        assert_eq!(meta.authors.len(), 2);
+        assert_eq!(
+            meta.published.map(|t| t.to_rfc3339()).as_deref(),
+            Some("2021-05-27T09:30:00+00:00")
+        );
+        assert!(meta.expires.is_none());
+    }
 }
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+use std::fs;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+
+use anyhow::{anyhow, Context};
+
+use super::DocumentFetcher;
+use crate::models::{DocumentType, Format, FormatPreference};
+use crate::{Error, Result};
+
+/// A boxed future returned by [`DocumentSource`] methods
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Where documents are fetched from
+///
+/// Implemented by [`DocumentFetcher`], by [`FixtureSource`] and by
+/// [`PluginRegistry`](crate::PluginRegistry), so code written against this
+/// trait (e.g., a [`LookupService`](crate::service::LookupService)) can be
+/// tested without the network or serve documents of other publishers.
+pub trait DocumentSource: Send + Sync {
+    /// Fetch a document in the preferred format, falling back to the others
+    fn fetch<'a>(&'a self, doc: &'a DocumentType) -> BoxFuture<'a, Result<(String, Format)>>;
+
+    /// Fetch the raw content of a document in a specific format
+    fn fetch_bytes<'a>(
+        &'a self,
+        doc: &'a DocumentType,
+        format: Format,
+    ) -> BoxFuture<'a, Result<Vec<u8>>>;
+
+    /// Fetch a document in a specific format, without falling back to another one
+    fn fetch_format<'a>(
+        &'a self,
+        doc: &'a DocumentType,
+        format: Format,
+    ) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            if format.is_binary() {
+                return Err(
+                    anyhow!("{} is a binary format; use fetch_bytes", format.extension()).into(),
+                );
+            }
+            let bytes = self.fetch_bytes(doc, format).await?;
+            Ok(String::from_utf8(bytes).context("Document is not valid UTF-8")?)
+        })
+    }
+}
+
+impl DocumentSource for DocumentFetcher {
+    fn fetch<'a>(&'a self, doc: &'a DocumentType) -> BoxFuture<'a, Result<(String, Format)>> {
+        Box::pin(DocumentFetcher::fetch(self, doc))
+    }
+
+    fn fetch_bytes<'a>(
+        &'a self,
+        doc: &'a DocumentType,
+        format: Format,
+    ) -> BoxFuture<'a, Result<Vec<u8>>> {
+        Box::pin(DocumentFetcher::fetch_bytes(self, doc, format))
+    }
+
+    fn fetch_format<'a>(
+        &'a self,
+        doc: &'a DocumentType,
+        format: Format,
+    ) -> BoxFuture<'a, Result<String>> {
+        Box::pin(DocumentFetcher::fetch_format(self, doc, format))
+    }
+}
+
+/// Serves documents from fixtures, for deterministic tests
+///
+/// Documents missing from the fixtures fail with [`Error::NotFound`];
+/// `fetch` prefers plain text, then HTML, then XML, unless given a
+/// [`FormatPreference`] as a [`DocumentFetcher`] would be.
+#[derive(Debug, Clone, Default)]
+pub struct FixtureSource {
+    documents: HashMap<(DocumentType, Format), Vec<u8>>,
+    preference: Option<FormatPreference>,
+}
+
+impl FixtureSource {
+    /// Create a source without any documents
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every document of a directory, named as in the cache (e.g., "rfc9000.txt")
+    ///
+    /// Files whose name isn't a document and format are skipped.
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        let mut source = Self::new();
+        let entries = fs::read_dir(dir)
+            .with_context(|| format!("Failed to read fixtures in {}", dir.display()))?;
+        for entry in entries {
+            let path = entry.context("Failed to read fixtures")?.path();
+            let parsed = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(DocumentType::parse)
+                .zip(
+                    path.extension()
+                        .and_then(|s| s.to_str())
+                        .and_then(Format::from_extension),
+                );
+            if let Some((doc, format)) = parsed {
+                let content = fs::read(&path)
+                    .with_context(|| format!("Failed to read fixture {}", path.display()))?;
+                source.documents.insert((doc, format), content);
+            }
+        }
+        Ok(source)
+    }
+
+    /// Add a document, replacing any fixture of the same document and format
+    pub fn with_document(
+        mut self,
+        doc: DocumentType,
+        format: Format,
+        content: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.documents.insert((doc, format), content.into());
+        self
+    }
+
+    /// Try the formats of `fetch` in this order
+    pub fn with_format_preference(mut self, preference: FormatPreference) -> Self {
+        self.preference = Some(preference);
+        self
+    }
+
+    /// Get the order in which `fetch` tries the formats of a document
+    pub fn format_preference(&self) -> FormatPreference {
+        self.preference
+            .clone()
+            .unwrap_or_else(|| FormatPreference::new([Format::Text, Format::Html, Format::Xml]))
+    }
+
+    /// List the documents served, sorted
+    pub fn documents(&self) -> Vec<DocumentType> {
+        let mut docs: Vec<DocumentType> = self.documents.keys().map(|(d, _)| d.clone()).collect();
+        docs.sort();
+        docs.dedup();
+        docs
+    }
+}
+
+impl DocumentSource for FixtureSource {
+    fn fetch<'a>(&'a self, doc: &'a DocumentType) -> BoxFuture<'a, Result<(String, Format)>> {
+        Box::pin(async move {
+            for &format in self.format_preference().formats() {
+                if self.documents.contains_key(&(doc.clone(), format)) {
+                    return Ok((self.fetch_format(doc, format).await?, format));
+                }
+            }
+            Err(Error::not_found(doc.clone()))
+        })
+    }
+
+    fn fetch_bytes<'a>(
+        &'a self,
+        doc: &'a DocumentType,
+        format: Format,
+    ) -> BoxFuture<'a, Result<Vec<u8>>> {
+        let content = self.documents.get(&(doc.clone(), format)).cloned();
+        Box::pin(async move { content.ok_or_else(|| Error::not_found(doc.clone())) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Count the words of a document, as an app generic over its source would
+    async fn word_count(source: &dyn DocumentSource, doc: &DocumentType) -> Result<usize> {
+        let (content, _) = source.fetch(doc).await?;
+        Ok(content.split_whitespace().count())
+    }
+
+    #[test]
+    fn test_fixture_source() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("rfc9000.txt"),
+            "QUIC: A UDP-Based Transport",
+        )
+        .unwrap();
+        fs::write(temp.path().join("rfc9114.html"), "<p>HTTP/3</p>").unwrap();
+        fs::write(temp.path().join("README.md"), "Fixtures").unwrap();
+        let source = FixtureSource::from_dir(temp.path()).unwrap().with_document(
+            DocumentType::Rfc(9001),
+            Format::Pdf,
+            vec![0x25, 0x50],
+        );
+
+        assert_eq!(
+            source.documents(),
+            vec![
+                DocumentType::Rfc(9000),
+                DocumentType::Rfc(9001),
+                DocumentType::Rfc(9114)
+            ]
+        );
+        let rfc9000 = DocumentType::Rfc(9000);
+        assert_eq!(
+            tokio_test::block_on(word_count(&source, &rfc9000)).unwrap(),
+            4
+        );
+        let (content, format) =
+            tokio_test::block_on(source.fetch(&DocumentType::Rfc(9114))).unwrap();
+        assert_eq!((content.as_str(), format), ("<p>HTTP/3</p>", Format::Html));
+        assert_eq!(
+            tokio_test::block_on(source.fetch_bytes(&DocumentType::Rfc(9001), Format::Pdf))
+                .unwrap(),
+            vec![0x25, 0x50]
+        );
+        assert!(
+            tokio_test::block_on(source.fetch_format(&DocumentType::Rfc(9001), Format::Pdf))
+                .is_err()
+        );
+        assert!(matches!(
+            tokio_test::block_on(source.fetch_format(&rfc9000, Format::Html)),
+            Err(Error::NotFound {
+                doc: DocumentType::Rfc(9000),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_fixture_format_preference() {
+        let rfc = DocumentType::Rfc(9000);
+        let source = FixtureSource::new()
+            .with_document(rfc.clone(), Format::Text, "QUIC")
+            .with_document(rfc.clone(), Format::Xml, "<rfc/>");
+        let (_, format) = tokio_test::block_on(source.fetch(&rfc)).unwrap();
+        assert_eq!(format, Format::Text);
+
+        let source = source.with_format_preference(FormatPreference::new([
+            Format::Html,
+            Format::Xml,
+            Format::Text,
+        ]));
+        let (content, format) = tokio_test::block_on(source.fetch(&rfc)).unwrap();
+        assert_eq!((content.as_str(), format), ("<rfc/>", Format::Xml));
+
+        // Formats left out of the preference aren't served by fetch
+        let source = source.with_format_preference(FormatPreference::new([Format::Html]));
+        assert!(matches!(
+            tokio_test::block_on(source.fetch(&rfc)),
+            Err(Error::NotFound { .. })
+        ));
+    }
+}
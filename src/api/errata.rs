@@ -0,0 +1,126 @@
+use anyhow::Context;
+use serde::Deserialize;
+
+use super::retry::status_error;
+use super::DocumentFetcher;
+use crate::cache::CacheManager;
+use crate::models::{Erratum, ErratumStatus, ErratumType};
+use crate::profile::{self, Phase};
+use crate::Result;
+
+/// Bulk dump of all errata published by the RFC Editor
+const ERRATA_PATH: &str = "/errata.json";
+
+/// Single record of the RFC Editor errata dump
+#[derive(Debug, Deserialize)]
+struct ApiErratum {
+    #[serde(rename = "doc-id")]
+    doc_id: String,
+    // Published as a string ("7392") in most records, but be lenient
+    errata_id: serde_json::Value,
+    errata_type_code: Option<String>,
+    errata_status_code: Option<String>,
+    section: Option<String>,
+    orig_text: Option<String>,
+    correct_text: Option<String>,
+    notes: Option<String>,
+    submit_date: Option<String>,
+    submitter_name: Option<String>,
+}
+
+impl DocumentFetcher {
+    /// Fetch the errata reported against an RFC
+    pub async fn errata(&self, number: u32) -> Result<Vec<Erratum>> {
+        let url = self.sources.rfc_editor_url(ERRATA_PATH);
+        let response = self
+            .send(&url, self.client.get(&url))
+            .await
+            .context("Failed to query errata")?;
+
+        if !response.status().is_success() {
+            return Err(status_error(&url, &response, None));
+        }
+
+        let records: Vec<ApiErratum> = profile::time_async(Phase::Network, response.json())
+            .await
+            .context("Failed to parse errata")?;
+        Ok(convert_errata(number, records))
+    }
+
+    /// Get the errata of an RFC from the cache, fetching and caching them if missing or stale
+    ///
+    /// Cached errata are fetched again after a day; when that fails, the
+    /// stale copy is returned.
+    pub async fn errata_cached(&self, number: u32, cache: &CacheManager) -> Result<Vec<Erratum>> {
+        if let Some(errata) = cache.get_fresh_errata(number) {
+            return Ok(errata);
+        }
+        let errata = match self.errata(number).await {
+            Ok(errata) => errata,
+            Err(err) => return cache.get_errata(number).ok_or(err),
+        };
+        cache.store_errata(number, &errata)?;
+        Ok(errata)
+    }
+}
+
+/// Select and convert the records of one RFC, ordered by ID
+fn convert_errata(number: u32, records: Vec<ApiErratum>) -> Vec<Erratum> {
+    let doc_id = format!("RFC{}", number);
+    let non_empty = |s: Option<String>| s.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+
+    let mut errata: Vec<Erratum> = records
+        .into_iter()
+        .filter(|r| r.doc_id.eq_ignore_ascii_case(&doc_id))
+        .filter_map(|r| {
+            let id = match &r.errata_id {
+                serde_json::Value::Number(n) => n.as_u64().map(|n| n as u32),
+                serde_json::Value::String(s) => s.trim().parse().ok(),
+                _ => None,
+            }?;
+            Some(Erratum {
+                id,
+                rfc: number,
+                kind: ErratumType::parse(r.errata_type_code.as_deref().unwrap_or_default()),
+                status: ErratumStatus::parse(r.errata_status_code.as_deref().unwrap_or_default()),
+                section: non_empty(r.section),
+                original_text: non_empty(r.orig_text),
+                corrected_text: non_empty(r.correct_text),
+                notes: non_empty(r.notes),
+                submitted: non_empty(r.submit_date),
+                submitter: non_empty(r.submitter_name),
+            })
+        })
+        .collect();
+    errata.sort_by_key(|e| e.id);
+    errata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_errata() {
+        let json = r#"[
+            {"doc-id": "RFC9000", "errata_id": "7392", "errata_type_code": "Technical",
+             "errata_status_code": "Verified", "section": "19.8",
+             "orig_text": "Offset field", "correct_text": "Length field", "notes": "",
+             "submit_date": "2023-03-01", "submitter_name": "A. Reader"},
+            {"doc-id": "RFC9000", "errata_id": 6895, "errata_type_code": "Editorial",
+             "errata_status_code": "Held for Document Update"},
+            {"doc-id": "RFC8446", "errata_id": "5682"}
+        ]"#;
+        let records: Vec<ApiErratum> = serde_json::from_str(json).unwrap();
+        let errata = convert_errata(9000, records);
+
+        assert_eq!(errata.len(), 2);
+        assert_eq!(errata[0].id, 6895);
+        assert_eq!(errata[0].status, ErratumStatus::HeldForDocumentUpdate);
+        assert_eq!(errata[1].kind, ErratumType::Technical);
+        assert_eq!(errata[1].section.as_deref(), Some("19.8"));
+        assert_eq!(errata[1].corrected_text.as_deref(), Some("Length field"));
+        assert_eq!(errata[1].notes, None);
+        assert_eq!(errata[1].url(), "https://www.rfc-editor.org/errata/eid7392");
+    }
+}
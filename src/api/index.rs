@@ -0,0 +1,374 @@
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use super::DocumentFetcher;
+use crate::cache::CacheManager;
+use crate::models::{Author, DocumentType, RfcMetadata};
+use crate::parse::unescape;
+use crate::Result;
+
+/// Path of the index on the RFC Editor and its mirrors
+pub(super) const RFC_INDEX_PATH: &str = "/rfc-index.xml";
+
+/// Name of the synchronized index in the cache directory
+const INDEX_FILE: &str = "rfc-index.json";
+
+/// Size and modification time of the synchronized index file
+type IndexStamp = (u64, SystemTime);
+
+/// The synchronized index loaded by a cache manager, with the stamp of the
+/// file it was read from
+#[derive(Debug, Default)]
+pub(crate) struct LoadedIndex(Mutex<Option<(IndexStamp, Arc<RfcIndex>)>>);
+
+/// Metadata of every published RFC, for offline lookups
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RfcIndex {
+    /// Entries sorted by RFC number
+    entries: Vec<RfcMetadata>,
+}
+
+impl RfcIndex {
+    /// Parse the RFC Editor's `rfc-index.xml`
+    pub fn parse(xml: &str) -> Self {
+        let mut entries: Vec<RfcMetadata> = xml
+            .split("<rfc-entry>")
+            .skip(1)
+            .filter_map(|entry| parse_entry(entry.split("</rfc-entry>").next()?))
+            .collect();
+        entries.sort_by_key(|e| e.number);
+        entries.dedup_by_key(|e| e.number);
+        Self { entries }
+    }
+
+    /// Load the index synchronized into the cache, if any
+    ///
+    /// The index is read once per cache manager (and its clones), and again
+    /// only when the synchronized file changes.
+    pub fn cached(cache: &CacheManager) -> Option<Arc<Self>> {
+        let path = cache.cache_dir().join(INDEX_FILE);
+        let metadata = fs::metadata(&path).ok()?;
+        let stored: IndexStamp = (metadata.len(), metadata.modified().ok()?);
+        let mut loaded = cache.loaded_index().0.lock().unwrap();
+        if let Some((at, index)) = loaded.as_ref() {
+            if *at == stored {
+                return Some(index.clone());
+            }
+        }
+        let json = fs::read_to_string(&path).ok()?;
+        let index: Arc<Self> = Arc::new(serde_json::from_str(&json).ok()?);
+        *loaded = Some((stored, index.clone()));
+        Some(index)
+    }
+
+    /// Store the index in the cache
+    pub fn store(&self, cache: &CacheManager) -> Result<()> {
+        fs::create_dir_all(cache.cache_dir()).context("Failed to create cache directory")?;
+        let json = serde_json::to_string(self).context("Failed to serialize RFC index")?;
+        fs::write(cache.cache_dir().join(INDEX_FILE), json)
+            .context("Failed to write RFC index to cache")?;
+        *cache.loaded_index().0.lock().unwrap() = None;
+        Ok(())
+    }
+
+    /// Download the index and store it in the cache
+    pub async fn sync(fetcher: &DocumentFetcher, cache: &CacheManager) -> Result<Self> {
+        let index = fetcher.rfc_index().await?;
+        index.store(cache)?;
+        Ok(index)
+    }
+
+    /// Get the metadata of an RFC
+    pub fn lookup(&self, number: u32) -> Option<&RfcMetadata> {
+        self.entries
+            .binary_search_by_key(&number, |e| e.number)
+            .ok()
+            .map(|i| &self.entries[i])
+    }
+
+    /// Get the metadata of many documents in one pass, in the order given
+    ///
+    /// Entries are None for documents that aren't RFCs or aren't in the index.
+    pub fn metadata_many(&self, docs: &[DocumentType]) -> Vec<Option<&RfcMetadata>> {
+        docs.iter()
+            .map(|doc| match doc {
+                DocumentType::Rfc(number) => self.lookup(*number),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Find RFCs whose title contains every word of the query, ignoring case
+    ///
+    /// Results are sorted newest first.
+    pub fn search_titles(&self, query: &str) -> Vec<&RfcMetadata> {
+        let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        if words.is_empty() {
+            return Vec::new();
+        }
+        self.entries
+            .iter()
+            .rev()
+            .filter(|e| {
+                let title = e.title.to_lowercase();
+                words.iter().all(|w| title.contains(w.as_str()))
+            })
+            .collect()
+    }
+
+    /// Iterate over all entries in RFC number order
+    pub fn iter(&self) -> impl Iterator<Item = &RfcMetadata> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl DocumentFetcher {
+    /// Download and parse the RFC Editor's index of every RFC
+    pub async fn rfc_index(&self) -> Result<RfcIndex> {
+        let url = self.sources.rfc_editor_url(RFC_INDEX_PATH);
+        let xml = self
+            .fetch_content(&url)
+            .await
+            .context("Failed to fetch RFC index")?;
+        Ok(RfcIndex::parse(&xml))
+    }
+}
+
+/// Parse the body of an `<rfc-entry>`
+fn parse_entry(entry: &str) -> Option<RfcMetadata> {
+    let number = element(entry, "doc-id")?
+        .strip_prefix("RFC")?
+        .parse()
+        .ok()?;
+    // Authors have titles of their own ("Editor"); the document title comes first
+    let head = &entry[..entry.find("<author>").unwrap_or(entry.len())];
+    let text = |tag| {
+        element(entry, tag)
+            .map(|s| unescape(s.trim()))
+            .filter(|s| !s.is_empty())
+    };
+
+    let authors = entry
+        .split("<author>")
+        .skip(1)
+        .filter_map(|a| element(a, "name"))
+        .map(|name| Author::new(unescape(name.trim())))
+        .collect();
+    let pub_date = element(entry, "date").and_then(|date| {
+        let year = element(date, "year")?;
+        Some(match element(date, "month") {
+            Some(month) => format!("{} {}", month, year),
+            None => year.to_string(),
+        })
+    });
+    let source = text("wg_acronym")
+        .filter(|wg| wg != "NON WORKING GROUP")
+        .map(|wg| wg.to_uppercase())
+        .or_else(|| text("stream"));
+    let abstract_text = element(entry, "abstract").map(|a| {
+        a.split("<p>")
+            .filter_map(|p| p.split("</p>").next())
+            .map(|p| unescape(p.trim()))
+            .filter(|p| !p.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    });
+
+    Some(RfcMetadata {
+        number,
+        title: element(head, "title").map(|t| unescape(t.trim()))?,
+        authors,
+        pub_date,
+        status: text("current-status"),
+        pub_status: text("publication-status"),
+        source,
+        pages: text("page-count").and_then(|p| p.parse().ok()),
+        abstract_text: abstract_text.filter(|a| !a.is_empty()),
+        draft: text("draft"),
+        obsoletes: doc_ids(entry, "obsoletes"),
+        obsoleted_by: doc_ids(entry, "obsoleted-by"),
+        updates: doc_ids(entry, "updates"),
+        updated_by: doc_ids(entry, "updated-by"),
+        errata_url: text("errata-url"),
+        doi: text("doi"),
+        license: None,
+    })
+}
+
+/// Get the content of the first `<tag>` element
+fn element<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let open = format!("<{}>", tag);
+    let start = xml.find(&open)? + open.len();
+    let end = xml[start..].find(&format!("</{}>", tag))? + start;
+    Some(&xml[start..end])
+}
+
+/// Get the documents listed by `<doc-id>` in the first `<tag>` element
+fn doc_ids(entry: &str, tag: &str) -> Vec<DocumentType> {
+    element(entry, tag)
+        .map(|list| {
+            list.split("<doc-id>")
+                .skip(1)
+                .filter_map(|id| DocumentType::parse(id.split('<').next()?))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const INDEX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rfc-index xmlns="https://www.rfc-editor.org/rfc-index">
+    <bcp-entry>
+        <doc-id>BCP0014</doc-id>
+        <is-also><doc-id>RFC2119</doc-id></is-also>
+    </bcp-entry>
+    <rfc-entry>
+        <doc-id>RFC9000</doc-id>
+        <title>QUIC: A UDP-Based Multiplexed and Secure Transport</title>
+        <author><name>J. Iyengar</name><title>Editor</title></author>
+        <author><name>M. Thomson</name><title>Editor</title></author>
+        <date><month>May</month><year>2021</year></date>
+        <page-count>151</page-count>
+        <abstract><p>This document defines the core of the QUIC transport protocol.</p><p>Second.</p></abstract>
+        <draft>draft-ietf-quic-transport-34</draft>
+        <current-status>PROPOSED STANDARD</current-status>
+        <publication-status>PROPOSED STANDARD</publication-status>
+        <stream>IETF</stream>
+        <wg_acronym>quic</wg_acronym>
+        <doi>10.17487/RFC9000</doi>
+    </rfc-entry>
+    <rfc-entry>
+        <doc-id>RFC2119</doc-id>
+        <title>Key words for use in RFCs to Indicate Requirement Levels</title>
+        <author><name>S. Bradner</name></author>
+        <date><month>March</month><year>1997</year></date>
+        <updated-by><doc-id>RFC8174</doc-id></updated-by>
+        <is-also><doc-id>BCP0014</doc-id></is-also>
+        <current-status>BEST CURRENT PRACTICE</current-status>
+        <stream>IETF</stream>
+        <wg_acronym>NON WORKING GROUP</wg_acronym>
+        <errata-url>https://www.rfc-editor.org/errata/rfc2119</errata-url>
+    </rfc-entry>
+    <rfc-entry>
+        <doc-id>RFC8174</doc-id>
+        <title>Ambiguity of Uppercase vs Lowercase in RFC 2119 Key Words</title>
+        <author><name>B. Leiba</name></author>
+        <updates><doc-id>RFC2119</doc-id></updates>
+    </rfc-entry>
+</rfc-index>"#;
+
+    #[test]
+    fn test_parse_index() {
+        let index = RfcIndex::parse(INDEX);
+        assert_eq!(index.len(), 3);
+        assert_eq!(
+            index.iter().map(|e| e.number).collect::<Vec<_>>(),
+            vec![2119, 8174, 9000]
+        );
+
+        let quic = index.lookup(9000).unwrap();
+        assert_eq!(
+            quic.title,
+            "QUIC: A UDP-Based Multiplexed and Secure Transport"
+        );
+        assert_eq!(
+            quic.authors,
+            vec![Author::new("J. Iyengar"), Author::new("M. Thomson")]
+        );
+        assert_eq!(quic.pub_date.as_deref(), Some("May 2021"));
+        assert_eq!(quic.pages, Some(151));
+        assert_eq!(quic.source.as_deref(), Some("QUIC"));
+        assert_eq!(quic.draft.as_deref(), Some("draft-ietf-quic-transport-34"));
+        assert!(quic
+            .abstract_text
+            .as_deref()
+            .unwrap()
+            .ends_with("protocol.\n\nSecond."));
+
+        let keywords = index.lookup(2119).unwrap();
+        assert_eq!(keywords.source.as_deref(), Some("IETF"));
+        assert_eq!(keywords.updated_by, vec![DocumentType::Rfc(8174)]);
+        assert_eq!(
+            index.lookup(8174).unwrap().updates,
+            vec![DocumentType::Rfc(2119)]
+        );
+        assert!(index.lookup(1).is_none());
+    }
+
+    #[test]
+    fn test_metadata_many() {
+        let index = RfcIndex::parse(INDEX);
+        let titles: Vec<Option<&str>> = index
+            .metadata_many(&[
+                DocumentType::Rfc(9000),
+                DocumentType::Rfc(1),
+                DocumentType::Draft("draft-ietf-quic-transport".to_string()),
+                DocumentType::Rfc(2119),
+            ])
+            .into_iter()
+            .map(|m| m.map(|m| m.title.as_str()))
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                Some("QUIC: A UDP-Based Multiplexed and Secure Transport"),
+                None,
+                None,
+                Some("Key words for use in RFCs to Indicate Requirement Levels"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_titles() {
+        let index = RfcIndex::parse(INDEX);
+        let numbers = |query| {
+            index
+                .search_titles(query)
+                .iter()
+                .map(|e| e.number)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(numbers("key words"), vec![8174, 2119]);
+        assert_eq!(numbers("quic TRANSPORT"), vec![9000]);
+        assert!(numbers("  ").is_empty());
+    }
+
+    #[test]
+    fn test_cached_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        assert!(RfcIndex::cached(&cache).is_none());
+
+        let index = RfcIndex::parse(INDEX);
+        index.store(&cache).unwrap();
+        let loaded = RfcIndex::cached(&cache).unwrap();
+        assert_eq!(*loaded, index);
+        // Loaded once and shared by clones of the manager
+        assert!(Arc::ptr_eq(
+            &RfcIndex::cached(&cache.clone()).unwrap(),
+            &loaded
+        ));
+
+        // Storing another index replaces the loaded one
+        RfcIndex::default().store(&cache).unwrap();
+        assert!(RfcIndex::cached(&cache).unwrap().is_empty());
+    }
+}
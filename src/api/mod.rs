@@ -1,5 +1,34 @@
+mod bibxml;
+mod builder;
+mod circuit;
+mod clusters;
+mod coalesce;
 mod datatracker;
+mod document_source;
+mod errata;
+mod index;
+mod offline;
+mod prefetch;
+mod ratelimit;
+mod retry;
 mod rfc_editor;
+mod schema;
+mod sources;
+mod translations;
 
+pub use bibxml::bibxml_file_name;
+pub use builder::FetcherBuilder;
+pub use circuit::CircuitBreaker;
+pub use coalesce::Coalescer;
 pub use datatracker::{DataTrackerClient, DATATRACKER_BASE_URL};
+pub use document_source::{BoxFuture, DocumentSource, FixtureSource};
+pub(crate) use index::LoadedIndex;
+pub use index::RfcIndex;
+pub use offline::{OfflineError, OfflineMode};
+pub use prefetch::DEFAULT_PARALLELISM;
+pub use ratelimit::RateLimiter;
+pub use retry::RetryPolicy;
 pub use rfc_editor::DocumentFetcher;
+pub use schema::SchemaError;
+pub use sources::{SourceConfig, BIBXML_BASE_URL, DRAFTS_BASE_URL, RFC_EDITOR_BASE_URL};
+pub use translations::{is_language_tag, TranslationSources};
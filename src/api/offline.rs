@@ -0,0 +1,118 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::cache::CacheManager;
+use crate::models::DocumentType;
+
+/// Set by [`OfflineMode::force_offline`] for every client created afterwards
+static FORCED: AtomicBool = AtomicBool::new(false);
+
+/// Cached documents named in an offline error before the rest are counted
+const LISTED: usize = 10;
+
+/// Whether the clients may use the network
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OfflineMode {
+    #[default]
+    Online,
+    /// Serve everything from the cache and fail right away with an
+    /// [`OfflineError`] for anything that isn't cached
+    Offline,
+}
+
+impl OfflineMode {
+    /// Go offline if `RFC_OFFLINE` is set ("1", "true" or "yes") or offline mode was forced
+    pub fn from_env() -> Self {
+        let set = std::env::var("RFC_OFFLINE")
+            .is_ok_and(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"));
+        if set || FORCED.load(Ordering::Relaxed) {
+            OfflineMode::Offline
+        } else {
+            OfflineMode::Online
+        }
+    }
+
+    /// Make every client created from now on start offline, e.g. for a `--offline` flag
+    pub fn force_offline() {
+        FORCED.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_offline(&self) -> bool {
+        *self == OfflineMode::Offline
+    }
+}
+
+/// Something had to be fetched from the network while offline
+///
+/// Returned as [`crate::Error::Offline`] by clients in [`OfflineMode::Offline`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OfflineError {
+    /// The document or URL that wasn't available locally
+    pub requested: String,
+    /// Everything in the cache, for suggesting what is available
+    pub cached: Vec<DocumentType>,
+}
+
+impl OfflineError {
+    pub fn new(requested: impl Into<String>, cache: Option<&CacheManager>) -> Self {
+        let cached = match cache {
+            Some(cache) => cache.list_cached(),
+            None => CacheManager::new()
+                .map(|cache| cache.list_cached())
+                .unwrap_or_default(),
+        };
+        Self {
+            requested: requested.into(),
+            cached,
+        }
+    }
+}
+
+impl fmt::Display for OfflineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} is not available offline", self.requested)?;
+        if self.cached.is_empty() {
+            return f.write_str(" (the cache is empty)");
+        }
+        let listed: Vec<String> = self
+            .cached
+            .iter()
+            .take(LISTED)
+            .map(|d| d.to_string())
+            .collect();
+        write!(f, "; cached: {}", listed.join(", "))?;
+        if self.cached.len() > LISTED {
+            write!(f, " and {} more", self.cached.len() - LISTED)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for OfflineError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offline_error() {
+        let err = OfflineError {
+            requested: "RFC 9000".to_string(),
+            cached: Vec::new(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "RFC 9000 is not available offline (the cache is empty)"
+        );
+
+        let err = OfflineError {
+            cached: (8999..9012).map(DocumentType::Rfc).collect(),
+            ..err
+        };
+        let message = err.to_string();
+        assert!(
+            message.starts_with("RFC 9000 is not available offline; cached: RFC 8999, RFC 9000")
+        );
+        assert!(message.ends_with("RFC 9008 and 3 more"));
+    }
+}
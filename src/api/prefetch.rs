@@ -0,0 +1,148 @@
+use futures::stream::{self, FuturesUnordered, StreamExt};
+use tokio::sync::Semaphore;
+
+use super::DocumentFetcher;
+use crate::cache::{CacheManager, SyncReport};
+use crate::models::{DocumentType, Format};
+use crate::Result;
+
+/// Number of concurrent downloads used by [`DocumentFetcher::prefetch`] by default
+pub const DEFAULT_PARALLELISM: usize = 4;
+
+/// Result of prefetching a single document
+enum Outcome {
+    Present,
+    Fetched,
+    Failed(String),
+}
+
+impl DocumentFetcher {
+    /// Download many documents into the cache concurrently
+    ///
+    /// Documents with a fresh cached copy are skipped. At most
+    /// [`with_parallelism`](Self::with_parallelism) downloads run at once, and a
+    /// failed download doesn't stop the others; it is recorded in the report.
+    pub async fn prefetch(&self, docs: &[DocumentType], cache: &CacheManager) -> SyncReport {
+        let outcomes: Vec<Outcome> = stream::iter(docs)
+            .map(|doc| self.prefetch_one(doc, cache))
+            .buffered(self.parallelism)
+            .collect()
+            .await;
+
+        let mut report = SyncReport::default();
+        for (doc, outcome) in docs.iter().cloned().zip(outcomes) {
+            match outcome {
+                Outcome::Present => report.present.push((doc, Format::Text)),
+                Outcome::Fetched => report.fetched.push((doc, Format::Text)),
+                Outcome::Failed(e) => report.failed.push((doc, Format::Text, e)),
+            }
+        }
+        report
+    }
+
+    /// Fetch many documents concurrently, returning the results in input order
+    ///
+    /// At most `max_concurrent` fetches run at once; each document gets its
+    /// own result, so one failure doesn't stop the others.
+    pub async fn fetch_many(
+        &self,
+        docs: &[DocumentType],
+        max_concurrent: usize,
+    ) -> Vec<Result<(String, Format)>> {
+        let permits = Semaphore::new(max_concurrent.max(1));
+        let mut pending: FuturesUnordered<_> = docs
+            .iter()
+            .enumerate()
+            .map(|(i, doc)| {
+                let permits = &permits;
+                async move {
+                    let _permit = permits.acquire().await.expect("semaphore is never closed");
+                    (i, self.fetch(doc).await)
+                }
+            })
+            .collect();
+
+        let mut results: Vec<Option<Result<(String, Format)>>> =
+            docs.iter().map(|_| None).collect();
+        while let Some((i, result)) = pending.next().await {
+            results[i] = Some(result);
+        }
+        results.into_iter().flatten().collect()
+    }
+
+    async fn prefetch_one(&self, doc: &DocumentType, cache: &CacheManager) -> Outcome {
+        if cache.get_fresh(doc, Format::Text).is_some() {
+            return Outcome::Present;
+        }
+        let stored = async {
+            let (content, format) = self.fetch(doc).await?;
+            let text = crate::render::to_text(content, format, 80)?;
+            cache.store_document(doc, Format::Text, &text)
+        };
+        match stored.await {
+            Ok(()) => Outcome::Fetched,
+            Err(e) => Outcome::Failed(format!("{:#}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::OfflineMode;
+    use crate::Error;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_prefetch_reports_each_document() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        cache
+            .store_document(&DocumentType::Rfc(9000), Format::Text, "quic")
+            .unwrap();
+        let unsupported = DocumentType::External {
+            source: "iso".to_string(),
+            name: "8601".to_string(),
+        };
+
+        let fetcher = DocumentFetcher::new().unwrap().with_parallelism(2);
+        let report = fetcher
+            .prefetch(&[DocumentType::Rfc(9000), unsupported.clone()], &cache)
+            .await;
+
+        assert_eq!(
+            report.present,
+            vec![(DocumentType::Rfc(9000), Format::Text)]
+        );
+        assert!(report.fetched.is_empty());
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, unsupported);
+        assert!(!report.is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_many_keeps_input_order() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        for (rfc, text) in [(9000, "quic"), (9001, "tls")] {
+            cache
+                .store_document(&DocumentType::Rfc(rfc), Format::Text, text)
+                .unwrap();
+        }
+        let fetcher = DocumentFetcher::new()
+            .unwrap()
+            .with_cache(cache)
+            .with_offline_mode(OfflineMode::Offline);
+
+        let docs: Vec<DocumentType> = [9001, 9999, 9000].map(DocumentType::Rfc).to_vec();
+        let results = fetcher.fetch_many(&docs, 2).await;
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().0, "tls");
+        let Err(Error::Offline { error: err, .. }) = &results[1] else {
+            panic!("expected an offline error");
+        };
+        assert_eq!(err.requested, "RFC 9999");
+        assert_eq!(results[2].as_ref().unwrap().0, "quic");
+        assert!(fetcher.fetch_many(&[], 0).await.is_empty());
+    }
+}
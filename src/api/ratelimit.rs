@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use super::circuit::host_of;
+
+/// Requests per second allowed to each host unless configured otherwise
+const DEFAULT_RATE: f64 = 5.0;
+
+/// Token bucket of one host
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    /// Available requests; negative when callers are already waiting
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Per-host token-bucket rate limiter shared by the HTTP clients
+///
+/// Each host gets a bucket of `burst` requests that refills at `rate` requests
+/// per second. A request finding the bucket empty waits for its turn, so batch
+/// operations spread out instead of tripping the servers' abuse protections.
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Requests per second; `None` disables limiting
+    rate: Option<f64>,
+    burst: f64,
+    hosts: Mutex<HashMap<String, Bucket>>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::per_second(DEFAULT_RATE)
+    }
+}
+
+impl RateLimiter {
+    /// Allow `rate` requests per second to each host, in bursts of up to `burst`
+    ///
+    /// A rate of zero (or less) disables limiting.
+    pub fn new(rate: f64, burst: u32) -> Self {
+        Self {
+            rate: (rate > 0.0).then_some(rate),
+            burst: f64::from(burst.max(1)),
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Allow `rate` requests per second, in bursts of one second's worth
+    pub fn per_second(rate: f64) -> Self {
+        Self::new(rate, rate.ceil().clamp(1.0, f64::from(u32::MAX)) as u32)
+    }
+
+    /// Don't limit requests at all
+    pub fn unlimited() -> Self {
+        Self::new(0.0, 1)
+    }
+
+    /// Read the rate from `RFC_RATE_LIMIT` (requests per second, "0" or "off" to disable)
+    pub fn from_env() -> Self {
+        match std::env::var("RFC_RATE_LIMIT") {
+            Ok(value) if value.eq_ignore_ascii_case("off") => Self::unlimited(),
+            Ok(value) => value
+                .trim()
+                .parse()
+                .map(Self::per_second)
+                .unwrap_or_default(),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Get the process-wide limiter used by default by all clients
+    pub fn shared() -> Arc<RateLimiter> {
+        static SHARED: OnceLock<Arc<RateLimiter>> = OnceLock::new();
+        SHARED.get_or_init(|| Arc::new(Self::from_env())).clone()
+    }
+
+    /// Get the configured requests per second, if limiting is enabled
+    pub fn rate(&self) -> Option<f64> {
+        self.rate
+    }
+
+    /// Take a token for the host of a URL, returning how long to wait before using it
+    fn reserve(&self, url: &str, now: Instant) -> Duration {
+        let Some(rate) = self.rate else {
+            return Duration::ZERO;
+        };
+        let mut hosts = self.hosts.lock().unwrap();
+        let bucket = hosts.entry(host_of(url)).or_insert(Bucket {
+            tokens: self.burst,
+            updated: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(self.burst) - 1.0;
+        bucket.updated = now;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / rate)
+        }
+    }
+
+    /// Wait until a request to the host of a URL is allowed
+    pub async fn acquire(&self, url: &str) {
+        let wait = self.reserve(url, Instant::now());
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://datatracker.ietf.org/api/v1/doc/document/";
+
+    #[test]
+    fn test_bursts_then_waits() {
+        let limiter = RateLimiter::new(2.0, 2);
+        let now = Instant::now();
+        assert_eq!(limiter.reserve(URL, now), Duration::ZERO);
+        assert_eq!(limiter.reserve(URL, now), Duration::ZERO);
+        assert_eq!(limiter.reserve(URL, now), Duration::from_millis(500));
+        // Waiting callers queue up behind each other
+        assert_eq!(limiter.reserve(URL, now), Duration::from_secs(1));
+
+        // Other hosts have their own bucket
+        let other = "https://www.rfc-editor.org/rfc/rfc9000.txt";
+        assert_eq!(limiter.reserve(other, now), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_refills_over_time() {
+        let limiter = RateLimiter::new(1.0, 1);
+        let now = Instant::now();
+        assert_eq!(limiter.reserve(URL, now), Duration::ZERO);
+        assert_eq!(limiter.reserve(URL, now), Duration::from_secs(1));
+        let later = now + Duration::from_secs(5);
+        // The bucket never holds more than the burst
+        assert_eq!(limiter.reserve(URL, later), Duration::ZERO);
+        assert_eq!(limiter.reserve(URL, later), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_unlimited() {
+        let limiter = RateLimiter::unlimited();
+        let now = Instant::now();
+        assert_eq!(limiter.rate(), None);
+        for _ in 0..100 {
+            assert_eq!(limiter.reserve(URL, now), Duration::ZERO);
+        }
+        assert_eq!(RateLimiter::per_second(0.5).rate(), Some(0.5));
+    }
+}
@@ -0,0 +1,257 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{RequestBuilder, Response, StatusCode};
+
+use super::{CircuitBreaker, RateLimiter};
+use crate::models::DocumentType;
+use crate::Error;
+
+/// Retries after a 429 or 503 response unless configured otherwise
+const MAX_RETRIES: u32 = 3;
+/// Longest Retry-After that is waited for
+const MAX_DELAY: Duration = Duration::from_secs(60);
+/// First delay when the server doesn't send Retry-After; doubled on every retry
+const BACKOFF: Duration = Duration::from_secs(1);
+
+/// How rate-limited (429) and unavailable (503) responses are retried
+///
+/// The delay the server asks for in `Retry-After` (seconds or an HTTP date) is
+/// honored up to `max_delay`; without the header the delay starts at
+/// `backoff` and doubles with every attempt. A response asking for longer than
+/// `max_delay`, or the last one after `max_retries`, is returned as is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub max_delay: Duration,
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: MAX_RETRIES,
+            max_delay: MAX_DELAY,
+            backoff: BACKOFF,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Read the number of retries from `RFC_MAX_RETRIES` ("0" disables retrying)
+    pub fn from_env() -> Self {
+        let max_retries = std::env::var("RFC_MAX_RETRIES")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(MAX_RETRIES);
+        Self {
+            max_retries,
+            ..Self::default()
+        }
+    }
+
+    /// Get how long to wait before retrying a response, or `None` to return it
+    pub fn delay(
+        &self,
+        status: StatusCode,
+        headers: &HeaderMap,
+        attempt: u32,
+        now: DateTime<Utc>,
+    ) -> Option<Duration> {
+        let retryable = matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+        );
+        if !retryable || attempt >= self.max_retries {
+            return None;
+        }
+        let delay = match headers.get(RETRY_AFTER).and_then(|v| v.to_str().ok()) {
+            Some(value) => retry_after(value, now)?,
+            None => self.backoff.saturating_mul(2u32.saturating_pow(attempt)),
+        };
+        (delay <= self.max_delay).then_some(delay)
+    }
+}
+
+/// Parse a Retry-After value: delay seconds or an HTTP date
+fn retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    // A date in the past means "retry now"
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+/// Get the error for an unsuccessful response to a request for `url`
+///
+/// A 404 for a known document is [`Error::NotFound`] and a 429 that outlasted
+/// the retries is [`Error::RateLimited`]; anything else is a network error.
+pub(super) fn status_error(url: &str, response: &Response, doc: Option<&DocumentType>) -> Error {
+    let status = response.status();
+    match (status, doc) {
+        (StatusCode::NOT_FOUND, Some(doc)) => Error::not_found(doc.clone()),
+        (StatusCode::TOO_MANY_REQUESTS, _) => Error::rate_limited(
+            response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|value| retry_after(value, Utc::now())),
+        ),
+        _ => Error::Network(anyhow::anyhow!("Failed to fetch {}: HTTP {}", url, status)),
+    }
+}
+
+/// Send a GET request within the rate limit and through the circuit breaker,
+/// retrying it as the policy allows
+///
+/// Only the outcome of the last attempt counts toward the breaker, so the
+/// 503s being retried can't open the circuit in the middle of the retries.
+pub(super) async fn send(
+    breaker: &CircuitBreaker,
+    limiter: &RateLimiter,
+    policy: &RetryPolicy,
+    url: &str,
+    mut request: RequestBuilder,
+) -> Result<Response> {
+    breaker.check(url)?;
+    let mut attempt = 0;
+    loop {
+        let retry = request.try_clone();
+        limiter.acquire(url).await;
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                breaker.record_failure(url);
+                return Err(e.into());
+            }
+        };
+        let delay = policy.delay(response.status(), response.headers(), attempt, Utc::now());
+        match (delay, retry) {
+            (Some(delay), Some(next)) => {
+                tokio::time::sleep(delay).await;
+                request = next;
+                attempt += 1;
+            }
+            _ => {
+                breaker.record_response(url, &response);
+                return Ok(response);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn retry_after_headers(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_honors_retry_after() {
+        let policy = RetryPolicy::default();
+        let now = DateTime::parse_from_rfc3339("2026-10-14T09:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let too_many = StatusCode::TOO_MANY_REQUESTS;
+
+        let seconds = retry_after_headers("5");
+        assert_eq!(
+            policy.delay(too_many, &seconds, 0, now),
+            Some(Duration::from_secs(5))
+        );
+        let date = retry_after_headers("Wed, 14 Oct 2026 09:30:20 GMT");
+        assert_eq!(
+            policy.delay(StatusCode::SERVICE_UNAVAILABLE, &date, 0, now),
+            Some(Duration::from_secs(20))
+        );
+        let past = retry_after_headers("Wed, 14 Oct 2026 09:00:00 GMT");
+        assert_eq!(policy.delay(too_many, &past, 0, now), Some(Duration::ZERO));
+
+        // Waits longer than the maximum are not worth it
+        let long = retry_after_headers("3600");
+        assert_eq!(policy.delay(too_many, &long, 0, now), None);
+    }
+
+    #[test]
+    fn test_backoff_and_limits() {
+        let policy = RetryPolicy::default();
+        let now = Utc::now();
+        let none = HeaderMap::new();
+        let unavailable = StatusCode::SERVICE_UNAVAILABLE;
+
+        assert_eq!(
+            policy.delay(unavailable, &none, 0, now),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            policy.delay(unavailable, &none, 2, now),
+            Some(Duration::from_secs(4))
+        );
+        assert_eq!(policy.delay(unavailable, &none, 3, now), None);
+        assert_eq!(
+            policy.delay(StatusCode::INTERNAL_SERVER_ERROR, &none, 0, now),
+            None
+        );
+        assert_eq!(RetryPolicy::none().delay(unavailable, &none, 0, now), None);
+    }
+
+    #[tokio::test]
+    async fn test_retries_dont_open_the_circuit() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Answer three requests with 503 and the fourth with the document
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/rfc/rfc9000.txt", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            for status in ["503 Service Unavailable"; 3].into_iter().chain(["200 OK"]) {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = [0; 1024];
+                let _ = socket.read(&mut request).await.unwrap();
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: 4\r\nConnection: close\r\n\r\nQUIC",
+                    status
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+        let policy = RetryPolicy {
+            backoff: Duration::ZERO,
+            ..RetryPolicy::default()
+        };
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let response = send(
+            &breaker,
+            &RateLimiter::unlimited(),
+            &policy,
+            &url,
+            client.get(&url),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(breaker.check(&url).is_ok());
+    }
+}
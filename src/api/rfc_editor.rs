@@ -1,92 +1,810 @@
-use std::time::Duration;
+use std::io::Write;
+use std::sync::Arc;
 
-use anyhow::{Context, Result};
-use reqwest::Client;
+use anyhow::{anyhow, Context, Result};
+use reqwest::header::{
+    HeaderMap, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 
-use crate::models::{DocumentType, Format};
+use super::retry::status_error;
+use super::schema;
+use super::{
+    CircuitBreaker, Coalescer, DocumentSource, FetcherBuilder, OfflineError, OfflineMode,
+    RateLimiter, RetryPolicy, SourceConfig, TranslationSources,
+};
+use crate::cache::{CacheManager, ContentChange, Provenance, Validators};
+use crate::hooks::{Hook, HookEvent, Hooks};
+use crate::models::{
+    Author, DocumentType, DraftRevision, Format, FormatPreference, FormatStrategy, RfcMetadata,
+    Subseries, FIRST_V3_RFC,
+};
+use crate::parse::{Artwork, ParsedDocument, SectionExcerpt};
+use crate::plugin::PluginRegistry;
+use crate::profile::{self, Phase};
+use crate::Error;
 
 /// Response from datatracker document API
 #[derive(Debug, Deserialize)]
 struct DraftInfo {
+    #[serde(default, deserialize_with = "schema::lenient_string")]
     rev: Option<String>,
+    #[serde(default, deserialize_with = "lenient_history")]
+    rev_history: Vec<ApiRevision>,
 }
 
+/// Entry of the revision history in datatracker's `doc.json`
+#[derive(Debug, Deserialize)]
+struct ApiRevision {
+    name: String,
+    #[serde(deserialize_with = "schema::lenient_text")]
+    rev: String,
+    #[serde(default, deserialize_with = "schema::lenient_string")]
+    published: Option<String>,
+}
+
+/// Read a revision history, leaving out entries that can't be read
+fn lenient_history<'de, D: serde::Deserializer<'de>>(de: D) -> Result<Vec<ApiRevision>, D::Error> {
+    Ok(match serde_json::Value::deserialize(de)? {
+        serde_json::Value::Array(entries) => entries
+            .into_iter()
+            .filter_map(|e| serde_json::from_value(e).ok())
+            .collect(),
+        _ => Vec::new(),
+    })
+}
+
+/// Response from the RFC Editor `rfcNNNN.json` metadata endpoint
+#[derive(Debug, Deserialize)]
+struct ApiRfcMetadata {
+    title: String,
+    #[serde(default)]
+    authors: Vec<String>,
+    pub_date: Option<String>,
+    status: Option<String>,
+    pub_status: Option<String>,
+    source: Option<String>,
+    // Published as a string ("151") in most records, but be lenient
+    page_count: Option<serde_json::Value>,
+    #[serde(rename = "abstract")]
+    abstract_text: Option<String>,
+    draft: Option<String>,
+    #[serde(default)]
+    obsoletes: Vec<String>,
+    #[serde(default)]
+    obsoleted_by: Vec<String>,
+    #[serde(default)]
+    updates: Vec<String>,
+    #[serde(default)]
+    updated_by: Vec<String>,
+    errata_url: Option<String>,
+    doi: Option<String>,
+}
+
+/// Largest buffer allocated up front for a response body, whatever its Content-Length
+const MAX_PREALLOCATION: u64 = 16 * 1024 * 1024;
+
+/// Called with the document, the bytes read so far and the total size, if known
+type ProgressFn = dyn Fn(&DocumentType, u64, Option<u64>) + Send + Sync;
+
 /// Client for fetching RFC and draft content
 pub struct DocumentFetcher {
-    client: Client,
+    pub(super) client: Client,
+    pub(super) breaker: Arc<CircuitBreaker>,
+    limiter: Arc<RateLimiter>,
+    retry: RetryPolicy,
+    inflight: Coalescer,
+    /// Cache whose documents are revalidated instead of downloaded again
+    cache: Option<CacheManager>,
+    /// Maximum number of concurrent downloads of bulk operations
+    pub(super) parallelism: usize,
+    /// Where community translations are published
+    pub(super) translations: TranslationSources,
+    /// Which format `fetch` tries first
+    strategy: FormatStrategy,
+    /// Formats `fetch` tries in order, replacing the strategy
+    preference: Option<FormatPreference>,
+    /// Fired on downloads and new draft revisions
+    hooks: Hooks,
+    /// Base URLs and mirrors documents are fetched from
+    pub(super) sources: SourceConfig,
+    /// Serve only from the cache instead of the network
+    offline: OfflineMode,
+    /// Told about every chunk of a document download
+    progress: Option<Arc<ProgressFn>>,
+    /// Sources of the external documents of registered schemes
+    plugins: Option<Arc<PluginRegistry>>,
 }
 
 impl DocumentFetcher {
     /// Create a new RFC Editor client
-    pub fn new() -> Result<Self> {
-        Ok(Self {
-            client: Client::builder()
-                .user_agent(concat!("rfc-cli/", env!("CARGO_PKG_VERSION")))
-                .timeout(Duration::from_secs(30))
-                .build()
-                .context("Failed to create HTTP client")?,
-        })
+    ///
+    /// The format strategy is read from `RFC_FORMAT_STRATEGY` ("text" or "auto")
+    /// and replaced by the formats listed in `RFC_FORMAT_PREFERENCE` (e.g.,
+    /// "xml,text,html"), and `RFC_HOOK` names a command run on every download and new revision.
+    /// Proxies and certificates are configured as in [`FetcherBuilder::from_env`].
+    pub fn new() -> crate::Result<Self> {
+        FetcherBuilder::from_env().build()
+    }
+
+    /// Get a builder to configure proxies and TLS
+    pub fn builder() -> FetcherBuilder {
+        FetcherBuilder::from_env()
+    }
+
+    /// Create a client sending its requests with `client`
+    pub(super) fn from_client(client: Client) -> Self {
+        Self {
+            client,
+            breaker: CircuitBreaker::shared(),
+            limiter: RateLimiter::shared(),
+            retry: RetryPolicy::from_env(),
+            inflight: Coalescer::new(),
+            cache: None,
+            parallelism: super::prefetch::DEFAULT_PARALLELISM,
+            translations: TranslationSources::default(),
+            strategy: FormatStrategy::from_env(),
+            preference: FormatPreference::from_env(),
+            hooks: Hooks::from_env(),
+            sources: SourceConfig::from_env(),
+            offline: OfflineMode::from_env(),
+            progress: None,
+            plugins: None,
+        }
+    }
+
+    /// Revalidate documents cached in `cache` with conditional requests
+    ///
+    /// The ETag and Last-Modified headers of every download are stored in the
+    /// cache; when the document is fetched again while a copy is cached, a 304
+    /// response returns the cached copy instead of downloading it again.
+    /// Every fetch is also recorded in the document's provenance log.
+    pub fn with_cache(mut self, cache: CacheManager) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Limit bulk operations like [`prefetch`](Self::prefetch) to `n` concurrent downloads
+    pub fn with_parallelism(mut self, n: usize) -> Self {
+        self.parallelism = n.max(1);
+        self
+    }
+
+    /// Fire a hook on every download and whenever a newer draft revision is seen
+    ///
+    /// New revisions are detected against the cache set with `with_cache`.
+    pub fn with_hook(mut self, hook: impl Hook + 'static) -> Self {
+        self.hooks.register(hook);
+        self
+    }
+
+    /// Report the progress of document downloads, e.g. for a progress bar
+    ///
+    /// `callback` is called with the document, the bytes read so far and the
+    /// size announced by the server (`None` without a Content-Length) after
+    /// every chunk of the response body. Revalidated and cached documents
+    /// don't report progress.
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&DocumentType, u64, Option<u64>) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Fetch external documents of the schemes registered in `plugins` from their sources
+    ///
+    /// A document resolved by [`PluginRegistry::resolve`] as "3gpp:23.501"
+    /// is then fetched (and cached by [`fetch_to_cache`](Self::fetch_to_cache))
+    /// through the "3gpp" source; the registry's default source isn't used.
+    pub fn with_plugins(mut self, plugins: Arc<PluginRegistry>) -> Self {
+        self.plugins = Some(plugins);
+        self
+    }
+
+    /// Get the registered source of an external document, if any
+    fn plugin_source(&self, doc: &DocumentType) -> Option<&dyn DocumentSource> {
+        let DocumentType::External { source, .. } = doc else {
+            return None;
+        };
+        self.plugins.as_ref()?.source(source)
+    }
+
+    /// Limit requests with `limiter` instead of the process-wide limiter
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Retry 429 and 503 responses as `policy` allows instead of failing
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Send a request within the rate limit, through the circuit breaker, with retries
+    pub(super) async fn send(&self, url: &str, request: RequestBuilder) -> Result<Response> {
+        if self.offline.is_offline() {
+            return Err(OfflineError::new(url, self.cache.as_ref()).into());
+        }
+        let sent = super::retry::send(&self.breaker, &self.limiter, &self.retry, url, request);
+        profile::time_async(Phase::Network, sent).await
+    }
+
+    /// Fetch documents from the base URLs and mirrors of `sources`
+    pub fn with_sources(mut self, sources: SourceConfig) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Serve documents only from the cache (see [`OfflineMode`])
+    ///
+    /// The cache set with `with_cache` is used, or the default cache.
+    pub fn with_offline_mode(mut self, mode: OfflineMode) -> Self {
+        self.offline = mode;
+        self
+    }
+
+    /// Get the cache documents are served from while offline
+    fn offline_cache(&self) -> Option<CacheManager> {
+        self.cache.clone().or_else(|| CacheManager::new().ok())
+    }
+
+    /// Choose which format `fetch` tries first
+    pub fn with_format_strategy(mut self, strategy: FormatStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Try the formats of `fetch` in this order instead of following the format strategy
+    pub fn with_format_preference(mut self, preference: FormatPreference) -> Self {
+        self.preference = Some(preference);
+        self
+    }
+
+    /// Get the order in which `fetch` tries the formats of a document
+    pub fn format_preference(&self, doc: &DocumentType) -> FormatPreference {
+        match &self.preference {
+            Some(preference) => preference.clone(),
+            None => self.strategy.preference(doc),
+        }
     }
 
     /// Fetch document in the preferred format (text first, fallback to HTML)
-    pub async fn fetch(&self, doc: &DocumentType) -> Result<(String, Format)> {
+    ///
+    /// With [`FormatStrategy::Auto`], v3-native RFCs are fetched as HTML first;
+    /// a [`FormatPreference`] set with [`with_format_preference`](Self::with_format_preference)
+    /// replaces the strategy.
+    pub async fn fetch(&self, doc: &DocumentType) -> crate::Result<(String, Format)> {
+        self.fetch_preferring(doc, &self.format_preference(doc))
+            .await
+    }
+
+    /// Fetch a document in the first format of `preference` it is published in
+    ///
+    /// Subseries are fetched as the RFC Editor's text bundle and external
+    /// specifications as HTML, whatever the preference.
+    pub async fn fetch_preferring(
+        &self,
+        doc: &DocumentType,
+        preference: &FormatPreference,
+    ) -> crate::Result<(String, Format)> {
+        if self.offline.is_offline() {
+            return Ok(self.fetch_cached(doc, preference).await?);
+        }
+        if let Some(source) = self.plugin_source(doc) {
+            return source.fetch(doc).await;
+        }
+
+        // W3C and WHATWG specifications are only published as HTML
+        if let DocumentType::External { source, .. } = doc {
+            let url = doc
+                .external_url()
+                .with_context(|| format!("Unsupported document source: {}", source))?;
+            let content = self.fetch_document(&url, doc, Format::Html).await?;
+            return Ok((content, Format::Html));
+        }
+
+        let doc = match doc {
+            // Prefer the bundle, which contains every member RFC; subseries
+            // without one are served by their most recent member
+            DocumentType::Subseries(series, number) => {
+                match self
+                    .fetch_document(&self.text_url(doc), doc, Format::Text)
+                    .await
+                {
+                    Ok(content) => return Ok((content, Format::Text)),
+                    Err(bundle_err) => self.bundle_fallback(*series, *number, bundle_err).await?,
+                }
+            }
+            _ => self.resolve_draft_version(doc).await?,
+        };
+
+        // Try each format in turn, text from each mirror; hosts whose circuit
+        // is open fail immediately, so a down mirror costs no timeout
+        let mut failures = Vec::new();
+        for &format in preference.formats() {
+            let urls = match format {
+                Format::Text => self.text_mirrors(&doc),
+                _ => match self.urls(&doc, format) {
+                    Ok(urls) => urls,
+                    Err(e) => {
+                        failures.push((format, e.into()));
+                        continue;
+                    }
+                },
+            };
+            let mut errors = Vec::new();
+            for url in urls {
+                match self.fetch_document(&url, &doc, format).await {
+                    Ok(content) => return Ok((content, format)),
+                    Err(e) => errors.push(e),
+                }
+            }
+            let err = mirror_error(errors).unwrap_or_else(|| anyhow!("no {} source", format));
+            failures.push((format, err));
+        }
+        Err(fallback_error(failures, &doc).into())
+    }
+
+    /// Get a document from the cache in the preferred format, or fail with an [`OfflineError`]
+    async fn fetch_cached(
+        &self,
+        doc: &DocumentType,
+        preference: &FormatPreference,
+    ) -> Result<(String, Format)> {
+        let cache = self.offline_cache();
+        let doc = self.resolve_draft_version(doc).await?;
+        preference
+            .formats()
+            .iter()
+            .find_map(|&format| Some((cache.as_ref()?.get_document(&doc, format)?, format)))
+            .ok_or_else(|| OfflineError::new(doc.to_string(), cache.as_ref()).into())
+    }
+
+    /// Fetch a document in a specific format, without falling back to another one
+    pub async fn fetch_format(&self, doc: &DocumentType, format: Format) -> crate::Result<String> {
+        if format.is_binary() {
+            return Err(
+                anyhow!("{} is a binary format; use fetch_bytes", format.extension()).into(),
+            );
+        }
+        if let Some(source) = self.plugin_source(doc) {
+            return source.fetch_format(doc, format).await;
+        }
+        let bytes = self.fetch_bytes(doc, format).await?;
+        Ok(String::from_utf8(bytes).context("Document is not valid UTF-8")?)
+    }
+
+    /// Fetch the raw content of a document in a specific format
+    pub async fn fetch_bytes(&self, doc: &DocumentType, format: Format) -> crate::Result<Vec<u8>> {
+        if let Some(source) = self.plugin_source(doc) {
+            return source.fetch_bytes(doc, format).await;
+        }
+        let doc = self.resolve_draft_version(doc).await?;
+        let urls = match format {
+            Format::Text => self.text_mirrors(&doc),
+            _ => self.urls(&doc, format)?,
+        };
+        // Fail over to the mirrors in order
+        let mut errors = Vec::new();
+        for url in urls {
+            match self.download(&url, Some((&doc, format))).await {
+                Ok(content) => return Ok(content.to_vec()),
+                Err(e) => errors.push(e),
+            }
+        }
+        Err(mirror_error(errors)
+            .unwrap_or_else(|| anyhow!("No source for {}", doc))
+            .into())
+    }
+
+    /// Download a document straight into `cache`, without holding it in memory
+    ///
+    /// The body is streamed to a temporary file next to the cached copy and
+    /// moved into place once complete, so readers never see a partial
+    /// document. Formats are tried in the order [`fetch`](Self::fetch) uses,
+    /// and the format that was stored is returned. A copy already in `cache`
+    /// is revalidated with a conditional request. A subseries without a
+    /// bundle is stored as its most recent member, as `fetch` serves it.
+    pub async fn fetch_to_cache(
+        &self,
+        doc: &DocumentType,
+        cache: &CacheManager,
+    ) -> crate::Result<Format> {
         let doc = self.resolve_draft_version(doc).await?;
+        let formats = match &doc {
+            DocumentType::External { .. } => vec![Format::Html],
+            // Subseries are stored as the RFC Editor's bundle of their members
+            DocumentType::Subseries(..) => vec![Format::Text],
+            _ => self.format_preference(&doc).formats().to_vec(),
+        };
+        if self.offline.is_offline() {
+            let cached = cache.cached_formats(&doc);
+            return formats
+                .into_iter()
+                .find(|format| cached.contains(format))
+                .ok_or_else(|| OfflineError::new(doc.to_string(), Some(cache)).into());
+        }
+        if let Some(source) = self.plugin_source(&doc) {
+            let (content, format) = source.fetch(&doc).await?;
+            cache.store_document(&doc, format, &content)?;
+            return Ok(format);
+        }
+
+        match self.stream_any(&doc, &doc, &formats, cache).await {
+            Ok(format) => Ok(format),
+            // Subseries without a bundle are stored as their most recent member, as fetch serves them
+            Err(bundle_err) => match &doc {
+                DocumentType::Subseries(series, number) => {
+                    let member = self.bundle_fallback(*series, *number, bundle_err).await?;
+                    let formats = self.format_preference(&member).formats().to_vec();
+                    Ok(self.stream_any(&member, &doc, &formats, cache).await?)
+                }
+                _ => Err(bundle_err.into()),
+            },
+        }
+    }
+
+    /// Get the most recent member serving a subseries whose bundle couldn't be fetched
+    async fn bundle_fallback(
+        &self,
+        series: Subseries,
+        number: u32,
+        bundle_err: impl std::fmt::Display,
+    ) -> crate::Result<DocumentType> {
+        Ok(self.latest_member(series, number).await.with_context(|| {
+            format!(
+                "No bundle for {} {} ({})",
+                series.label(),
+                number,
+                bundle_err
+            )
+        })?)
+    }
+
+    /// Stream the first of `formats` that can be downloaded for `source` into the cached copy of `doc`
+    async fn stream_any(
+        &self,
+        source: &DocumentType,
+        doc: &DocumentType,
+        formats: &[Format],
+        cache: &CacheManager,
+    ) -> Result<Format> {
+        let mut last_err = None;
+        for &format in formats {
+            let mut errors = Vec::new();
+            for url in self.urls(source, format)? {
+                match self.stream_to_cache(&url, doc, format, cache).await {
+                    Ok(()) => return Ok(format),
+                    Err(e) => errors.push(e),
+                }
+            }
+            last_err = mirror_error(errors).or(last_err);
+        }
+        Err(last_err.unwrap_or_else(|| anyhow!("No source for {}", source)))
+    }
+
+    /// Stream one URL into the cache, revalidating the cached copy if there is one
+    async fn stream_to_cache(
+        &self,
+        url: &str,
+        doc: &DocumentType,
+        format: Format,
+        cache: &CacheManager,
+    ) -> Result<()> {
+        let cached = cache
+            .get_validators(doc, format)
+            .filter(|v| v.url == url && cache.document_metadata(doc, format).is_some());
+
+        let mut request = self.client.get(url);
+        if let Some(validators) = &cached {
+            if let Some(etag) = &validators.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &validators.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        let mut response = self
+            .send(url, request)
+            .await
+            .context("Failed to fetch document")?;
+
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            if let Some(validators) = cached {
+                // Hash the cached copy as it is read, without loading it whole
+                let mut content = cache
+                    .open_document(doc, format)
+                    .context("Failed to read cached document")?;
+                let mut hasher = Sha256::new();
+                let size = std::io::copy(&mut content, &mut hasher)
+                    .context("Failed to read cached document")?;
+                let sha256 = format!("{:x}", hasher.finalize());
+                let record =
+                    Provenance::with_checksum(format, status.as_u16(), &validators, sha256, size);
+                return Ok(cache.record_provenance(doc, &record)?);
+            }
+        }
+        if !status.is_success() {
+            return Err(status_error(url, &response, Some(doc)).into());
+        }
 
-        // Try text first
-        let text_url = self.text_url(&doc);
-        match self.fetch_content(&text_url).await {
-            Ok(content) => Ok((content, Format::Text)),
-            Err(text_err) => {
-                // Fallback to HTML
-                let html_url = self.html_url(&doc);
-                let content = self.fetch_content(&html_url).await.with_context(|| {
-                    format!(
-                        "Plain text fetch failed ({}); HTML fallback also failed",
-                        text_err
-                    )
-                })?;
-                Ok((content, Format::Html))
+        let validators = validators_from_headers(url, response.headers());
+        let total = response.content_length();
+        let mut staged = cache.stage(doc, format, total.unwrap_or(0))?;
+        let mut hasher = Sha256::new();
+        while let Some(chunk) = profile::time_async(Phase::Network, response.chunk())
+            .await
+            .context("Failed to read document content")?
+        {
+            staged
+                .write_all(&chunk)
+                .context("Failed to write document to cache")?;
+            hasher.update(&chunk);
+            if let Some(progress) = &self.progress {
+                progress(doc, staged.size(), total);
+            }
+        }
+        staged
+            .flush()
+            .context("Failed to write document to cache")?;
+        let size = staged.size();
+        let sha256 = format!("{:x}", hasher.finalize());
+        let change = match cache.pinned_checksum(doc, format) {
+            Some(pinned) if pinned != sha256 => {
+                cache.check_content(doc, format, url, &staged.content()?)
             }
+            _ => None,
+        };
+        let record = Provenance::with_checksum(format, status.as_u16(), &validators, sha256, size);
+        if let Some(change) = change {
+            // Keep the pinned copy; the changed one waits for CacheManager::accept_change
+            cache.quarantine_staged(staged)?;
+            cache.record_provenance(doc, &record)?;
+            return self.changed(change, cache).map(|_| ());
+        }
+        cache.install(staged)?;
+
+        if !validators.is_empty() {
+            cache.store_validators(doc, format, &validators)?;
+        }
+        cache.record_provenance(doc, &record)?;
+        self.hooks.emit(&HookEvent::Fetched {
+            doc: doc.clone(),
+            format,
+            url: url.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Fetch the authors of a document from its xml2rfc source, with their
+    /// names as written and in ASCII
+    pub async fn authors(&self, doc: &DocumentType) -> crate::Result<Vec<Author>> {
+        let xml = self.fetch_format(doc, Format::Xml).await?;
+        Ok(crate::parse::xml_authors(&xml))
+    }
+
+    /// Extract the figures and code samples of a document, from its xml2rfc
+    /// source if one is published and from its text otherwise
+    ///
+    /// Only a missing source falls back to the text; other failures to fetch
+    /// it are returned.
+    pub async fn artwork(&self, doc: &DocumentType) -> crate::Result<Vec<Artwork>> {
+        let has_xml = match doc {
+            DocumentType::Rfc(number) => *number >= FIRST_V3_RFC,
+            DocumentType::Draft(_) => true,
+            DocumentType::Subseries(..) | DocumentType::External { .. } => false,
+        };
+        if has_xml {
+            match self.fetch_format(doc, Format::Xml).await {
+                Ok(xml) => return Ok(crate::parse::xml_artwork(&xml)),
+                Err(Error::NotFound { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        let (content, format) = self.fetch(doc).await?;
+        let text = crate::render::to_text(content, format, 80)?;
+        Ok(crate::parse::artwork(&text))
+    }
+
+    /// Fetch publication metadata for an RFC from the RFC Editor
+    pub async fn rfc_metadata(&self, number: u32) -> crate::Result<RfcMetadata> {
+        let url = self
+            .sources
+            .rfc_editor_url(&format!("/rfc/rfc{}.json", number));
+        let response = self
+            .send(&url, self.client.get(&url))
+            .await
+            .context("Failed to query RFC metadata")?;
+
+        if !response.status().is_success() {
+            return Err(status_error(
+                &url,
+                &response,
+                Some(&DocumentType::Rfc(number)),
+            ));
         }
+
+        let api: ApiRfcMetadata = profile::time_async(Phase::Network, response.json())
+            .await
+            .context("Failed to parse RFC metadata")?;
+
+        Ok(Self::convert_rfc_metadata(number, api))
+    }
+
+    /// Convert an RFC Editor metadata response to our model
+    fn convert_rfc_metadata(number: u32, api: ApiRfcMetadata) -> RfcMetadata {
+        fn doc_list(ids: Vec<String>) -> Vec<DocumentType> {
+            ids.iter()
+                .filter_map(|id| DocumentType::parse(id))
+                .collect()
+        }
+
+        let pages = api.page_count.and_then(|v| match v {
+            serde_json::Value::Number(n) => n.as_u64().and_then(|n| u32::try_from(n).ok()),
+            serde_json::Value::String(s) => s.trim().parse().ok(),
+            _ => None,
+        });
+
+        RfcMetadata {
+            number,
+            title: api.title,
+            authors: api.authors.into_iter().map(Author::new).collect(),
+            pub_date: api.pub_date,
+            status: api.status,
+            pub_status: api.pub_status,
+            source: api.source,
+            pages,
+            abstract_text: api.abstract_text,
+            draft: api.draft,
+            obsoletes: doc_list(api.obsoletes),
+            obsoleted_by: doc_list(api.obsoleted_by),
+            updates: doc_list(api.updates),
+            updated_by: doc_list(api.updated_by),
+            errata_url: api.errata_url.filter(|u| !u.is_empty()),
+            doi: api.doi.filter(|d| !d.trim().is_empty()),
+            license: None,
+        }
+    }
+
+    /// Get the RFCs that currently make up a subseries number, from the RFC index
+    pub async fn subseries_members(
+        &self,
+        series: Subseries,
+        number: u32,
+    ) -> crate::Result<Vec<u32>> {
+        let index = self
+            .fetch_content(&self.sources.rfc_editor_url(super::index::RFC_INDEX_PATH))
+            .await
+            .context("Failed to fetch RFC index")?;
+        Ok(parse_subseries_members(&index, series, number))
+    }
+
+    /// Get the most recent RFC of a subseries number
+    pub async fn latest_member(
+        &self,
+        series: Subseries,
+        number: u32,
+    ) -> crate::Result<DocumentType> {
+        let members = self.subseries_members(series, number).await?;
+        Ok(members
+            .into_iter()
+            .max()
+            .map(DocumentType::Rfc)
+            .with_context(|| format!("{} {} has no member RFCs", series.label(), number))?)
+    }
+
+    /// Fetch only the Security Considerations section of a document, with its
+    /// cross-references resolved; None if the document has no such section
+    pub async fn security_considerations(
+        &self,
+        doc: &DocumentType,
+    ) -> crate::Result<Option<SectionExcerpt>> {
+        let (content, format) = self.fetch(doc).await?;
+        let text = crate::render::to_text(content, format, 80)?;
+        let parsed = ParsedDocument::parse(&text);
+        Ok(parsed
+            .security_considerations()
+            .map(|section| parsed.excerpt(&text, section)))
+    }
+
+    /// Fetch a single section of a document, with its cross-references resolved
+    ///
+    /// The section is given by number, anchor or title (e.g., "5.2",
+    /// "Appendix A", "#section-3.1", "Security Considerations").
+    pub async fn get_section(
+        &self,
+        doc: &DocumentType,
+        section: &str,
+    ) -> crate::Result<SectionExcerpt> {
+        let (content, format) = self.fetch(doc).await?;
+        let text = crate::render::to_text(content, format, 80)?;
+        let parsed = ParsedDocument::parse(&text);
+        let found = parsed
+            .find_section(section)
+            .with_context(|| format!("{} has no section '{}'", doc, section.trim()))?;
+        Ok(parsed.excerpt(&text, found))
     }
 
     /// Resolve a draft name to include its version number if missing
     async fn resolve_draft_version(&self, doc: &DocumentType) -> Result<DocumentType> {
         match doc {
-            DocumentType::Rfc(_) => Ok(doc.clone()),
+            DocumentType::Rfc(_) | DocumentType::Subseries(..) | DocumentType::External { .. } => {
+                Ok(doc.clone())
+            }
             DocumentType::Draft(name) => {
                 // Check if already has a version number (ends with -NN)
                 if Self::has_version_suffix(name) {
                     return Ok(doc.clone());
                 }
 
-                // Query datatracker for the latest version
-                let url = format!("https://datatracker.ietf.org/doc/{}/doc.json", name);
-                let response = self
-                    .client
-                    .get(&url)
-                    .send()
-                    .await
-                    .context("Failed to query draft info")?;
-
-                if !response.status().is_success() {
-                    anyhow::bail!("Draft not found: {}", name);
+                // Offline, the newest cached revision is the latest one known
+                if self.offline.is_offline() {
+                    let cache = self.offline_cache();
+                    return match cache.as_ref().and_then(|c| c.latest_known_revision(name)) {
+                        Some(rev) => Ok(DocumentType::Draft(format!("{}-{:02}", name, rev))),
+                        None => Err(OfflineError::new(doc.to_string(), cache.as_ref()).into()),
+                    };
                 }
 
-                let info: DraftInfo = response
-                    .json()
-                    .await
-                    .context("Failed to parse draft info")?;
-
-                match info.rev {
-                    Some(rev) => Ok(DocumentType::Draft(format!("{}-{}", name, rev))),
-                    None => Ok(doc.clone()),
+                // Query datatracker for the latest version
+                let info = self.draft_info(name).await?;
+                let Some(rev) = info.rev else {
+                    return Ok(doc.clone());
+                };
+                if let (Some(cache), Ok(revision)) = (&self.cache, rev.parse::<u32>()) {
+                    match cache.latest_known_revision(name) {
+                        Some(previous) if previous < revision => {
+                            self.hooks.emit(&HookEvent::NewRevision {
+                                doc: doc.clone(),
+                                previous,
+                                revision,
+                            });
+                        }
+                        _ => {}
+                    }
                 }
+                Ok(DocumentType::Draft(format!("{}-{}", name, rev)))
             }
         }
     }
 
+    /// Query datatracker's `doc.json` of a draft
+    async fn draft_info(&self, name: &str) -> Result<DraftInfo> {
+        let url = self
+            .sources
+            .datatracker_url(&format!("/doc/{}/doc.json", name));
+        let response = self
+            .send(&url, self.client.get(&url))
+            .await
+            .context("Failed to query draft info")?;
+
+        if !response.status().is_success() {
+            let doc = DocumentType::Draft(name.to_string());
+            return Err(status_error(&url, &response, Some(&doc)).into());
+        }
+
+        let body = profile::time_async(Phase::Network, response.text())
+            .await
+            .context("Failed to read draft info")?;
+        schema::parse_object(super::datatracker::DOC_JSON_ENDPOINT, &body)
+    }
+
+    /// List every published revision of a draft, oldest first
+    ///
+    /// A revision suffix in `name` is ignored. Revisions of drafts that the
+    /// draft replaced are included, as listed by datatracker.
+    pub async fn draft_revisions(&self, name: &str) -> crate::Result<Vec<DraftRevision>> {
+        let doc = DocumentType::Draft(name.to_string());
+        let base = doc.draft_revision().map_or(name, |(base, _)| base);
+        let info = self.draft_info(base).await?;
+        Ok(convert_revisions(info.rev_history))
+    }
+
     /// Check if a draft name already has a version suffix (e.g., -06, -12)
     fn has_version_suffix(name: &str) -> bool {
         // Look for pattern like -NN at the end where NN is digits
@@ -100,48 +818,320 @@ impl DocumentFetcher {
 
     /// Get the HTML URL for a document
     pub fn html_url(&self, doc: &DocumentType) -> String {
-        match doc {
-            DocumentType::Rfc(num) => {
-                format!("https://www.rfc-editor.org/rfc/rfc{}.html", num)
-            }
-            DocumentType::Draft(name) => {
-                format!("https://datatracker.ietf.org/doc/html/{}", name)
-            }
-        }
+        self.urls(doc, Format::Html)
+            .ok()
+            .and_then(|urls| urls.into_iter().next())
+            .unwrap_or_default()
     }
 
     /// Get the plain text URL for a document
+    /// External specifications have no plain text rendering, so their HTML URL is returned
     pub fn text_url(&self, doc: &DocumentType) -> String {
+        self.text_mirrors(doc).swap_remove(0)
+    }
+
+    /// Get the xml2rfc source URL for a document
+    /// Only RFCs and Internet-Drafts are published as XML
+    pub fn xml_url(&self, doc: &DocumentType) -> crate::Result<String> {
+        Ok(self.urls(doc, Format::Xml)?.swap_remove(0))
+    }
+
+    /// Get the PDF URL for a document; only RFCs are published as PDF
+    pub fn pdf_url(&self, doc: &DocumentType) -> crate::Result<String> {
+        Ok(self.urls(doc, Format::Pdf)?.swap_remove(0))
+    }
+
+    /// Get the plain text URLs of a document, primary source first
+    pub fn text_mirrors(&self, doc: &DocumentType) -> Vec<String> {
         match doc {
-            DocumentType::Rfc(num) => {
-                format!("https://www.rfc-editor.org/rfc/rfc{}.txt", num)
+            DocumentType::External { .. } => vec![self.html_url(doc)],
+            _ => self.urls(doc, Format::Text).unwrap_or_default(),
+        }
+    }
+
+    /// Get the URLs of a document in a format, primary source first
+    ///
+    /// RFCs are also offered by every configured mirror.
+    pub fn urls(&self, doc: &DocumentType, format: Format) -> crate::Result<Vec<String>> {
+        let sources = &self.sources;
+        let urls = match (doc, format) {
+            (DocumentType::Rfc(num), _) => {
+                sources.rfc_editor_urls(&format!("/rfc/rfc{}.{}", num, format.extension()))
             }
-            DocumentType::Draft(name) => {
-                format!("https://www.ietf.org/archive/id/{}.txt", name)
+            (DocumentType::Draft(name), Format::Html) => {
+                vec![sources.datatracker_url(&format!("/doc/html/{}", name))]
             }
-        }
+            (DocumentType::Draft(name), Format::Text | Format::Xml) => {
+                vec![sources.draft_url(&format!("{}.{}", name, format.extension()))]
+            }
+            (DocumentType::Subseries(..), Format::Html) => {
+                vec![sources.rfc_editor_url(&format!("/info/{}", doc.name()))]
+            }
+            (DocumentType::Subseries(series, _), Format::Text) => {
+                vec![sources.rfc_editor_url(&format!("/{}/{}.txt", series.prefix(), doc.name()))]
+            }
+            (DocumentType::External { .. }, Format::Html) => {
+                vec![doc.external_url().unwrap_or_default()]
+            }
+            (_, Format::Xml) => return Err(anyhow!("{} has no xml2rfc source", doc).into()),
+            (_, Format::Pdf) => return Err(anyhow!("{} has no PDF rendering", doc).into()),
+            (_, format) => {
+                return Err(anyhow!("{} has no {} rendering", doc, format.extension()).into())
+            }
+        };
+        Ok(urls)
+    }
+
+    /// Download a URL; concurrent downloads of the same URL share one request
+    async fn download(
+        &self,
+        url: &str,
+        target: Option<(&DocumentType, Format)>,
+    ) -> Result<Arc<Vec<u8>>> {
+        Ok(self
+            .inflight
+            .run(url, || self.get_revalidated(url, target))
+            .await?)
     }
 
-    /// Fetch content from a URL
-    async fn fetch_content(&self, url: &str) -> Result<String> {
+    /// Send a GET request, revalidating the cached copy of `target` if there is one
+    async fn get_revalidated(
+        &self,
+        url: &str,
+        target: Option<(&DocumentType, Format)>,
+    ) -> Result<Vec<u8>> {
+        if self.offline.is_offline() {
+            let cache = self.offline_cache();
+            return match (cache.as_ref(), target) {
+                (Some(cache), Some((doc, format))) => cache
+                    .get_bytes(doc, format)
+                    .ok_or_else(|| OfflineError::new(doc.to_string(), Some(cache)).into()),
+                _ => Err(OfflineError::new(url, cache.as_ref()).into()),
+            };
+        }
+
+        let cached = match (&self.cache, target) {
+            (Some(cache), Some((doc, format))) => cache
+                .get_validators(doc, format)
+                .filter(|v| v.url == url)
+                .and_then(|v| Some((v, cache.get_bytes(doc, format)?))),
+            _ => None,
+        };
+
+        let mut request = self.client.get(url);
+        if let Some((validators, _)) = &cached {
+            if let Some(etag) = &validators.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &validators.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
         let response = self
-            .client
-            .get(url)
-            .send()
+            .send(url, request)
             .await
             .context("Failed to fetch document")?;
 
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to fetch {}: HTTP {}", url, response.status());
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            if let Some((validators, content)) = cached {
+                if let (Some(cache), Some((doc, format))) = (&self.cache, target) {
+                    let record = Provenance::new(format, status.as_u16(), &validators, &content);
+                    cache.record_provenance(doc, &record)?;
+                }
+                return Ok(content);
+            }
         }
+        if !status.is_success() {
+            return Err(status_error(url, &response, target.map(|(doc, _)| doc)).into());
+        }
+
+        let validators = validators_from_headers(url, response.headers());
+        let content = self.read_body(response, target.map(|(doc, _)| doc)).await?;
+        if let (Some(cache), Some((doc, format))) = (&self.cache, target) {
+            if let Some(change) = cache.check_content(doc, format, url, &content) {
+                // Keep the pinned copy; the changed one waits for CacheManager::accept_change
+                cache.quarantine(doc, format, &content)?;
+                let record = Provenance::new(format, status.as_u16(), &validators, &content);
+                cache.record_provenance(doc, &record)?;
+                return self.changed(change, cache);
+            }
+            if !validators.is_empty() {
+                cache.store_validators(doc, format, &validators)?;
+            }
+            let record = Provenance::new(format, status.as_u16(), &validators, &content);
+            cache.record_provenance(doc, &record)?;
+        }
+        if let Some((doc, format)) = target {
+            self.hooks.emit(&HookEvent::Fetched {
+                doc: doc.clone(),
+                format,
+                url: url.to_string(),
+            });
+        }
+        Ok(content)
+    }
+
+    /// Report a changed copy of an immutable document that was quarantined
+    ///
+    /// Returns the pinned copy if it is still cached, and an error otherwise.
+    fn changed(&self, change: ContentChange, cache: &CacheManager) -> Result<Vec<u8>> {
+        let pinned = cache
+            .holds_pinned(&change.doc, change.format)
+            .then(|| cache.read_document(&change.doc, change.format))
+            .flatten();
+        let message = change.to_string();
+        self.hooks.emit(&HookEvent::ContentChanged { change });
+        pinned.ok_or_else(|| anyhow!("{}; the changed copy was quarantined", message))
+    }
 
-        response
-            .text()
+    /// Read a response body chunk by chunk, reporting progress for `doc`
+    async fn read_body(
+        &self,
+        mut response: Response,
+        doc: Option<&DocumentType>,
+    ) -> Result<Vec<u8>> {
+        let total = response.content_length();
+        // Don't trust the announced size for more than a first allocation
+        let capacity = total.unwrap_or(0).min(MAX_PREALLOCATION) as usize;
+        let mut content = Vec::with_capacity(capacity);
+        while let Some(chunk) = profile::time_async(Phase::Network, response.chunk())
             .await
-            .context("Failed to read document content")
+            .context("Failed to read document content")?
+        {
+            content.extend_from_slice(&chunk);
+            if let (Some(progress), Some(doc)) = (&self.progress, doc) {
+                progress(doc, content.len() as u64, total);
+            }
+        }
+        Ok(content)
+    }
+
+    async fn fetch_document(
+        &self,
+        url: &str,
+        doc: &DocumentType,
+        format: Format,
+    ) -> Result<String> {
+        let bytes = self.download(url, Some((doc, format))).await?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    pub(super) async fn fetch_content(&self, url: &str) -> Result<String> {
+        let bytes = self.download(url, None).await?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+/// Combine the failures of a download from the primary source and then each mirror
+///
+/// The primary source's failure stays the source, so its typed error (such
+/// as [`crate::Error::NotFound`]) is reported; the mirrors' become context.
+fn mirror_error(mut errors: Vec<anyhow::Error>) -> Option<anyhow::Error> {
+    if errors.is_empty() {
+        return None;
+    }
+    let primary = errors.remove(0);
+    if errors.is_empty() {
+        return Some(primary);
+    }
+    let mirrors: Vec<String> = errors.iter().map(|e| format!("{:#}", e)).collect();
+    Some(primary.context(format!("Mirrors also failed ({})", mirrors.join("; "))))
+}
+
+/// Combine the failures of every format `fetch` tried into one error
+///
+/// The last failure stays the source, so a typed error such as
+/// [`crate::Error::NotFound`] is still recognized; earlier ones become the message.
+fn fallback_error(mut failures: Vec<(Format, anyhow::Error)>, doc: &DocumentType) -> anyhow::Error {
+    fn label(format: Format) -> &'static str {
+        match format {
+            Format::Text => "Plain text",
+            Format::Html => "HTML",
+            Format::Xml => "XML",
+            Format::Pdf => "PDF",
+        }
+    }
+    let Some((last_format, last_err)) = failures.pop() else {
+        return anyhow!("No format to fetch {} in", doc);
+    };
+    if failures.is_empty() {
+        return last_err;
+    }
+    let earlier: Vec<String> = failures
+        .iter()
+        .map(|(format, e)| format!("{} fetch failed ({:#})", label(*format), e))
+        .collect();
+    last_err.context(format!(
+        "{}; {} fallback also failed",
+        earlier.join("; "),
+        label(last_format)
+    ))
+}
+
+/// Read the validators of a response from its headers
+fn validators_from_headers(url: &str, headers: &HeaderMap) -> Validators {
+    let header = |name| {
+        headers
+            .get(name)
+            .and_then(|value: &HeaderValue| value.to_str().ok())
+            .map(str::to_string)
+    };
+    Validators {
+        url: url.to_string(),
+        etag: header(ETAG),
+        last_modified: header(LAST_MODIFIED),
     }
 }
 
+/// Keep the draft revisions of a datatracker revision history, oldest first
+fn convert_revisions(history: Vec<ApiRevision>) -> Vec<DraftRevision> {
+    let mut revisions: Vec<DraftRevision> = history
+        .into_iter()
+        .filter(|r| r.name.starts_with("draft-"))
+        .filter_map(|r| {
+            Some(DraftRevision {
+                rev: r.rev.parse().ok()?,
+                published: r
+                    .published
+                    .and_then(|p| chrono::DateTime::parse_from_rfc3339(&p).ok())
+                    .map(|p| p.with_timezone(&chrono::Utc)),
+                name: r.name,
+            })
+        })
+        .collect();
+    revisions.sort_by_key(|r| r.published);
+    revisions
+}
+
+/// Find the member RFCs of a subseries entry in the RFC index XML
+///
+/// Entries look like `<bcp-entry><doc-id>BCP0014</doc-id><is-also>
+/// <doc-id>RFC2119</doc-id>...</is-also></bcp-entry>`.
+fn parse_subseries_members(index: &str, series: Subseries, number: u32) -> Vec<u32> {
+    let open = format!("<{}-entry>", series.prefix());
+    let close = format!("</{}-entry>", series.prefix());
+    let id = format!("<doc-id>{}{:04}</doc-id>", series.label(), number);
+
+    for entry in index.split(open.as_str()).skip(1) {
+        let entry = entry.split(close.as_str()).next().unwrap_or(entry);
+        if !entry.contains(&id) {
+            continue;
+        }
+        let Some(start) = entry.find("<is-also>") else {
+            return Vec::new();
+        };
+        return entry[start..]
+            .split("<doc-id>RFC")
+            .skip(1)
+            .filter_map(|s| s.split('<').next()?.parse().ok())
+            .collect();
+    }
+
+    Vec::new()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,6 +1150,572 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_xml_urls() {
+        let editor = DocumentFetcher::new().unwrap();
+
+        assert_eq!(
+            editor.xml_url(&DocumentType::Rfc(9000)).unwrap(),
+            "https://www.rfc-editor.org/rfc/rfc9000.xml"
+        );
+        assert_eq!(
+            editor
+                .xml_url(&DocumentType::Draft("draft-foo-00".to_string()))
+                .unwrap(),
+            "https://www.ietf.org/archive/id/draft-foo-00.xml"
+        );
+        assert!(editor
+            .xml_url(&DocumentType::Subseries(Subseries::Bcp, 14))
+            .is_err());
+    }
+
+    #[test]
+    fn test_pdf_url() {
+        let editor = DocumentFetcher::new().unwrap();
+
+        assert_eq!(
+            editor.pdf_url(&DocumentType::Rfc(9000)).unwrap(),
+            "https://www.rfc-editor.org/rfc/rfc9000.pdf"
+        );
+        assert!(editor
+            .pdf_url(&DocumentType::Draft("draft-foo-00".to_string()))
+            .is_err());
+    }
+
+    #[test]
+    fn test_validators_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"5e3a-5c4f\""));
+        let url = "https://www.rfc-editor.org/rfc/rfc9000.txt";
+
+        let validators = validators_from_headers(url, &headers);
+        assert_eq!(validators.url, url);
+        assert_eq!(validators.etag.as_deref(), Some("\"5e3a-5c4f\""));
+        assert_eq!(validators.last_modified, None);
+        assert!(validators_from_headers(url, &HeaderMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_text_mirrors() {
+        let editor = DocumentFetcher::new().unwrap();
+
+        assert_eq!(
+            editor.text_mirrors(&DocumentType::Rfc(9000)),
+            vec![
+                "https://www.rfc-editor.org/rfc/rfc9000.txt",
+                "https://www.ietf.org/rfc/rfc9000.txt"
+            ]
+        );
+        assert_eq!(
+            editor.text_mirrors(&DocumentType::Draft("draft-foo-00".to_string())),
+            vec!["https://www.ietf.org/archive/id/draft-foo-00.txt"]
+        );
+    }
+
+    #[test]
+    fn test_configured_sources() {
+        let sources = SourceConfig::default()
+            .with_rfc_editor("https://rfc.corp.example")
+            .with_drafts("https://rfc.corp.example/id")
+            .with_mirrors(["https://mirror.corp.example"]);
+        let editor = DocumentFetcher::new().unwrap().with_sources(sources);
+
+        assert_eq!(
+            editor.urls(&DocumentType::Rfc(9000), Format::Pdf).unwrap(),
+            vec![
+                "https://rfc.corp.example/rfc/rfc9000.pdf",
+                "https://mirror.corp.example/rfc/rfc9000.pdf"
+            ]
+        );
+        assert_eq!(
+            editor.text_url(&DocumentType::Draft("draft-foo-00".to_string())),
+            "https://rfc.corp.example/id/draft-foo-00.txt"
+        );
+        assert_eq!(
+            editor.text_mirrors(&DocumentType::Subseries(Subseries::Bcp, 14)),
+            vec!["https://rfc.corp.example/bcp/bcp14.txt"]
+        );
+
+        let direct = DocumentFetcher::new()
+            .unwrap()
+            .with_sources(SourceConfig::default().with_mirrors(Vec::<String>::new()));
+        assert_eq!(direct.text_mirrors(&DocumentType::Rfc(9000)).len(), 1);
+    }
+
+    /// Answer one connection per response on the loopback interface, recording the requests
+    async fn serve(responses: Vec<String>) -> (String, Arc<std::sync::Mutex<Vec<String>>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = [0; 4096];
+                let n = socket.read(&mut request).await.unwrap();
+                seen.lock()
+                    .unwrap()
+                    .push(String::from_utf8_lossy(&request[..n]).into_owned());
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (base, requests)
+    }
+
+    /// Get a fetcher downloading RFCs from `base` only
+    fn local_fetcher(base: &str) -> DocumentFetcher {
+        DocumentFetcher::builder()
+            .without_proxy()
+            .build()
+            .unwrap()
+            .with_sources(
+                SourceConfig::default()
+                    .with_rfc_editor(base)
+                    .with_mirrors(Vec::<String>::new()),
+            )
+            .with_offline_mode(OfflineMode::Online)
+            .with_format_strategy(FormatStrategy::TextFirst)
+    }
+
+    fn ok_response(headers: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+            body.len(),
+            headers,
+            body
+        )
+    }
+
+    #[tokio::test]
+    async fn test_get_section() {
+        let text = "1.  Introduction\n\n   Text.\n\n3.  Protocol\n\n3.1.  The Application-Layer \
+                    Protocol Negotiation Extension\n\n   Clients send ALPN, see Section 3.\n\n\
+                    4.  Security Considerations\n\n   None.\n";
+        let (base, requests) = serve(vec![ok_response("", text), ok_response("", text)]).await;
+        let fetcher = local_fetcher(&base);
+        let doc = DocumentType::Rfc(7301);
+
+        let excerpt = fetcher.get_section(&doc, "3.1").await.unwrap();
+        assert_eq!(
+            excerpt.text,
+            "3.1.  The Application-Layer Protocol Negotiation Extension\n\n   \
+             Clients send ALPN, see Section 3."
+        );
+        assert_eq!(excerpt.references[0].title.as_deref(), Some("Protocol"));
+        assert!(requests.lock().unwrap()[0].starts_with("GET /rfc/rfc7301.txt "));
+
+        let err = fetcher.get_section(&doc, "Appendix A").await.unwrap_err();
+        assert_eq!(err.to_string(), "RFC 7301 has no section 'Appendix A'");
+    }
+
+    #[tokio::test]
+    async fn test_artwork_falls_back_to_text() {
+        let text = "1.  Format\n\n   +------+\n   | Type |\n   +------+\n\n\
+                    Figure 1: Header\n";
+        let not_found = "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+        let (base, requests) = serve(vec![not_found.to_string(), ok_response("", text)]).await;
+        let fetcher = local_fetcher(&base);
+
+        let artwork = fetcher.artwork(&DocumentType::Rfc(9000)).await.unwrap();
+        assert_eq!(artwork.len(), 1);
+        assert_eq!(artwork[0].label(), "Figure 1: Header");
+        assert_eq!(artwork[0].content, "+------+\n| Type |\n+------+");
+        {
+            let requests = requests.lock().unwrap();
+            assert!(requests[0].starts_with("GET /rfc/rfc9000.xml "));
+            assert!(requests[1].starts_with("GET /rfc/rfc9000.txt "));
+        }
+
+        // RFCs from before xml2rfc v3 are read from their text right away
+        let (base, requests) = serve(vec![ok_response("", text)]).await;
+        let artwork = local_fetcher(&base)
+            .artwork(&DocumentType::Rfc(2549))
+            .await
+            .unwrap();
+        assert_eq!(artwork.len(), 1);
+        assert!(requests.lock().unwrap()[0].starts_with("GET /rfc/rfc2549.txt "));
+
+        // Other failures to fetch the source are not hidden by the fallback
+        let failed = "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\n\
+                      Connection: close\r\n\r\n";
+        let (base, requests) = serve(vec![failed.to_string(), ok_response("", text)]).await;
+        let err = local_fetcher(&base)
+            .artwork(&DocumentType::Rfc(9000))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Network(_)));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_typed_http_errors() {
+        let not_found = "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+        let limited = "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 120\r\n\
+                       Content-Length: 0\r\nConnection: close\r\n\r\n";
+        let (base, _) = serve(vec![not_found.to_string(), not_found.to_string()]).await;
+        let doc = DocumentType::Rfc(99999);
+
+        // Both the text and the HTML fallback are missing
+        let err = local_fetcher(&base).fetch(&doc).await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::NotFound {
+                doc: DocumentType::Rfc(99999),
+                ..
+            }
+        ));
+        assert_eq!(
+            format!("{:#}", err),
+            "Plain text fetch failed (RFC 99999 not found); HTML fallback also failed: \
+             RFC 99999 not found"
+        );
+
+        let (base, _) = serve(vec![limited.to_string()]).await;
+        let err = local_fetcher(&base)
+            .fetch_format(&doc, Format::Text)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::RateLimited {
+                retry_after: Some(delay),
+                ..
+            } if delay == std::time::Duration::from_secs(120)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_subseries_to_cache() {
+        let not_found = "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+        let index = "<rfc-index><bcp-entry><doc-id>BCP0014</doc-id><is-also>\
+                     <doc-id>RFC2119</doc-id><doc-id>RFC8174</doc-id></is-also></bcp-entry></rfc-index>";
+        let (base, requests) = serve(vec![
+            not_found.to_string(),
+            ok_response("", index),
+            ok_response("", "Key words"),
+        ])
+        .await;
+        let fetcher = local_fetcher(&base);
+        let temp = tempfile::TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let bcp = DocumentType::Subseries(Subseries::Bcp, 14);
+
+        // Without a bundle, the most recent member is stored for the subseries
+        assert_eq!(
+            fetcher.fetch_to_cache(&bcp, &cache).await.unwrap(),
+            Format::Text
+        );
+        assert_eq!(
+            cache.get_document(&bcp, Format::Text).as_deref(),
+            Some("Key words")
+        );
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].starts_with("GET /bcp/bcp14.txt"));
+        assert!(requests[2].starts_with("GET /rfc/rfc8174.txt"));
+    }
+
+    #[tokio::test]
+    async fn test_plugin_sources() {
+        let spec = DocumentType::External {
+            source: "3gpp".to_string(),
+            name: "23.501".to_string(),
+        };
+        let mut plugins = PluginRegistry::new();
+        plugins.register_source(
+            "3gpp",
+            super::super::FixtureSource::new().with_document(
+                spec.clone(),
+                Format::Text,
+                "5G System",
+            ),
+        );
+        let fetcher = DocumentFetcher::new()
+            .unwrap()
+            .with_plugins(Arc::new(plugins));
+
+        assert_eq!(
+            fetcher.fetch(&spec).await.unwrap(),
+            ("5G System".to_string(), Format::Text)
+        );
+        assert_eq!(
+            fetcher.fetch_format(&spec, Format::Text).await.unwrap(),
+            "5G System"
+        );
+        let temp = tempfile::TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        assert_eq!(
+            fetcher.fetch_to_cache(&spec, &cache).await.unwrap(),
+            Format::Text
+        );
+        assert_eq!(
+            cache.get_document(&spec, Format::Text).as_deref(),
+            Some("5G System")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mirror_errors() {
+        let not_found = "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+        let failed = "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\n\
+                      Connection: close\r\n\r\n";
+        let (base, requests) = serve(vec![not_found.to_string(), failed.to_string()]).await;
+        let mirror = format!("{}/mirror", base);
+        let fetcher = local_fetcher(&base).with_sources(
+            SourceConfig::default()
+                .with_rfc_editor(&base)
+                .with_mirrors([&mirror]),
+        );
+
+        // The primary source's error is reported, with the mirror's as context
+        let err = fetcher
+            .fetch_bytes(&DocumentType::Rfc(9000), Format::Pdf)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::NotFound { .. }));
+        assert_eq!(
+            format!("{:#}", err),
+            format!(
+                "Mirrors also failed (Failed to fetch {}/rfc/rfc9000.pdf: HTTP 500 Internal \
+                 Server Error): RFC 9000 not found",
+                mirror
+            )
+        );
+        assert!(requests.lock().unwrap()[1].starts_with("GET /mirror/rfc/rfc9000.pdf "));
+    }
+
+    #[tokio::test]
+    async fn test_format_preference() {
+        let not_found = "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+        let (base, requests) = serve(vec![
+            not_found.to_string(),
+            ok_response("", "QUIC"),
+            ok_response("", "<rfc/>"),
+        ])
+        .await;
+        let fetcher = local_fetcher(&base)
+            .with_format_preference(FormatPreference::parse("pdf,xml,text,html").unwrap());
+        let doc = DocumentType::Rfc(9000);
+
+        // The missing XML falls back to text
+        let (content, format) = fetcher.fetch(&doc).await.unwrap();
+        assert_eq!((content.as_str(), format), ("QUIC", Format::Text));
+        let preference = FormatPreference::new([Format::Xml]);
+        let (content, format) = fetcher.fetch_preferring(&doc, &preference).await.unwrap();
+        assert_eq!((content.as_str(), format), ("<rfc/>", Format::Xml));
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].starts_with("GET /rfc/rfc9000.xml "));
+        assert!(requests[1].starts_with("GET /rfc/rfc9000.txt "));
+    }
+
+    #[tokio::test]
+    async fn test_download_progress() {
+        use std::sync::Mutex;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Serve a single response on the loopback interface
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let body = "QUIC ".repeat(20_000);
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0; 1024];
+            let _ = socket.read(&mut request).await.unwrap();
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let seen = reports.clone();
+        let fetcher = DocumentFetcher::builder()
+            .without_proxy()
+            .build()
+            .unwrap()
+            .with_sources(
+                SourceConfig::default()
+                    .with_rfc_editor(&format!("http://{}", addr))
+                    .with_mirrors(Vec::<String>::new()),
+            )
+            .with_offline_mode(OfflineMode::Online)
+            .on_progress(move |doc, read, total| {
+                seen.lock().unwrap().push((doc.clone(), read, total));
+            });
+
+        let text = fetcher
+            .fetch_format(&DocumentType::Rfc(9000), Format::Text)
+            .await
+            .unwrap();
+        assert_eq!(text.len(), 100_000);
+
+        let reports = reports.lock().unwrap();
+        let (doc, read, total) = reports.last().unwrap();
+        assert_eq!(*doc, DocumentType::Rfc(9000));
+        assert_eq!((*read, *total), (100_000, Some(100_000)));
+        assert!(reports.windows(2).all(|w| w[0].1 < w[1].1));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_to_cache() {
+        let body = "QUIC ".repeat(20_000);
+        let (base, requests) = serve(vec![
+            ok_response("ETag: \"v1\"\r\n", &body),
+            "HTTP/1.1 304 Not Modified\r\nConnection: close\r\n\r\n".to_string(),
+        ])
+        .await;
+        let temp = tempfile::TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let doc = DocumentType::Rfc(9000);
+        let reports = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = reports.clone();
+        let fetcher = local_fetcher(&base).on_progress(move |_, read, total| {
+            seen.lock().unwrap().push((read, total));
+        });
+
+        let format = fetcher.fetch_to_cache(&doc, &cache).await.unwrap();
+        assert_eq!(format, Format::Text);
+        assert_eq!(cache.get_document(&doc, Format::Text).unwrap(), body);
+        // Streaming into the cache reports progress like a download into memory
+        assert_eq!(
+            reports.lock().unwrap().last(),
+            Some(&(100_000, Some(100_000)))
+        );
+        let validators = cache.get_validators(&doc, Format::Text).unwrap();
+        assert_eq!(validators.etag.as_deref(), Some("\"v1\""));
+
+        // The second fetch revalidates the cached copy
+        fetcher.fetch_to_cache(&doc, &cache).await.unwrap();
+        assert!(requests.lock().unwrap()[1].contains("if-none-match: \"v1\""));
+        let log = cache.provenance(&doc).unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!((log[0].status, log[1].status), (200, 304));
+        assert_eq!(log[0].sha256, log[1].sha256);
+        assert_eq!(log[1].size, 100_000);
+    }
+
+    #[tokio::test]
+    async fn test_changed_rfc_is_reported() {
+        let (base, _) = serve(vec![
+            ok_response("", "QUIC\nA UDP-Based Transport\n"),
+            ok_response("", "QUIC\nA TCP-Based Transport\n"),
+        ])
+        .await;
+        let temp = tempfile::TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let doc = DocumentType::Rfc(9000);
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = events.clone();
+        let fetcher = local_fetcher(&base).with_hook(move |event: &HookEvent| {
+            seen.lock().unwrap().push(event.clone());
+            Ok(())
+        });
+
+        fetcher.fetch_to_cache(&doc, &cache).await.unwrap();
+        fetcher.fetch_to_cache(&doc, &cache).await.unwrap();
+        let events = events.lock().unwrap();
+        let changes: Vec<&ContentChange> = events
+            .iter()
+            .filter_map(|event| match event {
+                HookEvent::ContentChanged { change } => Some(change),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(
+            changes[0].diff.as_deref(),
+            Some("@@ -2,1 +2,1 @@\n-A UDP-Based Transport\n+A TCP-Based Transport\n")
+        );
+        // The first copy stays pinned and cached; the changed one is put aside
+        let log = cache.provenance(&doc).unwrap();
+        assert_eq!(changes[0].trusted_sha256, log[0].sha256);
+        assert_eq!(changes[0].sha256, log[1].sha256);
+        assert_eq!(
+            cache.get_document(&doc, Format::Text).unwrap(),
+            "QUIC\nA UDP-Based Transport\n"
+        );
+        assert_eq!(
+            cache.quarantined(&doc, Format::Text).unwrap(),
+            b"QUIC\nA TCP-Based Transport\n"
+        );
+
+        // Accepting the change installs it and pins it
+        assert!(cache.accept_change(&doc, Format::Text).unwrap());
+        assert_eq!(
+            cache.get_document(&doc, Format::Text).unwrap(),
+            "QUIC\nA TCP-Based Transport\n"
+        );
+        assert_eq!(
+            cache.pinned_checksum(&doc, Format::Text),
+            Some(changes[0].sha256.clone())
+        );
+        assert!(cache.quarantined(&doc, Format::Text).is_none());
+        assert!(!cache.accept_change(&doc, Format::Text).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_changed_rfc_keeps_the_pinned_copy() {
+        let (base, _) = serve(vec![
+            ok_response("", "QUIC\nA UDP-Based Transport\n"),
+            ok_response("", "QUIC\nA TCP-Based Transport\n"),
+        ])
+        .await;
+        let temp = tempfile::TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let doc = DocumentType::Rfc(9000);
+        let fetcher = local_fetcher(&base).with_cache(cache.clone());
+
+        let original = fetcher.fetch_format(&doc, Format::Text).await.unwrap();
+        cache.store_document(&doc, Format::Text, &original).unwrap();
+        // The refetch serves the pinned copy instead of the changed one
+        let refetched = fetcher.fetch_format(&doc, Format::Text).await.unwrap();
+        assert_eq!(refetched, "QUIC\nA UDP-Based Transport\n");
+        assert_eq!(cache.get_document(&doc, Format::Text).unwrap(), original);
+        assert!(cache.quarantined(&doc, Format::Text).is_some());
+
+        // Without the pinned copy, the changed one is refused
+        cache.remove(&doc).unwrap();
+        let (base, _) = serve(vec![ok_response("", "QUIC\nA TCP-Based Transport\n")]).await;
+        let fetcher = local_fetcher(&base).with_cache(cache.clone());
+        assert!(fetcher.fetch_format(&doc, Format::Text).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_offline_serves_the_cache() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        cache
+            .store_document(&DocumentType::Rfc(9000), Format::Text, "QUIC")
+            .unwrap();
+        let draft = DocumentType::Draft("draft-ietf-quic-qlog-03".to_string());
+        cache
+            .store_document(&draft, Format::Html, "<p>qlog</p>")
+            .unwrap();
+        let fetcher = DocumentFetcher::new()
+            .unwrap()
+            .with_cache(cache)
+            .with_offline_mode(OfflineMode::Offline);
+
+        let (text, format) = fetcher.fetch(&DocumentType::Rfc(9000)).await.unwrap();
+        assert_eq!((text.as_str(), format), ("QUIC", Format::Text));
+        let latest = DocumentType::Draft("draft-ietf-quic-qlog".to_string());
+        assert_eq!(fetcher.fetch(&latest).await.unwrap().1, Format::Html);
+
+        let Err(Error::Offline { error: offline, .. }) =
+            fetcher.fetch(&DocumentType::Rfc(9001)).await
+        else {
+            panic!("expected an offline error");
+        };
+        assert_eq!(offline.requested, "RFC 9001");
+        assert_eq!(offline.cached.len(), 2);
+        let err = fetcher.rfc_metadata(9000).await.unwrap_err();
+        assert!(matches!(err, Error::Offline { .. }));
+    }
+
     #[test]
     fn test_draft_urls() {
         let editor = DocumentFetcher::new().unwrap();
@@ -175,6 +1731,132 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_external_urls() {
+        let editor = DocumentFetcher::new().unwrap();
+        let fetch = DocumentType::parse("whatwg:fetch").unwrap();
+        let webauthn = DocumentType::parse("w3c:webauthn-2").unwrap();
+
+        assert_eq!(editor.html_url(&fetch), "https://fetch.spec.whatwg.org/");
+        assert_eq!(editor.text_url(&fetch), editor.html_url(&fetch));
+        assert_eq!(
+            editor.html_url(&webauthn),
+            "https://www.w3.org/TR/webauthn-2/"
+        );
+    }
+
+    #[test]
+    fn test_subseries_urls() {
+        let editor = DocumentFetcher::new().unwrap();
+        let bcp = DocumentType::Subseries(Subseries::Bcp, 14);
+
+        assert_eq!(
+            editor.text_url(&bcp),
+            "https://www.rfc-editor.org/bcp/bcp14.txt"
+        );
+        assert_eq!(
+            editor.html_url(&bcp),
+            "https://www.rfc-editor.org/info/bcp14"
+        );
+    }
+
+    #[test]
+    fn test_convert_revisions() {
+        let json = r#"{
+            "rev": "34",
+            "rev_history": [
+                {"name": "draft-ietf-quic-transport", "rev": "01", "published": "2017-01-14T10:00:00-08:00", "url": "/doc/draft-ietf-quic-transport/01/"},
+                {"name": "draft-ietf-quic-transport", "rev": "00", "published": "2016-11-28T14:55:31-08:00", "url": "/doc/draft-ietf-quic-transport/00/"},
+                {"name": "rfc9000", "rev": "rfc9000", "published": "2021-05-27T00:00:00Z", "url": "/doc/rfc9000/"}
+            ]
+        }"#;
+        let info: DraftInfo = serde_json::from_str(json).unwrap();
+        let revisions = convert_revisions(info.rev_history);
+
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].label(), "00");
+        assert_eq!(
+            revisions[0].published.unwrap().to_rfc3339(),
+            "2016-11-28T22:55:31+00:00"
+        );
+        assert_eq!(
+            revisions[1].document(),
+            DocumentType::Draft("draft-ietf-quic-transport-01".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_subseries_members() {
+        let index = r#"<rfc-index>
+            <bcp-entry>
+                <doc-id>BCP0013</doc-id>
+                <is-also><doc-id>RFC4289</doc-id></is-also>
+            </bcp-entry>
+            <bcp-entry>
+                <doc-id>BCP0014</doc-id>
+                <is-also>
+                    <doc-id>RFC2119</doc-id>
+                    <doc-id>RFC8174</doc-id>
+                </is-also>
+            </bcp-entry>
+            <std-entry>
+                <doc-id>STD0090</doc-id>
+                <title>JSON</title>
+                <is-also><doc-id>RFC8259</doc-id></is-also>
+            </std-entry>
+            <fyi-entry><doc-id>FYI0001</doc-id></fyi-entry>
+        </rfc-index>"#;
+
+        assert_eq!(
+            parse_subseries_members(index, Subseries::Bcp, 14),
+            vec![2119, 8174]
+        );
+        assert_eq!(
+            parse_subseries_members(index, Subseries::Std, 90),
+            vec![8259]
+        );
+        assert!(parse_subseries_members(index, Subseries::Fyi, 1).is_empty());
+        assert!(parse_subseries_members(index, Subseries::Bcp, 90).is_empty());
+    }
+
+    #[test]
+    fn test_convert_rfc_metadata() {
+        let json = r#"{
+            "draft": "draft-ietf-httpbis-p1-messaging-26",
+            "doc_id": "RFC7230",
+            "title": "Hypertext Transfer Protocol (HTTP/1.1): Message Syntax and Routing",
+            "authors": ["R. Fielding, Ed.", "J. Reschke, Ed."],
+            "page_count": "89",
+            "pub_status": "PROPOSED STANDARD",
+            "status": "PROPOSED STANDARD",
+            "source": "Hypertext Transfer Protocol Bis",
+            "pub_date": "June 2014",
+            "obsoletes": ["RFC2145", "RFC2616"],
+            "obsoleted_by": ["RFC9110", "RFC9112"],
+            "updates": ["RFC2817", "RFC2818"],
+            "updated_by": ["RFC8615"],
+            "errata_url": "https://www.rfc-editor.org/errata/rfc7230",
+            "doi": "10.17487/RFC7230"
+        }"#;
+        let api: ApiRfcMetadata = serde_json::from_str(json).unwrap();
+        let meta = DocumentFetcher::convert_rfc_metadata(7230, api);
+
+        assert_eq!(meta.number, 7230);
+        assert_eq!(meta.pages, Some(89));
+        assert_eq!(
+            meta.obsoletes,
+            vec![DocumentType::Rfc(2145), DocumentType::Rfc(2616)]
+        );
+        assert_eq!(
+            meta.obsoleted_by,
+            vec![DocumentType::Rfc(9110), DocumentType::Rfc(9112)]
+        );
+        assert_eq!(meta.updated_by, vec![DocumentType::Rfc(8615)]);
+        assert!(meta.is_obsoleted());
+        assert!(meta.errata_url.is_some());
+        assert_eq!(meta.doi.as_deref(), Some("10.17487/RFC7230"));
+    }
+
     #[test]
     fn test_has_version_suffix() {
         // Has version suffix
@@ -0,0 +1,247 @@
+use std::fmt;
+
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+
+/// A Datatracker response this version can't read, because the API changed upstream
+///
+/// Returned as [`crate::Error::Schema`] when a response is JSON of no known
+/// shape; a body that isn't JSON at all (e.g., an HTML error page) is a
+/// [`crate::Error::Network`] error instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    /// API endpoint that answered (e.g., "doc.json")
+    pub endpoint: &'static str,
+    pub detail: String,
+}
+
+impl SchemaError {
+    fn new(endpoint: &'static str, detail: impl fmt::Display) -> Self {
+        Self {
+            endpoint,
+            detail: detail.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The Datatracker {} API changed upstream and can't be read by this version of rfc ({}); \
+             a newer release may support it",
+            self.endpoint, self.detail
+        )
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// Layout of a paginated list response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ListSchema {
+    /// Tastypie (`/api/v1/`): `{"meta": {"next": ...}, "objects": [...]}`
+    Tastypie,
+    /// Django REST framework style: `{"next": ..., "results": [...]}`
+    Paginated,
+    /// A bare JSON array without pagination
+    Array,
+}
+
+/// One page of a list response
+#[derive(Debug)]
+pub(super) struct Page<T> {
+    pub objects: Vec<T>,
+    /// Path or URL of the next page
+    pub next: Option<String>,
+    /// Index of each object among the response's records, counting those
+    /// that couldn't be read and were left out
+    pub positions: Vec<usize>,
+    /// Number of records in the response, read or not
+    pub records: usize,
+}
+
+/// Parse a response body as JSON
+///
+/// A body that isn't JSON comes from something other than the API (e.g., a
+/// proxy's error page), so it fails as a network error rather than a schema change.
+fn parse_json(endpoint: &'static str, body: &str) -> Result<Value> {
+    serde_json::from_str(body).map_err(|e| {
+        crate::Error::Network(anyhow::anyhow!(
+            "The Datatracker {} API returned a response that isn't JSON: {}",
+            endpoint,
+            e
+        ))
+        .into()
+    })
+}
+
+/// Parse a list response of any known layout
+///
+/// Records that can't be read are skipped rather than failing the page; only
+/// a page where no record can be read is a schema error.
+pub(super) fn parse_list<T: DeserializeOwned>(
+    endpoint: &'static str,
+    body: &str,
+) -> Result<Page<T>> {
+    let mut value = parse_json(endpoint, body)?;
+    let (schema, records) = match &mut value {
+        Value::Array(records) => (ListSchema::Array, std::mem::take(records)),
+        Value::Object(map) => match (map.remove("objects"), map.remove("results")) {
+            (Some(Value::Array(records)), _) => (ListSchema::Tastypie, records),
+            (_, Some(Value::Array(records))) => (ListSchema::Paginated, records),
+            _ => return Err(SchemaError::new(endpoint, "no list of records").into()),
+        },
+        _ => return Err(SchemaError::new(endpoint, "not a list response").into()),
+    };
+    let next = match schema {
+        ListSchema::Tastypie => value["meta"]["next"].as_str(),
+        ListSchema::Paginated => value["next"].as_str(),
+        ListSchema::Array => None,
+    }
+    .map(str::to_string);
+
+    let total = records.len();
+    let mut first_err = None;
+    let mut positions = Vec::new();
+    let mut objects: Vec<T> = Vec::new();
+    for (position, record) in records.into_iter().enumerate() {
+        match serde_json::from_value(record) {
+            Ok(object) => {
+                objects.push(object);
+                positions.push(position);
+            }
+            Err(e) => {
+                first_err.get_or_insert(e);
+            }
+        }
+    }
+    if objects.is_empty() {
+        if let Some(e) = first_err {
+            return Err(SchemaError::new(endpoint, e).into());
+        }
+    }
+    Ok(Page {
+        objects,
+        next,
+        positions,
+        records: total,
+    })
+}
+
+/// Parse a single-object response
+pub(super) fn parse_object<T: DeserializeOwned>(endpoint: &'static str, body: &str) -> Result<T> {
+    let value = parse_json(endpoint, body)?;
+    if !value.is_object() {
+        return Err(SchemaError::new(endpoint, "not an object").into());
+    }
+    serde_json::from_value(value).map_err(|e| SchemaError::new(endpoint, e).into())
+}
+
+/// Read a text field written as a string, number, or an object with a `slug` or `name`
+///
+/// Anything else (null, lists) reads as missing.
+pub(super) fn lenient_string<'de, D: Deserializer<'de>>(de: D) -> Result<Option<String>, D::Error> {
+    Ok(text(Value::deserialize(de)?))
+}
+
+/// Read a required text field as leniently as [`lenient_string`], empty if missing
+pub(super) fn lenient_text<'de, D: Deserializer<'de>>(de: D) -> Result<String, D::Error> {
+    Ok(text(Value::deserialize(de)?).unwrap_or_default())
+}
+
+/// Read a count written as a number or a numeric string
+pub(super) fn lenient_u32<'de, D: Deserializer<'de>>(de: D) -> Result<Option<u32>, D::Error> {
+    Ok(match Value::deserialize(de)? {
+        Value::Number(n) => n.as_u64().and_then(|n| u32::try_from(n).ok()),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    })
+}
+
+/// Read a list of names written as strings or as objects with a `name`
+pub(super) fn lenient_names<'de, D: Deserializer<'de>>(de: D) -> Result<Vec<String>, D::Error> {
+    Ok(match Value::deserialize(de)? {
+        Value::Array(items) => items.into_iter().filter_map(text).collect(),
+        _ => Vec::new(),
+    })
+}
+
+fn text(value: Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Object(mut map) => ["name", "slug"]
+            .iter()
+            .find_map(|key| map.remove(*key))
+            .and_then(text),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Record {
+        name: String,
+        #[serde(default, deserialize_with = "lenient_u32")]
+        pages: Option<u32>,
+        #[serde(default, deserialize_with = "lenient_string")]
+        stream: Option<String>,
+        #[serde(default, deserialize_with = "lenient_names")]
+        authors: Vec<String>,
+    }
+
+    #[test]
+    fn test_list_layouts() {
+        let tastypie = r#"{"meta": {"next": "/api/v1/doc/document/?offset=2", "total_count": 9},
+            "objects": [{"name": "rfc9000", "pages": "151", "added_later": true}, {"title": "no name"}]}"#;
+        let page: Page<Record> = parse_list("document", tastypie).unwrap();
+        assert_eq!(page.objects[0].pages, Some(151));
+        assert_eq!(page.next.as_deref(), Some("/api/v1/doc/document/?offset=2"));
+        assert_eq!((page.positions, page.records), (vec![0], 2));
+
+        let paginated = r#"{"next": null, "results": [{"name": "rfc9001",
+            "stream": {"slug": "ietf", "name": "IETF"}, "authors": ["M. Thomson", {"name": "S. Turner"}]}]}"#;
+        let page: Page<Record> = parse_list("document", paginated).unwrap();
+        assert_eq!(page.next, None);
+        assert_eq!(page.objects[0].stream.as_deref(), Some("IETF"));
+        assert_eq!(page.objects[0].authors, vec!["M. Thomson", "S. Turner"]);
+
+        let bare: Page<Record> = parse_list("document", r#"[{"name": "rfc9002"}]"#).unwrap();
+        assert_eq!(bare.objects.len(), 1);
+    }
+
+    #[test]
+    fn test_schema_errors() {
+        // A body that isn't JSON is a network error, not a schema change
+        let err = parse_list::<Record>("document", "<html>Maintenance</html>").unwrap_err();
+        assert!(err.downcast_ref::<SchemaError>().is_none());
+        assert!(matches!(crate::Error::from(err), crate::Error::Network(_)));
+        let err = parse_object::<Record>("doc.json", "Bad Gateway").unwrap_err();
+        assert!(matches!(crate::Error::from(err), crate::Error::Network(_)));
+
+        let err = parse_list::<Record>("document", r#"{"objects": 1}"#).unwrap_err();
+        let schema = err.downcast_ref::<SchemaError>().unwrap();
+        assert_eq!(schema.endpoint, "document");
+
+        let err = parse_list::<Record>("document", r#"{"documents": []}"#).unwrap_err();
+        assert!(err.downcast_ref::<SchemaError>().is_some());
+        let err = parse_list::<Record>("document", r#"{"objects": [{"title": "x"}]}"#).unwrap_err();
+        assert!(err.to_string().contains("missing field `name`"));
+        assert!(parse_list::<Record>("document", r#"{"objects": []}"#)
+            .unwrap()
+            .objects
+            .is_empty());
+
+        let err = parse_object::<Record>("doc.json", "[]").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("The Datatracker doc.json API changed upstream"));
+    }
+}
@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+
+use super::DATATRACKER_BASE_URL;
+
+/// Where the RFC Editor publishes RFCs, subseries and the index
+pub const RFC_EDITOR_BASE_URL: &str = "https://www.rfc-editor.org";
+/// Where Internet-Drafts are archived
+pub const DRAFTS_BASE_URL: &str = "https://www.ietf.org/archive/id";
+/// Where the IETF bibxml service publishes xml2rfc references
+pub const BIBXML_BASE_URL: &str = "https://bib.ietf.org/public/rfc";
+/// Mirror of the RFC Editor's `rfc/` directory tried when it fails; only for plain text
+const DEFAULT_MIRROR: &str = "https://www.ietf.org";
+
+/// Base URLs documents and metadata are fetched from
+///
+/// Mirrors have the RFC Editor's layout (`{mirror}/rfc/rfc9000.txt`) and are
+/// tried in order when a download of an RFC from `rfc_editor` fails, so an
+/// internal mirror can stand in for the public servers. The built-in mirror
+/// is only tried for plain text, the one format it serves in that layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SourceConfig {
+    pub rfc_editor: String,
+    pub datatracker: String,
+    pub drafts: String,
+    pub bibxml: String,
+    pub mirrors: Vec<String>,
+}
+
+impl Default for SourceConfig {
+    fn default() -> Self {
+        Self {
+            rfc_editor: RFC_EDITOR_BASE_URL.to_string(),
+            datatracker: DATATRACKER_BASE_URL.to_string(),
+            drafts: DRAFTS_BASE_URL.to_string(),
+            bibxml: BIBXML_BASE_URL.to_string(),
+            mirrors: vec![DEFAULT_MIRROR.to_string()],
+        }
+    }
+}
+
+impl SourceConfig {
+    /// Read overrides from the environment
+    ///
+    /// `RFC_EDITOR_URL`, `RFC_DATATRACKER_URL`, `RFC_DRAFTS_URL` and
+    /// `RFC_BIBXML_URL` replace the base URLs; `RFC_MIRRORS` is a comma-separated list of mirrors, or empty
+    /// for none.
+    pub fn from_env() -> Self {
+        let var = |name| std::env::var(name).ok();
+        let mut config = Self::default();
+        if let Some(url) = var("RFC_EDITOR_URL").filter(|v| !v.trim().is_empty()) {
+            config = config.with_rfc_editor(&url);
+        }
+        if let Some(url) = var("RFC_DATATRACKER_URL").filter(|v| !v.trim().is_empty()) {
+            config = config.with_datatracker(&url);
+        }
+        if let Some(url) = var("RFC_DRAFTS_URL").filter(|v| !v.trim().is_empty()) {
+            config = config.with_drafts(&url);
+        }
+        if let Some(url) = var("RFC_BIBXML_URL").filter(|v| !v.trim().is_empty()) {
+            config = config.with_bibxml(&url);
+        }
+        if let Some(mirrors) = var("RFC_MIRRORS") {
+            config = config.with_mirrors(mirrors.split(','));
+        }
+        config
+    }
+
+    /// Fetch RFCs, subseries and the index from another RFC Editor
+    pub fn with_rfc_editor(mut self, url: &str) -> Self {
+        self.rfc_editor = base(url);
+        self
+    }
+
+    /// Query another Datatracker instance
+    pub fn with_datatracker(mut self, url: &str) -> Self {
+        self.datatracker = base(url);
+        self
+    }
+
+    /// Fetch Internet-Drafts from another archive
+    pub fn with_drafts(mut self, url: &str) -> Self {
+        self.drafts = base(url);
+        self
+    }
+
+    /// Fetch xml2rfc references from another bibxml service
+    pub fn with_bibxml(mut self, url: &str) -> Self {
+        self.bibxml = base(url);
+        self
+    }
+
+    /// Replace the mirrors tried after the RFC Editor, in order
+    pub fn with_mirrors<I, S>(mut self, mirrors: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.mirrors = mirrors
+            .into_iter()
+            .map(|m| base(m.as_ref()))
+            .filter(|m| !m.is_empty())
+            .collect();
+        self
+    }
+
+    /// Get the URL of a path on the RFC Editor (e.g., "/rfc-index.xml")
+    pub fn rfc_editor_url(&self, path: &str) -> String {
+        format!("{}{}", self.rfc_editor, path)
+    }
+
+    /// Get the URLs of a path on the RFC Editor and then each mirror serving it
+    pub fn rfc_editor_urls(&self, path: &str) -> Vec<String> {
+        let mirrors = self
+            .mirrors
+            .iter()
+            .filter(|mirror| *mirror != DEFAULT_MIRROR || path.ends_with(".txt"));
+        std::iter::once(&self.rfc_editor)
+            .chain(mirrors)
+            .map(|base| format!("{}{}", base, path))
+            .collect()
+    }
+
+    /// Get the URL of a path on the Datatracker (e.g., "/doc/rfc9000/")
+    pub fn datatracker_url(&self, path: &str) -> String {
+        format!("{}{}", self.datatracker, path)
+    }
+
+    /// Get the URL of a path on the bibxml service (e.g., "/bibxml/reference.RFC.9000.xml")
+    pub fn bibxml_url(&self, path: &str) -> String {
+        format!("{}{}", self.bibxml, path)
+    }
+
+    /// Get the URL of a file in the draft archive (e.g., "draft-foo-00.txt")
+    pub fn draft_url(&self, file: &str) -> String {
+        format!("{}/{}", self.drafts, file)
+    }
+}
+
+/// Normalize a base URL by dropping surrounding whitespace and trailing slashes
+fn base(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_urls() {
+        let sources = SourceConfig::default();
+        assert_eq!(
+            sources.rfc_editor_urls("/rfc/rfc9000.txt"),
+            vec![
+                "https://www.rfc-editor.org/rfc/rfc9000.txt",
+                "https://www.ietf.org/rfc/rfc9000.txt"
+            ]
+        );
+        // The built-in mirror only serves plain text
+        assert_eq!(
+            sources.rfc_editor_urls("/rfc/rfc9000.html"),
+            vec!["https://www.rfc-editor.org/rfc/rfc9000.html"]
+        );
+        assert_eq!(
+            sources.draft_url("draft-foo-00.txt"),
+            "https://www.ietf.org/archive/id/draft-foo-00.txt"
+        );
+
+        let internal = SourceConfig::default()
+            .with_rfc_editor("https://rfc.corp.example/ ")
+            .with_drafts("https://rfc.corp.example/drafts/")
+            .with_mirrors(["https://mirror-a.example/", "", "https://mirror-b.example"]);
+        assert_eq!(
+            internal.rfc_editor_urls("/rfc/rfc9000.txt"),
+            vec![
+                "https://rfc.corp.example/rfc/rfc9000.txt",
+                "https://mirror-a.example/rfc/rfc9000.txt",
+                "https://mirror-b.example/rfc/rfc9000.txt"
+            ]
+        );
+        assert_eq!(
+            internal.draft_url("draft-foo-00.txt"),
+            "https://rfc.corp.example/drafts/draft-foo-00.txt"
+        );
+        assert_eq!(
+            internal.datatracker_url("/doc/rfc9000/"),
+            "https://datatracker.ietf.org/doc/rfc9000/"
+        );
+    }
+}
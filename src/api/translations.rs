@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+
+use super::DocumentFetcher;
+use crate::models::{DocumentType, Format};
+
+/// Where community translations of documents are published, keyed by language
+///
+/// Templates are URLs with `{number}` (the RFC number) or `{name}` (the
+/// document name, e.g., "rfc9000") placeholders. A template ending in `.txt`
+/// serves plain text; anything else is treated as HTML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranslationSources {
+    templates: BTreeMap<String, String>,
+}
+
+impl Default for TranslationSources {
+    fn default() -> Self {
+        Self::new().with(
+            "ja",
+            "https://tex2e.github.io/rfc-translater/html/rfc{number}.html",
+        )
+    }
+}
+
+impl TranslationSources {
+    /// Create an empty set of sources
+    pub fn new() -> Self {
+        Self {
+            templates: BTreeMap::new(),
+        }
+    }
+
+    /// Add or replace the source of a language
+    pub fn with(mut self, language: &str, template: &str) -> Self {
+        self.templates
+            .insert(language.to_ascii_lowercase(), template.to_string());
+        self
+    }
+
+    /// Get the languages with a configured source, sorted
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.templates.keys().map(String::as_str)
+    }
+
+    /// Get the URL and format of a document's translation
+    ///
+    /// None if no source is configured for the language, or the source needs
+    /// an RFC number and the document isn't an RFC.
+    pub fn url(&self, doc: &DocumentType, language: &str) -> Option<(String, Format)> {
+        let template = self.templates.get(&language.to_ascii_lowercase())?;
+        let mut url = template.replace("{name}", &doc.name());
+        if url.contains("{number}") {
+            let DocumentType::Rfc(number) = doc else {
+                return None;
+            };
+            url = url.replace("{number}", &number.to_string());
+        }
+        let format = if url.ends_with(".txt") {
+            Format::Text
+        } else {
+            Format::Html
+        };
+        Some((url, format))
+    }
+}
+
+/// Check for a language tag like "ja" or "zh-hans"
+pub fn is_language_tag(tag: &str) -> bool {
+    !tag.is_empty()
+        && tag.len() <= 16
+        && tag
+            .split('-')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+impl DocumentFetcher {
+    /// Publish translations for `language` at `template` (see `TranslationSources`)
+    pub fn with_translation_source(mut self, language: &str, template: &str) -> Self {
+        self.translations = self.translations.with(language, template);
+        self
+    }
+
+    /// Get the configured translation sources
+    pub fn translation_sources(&self) -> &TranslationSources {
+        &self.translations
+    }
+
+    /// Fetch a community translation of a document (e.g., `fetch_translation(doc, "ja")`)
+    pub async fn fetch_translation(
+        &self,
+        doc: &DocumentType,
+        language: &str,
+    ) -> Result<(String, Format)> {
+        if !is_language_tag(language) {
+            anyhow::bail!("Invalid language '{}'", language);
+        }
+        let (url, format) = self.translations.url(doc, language).with_context(|| {
+            format!("No {} translation source configured for {}", language, doc)
+        })?;
+        let content = self
+            .fetch_content(&url)
+            .await
+            .with_context(|| format!("No {} translation of {} found", language, doc))?;
+        Ok((content, format))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translation_urls() {
+        let sources = TranslationSources::default().with("de", "https://example.org/de/{name}.txt");
+
+        assert_eq!(
+            sources.url(&DocumentType::Rfc(9000), "JA"),
+            Some((
+                "https://tex2e.github.io/rfc-translater/html/rfc9000.html".to_string(),
+                Format::Html
+            ))
+        );
+        assert_eq!(
+            sources.url(&DocumentType::Rfc(9000), "de"),
+            Some((
+                "https://example.org/de/rfc9000.txt".to_string(),
+                Format::Text
+            ))
+        );
+        assert!(sources
+            .url(
+                &DocumentType::Draft("draft-ietf-quic-qlog".to_string()),
+                "ja"
+            )
+            .is_none());
+        assert!(sources.url(&DocumentType::Rfc(9000), "fr").is_none());
+        assert_eq!(sources.languages().collect::<Vec<_>>(), vec!["de", "ja"]);
+    }
+
+    #[test]
+    fn test_is_language_tag() {
+        assert!(is_language_tag("ja"));
+        assert!(is_language_tag("zh-Hans"));
+        assert!(!is_language_tag(""));
+        assert!(!is_language_tag("../ja"));
+        assert!(!is_language_tag("ja-"));
+    }
+}
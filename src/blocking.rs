@@ -0,0 +1,347 @@
+//! Synchronous clients for scripts and applications without an async runtime
+//!
+//! [`DocumentFetcher`] and [`DataTrackerClient`] mirror the async clients of
+//! [`crate::api`], driving each request on a runtime of their own. Like
+//! `reqwest::blocking`, their methods panic when called from within an async
+//! runtime; use the async clients there.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use anyhow::Context;
+use futures::{Stream, StreamExt};
+use tokio::runtime::Runtime;
+
+use crate::api;
+use crate::cache::{CacheManager, SyncReport};
+use crate::models::{
+    Author, Cluster, Document, DocumentMetadata, DocumentType, DraftRevision, Erratum, Format,
+    FormatPreference, RfcMetadata, SearchFilter, SearchResult, Subseries,
+};
+use crate::parse::{Artwork, SectionExcerpt};
+use crate::{Error, Result};
+
+/// Create the runtime a blocking client drives its requests on
+fn runtime() -> Result<Runtime> {
+    Ok(tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("Failed to start the runtime of the blocking client")?)
+}
+
+/// Run a request of an async client to completion
+fn wait<T, E: Into<Error>>(
+    runtime: &Runtime,
+    request: impl Future<Output = std::result::Result<T, E>>,
+) -> Result<T> {
+    runtime.block_on(request).map_err(Into::into)
+}
+
+/// Blocking client for fetching RFC and draft content (see [`api::DocumentFetcher`])
+pub struct DocumentFetcher {
+    inner: api::DocumentFetcher,
+    runtime: Runtime,
+}
+
+impl DocumentFetcher {
+    /// Create a client configured from the environment, as [`api::DocumentFetcher::new`]
+    pub fn new() -> Result<Self> {
+        Self::from_async(api::DocumentFetcher::new()?)
+    }
+
+    /// Wrap a configured async client, e.g. one made with [`api::DocumentFetcher::builder`]
+    ///
+    /// The client should not have been used on another runtime, whose
+    /// connections would be reused here.
+    pub fn from_async(inner: api::DocumentFetcher) -> Result<Self> {
+        Ok(Self {
+            inner,
+            runtime: runtime()?,
+        })
+    }
+
+    /// Get the async client, e.g. for its URLs
+    pub fn get_ref(&self) -> &api::DocumentFetcher {
+        &self.inner
+    }
+
+    /// Fetch a document in the preferred format (see [`api::DocumentFetcher::fetch`])
+    pub fn fetch(&self, doc: &DocumentType) -> Result<(String, Format)> {
+        wait(&self.runtime, self.inner.fetch(doc))
+    }
+
+    /// Fetch a document in the first format of `preference` it is published in
+    pub fn fetch_preferring(
+        &self,
+        doc: &DocumentType,
+        preference: &FormatPreference,
+    ) -> Result<(String, Format)> {
+        wait(&self.runtime, self.inner.fetch_preferring(doc, preference))
+    }
+
+    /// Fetch a document in a specific format, without falling back to another one
+    pub fn fetch_format(&self, doc: &DocumentType, format: Format) -> Result<String> {
+        wait(&self.runtime, self.inner.fetch_format(doc, format))
+    }
+
+    /// Fetch the raw content of a document in a specific format
+    pub fn fetch_bytes(&self, doc: &DocumentType, format: Format) -> Result<Vec<u8>> {
+        wait(&self.runtime, self.inner.fetch_bytes(doc, format))
+    }
+
+    /// Download a document straight into `cache`, returning the format stored
+    pub fn fetch_to_cache(&self, doc: &DocumentType, cache: &CacheManager) -> Result<Format> {
+        wait(&self.runtime, self.inner.fetch_to_cache(doc, cache))
+    }
+
+    /// Fetch several documents, at most `max_concurrent` at once
+    pub fn fetch_many(
+        &self,
+        docs: &[DocumentType],
+        max_concurrent: usize,
+    ) -> Vec<Result<(String, Format)>> {
+        self.runtime
+            .block_on(self.inner.fetch_many(docs, max_concurrent))
+    }
+
+    /// Download every document missing from `cache`
+    pub fn prefetch(&self, docs: &[DocumentType], cache: &CacheManager) -> SyncReport {
+        self.runtime.block_on(self.inner.prefetch(docs, cache))
+    }
+
+    /// Fetch a community translation of a document
+    pub fn fetch_translation(
+        &self,
+        doc: &DocumentType,
+        language: &str,
+    ) -> Result<(String, Format)> {
+        wait(&self.runtime, self.inner.fetch_translation(doc, language))
+    }
+
+    /// Fetch the authors of a document from its xml2rfc source
+    pub fn authors(&self, doc: &DocumentType) -> Result<Vec<Author>> {
+        wait(&self.runtime, self.inner.authors(doc))
+    }
+
+    /// Extract the figures and code samples of a document
+    pub fn artwork(&self, doc: &DocumentType) -> Result<Vec<Artwork>> {
+        wait(&self.runtime, self.inner.artwork(doc))
+    }
+
+    /// Fetch publication metadata for an RFC from the RFC Editor
+    pub fn rfc_metadata(&self, number: u32) -> Result<RfcMetadata> {
+        wait(&self.runtime, self.inner.rfc_metadata(number))
+    }
+
+    /// Download and parse the RFC Editor's index of every RFC
+    pub fn rfc_index(&self) -> Result<api::RfcIndex> {
+        wait(&self.runtime, self.inner.rfc_index())
+    }
+
+    /// Get the RFCs that currently make up a subseries number
+    pub fn subseries_members(&self, series: Subseries, number: u32) -> Result<Vec<u32>> {
+        wait(&self.runtime, self.inner.subseries_members(series, number))
+    }
+
+    /// Get the most recent RFC of a subseries number
+    pub fn latest_member(&self, series: Subseries, number: u32) -> Result<DocumentType> {
+        wait(&self.runtime, self.inner.latest_member(series, number))
+    }
+
+    /// Fetch only the Security Considerations section of a document
+    pub fn security_considerations(&self, doc: &DocumentType) -> Result<Option<SectionExcerpt>> {
+        wait(&self.runtime, self.inner.security_considerations(doc))
+    }
+
+    /// Fetch a single section of a document by number, anchor or title
+    pub fn get_section(&self, doc: &DocumentType, section: &str) -> Result<SectionExcerpt> {
+        wait(&self.runtime, self.inner.get_section(doc, section))
+    }
+
+    /// List every published revision of a draft, oldest first
+    pub fn draft_revisions(&self, name: &str) -> Result<Vec<DraftRevision>> {
+        wait(&self.runtime, self.inner.draft_revisions(name))
+    }
+
+    /// Fetch the errata reported against an RFC
+    pub fn errata(&self, number: u32) -> Result<Vec<Erratum>> {
+        wait(&self.runtime, self.inner.errata(number))
+    }
+
+    /// Get the errata of an RFC from the cache, fetching and caching them if missing or stale
+    pub fn errata_cached(&self, number: u32, cache: &CacheManager) -> Result<Vec<Erratum>> {
+        wait(&self.runtime, self.inner.errata_cached(number, cache))
+    }
+
+    /// Fetch all RFC Editor clusters
+    pub fn clusters(&self) -> Result<Vec<Cluster>> {
+        wait(&self.runtime, self.inner.clusters())
+    }
+
+    /// Find the cluster a document was (or is being) published in
+    pub fn cluster_of(&self, doc: &DocumentType) -> Result<Option<Cluster>> {
+        wait(&self.runtime, self.inner.cluster_of(doc))
+    }
+
+    /// Fetch a document's xml2rfc `<reference>` from the IETF bibxml service
+    pub fn bibxml_reference(&self, doc: &DocumentType) -> Result<String> {
+        wait(&self.runtime, self.inner.bibxml_reference(doc))
+    }
+
+    /// Get a document's bibxml reference from the cache, fetching and caching it if missing
+    pub fn bibxml_reference_cached(
+        &self,
+        doc: &DocumentType,
+        cache: &CacheManager,
+    ) -> Result<String> {
+        wait(
+            &self.runtime,
+            self.inner.bibxml_reference_cached(doc, cache),
+        )
+    }
+}
+
+/// Blocking client for the IETF Datatracker API (see [`api::DataTrackerClient`])
+pub struct DataTrackerClient {
+    inner: api::DataTrackerClient,
+    runtime: Runtime,
+}
+
+impl DataTrackerClient {
+    /// Create a client configured from the environment, as [`api::DataTrackerClient::new`]
+    pub fn new() -> Result<Self> {
+        Self::from_async(api::DataTrackerClient::new()?)
+    }
+
+    /// Wrap a configured async client
+    ///
+    /// The client should not have been used on another runtime, whose
+    /// connections would be reused here.
+    pub fn from_async(inner: api::DataTrackerClient) -> Result<Self> {
+        Ok(Self {
+            inner,
+            runtime: runtime()?,
+        })
+    }
+
+    /// Get the async client
+    pub fn get_ref(&self) -> &api::DataTrackerClient {
+        &self.inner
+    }
+
+    /// Search for RFCs and Internet-Drafts matching the query
+    pub fn search(&self, query: &str, filter: SearchFilter, limit: u32) -> Result<SearchResult> {
+        wait(&self.runtime, self.inner.search(query, filter, limit))
+    }
+
+    /// Search for documents, optionally only those of an author
+    pub fn search_by_author(
+        &self,
+        query: &str,
+        author: Option<&str>,
+        filter: SearchFilter,
+        limit: u32,
+    ) -> Result<SearchResult> {
+        wait(
+            &self.runtime,
+            self.inner.search_by_author(query, author, filter, limit),
+        )
+    }
+
+    /// Get one page of search results, starting at `offset`
+    pub fn search_page(
+        &self,
+        query: &str,
+        author: Option<&str>,
+        filter: SearchFilter,
+        limit: u32,
+        offset: u64,
+    ) -> Result<SearchResult> {
+        wait(
+            &self.runtime,
+            self.inner.search_page(query, author, filter, limit, offset),
+        )
+    }
+
+    /// Iterate over every matching document, requesting further pages as needed
+    pub fn search_all<'a>(
+        &'a self,
+        query: &'a str,
+        author: Option<&'a str>,
+        filter: SearchFilter,
+    ) -> SearchIter<'a> {
+        SearchIter {
+            stream: Box::pin(self.inner.search_all(query, author, filter)),
+            runtime: &self.runtime,
+        }
+    }
+
+    /// List the RFCs and drafts of an IRTF research group (e.g., "cfrg")
+    pub fn rg_documents(&self, acronym: &str, limit: u32) -> Result<Vec<Document>> {
+        wait(&self.runtime, self.inner.rg_documents(acronym, limit))
+    }
+
+    /// List the active drafts and RFCs of an IETF working group (e.g., "quic")
+    pub fn wg_documents(&self, acronym: &str) -> Result<Vec<Document>> {
+        wait(&self.runtime, self.inner.wg_documents(acronym))
+    }
+
+    /// Look up several documents at once; unknown documents are None
+    pub fn documents(&self, docs: &[DocumentType]) -> Result<Vec<Option<Document>>> {
+        wait(&self.runtime, self.inner.documents(docs))
+    }
+
+    /// Fetch metadata for a single document
+    pub fn document(&self, doc: &DocumentType) -> Result<Document> {
+        wait(&self.runtime, self.inner.document(doc))
+    }
+
+    /// Fetch the full metadata of a document
+    pub fn metadata(&self, doc: &DocumentType) -> Result<DocumentMetadata> {
+        wait(&self.runtime, self.inner.metadata(doc))
+    }
+}
+
+/// Documents of [`DataTrackerClient::search_all`], fetched page by page as the iterator advances
+pub struct SearchIter<'a> {
+    stream: Pin<Box<dyn Stream<Item = Result<Document>> + 'a>>,
+    runtime: &'a Runtime,
+}
+
+impl Iterator for SearchIter<'_> {
+    type Item = Result<Document>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::OfflineMode;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_fetch_without_a_runtime() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        cache
+            .store_document(&DocumentType::Rfc(9000), Format::Text, "QUIC")
+            .unwrap();
+        let fetcher = DocumentFetcher::from_async(
+            api::DocumentFetcher::new()
+                .unwrap()
+                .with_cache(cache)
+                .with_offline_mode(OfflineMode::Offline),
+        )
+        .unwrap();
+
+        let (text, format) = fetcher.fetch(&DocumentType::Rfc(9000)).unwrap();
+        assert_eq!((text.as_str(), format), ("QUIC", Format::Text));
+        assert!(matches!(
+            fetcher.fetch(&DocumentType::Rfc(9001)),
+            Err(Error::Offline { .. })
+        ));
+    }
+}
@@ -0,0 +1,220 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::DocumentType;
+use crate::parse::SectionAnchor;
+use crate::paths::data_dir;
+
+/// A bookmarked document, or a section of one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub doc: DocumentType,
+    /// Section the bookmark points to; `None` for the whole document
+    #[serde(default)]
+    pub section: Option<SectionAnchor>,
+    #[serde(default)]
+    pub note: Option<String>,
+    pub created: DateTime<Utc>,
+}
+
+impl Bookmark {
+    pub fn new(doc: DocumentType, section: Option<SectionAnchor>) -> Self {
+        Self {
+            doc,
+            section,
+            note: None,
+            created: Utc::now(),
+        }
+    }
+
+    /// Attach a note to the bookmark
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    /// Parse a bookmark target like "RFC 9110 §15.5.4" or "rfc9110#section-15.5.4"
+    ///
+    /// The section may follow the document after "#", "§", "Section",
+    /// "Appendix" or a space; deep links into the HTML rendering work too.
+    pub fn parse_target(s: &str) -> Option<(DocumentType, Option<SectionAnchor>)> {
+        let lower = s.trim().to_lowercase();
+        let split = ["#", "§", " section ", " appendix ", " sec. "]
+            .iter()
+            .filter_map(|marker| lower.rfind(marker))
+            .max()
+            .or_else(|| lower.rfind(char::is_whitespace));
+        if let Some(at) = split {
+            let (doc, section) = lower.split_at(at);
+            if let (Some(doc), Some(section)) =
+                (DocumentType::parse(doc), SectionAnchor::parse(section))
+            {
+                return Some((doc, Some(section)));
+            }
+        }
+        Some((DocumentType::parse(&lower)?, None))
+    }
+
+    /// Get a label for the target (e.g., "RFC 9110 Section 15.5.4")
+    pub fn target(&self) -> String {
+        match &self.section {
+            Some(section) => format!("{} {}", self.doc, section),
+            None => self.doc.to_string(),
+        }
+    }
+
+    /// Check if the bookmark points to the given document and section
+    pub fn points_to(&self, doc: &DocumentType, section: Option<&SectionAnchor>) -> bool {
+        self.doc == *doc && self.section.as_ref() == section
+    }
+}
+
+/// The user's bookmarks, kept in a JSON file
+#[derive(Debug, Clone, Default)]
+pub struct Bookmarks {
+    path: PathBuf,
+    entries: Vec<Bookmark>,
+}
+
+impl Bookmarks {
+    /// Get the default bookmark file in the platform-specific data directory
+    pub fn default_path() -> crate::Result<PathBuf> {
+        Ok(data_dir()?.join("bookmarks.json"))
+    }
+
+    /// Load the bookmarks from the default bookmark file
+    pub fn open() -> crate::Result<Self> {
+        Self::load(&Self::default_path()?)
+    }
+
+    /// Load bookmarks from a file; a missing file holds no bookmarks
+    pub fn load(path: &Path) -> crate::Result<Self> {
+        let entries = match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse bookmarks {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display()))?,
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    /// Write the bookmarks back to their file
+    pub fn save(&self) -> crate::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let json =
+            serde_json::to_string_pretty(&self.entries).context("Failed to serialize bookmarks")?;
+        fs::write(&self.path, json)
+            .with_context(|| format!("Failed to write {}", self.path.display()))?;
+        Ok(())
+    }
+
+    /// Get the bookmarks, oldest first
+    pub fn entries(&self) -> &[Bookmark] {
+        &self.entries
+    }
+
+    /// Add a bookmark, replacing one with the same target
+    ///
+    /// Returns true if the target was not bookmarked before.
+    pub fn add(&mut self, bookmark: Bookmark) -> bool {
+        let existing = self
+            .entries
+            .iter_mut()
+            .find(|b| b.points_to(&bookmark.doc, bookmark.section.as_ref()));
+        match existing {
+            Some(existing) => {
+                *existing = bookmark;
+                false
+            }
+            None => {
+                self.entries.push(bookmark);
+                true
+            }
+        }
+    }
+
+    /// Remove the bookmark of a document and section
+    pub fn remove(
+        &mut self,
+        doc: &DocumentType,
+        section: Option<&SectionAnchor>,
+    ) -> Option<Bookmark> {
+        let index = self
+            .entries
+            .iter()
+            .position(|b| b.points_to(doc, section))?;
+        Some(self.entries.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn section(s: &str) -> Option<SectionAnchor> {
+        SectionAnchor::parse(s)
+    }
+
+    #[test]
+    fn test_parse_target() {
+        let expected = Some((DocumentType::Rfc(9110), section("15.5.4")));
+        for form in [
+            "RFC 9110 §15.5.4",
+            "rfc9110#section-15.5.4",
+            "RFC 9110 Section 15.5.4",
+            "9110 15.5.4",
+            "https://www.rfc-editor.org/rfc/rfc9110.html#section-15.5.4",
+        ] {
+            assert_eq!(Bookmark::parse_target(form), expected, "{}", form);
+        }
+
+        assert_eq!(
+            Bookmark::parse_target("RFC 9110"),
+            Some((DocumentType::Rfc(9110), None))
+        );
+        assert_eq!(
+            Bookmark::parse_target("draft-ietf-quic-qlog Appendix A"),
+            Some((
+                DocumentType::Draft("draft-ietf-quic-qlog".to_string()),
+                section("A")
+            ))
+        );
+        assert_eq!(Bookmark::parse_target("§5"), None);
+    }
+
+    #[test]
+    fn test_bookmarks_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("rfc").join("bookmarks.json");
+        let mut bookmarks = Bookmarks::load(&path).unwrap();
+        assert!(bookmarks.entries().is_empty());
+
+        let target = Bookmark::new(DocumentType::Rfc(9110), section("15.5.4"));
+        assert_eq!(target.target(), "RFC 9110 Section 15.5.4");
+        assert!(bookmarks.add(target.clone()));
+        assert!(bookmarks.add(Bookmark::new(DocumentType::Rfc(9110), None)));
+        // Bookmarking the same target again only updates the note
+        assert!(!bookmarks.add(target.with_note("404 Not Found")));
+        bookmarks.save().unwrap();
+
+        let mut loaded = Bookmarks::load(&path).unwrap();
+        assert_eq!(loaded.entries(), bookmarks.entries());
+        assert_eq!(loaded.entries()[0].note.as_deref(), Some("404 Not Found"));
+
+        let removed = loaded.remove(&DocumentType::Rfc(9110), None).unwrap();
+        assert_eq!(removed.section, None);
+        assert!(loaded.remove(&DocumentType::Rfc(9110), None).is_none());
+        assert_eq!(loaded.entries().len(), 1);
+    }
+}
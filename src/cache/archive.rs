@@ -0,0 +1,166 @@
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use tempfile::TempDir;
+
+use super::bundle::BUNDLE_INDEX;
+use super::{BundleIndex, CacheManager};
+use crate::models::{DocumentType, Format};
+
+/// Whether a path names a gzipped tarball rather than a bundle directory
+pub fn is_archive(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    name.ends_with(".tar.gz") || name.ends_with(".tgz")
+}
+
+/// Append a file to the archive with fixed metadata, so archives are reproducible
+fn append_file<W: std::io::Write>(
+    builder: &mut tar::Builder<W>,
+    path: &str,
+    content: &[u8],
+) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(0);
+    header.set_entry_type(tar::EntryType::Regular);
+    builder
+        .append_data(&mut header, path, content)
+        .with_context(|| format!("Failed to add {} to archive", path))
+}
+
+impl CacheManager {
+    /// Write every cached document to a portable `.tar.gz` archive
+    pub fn export(&self, path: &Path) -> crate::Result<BundleIndex> {
+        self.export_archive(&[], path)
+    }
+
+    /// Write the selected documents (or all, if none) to a `.tar.gz` archive
+    ///
+    /// The archive holds a regular bundle, `bundle.json` index included.
+    pub fn export_archive(
+        &self,
+        selected: &[(DocumentType, Format)],
+        path: &Path,
+    ) -> crate::Result<BundleIndex> {
+        let staging = TempDir::new().context("Failed to create staging directory")?;
+        let index = self.export_bundle(selected, staging.path())?;
+
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create archive {}", path.display()))?;
+        let mut builder =
+            tar::Builder::new(GzEncoder::new(BufWriter::new(file), Compression::default()));
+        let entries =
+            std::iter::once(BUNDLE_INDEX).chain(index.documents.iter().map(|d| d.path.as_str()));
+        for entry in entries {
+            let content = fs::read(staging.path().join(entry))?;
+            append_file(&mut builder, entry, &content)?;
+        }
+        builder
+            .into_inner()
+            .and_then(|gz| gz.finish())
+            .context("Failed to write archive")?;
+        Ok(index)
+    }
+
+    /// Verify a `.tar.gz` archive and copy its documents into the cache
+    ///
+    /// Returns the number of documents imported.
+    pub fn import(&self, path: &Path) -> crate::Result<usize> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open archive {}", path.display()))?;
+        let mut archive = tar::Archive::new(GzDecoder::new(BufReader::new(file)));
+        let staging = TempDir::new().context("Failed to create staging directory")?;
+
+        for entry in archive.entries().context("Failed to read archive")? {
+            let mut entry = entry.context("Failed to read archive")?;
+            if entry.header().entry_type() != tar::EntryType::Regular {
+                continue;
+            }
+            let name = entry.path()?.display().to_string();
+            // Refuses absolute paths and paths escaping the staging directory
+            if !entry.unpack_in(staging.path())? {
+                return Err(anyhow::anyhow!("Unsafe path '{}' in archive", name).into());
+            }
+        }
+        self.import_bundle(staging.path())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_with_documents(temp: &TempDir) -> CacheManager {
+        let cache = CacheManager::with_dir(temp.path().join("cache")).unwrap();
+        cache
+            .store_document(&DocumentType::Rfc(9000), Format::Text, "quic")
+            .unwrap();
+        cache
+            .store_bytes(&DocumentType::Rfc(9000), Format::Pdf, b"%PDF-1.7")
+            .unwrap();
+        cache
+    }
+
+    #[test]
+    fn test_is_archive() {
+        assert!(is_archive(Path::new("library.tar.gz")));
+        assert!(is_archive(Path::new("/tmp/Library.TGZ")));
+        assert!(!is_archive(Path::new("vendor/rfc")));
+    }
+
+    #[test]
+    fn test_archive_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let cache = cache_with_documents(&temp);
+        let archive = temp.path().join("library.tar.gz");
+        assert_eq!(cache.export(&archive).unwrap().documents.len(), 2);
+
+        let target = CacheManager::with_dir(temp.path().join("target")).unwrap();
+        assert_eq!(target.import(&archive).unwrap(), 2);
+        assert_eq!(
+            target.get_document(&DocumentType::Rfc(9000), Format::Text),
+            Some("quic".to_string())
+        );
+        assert_eq!(
+            target.get_bytes(&DocumentType::Rfc(9000), Format::Pdf),
+            Some(b"%PDF-1.7".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_archive_is_reproducible() {
+        let temp = TempDir::new().unwrap();
+        let cache = cache_with_documents(&temp);
+        let first = temp.path().join("a.tar.gz");
+        let second = temp.path().join("b.tar.gz");
+        cache.export(&first).unwrap();
+        cache.export(&second).unwrap();
+        assert_eq!(fs::read(first).unwrap(), fs::read(second).unwrap());
+    }
+
+    #[test]
+    fn test_import_rejects_tampered_archive() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("bad.tar.gz");
+        let file = File::create(&path).unwrap();
+        let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+        let index = r#"{"version":1,"documents":[{"name":"rfc1","path":"documents/rfc1.txt","sha256":"00"}]}"#;
+        append_file(&mut builder, BUNDLE_INDEX, index.as_bytes()).unwrap();
+        append_file(&mut builder, "documents/rfc1.txt", b"tampered").unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let target = CacheManager::with_dir(temp.path().join("target")).unwrap();
+        let err = target.import(&path).unwrap_err();
+        assert_eq!(err.to_string(), "Checksum mismatch for documents/rfc1.txt");
+        assert!(target.list_cached().is_empty());
+    }
+}
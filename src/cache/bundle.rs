@@ -0,0 +1,235 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::CacheManager;
+use crate::models::{DocumentType, Format};
+
+/// Version of the bundle layout
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Name of the index file at the root of a bundle
+pub(super) const BUNDLE_INDEX: &str = "bundle.json";
+
+/// Index of a cache bundle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleIndex {
+    pub version: u32,
+    /// Entries sorted by path
+    pub documents: Vec<BundleEntry>,
+}
+
+/// A document stored in a bundle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleEntry {
+    /// Document name (e.g., "rfc9000")
+    pub name: String,
+    /// Path relative to the bundle root (e.g., "documents/rfc9000.txt")
+    pub path: String,
+    /// Hex SHA-256 of the file content
+    pub sha256: String,
+}
+
+impl BundleEntry {
+    /// Get the document and format of the entry, rejecting paths outside `documents/`
+    fn resolve(&self) -> Result<(DocumentType, Format)> {
+        let doc = DocumentType::parse(&self.name)
+            .with_context(|| format!("Invalid document name '{}' in bundle", self.name))?;
+        let format = self
+            .path
+            .rsplit_once('.')
+            .and_then(|(_, ext)| Format::from_extension(ext))
+            .with_context(|| format!("Unknown format of '{}' in bundle", self.path))?;
+        if self.path != bundle_path(&doc, format) {
+            anyhow::bail!("Unexpected path '{}' for {} in bundle", self.path, doc);
+        }
+        Ok((doc, format))
+    }
+}
+
+/// Path of a document inside a bundle
+fn bundle_path(doc: &DocumentType, format: Format) -> String {
+    format!("documents/{}.{}", doc.name(), format.extension())
+}
+
+pub(super) fn sha256_hex(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+impl CacheManager {
+    /// Write cached documents into a deterministic, checksummed bundle directory
+    ///
+    /// With no documents selected, every cached document is bundled. The same
+    /// documents always produce byte-identical bundles.
+    pub fn export_bundle(
+        &self,
+        selected: &[(DocumentType, Format)],
+        dir: &Path,
+    ) -> crate::Result<BundleIndex> {
+        let selected: Vec<(DocumentType, Format)> = if selected.is_empty() {
+            self.list_cached()
+                .into_iter()
+                .flat_map(|doc| {
+                    self.cached_formats(&doc)
+                        .into_iter()
+                        .map(move |format| (doc.clone(), format))
+                })
+                .collect()
+        } else {
+            selected.to_vec()
+        };
+
+        let mut documents = Vec::with_capacity(selected.len());
+        fs::create_dir_all(dir.join("documents")).context("Failed to create bundle directory")?;
+        for (doc, format) in &selected {
+            let content = self
+                .read_shared(doc, *format)
+                .with_context(|| format!("{} ({}) is not cached", doc, format.extension()))?;
+            let path = bundle_path(doc, *format);
+            fs::write(dir.join(&path), content.as_slice())
+                .context("Failed to write bundle document")?;
+            documents.push(BundleEntry {
+                name: doc.name(),
+                path,
+                sha256: sha256_hex(&content),
+            });
+        }
+        documents.sort_by(|a, b| a.path.cmp(&b.path));
+        documents.dedup();
+
+        let index = BundleIndex {
+            version: BUNDLE_VERSION,
+            documents,
+        };
+        let json =
+            serde_json::to_string_pretty(&index).context("Failed to serialize bundle index")?;
+        fs::write(dir.join(BUNDLE_INDEX), json + "\n").context("Failed to write bundle index")?;
+        Ok(index)
+    }
+
+    /// Verify a bundle and copy its documents into the cache
+    ///
+    /// Every checksum is verified before anything is written, so a corrupt
+    /// bundle leaves the cache untouched. Returns the number of documents imported.
+    pub fn import_bundle(&self, dir: &Path) -> crate::Result<usize> {
+        let verified = verify_bundle(dir)?;
+        let size = verified.iter().map(|(_, _, c)| c.len() as u64).sum();
+        self.ensure_space(size)?;
+        for (doc, format, content) in &verified {
+            self.store_bytes(doc, *format, content)?;
+        }
+        Ok(verified.len())
+    }
+}
+
+/// Read a bundle and check every document against its checksum
+pub fn verify_bundle(dir: &Path) -> crate::Result<Vec<(DocumentType, Format, Vec<u8>)>> {
+    let json = fs::read_to_string(dir.join(BUNDLE_INDEX))
+        .with_context(|| format!("No bundle index in {}", dir.display()))?;
+    let index: BundleIndex = serde_json::from_str(&json).context("Failed to parse bundle index")?;
+    if index.version != BUNDLE_VERSION {
+        return Err(anyhow::anyhow!("Unsupported bundle version {}", index.version).into());
+    }
+
+    let documents = index
+        .documents
+        .iter()
+        .map(|entry| {
+            let (doc, format) = entry.resolve()?;
+            let content = fs::read(dir.join(&entry.path))
+                .with_context(|| format!("Missing bundle document {}", entry.path))?;
+            if sha256_hex(&content) != entry.sha256 {
+                anyhow::bail!("Checksum mismatch for {}", entry.path);
+            }
+            Ok((doc, format, content))
+        })
+        .collect::<Result<_>>()?;
+    Ok(documents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn cache_with_documents() -> (CacheManager, TempDir) {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().join("cache")).unwrap();
+        cache
+            .store_document(&DocumentType::Rfc(9000), Format::Text, "quic")
+            .unwrap();
+        cache
+            .store_document(&DocumentType::Rfc(9000), Format::Html, "<p>quic</p>")
+            .unwrap();
+        cache
+            .store_document(&DocumentType::Rfc(8446), Format::Text, "tls")
+            .unwrap();
+        (cache, temp)
+    }
+
+    #[test]
+    fn test_export_is_deterministic() {
+        let (cache, temp) = cache_with_documents();
+        let first = temp.path().join("a");
+        let second = temp.path().join("b");
+
+        let index = cache.export_bundle(&[], &first).unwrap();
+        cache.export_bundle(&[], &second).unwrap();
+
+        let paths: Vec<&str> = index.documents.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "documents/rfc8446.txt",
+                "documents/rfc9000.html",
+                "documents/rfc9000.txt"
+            ]
+        );
+        assert_eq!(
+            fs::read(first.join(BUNDLE_INDEX)).unwrap(),
+            fs::read(second.join(BUNDLE_INDEX)).unwrap()
+        );
+        assert_eq!(
+            index.documents[0].sha256,
+            "b7e651cbb43ba0ca3498759c8c3596c3a11a199004cd9e5a198d50d4585ec8c5"
+        );
+    }
+
+    #[test]
+    fn test_import_roundtrip() {
+        let (cache, temp) = cache_with_documents();
+        let bundle = temp.path().join("bundle");
+        cache
+            .export_bundle(&[(DocumentType::Rfc(8446), Format::Text)], &bundle)
+            .unwrap();
+
+        let target = CacheManager::with_dir(temp.path().join("target")).unwrap();
+        assert_eq!(target.import_bundle(&bundle).unwrap(), 1);
+        assert_eq!(
+            target.get_document(&DocumentType::Rfc(8446), Format::Text),
+            Some("tls".to_string())
+        );
+    }
+
+    #[test]
+    fn test_import_rejects_corruption() {
+        let (cache, temp) = cache_with_documents();
+        let bundle = temp.path().join("bundle");
+        cache.export_bundle(&[], &bundle).unwrap();
+        fs::write(bundle.join("documents/rfc9000.txt"), "tampered").unwrap();
+
+        let target = CacheManager::with_dir(temp.path().join("target")).unwrap();
+        let err = target.import_bundle(&bundle).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Checksum mismatch for documents/rfc9000.txt"
+        );
+        assert!(target.list_cached().is_empty());
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::models::Format;
+use crate::parse::is_page_artifact;
+
+/// Number of consecutive words hashed together when comparing renderings
+const SHINGLE_SIZE: usize = 8;
+
+/// Minimum fraction of shared content for two renderings to be considered consistent
+const MIN_COVERAGE: f64 = 0.95;
+
+/// Number of trailing words compared to detect a truncated rendering
+const TAIL_WORDS: usize = 24;
+
+/// A run of content present in one rendering but missing from the other
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Divergence {
+    /// The rendering that contains the content
+    pub present_in: Format,
+    /// Index of the first diverging word in that rendering
+    pub word_offset: usize,
+    /// Number of diverging words
+    pub word_count: usize,
+    /// Short excerpt of the diverging content
+    pub excerpt: String,
+}
+
+/// Result of comparing the text and HTML renderings of a document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConsistencyReport {
+    /// Number of words in the normalized text rendering
+    pub text_words: usize,
+    /// Number of words in the normalized HTML rendering
+    pub html_words: usize,
+    /// Fraction of the text rendering also found in the HTML rendering
+    pub text_coverage: f64,
+    /// Fraction of the HTML rendering also found in the text rendering
+    pub html_coverage: f64,
+    /// The rendering that appears to be cut off, if any
+    pub truncated: Option<Format>,
+    /// Runs of content missing from one of the renderings
+    pub divergences: Vec<Divergence>,
+}
+
+impl ConsistencyReport {
+    /// Check if the renderings agree closely enough to be trusted
+    #[must_use]
+    pub fn is_consistent(&self) -> bool {
+        self.truncated.is_none()
+            && self.text_coverage >= MIN_COVERAGE
+            && self.html_coverage >= MIN_COVERAGE
+    }
+}
+
+/// Compare a plain text rendering with the text converted from its HTML rendering
+pub fn compare_renderings(text: &str, html_text: &str) -> ConsistencyReport {
+    let text_words = normalize(text);
+    let html_words = normalize(html_text);
+
+    let text_shingles = shingles(&text_words);
+    let html_shingles = shingles(&html_words);
+
+    let (text_coverage, text_missing) = coverage(&text_words, &html_shingles);
+    let (html_coverage, html_missing) = coverage(&html_words, &text_shingles);
+
+    let mut divergences = Vec::new();
+    divergences.extend(missing_runs(&text_words, &text_missing, Format::Text));
+    divergences.extend(missing_runs(&html_words, &html_missing, Format::Html));
+
+    let truncated = if ends_within(&text_words, &html_words) {
+        Some(Format::Html)
+    } else if ends_within(&html_words, &text_words) {
+        Some(Format::Text)
+    } else {
+        None
+    };
+
+    ConsistencyReport {
+        text_words: text_words.len(),
+        html_words: html_words.len(),
+        text_coverage,
+        html_coverage,
+        truncated,
+        divergences,
+    }
+}
+
+/// Reduce a rendering to lowercase words, dropping pagination artifacts
+fn normalize(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter(|line| !is_page_artifact(line))
+        .flat_map(|line| line.split(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Hash every window of `SHINGLE_SIZE` words
+fn shingles(words: &[String]) -> HashSet<&[String]> {
+    words.windows(SHINGLE_SIZE).collect()
+}
+
+/// Compute the fraction of `words` covered by `other`, plus a per-word "missing" mask
+fn coverage(words: &[String], other: &HashSet<&[String]>) -> (f64, Vec<bool>) {
+    // Too short to compare meaningfully
+    if words.len() < SHINGLE_SIZE {
+        return (1.0, vec![false; words.len()]);
+    }
+
+    let mut missing = vec![true; words.len()];
+    for (i, window) in words.windows(SHINGLE_SIZE).enumerate() {
+        if other.contains(window) {
+            missing[i..i + SHINGLE_SIZE]
+                .iter_mut()
+                .for_each(|m| *m = false);
+        }
+    }
+
+    let covered = missing.iter().filter(|m| !**m).count();
+    (covered as f64 / words.len() as f64, missing)
+}
+
+/// Collect contiguous runs of missing words into divergences
+fn missing_runs(words: &[String], missing: &[bool], present_in: Format) -> Vec<Divergence> {
+    let mut runs = Vec::new();
+    let mut i = 0;
+    while i < missing.len() {
+        if !missing[i] {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < missing.len() && missing[i] {
+            i += 1;
+        }
+        runs.push(Divergence {
+            present_in,
+            word_offset: start,
+            word_count: i - start,
+            excerpt: words[start..i.min(start + 12)].join(" "),
+        });
+    }
+    runs
+}
+
+/// Check if `shorter` ends well before `longer` does, i.e. `shorter` was cut off
+fn ends_within(longer: &[String], shorter: &[String]) -> bool {
+    if shorter.len() < TAIL_WORDS || longer.len() <= shorter.len() + TAIL_WORDS {
+        return false;
+    }
+    let tail = &shorter[shorter.len() - TAIL_WORDS..];
+    let longer_tail = &longer[longer.len() - TAIL_WORDS..];
+    tail != longer_tail
+        && longer[..longer.len() - TAIL_WORDS]
+            .windows(TAIL_WORDS)
+            .any(|w| w == tail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(paragraphs: usize) -> String {
+        (0..paragraphs)
+            .map(|i| {
+                format!(
+                    "Paragraph {} describes how endpoints exchange frames number {} on a stream.\n",
+                    i, i
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_identical_renderings() {
+        let text = sample(20);
+        let report = compare_renderings(&text, &text);
+
+        assert!(report.is_consistent());
+        assert!(report.divergences.is_empty());
+        assert_eq!(report.truncated, None);
+    }
+
+    #[test]
+    fn test_ignores_pagination() {
+        let body = sample(20);
+        let mut paginated = body.clone();
+        paginated.push_str("Iyengar & Thomson       Standards Track                   [Page 12]\n");
+        paginated
+            .push_str("\u{c}\nRFC 9000            QUIC Transport Protocol              May 2021\n");
+
+        let report = compare_renderings(&paginated, &body);
+        assert!(report.is_consistent());
+        assert!(report.divergences.is_empty());
+    }
+
+    #[test]
+    fn test_detects_truncation() {
+        let full = sample(40);
+        let cut: String = full.lines().take(20).map(|l| format!("{}\n", l)).collect();
+
+        let report = compare_renderings(&full, &cut);
+        assert!(!report.is_consistent());
+        assert_eq!(report.truncated, Some(Format::Html));
+        assert!(report
+            .divergences
+            .iter()
+            .any(|d| d.present_in == Format::Text));
+    }
+
+    #[test]
+    fn test_reports_divergence() {
+        let text = sample(20);
+        let html = sample(20).replace(
+            "Paragraph 10 describes",
+            "An entirely different sentence that was inserted by a broken mirror here. Paragraph 10 describes",
+        );
+
+        let report = compare_renderings(&text, &html);
+        let inserted: Vec<_> = report
+            .divergences
+            .iter()
+            .filter(|d| d.present_in == Format::Html)
+            .collect();
+        assert_eq!(inserted.len(), 1);
+        assert!(inserted[0].excerpt.contains("entirely different"));
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::mpsc;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+use super::CacheManager;
+use crate::models::{DocumentType, Format};
+
+/// Name of the index database in the cache directory
+const INDEX_FILE: &str = "index.sqlite3";
+
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS documents (
+    name TEXT NOT NULL,
+    format TEXT NOT NULL,
+    title TEXT,
+    fetched INTEGER NOT NULL,
+    size INTEGER NOT NULL,
+    url TEXT,
+    etag TEXT,
+    PRIMARY KEY (name, format)
+)";
+
+/// A cached document as recorded in the metadata index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+    pub doc: DocumentType,
+    pub format: Format,
+    /// Document title, if known
+    pub title: Option<String>,
+    /// When the document was stored in the cache
+    pub fetched: DateTime<Utc>,
+    /// Size in bytes
+    pub size: u64,
+    /// URL the document was downloaded from
+    pub url: Option<String>,
+    /// ETag of the download
+    pub etag: Option<String>,
+}
+
+impl IndexEntry {
+    fn from_row(row: &Row) -> rusqlite::Result<Option<Self>> {
+        let name: String = row.get(0)?;
+        let format: String = row.get(1)?;
+        let (Some(doc), Some(format)) = (DocumentType::parse(&name), Format::parse(&format)) else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            doc,
+            format,
+            title: row.get(2)?,
+            fetched: DateTime::from_timestamp_millis(row.get(3)?).unwrap_or_default(),
+            size: row.get::<_, i64>(4)? as u64,
+            url: row.get(5)?,
+            etag: row.get(6)?,
+        }))
+    }
+}
+
+/// SQLite database of cached documents and their metadata
+///
+/// Kept up to date by `CacheManager` when the `index` feature is enabled, so
+/// listings don't need to scan the cache directory.
+pub struct CacheIndex {
+    conn: Connection,
+}
+
+impl CacheIndex {
+    /// Open or create an index database
+    pub fn open(path: &Path) -> crate::Result<Self> {
+        let conn = Connection::open(path).context("Failed to open cache index")?;
+        conn.execute(SCHEMA, [])
+            .context("Failed to create cache index")?;
+        Ok(Self { conn })
+    }
+
+    /// Insert or replace the entry of a document in one format
+    pub fn record(&self, entry: &IndexEntry) -> crate::Result<()> {
+        self.conn
+            .execute(
+                "INSERT OR REPLACE INTO documents
+                 (name, format, title, fetched, size, url, etag)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    entry.doc.name(),
+                    entry.format.name(),
+                    entry.title,
+                    entry.fetched.timestamp_millis(),
+                    entry.size as i64,
+                    entry.url,
+                    entry.etag,
+                ],
+            )
+            .context("Failed to update cache index")?;
+        Ok(())
+    }
+
+    /// Set the title of a document in all formats
+    pub fn set_title(&self, doc: &DocumentType, title: &str) -> crate::Result<()> {
+        self.conn
+            .execute(
+                "UPDATE documents SET title = ?2 WHERE name = ?1",
+                params![doc.name(), title],
+            )
+            .context("Failed to update cache index")?;
+        Ok(())
+    }
+
+    /// Set the source URL and ETag of a document in one format
+    pub fn set_source(
+        &self,
+        doc: &DocumentType,
+        format: Format,
+        url: &str,
+        etag: Option<&str>,
+    ) -> crate::Result<()> {
+        self.conn
+            .execute(
+                "UPDATE documents SET url = ?3, etag = ?4 WHERE name = ?1 AND format = ?2",
+                params![doc.name(), format.name(), url, etag],
+            )
+            .context("Failed to update cache index")?;
+        Ok(())
+    }
+
+    /// Remove a document in all formats
+    pub fn remove(&self, doc: &DocumentType) -> crate::Result<()> {
+        self.conn
+            .execute("DELETE FROM documents WHERE name = ?1", params![doc.name()])
+            .context("Failed to update cache index")?;
+        Ok(())
+    }
+
+    /// Get the entry of a document in one format
+    pub fn get(&self, doc: &DocumentType, format: Format) -> crate::Result<Option<IndexEntry>> {
+        let entry = self
+            .conn
+            .query_row(
+                "SELECT name, format, title, fetched, size, url, etag FROM documents
+                 WHERE name = ?1 AND format = ?2",
+                params![doc.name(), format.name()],
+                IndexEntry::from_row,
+            )
+            .optional()
+            .context("Failed to query cache index")?;
+        Ok(entry.flatten())
+    }
+
+    /// Get all entries, sorted by document and format
+    pub fn entries(&self) -> crate::Result<Vec<IndexEntry>> {
+        let mut statement = self
+            .conn
+            .prepare("SELECT name, format, title, fetched, size, url, etag FROM documents")
+            .context("Failed to query cache index")?;
+        let mut entries = statement
+            .query_map([], IndexEntry::from_row)
+            .context("Failed to query cache index")?
+            .filter_map(|row| row.ok().flatten())
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|e| (e.doc.clone(), e.format.name()));
+        Ok(entries)
+    }
+
+    /// Get the indexed documents, sorted
+    pub fn documents(&self) -> crate::Result<Vec<DocumentType>> {
+        let mut documents: Vec<_> = self.entries()?.into_iter().map(|e| e.doc).collect();
+        documents.dedup();
+        Ok(documents)
+    }
+
+    /// Bring the index in line with what is on disk
+    pub fn rebuild(&self, cache: &CacheManager) -> crate::Result<RebuildReport> {
+        self.rebuild_with_progress(cache, |_| {})
+    }
+
+    /// Bring the index in line with what is on disk, reporting each scanned document
+    ///
+    /// Only entries whose file changed are written, and entries of documents no
+    /// longer on disk are removed; titles of known documents are kept. Documents
+    /// are scanned on several threads.
+    pub fn rebuild_with_progress(
+        &self,
+        cache: &CacheManager,
+        mut on_progress: impl FnMut(&RebuildProgress),
+    ) -> crate::Result<RebuildReport> {
+        let known: HashMap<(String, Format), IndexEntry> = self
+            .entries()?
+            .into_iter()
+            .map(|e| ((e.doc.name(), e.format), e))
+            .collect();
+        let documents = cache.scan_cached();
+        let total = documents.len();
+        let mut report = RebuildReport::default();
+        let mut seen = HashSet::new();
+
+        let tx = self
+            .conn
+            .unchecked_transaction()
+            .context("Failed to update cache index")?;
+        let (sender, receiver) = mpsc::channel();
+        std::thread::scope(|scope| -> Result<()> {
+            let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+            let chunk = total.div_ceil(workers).max(1);
+            for docs in documents.chunks(chunk) {
+                let sender = sender.clone();
+                scope.spawn(move || {
+                    for doc in docs {
+                        let entries: Vec<_> = cache
+                            .cached_formats(doc)
+                            .into_iter()
+                            .filter_map(|format| cache.index_entry(doc, format).ok())
+                            .collect();
+                        if sender.send((doc, entries)).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+            drop(sender);
+
+            for (done, (doc, entries)) in receiver.into_iter().enumerate() {
+                for entry in entries {
+                    let key = (doc.name(), entry.format);
+                    let previous = known.get(&key);
+                    let entry = IndexEntry {
+                        title: previous.and_then(|e| e.title.clone()),
+                        ..entry
+                    };
+                    match previous {
+                        Some(previous) if *previous == entry => report.unchanged += 1,
+                        Some(_) => {
+                            self.record(&entry)?;
+                            report.updated += 1;
+                        }
+                        None => {
+                            self.record(&entry)?;
+                            report.added += 1;
+                        }
+                    }
+                    seen.insert(key);
+                }
+                on_progress(&RebuildProgress {
+                    done: done + 1,
+                    total,
+                    doc: doc.clone(),
+                });
+            }
+            Ok(())
+        })?;
+
+        for (name, format) in known.keys().filter(|key| !seen.contains(*key)) {
+            tx.execute(
+                "DELETE FROM documents WHERE name = ?1 AND format = ?2",
+                params![name, format.name()],
+            )
+            .context("Failed to update cache index")?;
+            report.removed += 1;
+        }
+        tx.commit().context("Failed to update cache index")?;
+        Ok(report)
+    }
+}
+
+/// Progress of an index rebuild, reported after each scanned document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebuildProgress {
+    /// Number of documents scanned so far
+    pub done: usize,
+    /// Number of documents in the cache
+    pub total: usize,
+    /// The document just scanned
+    pub doc: DocumentType,
+}
+
+/// Changes made to the index by a rebuild, counted per document and format
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RebuildReport {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+}
+
+impl CacheManager {
+    /// Open the metadata index of the cache, building it if it doesn't exist yet
+    pub fn index(&self) -> crate::Result<CacheIndex> {
+        let path = self.cache_dir().join(INDEX_FILE);
+        let exists = path.exists();
+        let index = CacheIndex::open(&path)?;
+        if !exists {
+            index.rebuild(self)?;
+        }
+        Ok(index)
+    }
+
+    /// Describe a document as currently stored on disk
+    fn index_entry(&self, doc: &DocumentType, format: Format) -> Result<IndexEntry> {
+        let metadata = self
+            .document_metadata(doc, format)
+            .context("Failed to read cached document metadata")?;
+        let validators = self.get_validators(doc, format);
+        Ok(IndexEntry {
+            doc: doc.clone(),
+            format,
+            title: None,
+            // Millisecond precision, as stored in the database
+            fetched: DateTime::from_timestamp_millis(
+                DateTime::<Utc>::from(metadata.modified).timestamp_millis(),
+            )
+            .unwrap_or_else(Utc::now),
+            size: metadata.size,
+            url: validators.as_ref().map(|v| v.url.clone()),
+            etag: validators.and_then(|v| v.etag),
+        })
+    }
+
+    /// Record a newly stored document in the index, keeping its known title
+    pub(super) fn index_stored(&self, doc: &DocumentType, format: Format) -> Result<()> {
+        let index = self.index()?;
+        let title = index.get(doc, format)?.and_then(|e| e.title);
+        Ok(index.record(&IndexEntry {
+            title,
+            ..self.index_entry(doc, format)?
+        })?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::Validators;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_index_tracks_cache() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let rfc = DocumentType::Rfc(9000);
+        let draft = DocumentType::Draft("draft-ietf-quic-qlog-main-schema-09".to_string());
+
+        cache
+            .store_document(&draft, Format::Html, "<p>qlog</p>")
+            .unwrap();
+        cache
+            .store_validators(
+                &rfc,
+                Format::Text,
+                &Validators {
+                    url: "https://www.rfc-editor.org/rfc/rfc9000.txt".to_string(),
+                    etag: Some("\"abc\"".to_string()),
+                    last_modified: None,
+                },
+            )
+            .unwrap();
+        cache.store_document(&rfc, Format::Text, "QUIC").unwrap();
+        cache.index().unwrap().set_title(&rfc, "QUIC").unwrap();
+
+        let entries = cache.index().unwrap().entries().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].doc, rfc);
+        assert_eq!(entries[0].title.as_deref(), Some("QUIC"));
+        assert_eq!(entries[0].size, 4);
+        assert_eq!(entries[0].etag.as_deref(), Some("\"abc\""));
+        assert_eq!(entries[1].format, Format::Html);
+        assert_eq!(cache.list_cached(), vec![rfc.clone(), draft.clone()]);
+
+        // Storing again keeps the title
+        cache.store_document(&rfc, Format::Text, "QUIC v1").unwrap();
+        let entry = cache
+            .index()
+            .unwrap()
+            .get(&rfc, Format::Text)
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.title.as_deref(), Some("QUIC"));
+        assert_eq!(entry.size, 7);
+
+        cache.remove(&rfc).unwrap();
+        assert_eq!(cache.index().unwrap().documents().unwrap(), vec![draft]);
+    }
+
+    #[test]
+    fn test_index_built_from_existing_cache() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("documents")).unwrap();
+        fs::write(temp.path().join("documents/rfc8446.txt"), "TLS 1.3").unwrap();
+
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let entries = cache.index().unwrap().entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].doc, DocumentType::Rfc(8446));
+        assert_eq!(entries[0].size, 7);
+    }
+
+    #[test]
+    fn test_rebuild_is_incremental() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let index = cache.index().unwrap();
+        for number in [8446, 9000, 9001] {
+            cache
+                .store_document(&DocumentType::Rfc(number), Format::Text, "text")
+                .unwrap();
+        }
+        index.set_title(&DocumentType::Rfc(9000), "QUIC").unwrap();
+
+        // Change files behind the cache's back
+        let documents = temp.path().join("documents");
+        fs::write(documents.join("rfc9000.txt"), "QUIC v1").unwrap();
+        fs::remove_file(documents.join("rfc9001.txt")).unwrap();
+        fs::write(documents.join("rfc9114.txt"), "HTTP/3").unwrap();
+
+        let mut progress = Vec::new();
+        let report = index
+            .rebuild_with_progress(&cache, |p| progress.push((p.done, p.total)))
+            .unwrap();
+        assert_eq!(
+            report,
+            RebuildReport {
+                added: 1,
+                updated: 1,
+                removed: 1,
+                unchanged: 1,
+            }
+        );
+        assert_eq!(progress, vec![(1, 3), (2, 3), (3, 3)]);
+
+        let entry = index
+            .get(&DocumentType::Rfc(9000), Format::Text)
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.title.as_deref(), Some("QUIC"));
+        assert_eq!(entry.size, 7);
+        assert_eq!(index.rebuild(&cache).unwrap().unchanged, 3);
+    }
+}
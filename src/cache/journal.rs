@@ -0,0 +1,201 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::CacheManager;
+use crate::models::{DocumentType, Format};
+
+/// Name of the journal in the cache directory
+const JOURNAL_FILE: &str = "journal.jsonl";
+
+/// Kind of cache mutation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JournalOp {
+    /// A document was written
+    Store,
+    /// A document was removed on request
+    Remove,
+    /// A document was removed to stay within a size limit or free disk space
+    Evict,
+    /// The whole cache was cleared
+    Clear,
+}
+
+/// One line of the cache journal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub time: DateTime<Utc>,
+    pub op: JournalOp,
+    /// Document name (e.g., "rfc9000"); absent for `Clear`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<Format>,
+    /// Path of the affected file relative to the cache directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Size in bytes of a stored document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+}
+
+impl CacheManager {
+    /// Get the path of the append-only journal of cache mutations
+    ///
+    /// Each line is a JSON `JournalEntry`; the journal is only written when
+    /// enabled with `with_journal`.
+    pub fn journal_path(&self) -> PathBuf {
+        self.cache_dir().join(JOURNAL_FILE)
+    }
+
+    /// Read every entry of the journal, oldest first
+    pub fn read_journal(&self) -> crate::Result<Vec<JournalEntry>> {
+        let content = match fs::read_to_string(self.journal_path()) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(Error::new(e).context("Failed to read cache journal").into()),
+        };
+        Ok(content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).context("Invalid cache journal entry"))
+            .collect::<Result<_>>()?)
+    }
+
+    /// Append a mutation of a document to the journal, if enabled
+    pub(super) fn journal(
+        &self,
+        op: JournalOp,
+        doc: Option<(&DocumentType, Format)>,
+        size: Option<u64>,
+    ) -> Result<()> {
+        if !self.journals() {
+            return Ok(());
+        }
+        let path = doc.map(|(doc, format)| Self::document_key(doc, format));
+        let entry = JournalEntry {
+            time: Utc::now(),
+            op,
+            doc: doc.map(|(doc, _)| doc.name()),
+            format: doc.map(|(_, format)| format),
+            path,
+            size,
+        };
+
+        let mut line =
+            serde_json::to_string(&entry).context("Failed to serialize journal entry")?;
+        line.push('\n');
+        // A single write per entry keeps lines intact when processes append concurrently
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.journal_path())
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .context("Failed to write cache journal")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::CacheLimit;
+    use tempfile::TempDir;
+
+    fn ops(cache: &CacheManager) -> Vec<(JournalOp, Option<String>)> {
+        cache
+            .read_journal()
+            .unwrap()
+            .into_iter()
+            .map(|e| (e.op, e.path))
+            .collect()
+    }
+
+    #[test]
+    fn test_journal_records_mutations() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf())
+            .unwrap()
+            .with_journal(true);
+        let rfc = DocumentType::Rfc(9000);
+
+        cache.store_document(&rfc, Format::Text, "QUIC").unwrap();
+        cache
+            .store_document(&rfc, Format::Html, "<p>QUIC</p>")
+            .unwrap();
+        cache.remove(&rfc).unwrap();
+
+        let entries = cache.read_journal().unwrap();
+        assert_eq!(entries[0].doc.as_deref(), Some("rfc9000"));
+        assert_eq!(entries[0].size, Some(4));
+        assert_eq!(
+            ops(&cache),
+            vec![
+                (JournalOp::Store, Some("documents/rfc9000.txt".to_string())),
+                (JournalOp::Store, Some("documents/rfc9000.html".to_string())),
+                (JournalOp::Remove, Some("documents/rfc9000.txt".to_string())),
+                (
+                    JournalOp::Remove,
+                    Some("documents/rfc9000.html".to_string())
+                ),
+            ]
+        );
+
+        cache.clear_cache().unwrap();
+        cache
+            .store_document(&DocumentType::Rfc(8446), Format::Text, "TLS")
+            .unwrap();
+        // Clearing starts a new journal, so consumers know to start over
+        assert_eq!(
+            ops(&cache),
+            vec![
+                (JournalOp::Clear, None),
+                (JournalOp::Store, Some("documents/rfc8446.txt".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_journal_distinguishes_evictions() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf())
+            .unwrap()
+            .with_journal(true)
+            .with_limit(CacheLimit {
+                max_bytes: None,
+                max_documents: Some(1),
+            });
+
+        cache
+            .store_document(&DocumentType::Rfc(8446), Format::Text, "TLS")
+            .unwrap();
+        cache.remove(&DocumentType::Rfc(1)).unwrap();
+        cache
+            .store_document(&DocumentType::Rfc(9000), Format::Text, "QUIC")
+            .unwrap();
+
+        assert_eq!(
+            ops(&cache),
+            vec![
+                (JournalOp::Store, Some("documents/rfc8446.txt".to_string())),
+                (JournalOp::Store, Some("documents/rfc9000.txt".to_string())),
+                (JournalOp::Evict, Some("documents/rfc8446.txt".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_journal_disabled_by_default() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        cache
+            .store_document(&DocumentType::Rfc(9000), Format::Text, "QUIC")
+            .unwrap();
+        assert!(!cache.journal_path().exists());
+        assert!(cache.read_journal().unwrap().is_empty());
+    }
+}
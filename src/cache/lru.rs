@@ -0,0 +1,476 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::journal::JournalOp;
+use super::CacheManager;
+use crate::models::DocumentType;
+
+/// Name of the access index in the cache directory
+const ACCESS_INDEX: &str = "access.json";
+
+/// Number of documents whose accesses are batched before saving the index
+const FLUSH_DOCUMENTS: usize = 32;
+
+/// Longest time accesses are batched before saving the index
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Limits on the size of the cache; `None` means unlimited
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheLimit {
+    /// Maximum total size of cached documents in bytes
+    pub max_bytes: Option<u64>,
+    /// Maximum number of cached documents
+    pub max_documents: Option<usize>,
+}
+
+impl CacheLimit {
+    /// Check whether a cache of the given size is within the limit
+    pub fn allows(&self, bytes: u64, documents: usize) -> bool {
+        self.max_bytes.is_none_or(|max| bytes <= max)
+            && self.max_documents.is_none_or(|max| documents <= max)
+    }
+}
+
+/// Access bookkeeping for one document
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct AccessEntry {
+    /// Last read or write, in milliseconds since the Unix epoch
+    last_access: i64,
+    /// Number of reads and writes
+    #[serde(default)]
+    count: u64,
+}
+
+impl AccessEntry {
+    /// Frecency score: the access count weighted by how recent the last access was
+    fn frecency(&self, now: DateTime<Utc>) -> u64 {
+        let age = now.timestamp_millis().saturating_sub(self.last_access) / (24 * 60 * 60 * 1000);
+        let weight = match age {
+            0..=3 => 100,
+            4..=14 => 70,
+            15..=31 => 50,
+            32..=90 => 30,
+            _ => 10,
+        };
+        self.count * weight
+    }
+}
+
+/// Accesses not yet saved to the access index, shared by clones of a manager
+///
+/// Reads are batched so they don't each rewrite the index. The batch is
+/// merged into the index on disk, so concurrent processes keep each other's
+/// history, and is saved when it grows, when it gets old, and on drop.
+#[derive(Debug)]
+pub(super) struct AccessLog {
+    path: PathBuf,
+    pending: Mutex<PendingAccess>,
+}
+
+#[derive(Debug)]
+struct PendingAccess {
+    /// Latest access and number of new accesses per document
+    entries: HashMap<String, AccessEntry>,
+    last_flush: Instant,
+}
+
+impl AccessLog {
+    pub(super) fn new(cache_dir: &Path) -> Self {
+        Self {
+            path: cache_dir.join(ACCESS_INDEX),
+            pending: Mutex::new(PendingAccess {
+                entries: HashMap::new(),
+                last_flush: Instant::now(),
+            }),
+        }
+    }
+
+    fn pending(&self) -> MutexGuard<'_, PendingAccess> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record an access, saving the batch if it is due
+    fn record(&self, name: String) {
+        let mut pending = self.pending();
+        let entry = pending.entries.entry(name).or_insert(AccessEntry {
+            last_access: 0,
+            count: 0,
+        });
+        entry.last_access = Utc::now().timestamp_millis();
+        entry.count += 1;
+        if pending.entries.len() >= FLUSH_DOCUMENTS
+            || pending.last_flush.elapsed() >= FLUSH_INTERVAL
+        {
+            if let Err(e) = self.flush_locked(&mut pending) {
+                eprintln!("Warning: {:#}", e);
+            }
+        }
+    }
+
+    /// Save the pending accesses
+    pub(super) fn flush(&self) -> Result<()> {
+        self.flush_locked(&mut self.pending())
+    }
+
+    fn flush_locked(&self, pending: &mut PendingAccess) -> Result<()> {
+        pending.last_flush = Instant::now();
+        if pending.entries.is_empty() {
+            return Ok(());
+        }
+        let mut index = self.load();
+        merge(&mut index, &pending.entries);
+        self.save(&index)?;
+        pending.entries.clear();
+        Ok(())
+    }
+
+    /// Get the saved index with the pending accesses applied
+    fn index(&self) -> HashMap<String, AccessEntry> {
+        let pending = self.pending();
+        let mut index = self.load();
+        merge(&mut index, &pending.entries);
+        index
+    }
+
+    /// Drop a document from the pending accesses and the saved index
+    fn forget(&self, name: &str) -> Result<()> {
+        let mut pending = self.pending();
+        pending.entries.remove(name);
+        let mut index = self.load();
+        if index.remove(name).is_some() {
+            self.save(&index)?;
+        }
+        Ok(())
+    }
+
+    /// Drop the pending accesses, after the cache was cleared
+    pub(super) fn clear(&self) {
+        self.pending().entries.clear();
+    }
+
+    fn load(&self) -> HashMap<String, AccessEntry> {
+        let Ok(json) = fs::read_to_string(&self.path) else {
+            return HashMap::new();
+        };
+        serde_json::from_str(&json).unwrap_or_else(|e| {
+            eprintln!(
+                "Warning: resetting unreadable access index {}: {}",
+                self.path.display(),
+                e
+            );
+            HashMap::new()
+        })
+    }
+
+    /// Write the index next to the old one and rename it over, so readers
+    /// never see a partial file
+    fn save(&self, index: &HashMap<String, AccessEntry>) -> Result<()> {
+        let json = serde_json::to_string(index).context("Failed to serialize access index")?;
+        let dir = self.path.parent().unwrap_or(Path::new("."));
+        let mut temp =
+            tempfile::NamedTempFile::new_in(dir).context("Failed to write access index")?;
+        std::io::Write::write_all(&mut temp, json.as_bytes())
+            .context("Failed to write access index")?;
+        temp.persist(&self.path)
+            .map_err(|e| e.error)
+            .context("Failed to write access index")?;
+        Ok(())
+    }
+}
+
+impl Drop for AccessLog {
+    fn drop(&mut self) {
+        if let Err(e) = self.flush() {
+            eprintln!("Warning: {:#}", e);
+        }
+    }
+}
+
+/// Apply new accesses to an index
+fn merge(index: &mut HashMap<String, AccessEntry>, pending: &HashMap<String, AccessEntry>) {
+    for (name, new) in pending {
+        let entry = index.entry(name.clone()).or_insert(AccessEntry {
+            last_access: 0,
+            count: 0,
+        });
+        entry.last_access = entry.last_access.max(new.last_access);
+        entry.count += new.count;
+    }
+}
+
+impl CacheManager {
+    /// Get when a cached document was last read or written
+    pub fn last_accessed(&self, doc: &DocumentType) -> Option<DateTime<Utc>> {
+        let entry = self.access_index().get(&doc.name()).copied()?;
+        DateTime::from_timestamp_millis(entry.last_access)
+    }
+
+    /// Get how often a cached document was read or written
+    pub fn access_count(&self, doc: &DocumentType) -> u64 {
+        self.access_index()
+            .get(&doc.name())
+            .map_or(0, |entry| entry.count)
+    }
+
+    /// Get up to `n` cached documents ranked by frecency, most used first
+    ///
+    /// Frecency combines how often and how recently a document was used, so a
+    /// document read daily this week ranks above one read often last year.
+    pub fn most_used(&self, n: usize) -> Vec<DocumentType> {
+        let now = Utc::now();
+        let mut ranked: Vec<(DocumentType, u64, i64)> = self
+            .access_index()
+            .into_iter()
+            .filter_map(|(name, entry)| {
+                let doc = DocumentType::parse(&name)?;
+                Some((doc, entry.frecency(now), entry.last_access))
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then(b.2.cmp(&a.2)));
+        ranked.into_iter().take(n).map(|(doc, ..)| doc).collect()
+    }
+
+    /// Evict the least recently accessed documents until the cache is within its limit
+    /// Returns the evicted documents
+    pub fn evict_to_fit(&self) -> crate::Result<Vec<DocumentType>> {
+        Ok(self.evict_except(None)?)
+    }
+
+    /// Evict to fit the limit, never evicting `keep`
+    pub(super) fn evict_except(&self, keep: Option<&DocumentType>) -> Result<Vec<DocumentType>> {
+        let limit = *self.limit();
+        let documents = self.documents_by_access();
+        let mut bytes: u64 = documents.iter().map(|(_, size)| size).sum();
+        let mut count = documents.len();
+
+        let mut evicted = Vec::new();
+        for (doc, size) in documents {
+            if limit.allows(bytes, count) {
+                break;
+            }
+            if Some(&doc) == keep {
+                continue;
+            }
+            if self.remove_as(&doc, JournalOp::Evict)? {
+                bytes = bytes.saturating_sub(size);
+                count -= 1;
+                evicted.push(doc);
+            }
+        }
+        Ok(evicted)
+    }
+
+    /// Cached documents with their size on disk, least recently accessed first
+    ///
+    /// Documents without a recorded access count from their modification time.
+    pub(super) fn documents_by_access(&self) -> Vec<(DocumentType, u64)> {
+        let index = self.access_index();
+        let mut documents: Vec<(DocumentType, u64, i64)> = Vec::new();
+        for doc in self.list_cached() {
+            let mut size = 0;
+            let mut modified = SystemTime::UNIX_EPOCH;
+            for format in self.cached_formats(&doc) {
+                if let Some(metadata) = self.document_metadata(&doc, format) {
+                    size += metadata.size;
+                    modified = modified.max(metadata.modified);
+                }
+            }
+            let accessed = match index.get(&doc.name()) {
+                Some(entry) => entry.last_access,
+                None => DateTime::<Utc>::from(modified).timestamp_millis(),
+            };
+            documents.push((doc, size, accessed));
+        }
+        documents.sort_by_key(|&(_, _, accessed)| accessed);
+        documents
+            .into_iter()
+            .map(|(doc, size, _)| (doc, size))
+            .collect()
+    }
+
+    /// Record that a document was read or written
+    ///
+    /// Accesses are saved in batches; a failure to save them is reported on
+    /// stderr, as they only guide eviction and ranking.
+    pub(super) fn record_access(&self, doc: &DocumentType) {
+        self.access_log().record(doc.name());
+    }
+
+    /// Save the accesses recorded so far, instead of waiting for the next batch
+    pub fn flush_access(&self) -> crate::Result<()> {
+        Ok(self.access_log().flush()?)
+    }
+
+    /// Forget the access time of a removed document
+    pub(super) fn forget_access(&self, doc: &DocumentType) -> Result<()> {
+        self.access_log().forget(&doc.name())
+    }
+
+    fn access_index(&self) -> HashMap<String, AccessEntry> {
+        self.access_log().index()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Format;
+    use tempfile::TempDir;
+
+    fn cache_with_limit(temp: &TempDir, limit: CacheLimit) -> CacheManager {
+        CacheManager::with_dir(temp.path().to_path_buf())
+            .unwrap()
+            .with_limit(limit)
+    }
+
+    #[test]
+    fn test_evicts_least_recently_accessed() {
+        let temp = TempDir::new().unwrap();
+        let cache = cache_with_limit(&temp, CacheLimit::default());
+        for number in [9000, 9001, 9002] {
+            let doc = DocumentType::Rfc(number);
+            cache.store_document(&doc, Format::Text, "text").unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        // Reading RFC 9000 makes RFC 9001 the least recently used
+        cache.get_document(&DocumentType::Rfc(9000), Format::Text);
+        assert!(cache.last_accessed(&DocumentType::Rfc(9000)).is_some());
+
+        let cache = cache.with_limit(CacheLimit {
+            max_documents: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(
+            cache.evict_to_fit().unwrap(),
+            vec![DocumentType::Rfc(9001), DocumentType::Rfc(9002)]
+        );
+        assert_eq!(cache.list_cached(), vec![DocumentType::Rfc(9000)]);
+        assert!(cache.last_accessed(&DocumentType::Rfc(9001)).is_none());
+    }
+
+    #[test]
+    fn test_eviction_fires_hooks() {
+        use crate::hooks::HookEvent;
+        use std::sync::{Arc, Mutex};
+
+        let temp = TempDir::new().unwrap();
+        let evicted = Arc::new(Mutex::new(Vec::new()));
+        let seen = evicted.clone();
+        let cache = cache_with_limit(
+            &temp,
+            CacheLimit {
+                max_documents: Some(1),
+                ..Default::default()
+            },
+        )
+        .with_hook(move |event: &HookEvent| {
+            seen.lock().unwrap().push(event.clone());
+            Ok(())
+        });
+        for number in [9000, 9001] {
+            let doc = DocumentType::Rfc(number);
+            cache.store_document(&doc, Format::Text, "text").unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        // Storing RFC 9001 evicts RFC 9000; a plain removal is no eviction
+        cache.remove(&DocumentType::Rfc(9001)).unwrap();
+        assert_eq!(
+            *evicted.lock().unwrap(),
+            vec![HookEvent::Evicted {
+                doc: DocumentType::Rfc(9000)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_frecency() {
+        let now = Utc::now();
+        let day = 24 * 60 * 60 * 1000;
+        let recent = AccessEntry {
+            last_access: now.timestamp_millis() - day,
+            count: 3,
+        };
+        let old = AccessEntry {
+            last_access: now.timestamp_millis() - 200 * day,
+            count: 20,
+        };
+        assert!(recent.frecency(now) > old.frecency(now));
+    }
+
+    #[test]
+    fn test_most_used() {
+        let temp = TempDir::new().unwrap();
+        let cache = cache_with_limit(&temp, CacheLimit::default());
+        let docs = [9000, 9001, 9002].map(DocumentType::Rfc);
+        for doc in &docs {
+            cache.store_document(doc, Format::Text, "text").unwrap();
+        }
+        for _ in 0..3 {
+            cache.get_document(&docs[1], Format::Text);
+        }
+        cache.get_document(&docs[2], Format::Text);
+
+        assert_eq!(cache.access_count(&docs[1]), 4);
+        assert_eq!(cache.most_used(2), vec![docs[1].clone(), docs[2].clone()]);
+
+        cache.remove(&docs[1]).unwrap();
+        assert_eq!(cache.access_count(&docs[1]), 0);
+        assert_eq!(cache.most_used(5).len(), 2);
+    }
+
+    #[test]
+    fn test_access_is_batched() {
+        let temp = TempDir::new().unwrap();
+        let doc = DocumentType::Rfc(9000);
+        let index = temp.path().join(ACCESS_INDEX);
+        {
+            let cache = cache_with_limit(&temp, CacheLimit::default());
+            cache.store_document(&doc, Format::Text, "text").unwrap();
+            cache.get_document(&doc, Format::Text);
+            assert!(!index.exists());
+            assert_eq!(cache.access_count(&doc), 2);
+
+            // Another process saves its own accesses meanwhile
+            fs::write(&index, r#"{"rfc9000":{"last_access":0,"count":5}}"#).unwrap();
+            cache.flush_access().unwrap();
+            assert_eq!(cache.access_count(&doc), 7);
+            cache.get_document(&doc, Format::Text);
+        }
+        // Dropping the manager saves the rest
+        let cache = cache_with_limit(&temp, CacheLimit::default());
+        assert_eq!(cache.access_count(&doc), 8);
+        assert!(cache.last_accessed(&doc).is_some());
+    }
+
+    #[test]
+    fn test_size_limit_applies_on_store() {
+        let temp = TempDir::new().unwrap();
+        let cache = cache_with_limit(
+            &temp,
+            CacheLimit {
+                max_bytes: Some(2500),
+                ..Default::default()
+            },
+        );
+        for number in [9000, 9001, 9002] {
+            let doc = DocumentType::Rfc(number);
+            cache
+                .store_document(&doc, Format::Text, &"x".repeat(1000))
+                .unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        assert_eq!(
+            cache.list_cached(),
+            vec![DocumentType::Rfc(9001), DocumentType::Rfc(9002)]
+        );
+        assert!(cache.evict_to_fit().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,275 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::CacheManager;
+use crate::api::DocumentFetcher;
+use crate::models::{DocumentType, Format};
+
+/// A declarative list of documents that should be available in the cache
+///
+/// ```toml
+/// documents = ["9000", "BCP 14"]
+///
+/// [[collections]]
+/// name = "quic"
+/// documents = [
+///     "9001",
+///     { document = "draft-ietf-quic-qlog-main-schema", revision = "09" },
+///     { document = "9002", format = "html" },
+/// ]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Manifest {
+    #[serde(default)]
+    pub documents: Vec<ManifestEntry>,
+    #[serde(default)]
+    pub collections: Vec<Collection>,
+}
+
+/// A named group of manifest entries
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Collection {
+    pub name: String,
+    #[serde(default)]
+    pub documents: Vec<ManifestEntry>,
+}
+
+/// A document listed in a manifest, either by name or with options
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum ManifestEntry {
+    Name(String),
+    Detailed {
+        document: String,
+        /// "text" (default), "html", "xml" or "pdf"
+        #[serde(default)]
+        format: Option<String>,
+        /// Draft revision to pin (e.g., "34")
+        #[serde(default)]
+        revision: Option<String>,
+    },
+}
+
+impl ManifestEntry {
+    /// Resolve the entry to the document and format to cache
+    pub fn resolve(&self) -> crate::Result<(DocumentType, Format)> {
+        let (document, format, revision) = match self {
+            ManifestEntry::Name(name) => (name.as_str(), None, None),
+            ManifestEntry::Detailed {
+                document,
+                format,
+                revision,
+            } => (document.as_str(), format.as_deref(), revision.as_deref()),
+        };
+
+        let doc = DocumentType::parse(document)
+            .with_context(|| format!("Invalid document identifier '{}'", document))?;
+        let doc = match (doc, revision) {
+            (DocumentType::Draft(name), Some(rev)) => {
+                // The pinned revision replaces one already in the name
+                let draft = DocumentType::Draft(name.clone());
+                let base = draft
+                    .draft_revision()
+                    .map_or(name.as_str(), |(base, _)| base);
+                DocumentType::Draft(format!("{}-{}", base, rev.trim_start_matches('-')))
+            }
+            (doc, Some(_)) => {
+                return Err(
+                    anyhow::anyhow!("Revisions can only be pinned for drafts ({})", doc).into(),
+                )
+            }
+            (doc, None) => doc,
+        };
+        let format = match format {
+            None => Format::Text,
+            Some(name) => Format::parse(name)
+                .with_context(|| format!("Unknown format '{}' for {}", name, doc))?,
+        };
+        Ok((doc, format))
+    }
+}
+
+impl Manifest {
+    /// Parse a TOML manifest
+    pub fn from_toml(s: &str) -> crate::Result<Self> {
+        Ok(toml::from_str(s).context("Failed to parse TOML manifest")?)
+    }
+
+    /// Parse a JSON manifest
+    pub fn from_json(s: &str) -> crate::Result<Self> {
+        Ok(serde_json::from_str(s).context("Failed to parse JSON manifest")?)
+    }
+
+    /// Load a manifest, choosing the format by file extension (TOML unless `.json`)
+    pub fn load(path: &Path) -> crate::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read manifest {}", path.display()))?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json(&content),
+            _ => Self::from_toml(&content),
+        }
+    }
+
+    /// Get every listed document with its format, without duplicates
+    pub fn entries(&self) -> crate::Result<Vec<(DocumentType, Format)>> {
+        let listed = self
+            .documents
+            .iter()
+            .chain(self.collections.iter().flat_map(|c| &c.documents));
+        Ok(resolve_unique(listed)?)
+    }
+
+    /// Get the documents of a named collection, without duplicates
+    pub fn collection(&self, name: &str) -> crate::Result<Vec<(DocumentType, Format)>> {
+        let collection = self
+            .collections
+            .iter()
+            .find(|c| c.name == name)
+            .with_context(|| format!("No collection named '{}' in manifest", name))?;
+        Ok(resolve_unique(&collection.documents)?)
+    }
+}
+
+fn resolve_unique<'a>(
+    listed: impl IntoIterator<Item = &'a ManifestEntry>,
+) -> Result<Vec<(DocumentType, Format)>> {
+    let mut entries: Vec<(DocumentType, Format)> = Vec::new();
+    for entry in listed {
+        let resolved = entry.resolve()?;
+        if !entries.contains(&resolved) {
+            entries.push(resolved);
+        }
+    }
+    Ok(entries)
+}
+
+/// Outcome of a manifest sync or a prefetch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Documents that were already cached
+    pub present: Vec<(DocumentType, Format)>,
+    /// Documents that were fetched and cached
+    pub fetched: Vec<(DocumentType, Format)>,
+    /// Documents that could not be fetched, with the error
+    pub failed: Vec<(DocumentType, Format, String)>,
+}
+
+impl SyncReport {
+    /// Check if every listed document is now cached
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Ensure every document listed in a manifest is cached in the desired format
+///
+/// A failed fetch doesn't stop the sync; it is recorded in the report.
+pub async fn sync_manifest(
+    manifest: &Manifest,
+    cache: &CacheManager,
+    fetcher: &DocumentFetcher,
+) -> crate::Result<SyncReport> {
+    let mut report = SyncReport::default();
+
+    for (doc, format) in manifest.entries()? {
+        if cache.cached_formats(&doc).contains(&format) {
+            report.present.push((doc, format));
+            continue;
+        }
+
+        let content = match format {
+            Format::Text => fetcher.fetch(&doc).await.and_then(|(content, format)| {
+                crate::render::to_text(content, format, 80).map(String::into_bytes)
+            }),
+            Format::Html | Format::Xml | Format::Pdf => fetcher.fetch_bytes(&doc, format).await,
+        };
+        match content {
+            Ok(content) => {
+                cache.store_bytes(&doc, format, &content)?;
+                report.fetched.push((doc, format));
+            }
+            Err(e) => report.failed.push((doc, format, format!("{:#}", e))),
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const TOML: &str = r#"
+documents = ["9000", "BCP 14", "rfc9000"]
+
+[[collections]]
+name = "quic"
+documents = [
+    { document = "draft-ietf-quic-qlog-main-schema", revision = "09" },
+    { document = "9001", format = "html" },
+    { document = "draft-ietf-quic-transport-05", revision = "34" },
+]
+"#;
+
+    #[test]
+    fn test_manifest_entries() {
+        let manifest = Manifest::from_toml(TOML).unwrap();
+        let entries = manifest.entries().unwrap();
+
+        assert_eq!(manifest.collections[0].name, "quic");
+        assert_eq!(entries.len(), 5);
+        assert_eq!(entries[0], (DocumentType::Rfc(9000), Format::Text));
+        assert_eq!(
+            entries[2].0,
+            DocumentType::Draft("draft-ietf-quic-qlog-main-schema-09".to_string())
+        );
+        assert_eq!(entries[3], (DocumentType::Rfc(9001), Format::Html));
+        assert_eq!(
+            entries[4].0,
+            DocumentType::Draft("draft-ietf-quic-transport-34".to_string())
+        );
+
+        let json = Manifest::from_json(r#"{"documents": ["9000"]}"#).unwrap();
+        assert_eq!(json.entries().unwrap().len(), 1);
+
+        let quic = manifest.collection("quic").unwrap();
+        assert_eq!(quic.len(), 3);
+        assert_eq!(quic[1], (DocumentType::Rfc(9001), Format::Html));
+        assert!(manifest.collection("tls").is_err());
+    }
+
+    #[test]
+    fn test_invalid_entries() {
+        let pinned_rfc =
+            Manifest::from_json(r#"{"documents": [{"document": "9000", "revision": "01"}]}"#);
+        assert!(pinned_rfc.unwrap().entries().is_err());
+
+        let bad_format =
+            Manifest::from_toml(r#"documents = [{ document = "9000", format = "epub" }]"#);
+        assert!(bad_format.unwrap().entries().is_err());
+    }
+
+    #[test]
+    fn test_sync_skips_cached() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        cache
+            .store_document(&DocumentType::Rfc(9000), Format::Text, "cached")
+            .unwrap();
+        let manifest = Manifest::from_toml(r#"documents = ["9000"]"#).unwrap();
+
+        let fetcher = DocumentFetcher::new().unwrap();
+        let report = tokio_test::block_on(sync_manifest(&manifest, &cache, &fetcher)).unwrap();
+
+        assert_eq!(
+            report.present,
+            vec![(DocumentType::Rfc(9000), Format::Text)]
+        );
+        assert!(report.fetched.is_empty());
+        assert!(report.is_complete());
+    }
+}
@@ -0,0 +1,99 @@
+use std::fs::File;
+use std::ops::Deref;
+
+use memmap2::Mmap;
+
+use super::CacheManager;
+use crate::models::{DocumentType, Format};
+
+/// A cached text document mapped into memory instead of read onto the heap
+#[derive(Debug)]
+pub struct MappedDocument {
+    map: Mmap,
+}
+
+impl MappedDocument {
+    /// Get the document content
+    pub fn as_str(&self) -> &str {
+        // SAFETY: the content was checked to be UTF-8 when the document was mapped
+        unsafe { std::str::from_utf8_unchecked(&self.map) }
+    }
+}
+
+impl Deref for MappedDocument {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for MappedDocument {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl CacheManager {
+    /// Get a cached text document without loading it onto the heap
+    ///
+    /// Pages are read on demand, which keeps scans of large documents cheap.
+    /// Returns None if the document isn't cached or is not valid UTF-8.
+    pub fn get_document_mapped(
+        &self,
+        doc: &DocumentType,
+        format: Format,
+    ) -> Option<MappedDocument> {
+        let path = self.store().local_path(&Self::document_key(doc, format))?;
+        let file = File::open(path).ok()?;
+        // SAFETY: the mapped bytes must not change while mapped, or the &[u8]
+        // behind `as_str` would be mutated under it (and a truncation would fault).
+        // Local stores never write to an existing entry: they write a new file
+        // and rename it over the old one, so this inode stays unchanged even
+        // after the document is replaced or removed. Editing files in the cache
+        // directory in place from outside the cache is not supported.
+        let map = unsafe { Mmap::map(&file) }.ok()?;
+        std::str::from_utf8(&map).ok()?;
+        self.record_access(doc);
+        Some(MappedDocument { map })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_mapped_document() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let rfc = DocumentType::Rfc(9000);
+        assert!(cache.get_document_mapped(&rfc, Format::Text).is_none());
+
+        cache.store_document(&rfc, Format::Text, "QUIC").unwrap();
+        let stored = cache.access_count(&rfc);
+        let mapped = cache.get_document_mapped(&rfc, Format::Text).unwrap();
+        assert_eq!(&*mapped, "QUIC");
+        // Mapped reads count as accesses
+        assert!(cache.access_count(&rfc) > stored);
+
+        // Storing a new copy leaves the existing mapping intact
+        cache.store_document(&rfc, Format::Text, "QUIC v2").unwrap();
+        assert_eq!(mapped.as_str(), "QUIC");
+        assert_eq!(
+            cache
+                .get_document_mapped(&rfc, Format::Text)
+                .unwrap()
+                .lines()
+                .count(),
+            1
+        );
+
+        cache.store_bytes(&rfc, Format::Pdf, &[0xff, 0xfe]).unwrap();
+        let read = cache.access_count(&rfc);
+        assert!(cache.get_document_mapped(&rfc, Format::Pdf).is_none());
+        // A document that can't be mapped isn't counted as read
+        assert_eq!(cache.access_count(&rfc), read);
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use super::EntryMetadata;
+use crate::models::{DocumentType, Format};
+use crate::parse::ParsedDocument;
+
+/// Recently read documents and their parse results, kept in memory in front
+/// of the store (see [`CacheManager::with_memory_limit`](super::CacheManager::with_memory_limit))
+///
+/// Entries remember the size and modification time of the stored copy, so a
+/// document replaced by another process is read again.
+#[derive(Debug)]
+pub(super) struct MemoryLayer {
+    max_bytes: u64,
+    inner: Mutex<Layer>,
+}
+
+#[derive(Debug, Default)]
+struct Layer {
+    entries: HashMap<(DocumentType, Format), Entry>,
+    /// Total size of the entries' content and parse results
+    size: u64,
+    /// Incremented on every use, to order entries by recency
+    clock: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    content: Arc<Vec<u8>>,
+    /// Stored copy the content was read from
+    stored: EntryMetadata,
+    /// Parse result of a text document, with its estimated size in memory
+    parsed: Option<(ParsedDocument, u64)>,
+    last_use: u64,
+}
+
+impl MemoryLayer {
+    pub(super) fn new(max_bytes: u64) -> Self {
+        Self {
+            max_bytes,
+            inner: Mutex::new(Layer::default()),
+        }
+    }
+
+    /// Get a document's content if it is in memory and the stored copy is unchanged
+    pub(super) fn get(
+        &self,
+        doc: &DocumentType,
+        format: Format,
+        stored: Option<EntryMetadata>,
+    ) -> Option<Arc<Vec<u8>>> {
+        let mut layer = self.inner.lock().unwrap();
+        let entry = layer.touch(doc, format, stored)?;
+        Some(entry.content.clone())
+    }
+
+    /// Keep a document's content read from the stored copy described by `stored`
+    pub(super) fn insert(
+        &self,
+        doc: &DocumentType,
+        format: Format,
+        content: Arc<Vec<u8>>,
+        stored: EntryMetadata,
+    ) {
+        let size = content.len() as u64;
+        if size > self.max_bytes {
+            return;
+        }
+        let mut layer = self.inner.lock().unwrap();
+        layer.remove(doc, format);
+        layer.clock += 1;
+        let entry = Entry {
+            content,
+            stored,
+            parsed: None,
+            last_use: layer.clock,
+        };
+        layer.entries.insert((doc.clone(), format), entry);
+        layer.size += size;
+        layer.evict(self.max_bytes);
+    }
+
+    /// Get the parse result of a text document in memory
+    pub(super) fn parsed(
+        &self,
+        doc: &DocumentType,
+        stored: Option<EntryMetadata>,
+    ) -> Option<ParsedDocument> {
+        let mut layer = self.inner.lock().unwrap();
+        let entry = layer.touch(doc, Format::Text, stored)?;
+        entry.parsed.as_ref().map(|(parsed, _)| parsed.clone())
+    }
+
+    /// Keep the parse result of a text document that is in memory
+    ///
+    /// `size` estimates the parse result's size in memory (e.g., its
+    /// serialized length) and counts towards the layer's limit.
+    pub(super) fn set_parsed(&self, doc: &DocumentType, parsed: &ParsedDocument, size: u64) {
+        let mut layer = self.inner.lock().unwrap();
+        let Some(entry) = layer.entries.get_mut(&(doc.clone(), Format::Text)) else {
+            return;
+        };
+        let previous = entry.parsed.replace((parsed.clone(), size));
+        let previous = previous.map_or(0, |(_, size)| size);
+        layer.size = layer.size - previous + size;
+        layer.evict(self.max_bytes);
+    }
+
+    /// Forget a document in one format
+    pub(super) fn invalidate(&self, doc: &DocumentType, format: Format) {
+        self.inner.lock().unwrap().remove(doc, format);
+    }
+
+    /// Forget a document in every format
+    pub(super) fn remove(&self, doc: &DocumentType) {
+        let mut layer = self.inner.lock().unwrap();
+        for format in Format::ALL {
+            layer.remove(doc, format);
+        }
+    }
+
+    pub(super) fn clear(&self) {
+        *self.inner.lock().unwrap() = Layer::default();
+    }
+
+    /// Get the total size of the documents and parse results in memory
+    pub(super) fn size(&self) -> u64 {
+        self.inner.lock().unwrap().size
+    }
+}
+
+impl Entry {
+    /// Get the size of the content and parse result
+    fn size(&self) -> u64 {
+        self.content.len() as u64 + self.parsed.as_ref().map_or(0, |(_, size)| *size)
+    }
+}
+
+impl Layer {
+    /// Mark an entry as used, dropping it if the stored copy changed
+    fn touch(
+        &mut self,
+        doc: &DocumentType,
+        format: Format,
+        stored: Option<EntryMetadata>,
+    ) -> Option<&mut Entry> {
+        let key = (doc.clone(), format);
+        let current = self.entries.get(&key)?.stored;
+        if stored != Some(current) {
+            self.remove(doc, format);
+            return None;
+        }
+        self.clock += 1;
+        let clock = self.clock;
+        let entry = self.entries.get_mut(&key)?;
+        entry.last_use = clock;
+        Some(entry)
+    }
+
+    fn remove(&mut self, doc: &DocumentType, format: Format) {
+        if let Some(entry) = self.entries.remove(&(doc.clone(), format)) {
+            self.size -= entry.size();
+        }
+    }
+
+    /// Drop the least recently used entries until the layer fits in `max_bytes`
+    fn evict(&mut self, max_bytes: u64) {
+        while self.size > max_bytes {
+            let Some(key) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_use)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.remove(&key.0, key.1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    fn stored(size: u64) -> EntryMetadata {
+        EntryMetadata {
+            size,
+            modified: SystemTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn test_least_recently_used_are_evicted() {
+        let layer = MemoryLayer::new(10);
+        let content = |s: &str| Arc::new(s.as_bytes().to_vec());
+        let (a, b, c) = (
+            DocumentType::Rfc(1),
+            DocumentType::Rfc(2),
+            DocumentType::Rfc(3),
+        );
+
+        layer.insert(&a, Format::Text, content("aaaa"), stored(4));
+        layer.insert(&b, Format::Text, content("bbbb"), stored(4));
+        assert!(layer.get(&a, Format::Text, Some(stored(4))).is_some());
+        layer.insert(&c, Format::Text, content("cccc"), stored(4));
+
+        // b was used least recently
+        assert!(layer.get(&b, Format::Text, Some(stored(4))).is_none());
+        assert!(layer.get(&a, Format::Text, Some(stored(4))).is_some());
+        assert_eq!(layer.size(), 8);
+
+        // Documents larger than the layer aren't kept
+        layer.insert(&b, Format::Text, content("bbbbbbbbbbbb"), stored(12));
+        assert!(layer.get(&b, Format::Text, Some(stored(12))).is_none());
+        assert_eq!(layer.size(), 8);
+    }
+
+    #[test]
+    fn test_changed_documents_are_dropped() {
+        let layer = MemoryLayer::new(100);
+        let doc = DocumentType::Rfc(9000);
+        layer.insert(&doc, Format::Text, Arc::new(b"QUIC".to_vec()), stored(4));
+        layer.set_parsed(&doc, &ParsedDocument::default(), 2);
+        assert!(layer.parsed(&doc, Some(stored(4))).is_some());
+        assert_eq!(layer.size(), 6);
+
+        let replaced = EntryMetadata {
+            size: 4,
+            modified: SystemTime::UNIX_EPOCH + Duration::from_secs(1),
+        };
+        assert!(layer.get(&doc, Format::Text, Some(replaced)).is_none());
+        assert_eq!(layer.size(), 0);
+    }
+
+    #[test]
+    fn test_parse_results_count_towards_limit() {
+        let layer = MemoryLayer::new(10);
+        let (a, b) = (DocumentType::Rfc(1), DocumentType::Rfc(2));
+        layer.insert(&a, Format::Text, Arc::new(b"aaaa".to_vec()), stored(4));
+        layer.insert(&b, Format::Text, Arc::new(b"bbbb".to_vec()), stored(4));
+        layer.set_parsed(&b, &ParsedDocument::default(), 1);
+        assert_eq!(layer.size(), 9);
+
+        // Replacing a parse result doesn't count the old one
+        layer.set_parsed(&b, &ParsedDocument::default(), 2);
+        assert_eq!(layer.size(), 10);
+
+        // A larger parse result evicts a least recently used document
+        layer.set_parsed(&b, &ParsedDocument::default(), 4);
+        assert!(layer.get(&a, Format::Text, Some(stored(4))).is_none());
+        assert!(layer.parsed(&b, Some(stored(4))).is_some());
+        assert_eq!(layer.size(), 8);
+    }
+}
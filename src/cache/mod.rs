@@ -1,3 +1,36 @@
+mod archive;
+mod bundle;
+mod consistency;
+#[cfg(feature = "index")]
+mod index;
+mod journal;
+mod lru;
+mod manifest;
+#[cfg(feature = "mmap")]
+mod mapped;
+mod memory;
+mod policy;
+mod provenance;
+mod space;
 mod storage;
+mod store;
+mod tofu;
+mod validators;
 
-pub use storage::CacheManager;
+pub use archive::is_archive;
+pub use bundle::{verify_bundle, BundleEntry, BundleIndex, BUNDLE_VERSION};
+pub use consistency::{compare_renderings, ConsistencyReport, Divergence};
+#[cfg(feature = "index")]
+pub use index::{CacheIndex, IndexEntry, RebuildProgress, RebuildReport};
+pub use journal::{JournalEntry, JournalOp};
+pub use lru::CacheLimit;
+pub use manifest::{sync_manifest, Collection, Manifest, ManifestEntry, SyncReport};
+#[cfg(feature = "mmap")]
+pub use mapped::MappedDocument;
+pub use policy::CachePolicy;
+pub use provenance::Provenance;
+pub use space::InsufficientSpace;
+pub use storage::{CacheManager, StagedDocument};
+pub use store::{CacheStore, EntryMetadata, FileStore, MemoryStore, StagedEntry};
+pub use tofu::{is_immutable, ContentChange};
+pub use validators::Validators;
@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use super::CacheManager;
+use crate::models::{DocumentType, Format};
+
+/// How long drafts are cached by default before they are fetched again
+const DRAFT_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// How long cached documents stay fresh; `None` caches forever
+///
+/// Published RFCs never change, so they are cached forever by default.
+/// Drafts, subseries (whose membership changes) and external living standards
+/// use the draft TTL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachePolicy {
+    pub rfc_ttl: Option<Duration>,
+    pub draft_ttl: Option<Duration>,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        Self {
+            rfc_ttl: None,
+            draft_ttl: Some(DRAFT_TTL),
+        }
+    }
+}
+
+impl CachePolicy {
+    /// Get the TTL applying to a document
+    pub fn ttl(&self, doc: &DocumentType) -> Option<Duration> {
+        match doc {
+            DocumentType::Rfc(_) => self.rfc_ttl,
+            _ => self.draft_ttl,
+        }
+    }
+}
+
+impl CacheManager {
+    /// Check whether the cached copy of a document is older than its TTL
+    /// Returns false if the document isn't cached
+    pub fn is_stale(&self, doc: &DocumentType) -> bool {
+        let Some(ttl) = self.policy().ttl(doc) else {
+            return false;
+        };
+        let newest = self
+            .cached_formats(doc)
+            .into_iter()
+            .filter_map(|format| self.document_metadata(doc, format))
+            .map(|metadata| metadata.modified)
+            .max();
+        match newest {
+            Some(modified) => modified.elapsed().unwrap_or_default() > ttl,
+            None => false,
+        }
+    }
+
+    /// Get cached document content unless it is stale
+    pub fn get_fresh(&self, doc: &DocumentType, format: Format) -> Option<String> {
+        if self.is_stale(doc) {
+            return None;
+        }
+        self.get_document(doc, format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::SystemTime;
+    use tempfile::TempDir;
+
+    fn age(cache: &CacheManager, doc: &DocumentType, days: u64) {
+        let file = fs::File::options()
+            .write(true)
+            .open(cache.document_path(doc, Format::Text))
+            .unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_default_policy() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let rfc = DocumentType::Rfc(9000);
+        let draft = DocumentType::Draft("draft-ietf-quic-transport-34".to_string());
+
+        assert!(!cache.is_stale(&draft));
+        for doc in [&rfc, &draft] {
+            cache.store_document(doc, Format::Text, "text").unwrap();
+            assert!(!cache.is_stale(doc));
+            age(&cache, doc, 30);
+        }
+
+        assert!(!cache.is_stale(&rfc));
+        assert!(cache.is_stale(&draft));
+        assert!(cache.get_fresh(&draft, Format::Text).is_none());
+        assert!(cache.get_document(&draft, Format::Text).is_some());
+    }
+
+    #[test]
+    fn test_custom_policy() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf())
+            .unwrap()
+            .with_policy(CachePolicy {
+                rfc_ttl: Some(Duration::from_secs(24 * 60 * 60)),
+                draft_ttl: None,
+            });
+        let rfc = DocumentType::Rfc(9000);
+        let draft = DocumentType::Draft("draft-foo-00".to_string());
+
+        for doc in [&rfc, &draft] {
+            cache.store_document(doc, Format::Text, "text").unwrap();
+            age(&cache, doc, 2);
+        }
+        assert!(cache.is_stale(&rfc));
+        assert!(!cache.is_stale(&draft));
+    }
+}
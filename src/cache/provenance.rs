@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::bundle::sha256_hex;
+use super::{CacheManager, Validators};
+use crate::models::{DocumentType, Format};
+
+/// Where and when one copy of a document was fetched
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    pub time: DateTime<Utc>,
+    /// URL the document was requested from
+    pub url: String,
+    /// Host that served the document (e.g., "www.rfc-editor.org")
+    pub mirror: String,
+    pub format: Format,
+    /// HTTP status of the response; 304 if the cached copy was revalidated
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    /// SHA-256 of the content relied upon, in hex
+    pub sha256: String,
+    pub size: u64,
+    /// Whether this changed copy was accepted in place of the pinned one
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub accepted: bool,
+}
+
+impl Provenance {
+    /// Describe a response, identifying its content by checksum
+    pub fn new(format: Format, status: u16, validators: &Validators, content: &[u8]) -> Self {
+        Self::with_checksum(
+            format,
+            status,
+            validators,
+            sha256_hex(content),
+            content.len() as u64,
+        )
+    }
+
+    /// Describe a response whose content was checksummed while it was streamed
+    pub fn with_checksum(
+        format: Format,
+        status: u16,
+        validators: &Validators,
+        sha256: String,
+        size: u64,
+    ) -> Self {
+        let mirror = reqwest::Url::parse(&validators.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_default();
+        Self {
+            time: Utc::now(),
+            url: validators.url.clone(),
+            mirror,
+            format,
+            status,
+            etag: validators.etag.clone(),
+            last_modified: validators.last_modified.clone(),
+            sha256,
+            size,
+            accepted: false,
+        }
+    }
+}
+
+/// Newest revision of every draft ever cached or fetched, by name without revision
+///
+/// Scanned from the cache directory on first use, then kept up to date as
+/// documents are stored and fetches recorded.
+#[derive(Debug, Default)]
+pub(super) struct KnownRevisions(Mutex<Option<HashMap<String, u32>>>);
+
+impl KnownRevisions {
+    /// Note a draft revision that was cached or fetched
+    pub(super) fn note(&self, doc: &DocumentType) {
+        let Some((base, revision)) = doc.draft_revision() else {
+            return;
+        };
+        if let Some(known) = self.0.lock().unwrap().as_mut() {
+            let latest = known.entry(base.to_string()).or_insert(revision);
+            *latest = (*latest).max(revision);
+        }
+    }
+
+    /// Get the newest revision of a draft, scanning for them if not done yet
+    fn latest(&self, draft: &str, scan: impl FnOnce() -> HashMap<String, u32>) -> Option<u32> {
+        let mut known = self.0.lock().unwrap();
+        known.get_or_insert_with(scan).get(draft).copied()
+    }
+
+    /// Forget the revisions seen, to scan the cache again on next use
+    pub(super) fn clear(&self) {
+        *self.0.lock().unwrap() = None;
+    }
+}
+
+impl CacheManager {
+    /// Get every recorded fetch of a document, oldest first
+    ///
+    /// The log survives removal and eviction of the document, so it also
+    /// shows copies that are no longer cached.
+    pub fn provenance(&self, doc: &DocumentType) -> crate::Result<Vec<Provenance>> {
+        let content = match fs::read_to_string(self.provenance_path(doc)) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => Err(e).context("Failed to read provenance log")?,
+        };
+        let records = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).context("Invalid provenance record"))
+            .collect::<Result<_>>()?;
+        Ok(records)
+    }
+
+    /// Append a fetch of a document to its provenance log
+    pub fn record_provenance(&self, doc: &DocumentType, record: &Provenance) -> crate::Result<()> {
+        let path = self.provenance_path(doc);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create provenance directory")?;
+        }
+        let mut line =
+            serde_json::to_string(record).context("Failed to serialize provenance record")?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .context("Failed to write provenance log")?;
+        self.known_revisions().note(doc);
+        Ok(())
+    }
+
+    /// Get the newest revision of a draft that was ever cached or fetched
+    ///
+    /// The cache directory is only scanned once per manager and its clones.
+    pub fn latest_known_revision(&self, draft: &str) -> Option<u32> {
+        self.known_revisions()
+            .latest(draft, || self.scan_revisions())
+    }
+
+    /// Find the newest revision of every draft cached or in the provenance logs
+    fn scan_revisions(&self) -> HashMap<String, u32> {
+        let logged = fs::read_dir(self.cache_dir().join("provenance"))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                Some(DocumentType::Draft(
+                    name.strip_suffix(".jsonl")?.to_string(),
+                ))
+            });
+        let mut known = HashMap::new();
+        for doc in self.list_cached().into_iter().chain(logged) {
+            if let Some((base, revision)) = doc.draft_revision() {
+                let latest = known.entry(base.to_string()).or_insert(revision);
+                *latest = (*latest).max(revision);
+            }
+        }
+        known
+    }
+
+    /// Get the path of a document's provenance log
+    fn provenance_path(&self, doc: &DocumentType) -> PathBuf {
+        self.cache_dir()
+            .join("provenance")
+            .join(format!("{}.jsonl", doc.name()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_provenance_log() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let doc = DocumentType::Rfc(9000);
+        let validators = Validators {
+            url: "https://www.rfc-editor.org/rfc/rfc9000.txt".to_string(),
+            etag: Some("\"5e3a\"".to_string()),
+            last_modified: None,
+        };
+
+        assert!(cache.provenance(&doc).unwrap().is_empty());
+        let fetched = Provenance::new(Format::Text, 200, &validators, b"QUIC");
+        assert_eq!(fetched.mirror, "www.rfc-editor.org");
+        assert_eq!(
+            fetched.sha256,
+            "e3ea013ec7135e2015e752f48e3cc41dfdbfb1863841879dcf736ecb7049254e"
+        );
+        cache.record_provenance(&doc, &fetched).unwrap();
+        let revalidated = Provenance::new(Format::Text, 304, &validators, b"QUIC");
+        cache.record_provenance(&doc, &revalidated).unwrap();
+
+        cache.store_document(&doc, Format::Text, "QUIC").unwrap();
+        cache.remove(&doc).unwrap();
+        assert_eq!(cache.provenance(&doc).unwrap(), vec![fetched, revalidated]);
+    }
+
+    #[test]
+    fn test_latest_known_revision() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let draft = |rev: &str| DocumentType::Draft(format!("draft-ietf-quic-qlog-{}", rev));
+        assert_eq!(cache.latest_known_revision("draft-ietf-quic-qlog"), None);
+
+        cache
+            .store_document(&draft("07"), Format::Text, "qlog")
+            .unwrap();
+        let validators = Validators {
+            url: "https://www.ietf.org/archive/id/draft-ietf-quic-qlog-08.txt".to_string(),
+            etag: None,
+            last_modified: None,
+        };
+        let record = Provenance::new(Format::Text, 200, &validators, b"qlog");
+        cache.record_provenance(&draft("08"), &record).unwrap();
+
+        assert_eq!(cache.latest_known_revision("draft-ietf-quic-qlog"), Some(8));
+        assert_eq!(cache.latest_known_revision("draft-ietf-quic"), None);
+
+        // Another manager finds the same revisions in the cache directory
+        let reopened = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        assert_eq!(
+            reopened.latest_known_revision("draft-ietf-quic-qlog"),
+            Some(8)
+        );
+        cache.clear_cache().unwrap();
+        assert_eq!(cache.latest_known_revision("draft-ietf-quic-qlog"), None);
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt;
+
+use anyhow::Result;
+
+use super::journal::JournalOp;
+use super::CacheManager;
+
+/// Free space left on the file system after a write, so the cache never fills it
+const SPACE_RESERVE: u64 = 16 * 1024 * 1024;
+
+/// Error returned when the cache's file system has no room for a write
+///
+/// Returned as [`crate::Error::InsufficientSpace`] by the cache's public API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsufficientSpace {
+    /// Bytes the write needs, including the reserve
+    pub required: u64,
+    /// Bytes available on the file system
+    pub available: u64,
+}
+
+impl fmt::Display for InsufficientSpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Not enough disk space for the cache: {} bytes needed, {} available",
+            self.required, self.available
+        )
+    }
+}
+
+impl std::error::Error for InsufficientSpace {}
+
+impl CacheManager {
+    /// Make sure `bytes` can be written to the cache
+    ///
+    /// With eviction enabled, the least recently used documents are removed
+    /// until the write fits; otherwise it fails with [`crate::Error::InsufficientSpace`].
+    /// If the free space can't be determined the write is allowed.
+    pub fn ensure_space(&self, bytes: u64) -> crate::Result<()> {
+        match fs2::available_space(self.cache_dir()) {
+            Ok(available) => Ok(self.make_room(bytes, available)?),
+            Err(_) => Ok(()),
+        }
+    }
+
+    fn make_room(&self, bytes: u64, mut available: u64) -> Result<()> {
+        let required = bytes.saturating_add(SPACE_RESERVE);
+        if available >= required {
+            return Ok(());
+        }
+        if self.evicts_when_full() {
+            for (doc, size) in self.documents_by_access() {
+                if available >= required {
+                    break;
+                }
+                if self.remove_as(&doc, JournalOp::Evict)? {
+                    available += size;
+                }
+            }
+        }
+        if available < required {
+            return Err(InsufficientSpace {
+                required,
+                available,
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DocumentType, Format};
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    fn cache_with_documents(temp: &TempDir) -> CacheManager {
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        for (number, age) in [(9000, 300), (9001, 200), (9002, 100)] {
+            let doc = DocumentType::Rfc(number);
+            cache
+                .store_document(&doc, Format::Text, &"x".repeat(1000))
+                .unwrap();
+            let file = fs::File::options()
+                .write(true)
+                .open(cache.document_path(&doc, Format::Text))
+                .unwrap();
+            file.set_modified(SystemTime::now() - Duration::from_secs(age))
+                .unwrap();
+        }
+        cache
+    }
+
+    #[test]
+    fn test_insufficient_space() {
+        let temp = TempDir::new().unwrap();
+        let cache = cache_with_documents(&temp);
+
+        assert!(cache.make_room(500, SPACE_RESERVE + 500).is_ok());
+
+        let err = cache.make_room(500, SPACE_RESERVE).unwrap_err();
+        let space = err.downcast_ref::<InsufficientSpace>().unwrap();
+        assert_eq!(space.required, SPACE_RESERVE + 500);
+        assert_eq!(space.available, SPACE_RESERVE);
+        assert_eq!(cache.list_cached().len(), 3);
+
+        // Through the public API the failure keeps its type
+        let required = u64::MAX / 2;
+        match cache.ensure_space(required) {
+            Err(crate::Error::InsufficientSpace { error, .. }) => {
+                assert_eq!(error.required, required + SPACE_RESERVE)
+            }
+            other => panic!("expected insufficient space, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_eviction_removes_oldest() {
+        let temp = TempDir::new().unwrap();
+        let cache = cache_with_documents(&temp).with_eviction(true);
+
+        cache.make_room(1500, SPACE_RESERVE).unwrap();
+        assert_eq!(cache.list_cached(), vec![DocumentType::Rfc(9002)]);
+
+        // Evicting everything still isn't enough
+        assert!(cache.make_room(5000, SPACE_RESERVE).is_err());
+        assert!(cache.list_cached().is_empty());
+    }
+}
@@ -1,131 +1,802 @@
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
-use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
 
-use crate::models::{DocumentType, Format};
+use super::consistency::{compare_renderings, ConsistencyReport};
+use super::journal::JournalOp;
+use super::lru::AccessLog;
+use super::memory::MemoryLayer;
+use super::provenance::KnownRevisions;
+use super::store;
+use super::{CacheLimit, CachePolicy, CacheStore, EntryMetadata, FileStore, StagedEntry};
+use crate::api::LoadedIndex;
+use crate::hooks::{Hook, HookEvent, Hooks};
+use crate::models::{DocumentMetadata, DocumentType, Erratum, Format};
+use crate::parse::{ParsedDocument, PARSE_VERSION};
+use crate::profile::{self, Phase};
+use crate::render::html_to_text;
+use crate::workspace::Workspace;
+
+/// Default size of the memory layer of [`CacheManager::new`], in MiB
+const DEFAULT_MEMORY_CACHE_MB: u64 = 64;
+/// How long cached Datatracker metadata is used before it is queried again
+const METADATA_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// How long cached errata are used before they are fetched again, so new
+/// reports and status changes show up
+const ERRATA_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A parsed document structure stored next to the raw document
+#[derive(Debug, Serialize, Deserialize)]
+struct ParsedArtifact {
+    parse_version: u32,
+    content_hash: String,
+    document: ParsedDocument,
+}
+
+/// A document being written to the cache, see [`CacheManager::stage`]
+#[derive(Debug)]
+pub struct StagedDocument {
+    entry: StagedEntry,
+    doc: DocumentType,
+    format: Format,
+    /// Bytes written so far
+    size: u64,
+}
+
+impl StagedDocument {
+    /// Get the number of bytes written so far
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Read back what was written so far, before installing it
+    pub fn content(&self) -> crate::Result<Vec<u8>> {
+        self.entry.content()
+    }
+
+    pub(super) fn doc(&self) -> &DocumentType {
+        &self.doc
+    }
+
+    pub(super) fn format(&self) -> Format {
+        self.format
+    }
+
+    pub(super) fn into_entry(self) -> StagedEntry {
+        self.entry
+    }
+}
+
+impl Write for StagedDocument {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = profile::time(Phase::CacheIo, || self.entry.write(buf))?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.entry.flush()
+    }
+}
 
 /// Manages local document caching
+#[derive(Debug, Clone)]
 pub struct CacheManager {
     cache_dir: PathBuf,
+    /// Where documents and their derived artifacts are kept
+    store: Arc<dyn CacheStore>,
+    /// Evict old documents instead of failing when the disk is full
+    evict_when_full: bool,
+    /// When cached documents become stale
+    policy: CachePolicy,
+    /// Maximum size of the cache, enforced after every write
+    limit: CacheLimit,
+    /// Append every mutation to the journal
+    journal: bool,
+    /// Fired when documents are evicted
+    hooks: Hooks,
+    /// Recently read documents, shared by clones of the manager
+    memory: Option<Arc<MemoryLayer>>,
+    /// Accesses not yet saved, shared by clones of the manager
+    access: Arc<AccessLog>,
+    /// Newest draft revisions seen, shared by clones of the manager
+    revisions: Arc<KnownRevisions>,
+    /// Synchronized RFC index, shared by clones of the manager
+    rfc_index: Arc<LoadedIndex>,
 }
 
 impl CacheManager {
     /// Create a new cache manager
-    pub fn new() -> Result<Self> {
-        let cache_dir = Self::default_cache_dir()?;
-        fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
-        Ok(Self { cache_dir })
+    ///
+    /// The journal is enabled when `RFC_CACHE_JOURNAL` is set to anything but "0",
+    /// and `RFC_HOOK` names a command run on every eviction. Up to
+    /// `RFC_MEMORY_CACHE_MB` (default 64, 0 to disable) of recently read
+    /// documents are kept in memory. A `cache_dir` pinned by the `.rfc.toml`
+    /// of the working directory replaces the default directory; a malformed
+    /// `.rfc.toml` fails with its error.
+    pub fn new() -> crate::Result<Self> {
+        let pinned = match std::env::current_dir() {
+            Ok(cwd) => Workspace::pinned_cache_dir(&cwd)?,
+            Err(_) => None,
+        };
+        let cache_dir = match pinned {
+            Some(dir) => dir,
+            None => {
+                // The default directory only ever holds the cache
+                let dir = Self::default_cache_dir()?;
+                store::tag_dir(&dir)?;
+                dir
+            }
+        };
+        let journal = std::env::var_os("RFC_CACHE_JOURNAL").is_some_and(|v| v != "0");
+        let memory_mb = std::env::var("RFC_MEMORY_CACHE_MB")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_MEMORY_CACHE_MB);
+        let mut cache = Self::with_dir(cache_dir)?
+            .with_journal(journal)
+            .with_memory_limit(memory_mb.saturating_mul(1024 * 1024));
+        cache.hooks = Hooks::from_env();
+        Ok(cache)
     }
 
     /// Create a cache manager with a custom directory
-    pub fn with_dir(cache_dir: PathBuf) -> Result<Self> {
-        fs::create_dir_all(&cache_dir).context("Failed to create cache directory")?;
-        Ok(Self { cache_dir })
+    ///
+    /// A new or empty directory is marked as a cache with a `CACHEDIR.TAG`
+    /// file; [`clear_cache`](Self::clear_cache) only clears marked directories.
+    pub fn with_dir(cache_dir: PathBuf) -> crate::Result<Self> {
+        store::claim_dir(&cache_dir)?;
+        Ok(Self {
+            store: Arc::new(FileStore::new(cache_dir.clone())),
+            access: Arc::new(AccessLog::new(&cache_dir)),
+            cache_dir,
+            evict_when_full: false,
+            policy: CachePolicy::default(),
+            limit: CacheLimit::default(),
+            journal: false,
+            hooks: Hooks::new(),
+            memory: None,
+            revisions: Arc::default(),
+            rfc_index: Arc::default(),
+        })
+    }
+
+    /// Keep documents and their derived artifacts in `store` instead of the cache directory
+    ///
+    /// Bookkeeping (journal, access times, HTTP validators, provenance) is
+    /// still kept in the cache directory.
+    pub fn with_store(mut self, store: impl CacheStore + 'static) -> Self {
+        self.store = Arc::new(store);
+        self
+    }
+
+    /// Get the store documents are kept in
+    pub fn store(&self) -> &dyn CacheStore {
+        self.store.as_ref()
+    }
+
+    /// Keep up to `max_bytes` of recently read documents and their parse
+    /// results in memory, in front of the store; 0 disables the memory layer
+    ///
+    /// Repeated reads of a document then skip reading and parsing it again,
+    /// as long as the stored copy is unchanged.
+    pub fn with_memory_limit(mut self, max_bytes: u64) -> Self {
+        self.memory = (max_bytes > 0).then(|| Arc::new(MemoryLayer::new(max_bytes)));
+        self
+    }
+
+    /// Get the total size of the documents and parse results kept in memory
+    pub fn memory_size(&self) -> u64 {
+        self.memory.as_ref().map_or(0, |memory| memory.size())
+    }
+
+    /// Evict the least recently written documents when the disk is full
+    pub fn with_eviction(mut self, enabled: bool) -> Self {
+        self.evict_when_full = enabled;
+        self
+    }
+
+    pub(super) fn evicts_when_full(&self) -> bool {
+        self.evict_when_full
+    }
+
+    /// Set when cached documents become stale
+    pub fn with_policy(mut self, policy: CachePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Get the staleness policy of the cache
+    pub fn policy(&self) -> &CachePolicy {
+        &self.policy
+    }
+
+    /// Bound the size of the cache, evicting least recently used documents
+    pub fn with_limit(mut self, limit: CacheLimit) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Get the size limit of the cache
+    pub fn limit(&self) -> &CacheLimit {
+        &self.limit
+    }
+
+    /// Record stores, removals and evictions in an append-only journal
+    ///
+    /// Sync tools can follow `journal_path` to mirror the cache incrementally.
+    pub fn with_journal(mut self, enabled: bool) -> Self {
+        self.journal = enabled;
+        self
+    }
+
+    /// Fire a hook whenever a document is evicted
+    pub fn with_hook(mut self, hook: impl Hook + 'static) -> Self {
+        self.hooks.register(hook);
+        self
+    }
+
+    pub(super) fn journals(&self) -> bool {
+        self.journal
+    }
+
+    pub(super) fn access_log(&self) -> &AccessLog {
+        &self.access
+    }
+
+    pub(super) fn known_revisions(&self) -> &KnownRevisions {
+        &self.revisions
+    }
+
+    pub(crate) fn loaded_index(&self) -> &LoadedIndex {
+        &self.rfc_index
     }
 
     /// Get the default cache directory
-    pub fn default_cache_dir() -> Result<PathBuf> {
-        if let Some(proj_dirs) = ProjectDirs::from("", "", "rfc") {
-            Ok(proj_dirs.cache_dir().to_path_buf())
-        } else {
-            // Fallback to home directory
-            let home = std::env::var("HOME").context("HOME not set")?;
-            Ok(PathBuf::from(home).join(".cache").join("rfc"))
-        }
+    pub fn default_cache_dir() -> crate::Result<PathBuf> {
+        crate::paths::cache_dir()
     }
 
     /// Get cached document content
     pub fn get_document(&self, doc: &DocumentType, format: Format) -> Option<String> {
-        let path = self.document_path(doc, format);
-        fs::read_to_string(path).ok()
+        profile::time(Phase::CacheIo, || {
+            let content = String::from_utf8(self.read_document(doc, format)?).ok()?;
+            self.record_access(doc);
+            Some(content)
+        })
+    }
+
+    /// Get cached document content without counting it as an access
+    ///
+    /// For scans over the whole cache, such as index builds, which would
+    /// otherwise make every document look recently used to eviction.
+    pub fn peek_document(&self, doc: &DocumentType, format: Format) -> Option<String> {
+        profile::time(Phase::CacheIo, || {
+            String::from_utf8(self.read_document(doc, format)?).ok()
+        })
     }
 
     /// Store document content in cache
-    pub fn store_document(&self, doc: &DocumentType, format: Format, content: &str) -> Result<()> {
-        let path = self.document_path(doc, format);
+    pub fn store_document(
+        &self,
+        doc: &DocumentType,
+        format: Format,
+        content: &str,
+    ) -> crate::Result<()> {
+        self.store_bytes(doc, format, content.as_bytes())
+    }
+
+    /// Get cached document content without UTF-8 decoding, for binary formats
+    pub fn get_bytes(&self, doc: &DocumentType, format: Format) -> Option<Vec<u8>> {
+        profile::time(Phase::CacheIo, || {
+            let content = self.read_document(doc, format)?;
+            self.record_access(doc);
+            Some(content)
+        })
+    }
+
+    /// Store raw document content in cache
+    pub fn store_bytes(
+        &self,
+        doc: &DocumentType,
+        format: Format,
+        content: &[u8],
+    ) -> crate::Result<()> {
+        let mut staged = self.stage(doc, format, content.len() as u64)?;
+        staged
+            .write_all(content)
+            .context("Failed to write document to cache")?;
+        self.install(staged)
+    }
+
+    /// Start writing a document to the cache, for content that doesn't fit in memory
+    ///
+    /// Room is made for `expected` bytes up front. The content is written to a
+    /// temporary file next to the cached copy and only replaces it once
+    /// [`install`](Self::install) is called; dropping the staged document
+    /// discards it.
+    pub fn stage(
+        &self,
+        doc: &DocumentType,
+        format: Format,
+        expected: u64,
+    ) -> crate::Result<StagedDocument> {
+        self.ensure_space(expected)?;
+        let entry = self
+            .store
+            .stage(&Self::document_key(doc, format))
+            .context("Failed to write document to cache")?;
+        Ok(StagedDocument {
+            entry,
+            doc: doc.clone(),
+            format,
+            size: 0,
+        })
+    }
 
-        // Ensure parent directory exists
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent).context("Failed to create document cache directory")?;
+    /// Move a staged document into place, replacing the cached copy
+    pub fn install(&self, staged: StagedDocument) -> crate::Result<()> {
+        Ok(profile::time(Phase::CacheIo, || {
+            self.install_staged(staged)
+        })?)
+    }
+
+    fn install_staged(&self, staged: StagedDocument) -> Result<()> {
+        let StagedDocument {
+            mut entry,
+            doc,
+            format,
+            size,
+        } = staged;
+        entry.flush().context("Failed to write document to cache")?;
+        self.store
+            .commit(&Self::document_key(&doc, format), entry)
+            .context("Failed to write document to cache")?;
+        if let Some(memory) = &self.memory {
+            memory.invalidate(&doc, format);
+        }
+        self.journal(JournalOp::Store, Some((&doc, format)), Some(size))?;
+        #[cfg(feature = "index")]
+        self.index_stored(&doc, format)?;
+        self.revisions.note(&doc);
+        self.record_access(&doc);
+        if self.limit != CacheLimit::default() {
+            self.evict_except(Some(&doc))?;
+        }
+        Ok(())
+    }
+
+    /// Get the parsed structure of a cached text document
+    /// The stored parse result is reused unless the text or parser version changed
+    pub fn parsed_document(&self, doc: &DocumentType) -> crate::Result<Option<ParsedDocument>> {
+        if let Some(memory) = &self.memory {
+            let stored = self.document_metadata(doc, Format::Text);
+            if let Some(parsed) = memory.parsed(doc, stored) {
+                self.record_access(doc);
+                return Ok(Some(parsed));
+            }
         }
+        let Some(content) = profile::time(Phase::CacheIo, || self.read_shared(doc, Format::Text))
+        else {
+            return Ok(None);
+        };
+        let Ok(text) = std::str::from_utf8(&content) else {
+            return Ok(None);
+        };
+        self.record_access(doc);
+        let hash = content_hash(text);
+
+        let key = Self::parsed_key(doc);
+        let cached = self.read(&key).and_then(|json| {
+            let artifact = serde_json::from_slice::<ParsedArtifact>(&json).ok()?;
+            Some((artifact, json.len()))
+        });
+        if let Some((artifact, size)) =
+            cached.filter(|(a, _)| a.parse_version == PARSE_VERSION && a.content_hash == hash)
+        {
+            self.keep_parsed(doc, &artifact.document, size);
+            return Ok(Some(artifact.document));
+        }
+
+        let artifact = ParsedArtifact {
+            parse_version: PARSE_VERSION,
+            content_hash: hash,
+            document: ParsedDocument::parse(text),
+        };
+        let json = serde_json::to_string(&artifact).context("Failed to serialize parse result")?;
+        self.store
+            .put(&key, json.as_bytes())
+            .context("Failed to write parse result to cache")?;
+
+        self.keep_parsed(doc, &artifact.document, json.len());
+        Ok(Some(artifact.document))
+    }
+
+    /// Keep a parse result in memory next to its text, counting `size` bytes
+    /// (the length of its serialized form) towards the memory limit
+    fn keep_parsed(&self, doc: &DocumentType, parsed: &ParsedDocument, size: usize) {
+        if let Some(memory) = &self.memory {
+            memory.set_parsed(doc, parsed, size as u64);
+        }
+    }
 
-        fs::write(&path, content).context("Failed to write document to cache")?;
+    /// Get the cached errata of an RFC, however old
+    pub fn get_errata(&self, number: u32) -> Option<Vec<Erratum>> {
+        let json = self.read(&Self::errata_key(number))?;
+        serde_json::from_slice(&json).ok()
+    }
+
+    /// Get the cached errata of an RFC, unless they are older than a day
+    pub fn get_fresh_errata(&self, number: u32) -> Option<Vec<Erratum>> {
+        let json = self.read_fresh(&Self::errata_key(number), ERRATA_TTL)?;
+        serde_json::from_slice(&json).ok()
+    }
+
+    /// Store the errata of an RFC next to its documents
+    pub fn store_errata(&self, number: u32, errata: &[Erratum]) -> crate::Result<()> {
+        let json = serde_json::to_string(errata).context("Failed to serialize errata")?;
+        self.store
+            .put(&Self::errata_key(number), json.as_bytes())
+            .context("Failed to write errata to cache")?;
         Ok(())
     }
 
-    /// Clear all cached documents
-    pub fn clear_cache(&self) -> Result<()> {
-        if self.cache_dir.exists() {
-            fs::remove_dir_all(&self.cache_dir).context("Failed to clear cache")?;
-            fs::create_dir_all(&self.cache_dir).context("Failed to recreate cache directory")?;
+    /// Get the cached Datatracker metadata of a document, unless it is older than a day
+    pub fn get_metadata(&self, doc: &DocumentType) -> Option<DocumentMetadata> {
+        let json = self.read_fresh(&Self::metadata_key(doc), METADATA_TTL)?;
+        serde_json::from_slice(&json).ok()
+    }
+
+    /// Store the Datatracker metadata of a document
+    pub fn store_metadata(
+        &self,
+        doc: &DocumentType,
+        metadata: &DocumentMetadata,
+    ) -> crate::Result<()> {
+        let json = serde_json::to_string(metadata).context("Failed to serialize metadata")?;
+        self.store
+            .put(&Self::metadata_key(doc), json.as_bytes())
+            .context("Failed to write metadata to cache")?;
+        Ok(())
+    }
+
+    /// Get the cached bibxml reference of a document
+    pub fn get_bibxml(&self, doc: &DocumentType) -> Option<String> {
+        String::from_utf8(self.read(&Self::bibxml_key(doc))?).ok()
+    }
+
+    /// Store the bibxml reference of a document
+    pub fn store_bibxml(&self, doc: &DocumentType, xml: &str) -> crate::Result<()> {
+        self.store
+            .put(&Self::bibxml_key(doc), xml.as_bytes())
+            .context("Failed to write bibxml reference to cache")?;
+        Ok(())
+    }
+
+    /// Get a cached translation of a document, with the format it was stored in
+    pub fn get_translation(&self, doc: &DocumentType, language: &str) -> Option<(String, Format)> {
+        [Format::Text, Format::Html].into_iter().find_map(|format| {
+            let content = self.read(&Self::translation_key(doc, language, format))?;
+            Some((String::from_utf8(content).ok()?, format))
+        })
+    }
+
+    /// Store a translation of a document under its language
+    pub fn store_translation(
+        &self,
+        doc: &DocumentType,
+        language: &str,
+        format: Format,
+        content: &str,
+    ) -> crate::Result<()> {
+        if !crate::api::is_language_tag(language) {
+            return Err(anyhow::anyhow!("Invalid language '{}'", language).into());
         }
+        self.ensure_space(content.len() as u64)?;
+        self.store
+            .put(
+                &Self::translation_key(doc, language, format),
+                content.as_bytes(),
+            )
+            .context("Failed to write translation to cache")?;
         Ok(())
     }
 
+    /// Clear all cached documents
+    ///
+    /// Fails without removing anything if the cache directory isn't marked
+    /// as a cache (see [`with_dir`](Self::with_dir)).
+    pub fn clear_cache(&self) -> crate::Result<()> {
+        store::check_tag(&self.cache_dir)?;
+        if let Some(memory) = &self.memory {
+            memory.clear();
+        }
+        self.store.clear()?;
+        self.access.clear();
+        self.revisions.clear();
+        store::clear_dir(&self.cache_dir)?;
+        Ok(self.journal(JournalOp::Clear, None, None)?)
+    }
+
     /// Remove a specific document from cache
     /// Returns true if the document was found and removed
-    pub fn remove(&self, doc: &DocumentType) -> Result<bool> {
-        let html_path = self.document_path(doc, Format::Html);
-        let text_path = self.document_path(doc, Format::Text);
+    pub fn remove(&self, doc: &DocumentType) -> crate::Result<bool> {
+        Ok(self.remove_as(doc, JournalOp::Remove)?)
+    }
 
+    /// Remove a document, journaling the removal as `op`
+    pub(super) fn remove_as(&self, doc: &DocumentType, op: JournalOp) -> Result<bool> {
         let mut removed = false;
+        if let Some(memory) = &self.memory {
+            memory.remove(doc);
+        }
+
+        for format in Format::ALL {
+            let deleted = self
+                .store
+                .delete(&Self::document_key(doc, format))
+                .with_context(|| format!("Failed to remove cached {} file", format.extension()))?;
+            if deleted {
+                self.journal(op, Some((doc, format)), None)?;
+                removed = true;
+            }
+            let validators_path = self.validators_path(doc, format);
+            if validators_path.exists() {
+                fs::remove_file(&validators_path).context("Failed to remove HTTP metadata")?;
+            }
+        }
 
-        if html_path.exists() {
-            fs::remove_file(&html_path).context("Failed to remove cached HTML file")?;
-            removed = true;
+        let translations = self
+            .store
+            .list("translations/")
+            .context("Failed to list cached translations")?;
+        for format in [Format::Text, Format::Html] {
+            let file = format!("/{}.{}", doc.name(), format.extension());
+            for key in translations.iter().filter(|key| key.ends_with(&file)) {
+                self.store
+                    .delete(key)
+                    .context("Failed to remove cached translation")?;
+            }
         }
 
-        if text_path.exists() {
-            fs::remove_file(&text_path).context("Failed to remove cached text file")?;
-            removed = true;
+        self.store
+            .delete(&Self::bibxml_key(doc))
+            .context("Failed to remove cached bibxml reference")?;
+        self.store
+            .delete(&Self::parsed_key(doc))
+            .context("Failed to remove cached parse result")?;
+        self.store
+            .delete(&Self::metadata_key(doc))
+            .context("Failed to remove cached metadata")?;
+        if let DocumentType::Rfc(number) = doc {
+            self.store
+                .delete(&Self::errata_key(*number))
+                .context("Failed to remove cached errata")?;
         }
 
+        #[cfg(feature = "index")]
+        self.index()?.remove(doc)?;
+        self.forget_access(doc)?;
+        if removed && op == JournalOp::Evict {
+            self.hooks.emit(&HookEvent::Evicted { doc: doc.clone() });
+        }
         Ok(removed)
     }
 
-    /// List all cached documents
+    /// Compare the cached text and HTML renderings of a document
+    /// Returns None unless both renderings are cached
+    pub fn check_consistency(
+        &self,
+        doc: &DocumentType,
+    ) -> crate::Result<Option<ConsistencyReport>> {
+        let (Some(text), Some(html)) = (
+            self.peek_document(doc, Format::Text),
+            self.peek_document(doc, Format::Html),
+        ) else {
+            return Ok(None);
+        };
+
+        let html_text = html_to_text(&html, 80)?;
+        Ok(Some(compare_renderings(&text, &html_text)))
+    }
+
+    /// List the formats in which a document is cached
+    pub fn cached_formats(&self, doc: &DocumentType) -> Vec<Format> {
+        Format::ALL
+            .into_iter()
+            .filter(|&format| self.document_metadata(doc, format).is_some())
+            .collect()
+    }
+
+    /// List all cached documents, sorted
     pub fn list_cached(&self) -> Vec<DocumentType> {
-        let docs_dir = self.cache_dir.join("documents");
-        if !docs_dir.exists() {
-            return Vec::new();
+        #[cfg(feature = "index")]
+        if let Ok(documents) = self.index().and_then(|index| index.documents()) {
+            return documents;
         }
+        self.scan_cached()
+    }
 
+    /// List all cached documents by listing the documents of the store
+    pub(super) fn scan_cached(&self) -> Vec<DocumentType> {
         let mut documents = Vec::new();
 
-        if let Ok(entries) = fs::read_dir(&docs_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                    if let Some(doc_type) = DocumentType::parse(stem) {
-                        if !documents.contains(&doc_type) {
-                            documents.push(doc_type);
-                        }
+        for key in self.store.list("documents/").unwrap_or_default() {
+            if let Some(stem) = Path::new(&key).file_stem().and_then(|s| s.to_str()) {
+                if let Some(doc_type) = DocumentType::parse(stem) {
+                    if !documents.contains(&doc_type) {
+                        documents.push(doc_type);
                     }
                 }
             }
         }
 
+        documents.sort();
         documents
     }
 
+    /// Get the total size in bytes of all files in the cache
+    pub fn total_size(&self) -> u64 {
+        fn dir_size(dir: &Path) -> u64 {
+            let Ok(entries) = fs::read_dir(dir) else {
+                return 0;
+            };
+            entries
+                .flatten()
+                .map(|e| match e.metadata() {
+                    Ok(m) if m.is_dir() => dir_size(&e.path()),
+                    Ok(m) => m.len(),
+                    Err(_) => 0,
+                })
+                .sum()
+        }
+        // A store outside the cache directory is counted entry by entry
+        let local = self
+            .store
+            .local_path("")
+            .is_some_and(|root| root.starts_with(&self.cache_dir));
+        let stored = match local {
+            true => 0,
+            false => self
+                .store
+                .list("")
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|key| self.store.metadata(key).ok().flatten())
+                .map(|metadata| metadata.size)
+                .sum(),
+        };
+        dir_size(&self.cache_dir) + stored
+    }
+
     /// Get the cache directory path
     pub fn cache_dir(&self) -> &Path {
         &self.cache_dir
     }
 
-    /// Get the path for a cached document
-    fn document_path(&self, doc: &DocumentType, format: Format) -> PathBuf {
-        self.cache_dir
-            .join("documents")
-            .join(format!("{}.{}", doc.name(), format.extension()))
+    /// Get the path of a cached document in the default file store
+    #[cfg(test)]
+    pub(crate) fn document_path(&self, doc: &DocumentType, format: Format) -> PathBuf {
+        self.cache_dir.join(Self::document_key(doc, format))
+    }
+
+    /// Get the store key of a cached document (e.g., "documents/rfc9000.txt")
+    pub(crate) fn document_key(doc: &DocumentType, format: Format) -> String {
+        format!("documents/{}.{}", doc.name(), format.extension())
+    }
+
+    /// Open a cached document for reading without loading it whole or recording an access
+    pub(crate) fn open_document(
+        &self,
+        doc: &DocumentType,
+        format: Format,
+    ) -> Option<Box<dyn std::io::Read + Send>> {
+        self.store
+            .open(&Self::document_key(doc, format))
+            .ok()
+            .flatten()
+    }
+
+    /// Read a cached document without recording an access
+    pub(crate) fn read_document(&self, doc: &DocumentType, format: Format) -> Option<Vec<u8>> {
+        self.read_shared(doc, format).map(Arc::unwrap_or_clone)
+    }
+
+    /// Read a cached document without recording an access, sharing the copy in memory
+    ///
+    /// For callers that only borrow the content, which [`Self::read_document`]
+    /// would otherwise copy out of the memory layer on every hit.
+    pub(crate) fn read_shared(&self, doc: &DocumentType, format: Format) -> Option<Arc<Vec<u8>>> {
+        let key = Self::document_key(doc, format);
+        let Some(memory) = &self.memory else {
+            return self.read(&key).map(Arc::new);
+        };
+        // Taken before reading, so a concurrent write is noticed on the next read
+        let stored = self.document_metadata(doc, format);
+        if let Some(content) = memory.get(doc, format, stored) {
+            return Some(content);
+        }
+        let content = Arc::new(self.read(&key)?);
+        if let Some(stored) = stored {
+            memory.insert(doc, format, content.clone(), stored);
+        }
+        Some(content)
+    }
+
+    /// Get the size and modification time of a cached document
+    pub(crate) fn document_metadata(
+        &self,
+        doc: &DocumentType,
+        format: Format,
+    ) -> Option<EntryMetadata> {
+        self.store
+            .metadata(&Self::document_key(doc, format))
+            .ok()
+            .flatten()
+    }
+
+    /// Read an entry of the store, treating errors as a miss
+    fn read(&self, key: &str) -> Option<Vec<u8>> {
+        self.store.get(key).ok().flatten()
+    }
+
+    /// Read an entry of the store written less than `ttl` ago
+    fn read_fresh(&self, key: &str, ttl: Duration) -> Option<Vec<u8>> {
+        let stored = self.store.metadata(key).ok().flatten()?;
+        if stored.modified.elapsed().unwrap_or_default() > ttl {
+            return None;
+        }
+        self.read(key)
+    }
+
+    /// Get the store key of a cached translation (e.g., "translations/ja/rfc9000.html")
+    fn translation_key(doc: &DocumentType, language: &str, format: Format) -> String {
+        format!(
+            "translations/{}/{}.{}",
+            language.to_ascii_lowercase(),
+            doc.name(),
+            format.extension()
+        )
+    }
+
+    /// Get the store key of a document's cached Datatracker metadata
+    fn metadata_key(doc: &DocumentType) -> String {
+        format!("metadata/{}.json", doc.name())
+    }
+
+    /// Get the store key of an RFC's cached errata
+    fn errata_key(number: u32) -> String {
+        format!("errata/rfc{}.json", number)
+    }
+
+    /// Get the store key of a document's cached bibxml reference (e.g., "bibxml/rfc9000.xml")
+    fn bibxml_key(doc: &DocumentType) -> String {
+        format!("bibxml/{}.xml", doc.name())
+    }
+
+    /// Get the store key of a document's cached parse result
+    fn parsed_key(doc: &DocumentType) -> String {
+        format!("parsed/{}.json", doc.name())
     }
 }
 
+/// Stable 64-bit FNV-1a hash of document content, as hex
+fn content_hash(content: &str) -> String {
+    let hash = content.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::MemoryStore;
     use tempfile::TempDir;
 
     fn test_cache() -> (CacheManager, TempDir) {
@@ -159,6 +830,12 @@ mod tests {
 
         let cached = cache.list_cached();
         assert_eq!(cached.len(), 2);
+        let metadata_size: u64 = ["access.json", "index.sqlite3", "CACHEDIR.TAG"]
+            .iter()
+            .filter_map(|name| fs::metadata(cache.cache_dir().join(name)).ok())
+            .map(|m| m.len())
+            .sum();
+        assert_eq!(cache.total_size(), 8 + metadata_size);
     }
 
     #[test]
@@ -171,6 +848,24 @@ mod tests {
 
         cache.clear_cache().unwrap();
         assert!(cache.get_document(&doc, Format::Html).is_none());
+        assert!(cache.cache_dir().join("CACHEDIR.TAG").is_file());
+    }
+
+    #[test]
+    fn test_clear_cache_needs_tag() {
+        // A directory that already held other files isn't claimed as a cache
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("Cargo.toml"), "[package]").unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        cache
+            .store_document(&DocumentType::Rfc(9000), Format::Text, "QUIC")
+            .unwrap();
+
+        assert!(cache.clear_cache().is_err());
+        assert!(temp.path().join("Cargo.toml").is_file());
+        assert!(cache
+            .get_document(&DocumentType::Rfc(9000), Format::Text)
+            .is_some());
     }
 
     #[test]
@@ -214,6 +909,279 @@ mod tests {
         assert!(cache.get_document(&doc, Format::Html).is_none());
     }
 
+    #[test]
+    fn test_cached_formats() {
+        let (cache, _temp) = test_cache();
+        let doc = DocumentType::Rfc(9000);
+
+        assert!(cache.cached_formats(&doc).is_empty());
+
+        cache.store_document(&doc, Format::Html, "html").unwrap();
+        assert_eq!(cache.cached_formats(&doc), vec![Format::Html]);
+
+        cache.store_document(&doc, Format::Text, "text").unwrap();
+        assert_eq!(cache.cached_formats(&doc), vec![Format::Text, Format::Html]);
+    }
+
+    #[test]
+    fn test_store_xml() {
+        let (cache, temp) = test_cache();
+        let doc = DocumentType::Rfc(9000);
+
+        cache
+            .store_document(&doc, Format::Xml, "<rfc number=\"9000\"/>")
+            .unwrap();
+        assert!(temp.path().join("documents/rfc9000.xml").exists());
+        assert_eq!(cache.cached_formats(&doc), vec![Format::Xml]);
+        assert_eq!(cache.list_cached(), vec![doc.clone()]);
+
+        assert!(cache.remove(&doc).unwrap());
+        assert!(cache.get_document(&doc, Format::Xml).is_none());
+    }
+
+    #[test]
+    fn test_store_binary() {
+        let (cache, _temp) = test_cache();
+        let doc = DocumentType::Rfc(9000);
+        let pdf = b"%PDF-1.7\n\xff\xd8\x00\x9c".to_vec();
+
+        cache.store_bytes(&doc, Format::Pdf, &pdf).unwrap();
+        assert_eq!(cache.get_bytes(&doc, Format::Pdf), Some(pdf));
+        assert!(cache.get_document(&doc, Format::Pdf).is_none());
+        assert_eq!(cache.cached_formats(&doc), vec![Format::Pdf]);
+
+        assert!(cache.remove(&doc).unwrap());
+        assert!(cache.get_bytes(&doc, Format::Pdf).is_none());
+    }
+
+    #[test]
+    fn test_check_consistency() {
+        let (cache, _temp) = test_cache();
+        let doc = DocumentType::Rfc(9000);
+        let body = "QUIC is a secure general-purpose transport protocol that provides streams.";
+
+        cache.store_document(&doc, Format::Text, body).unwrap();
+        assert!(cache.check_consistency(&doc).unwrap().is_none());
+
+        let html = format!("<html><body><p>{}</p></body></html>", body);
+        cache.store_document(&doc, Format::Html, &html).unwrap();
+        let report = cache.check_consistency(&doc).unwrap().unwrap();
+        assert!(report.is_consistent());
+    }
+
+    #[test]
+    fn test_parsed_document() {
+        let (cache, _temp) = test_cache();
+        let doc = DocumentType::Rfc(9000);
+
+        assert!(cache.parsed_document(&doc).unwrap().is_none());
+
+        cache
+            .store_document(&doc, Format::Text, "1.  Overview\n\n   Text.\n")
+            .unwrap();
+        let parsed = cache.parsed_document(&doc).unwrap().unwrap();
+        assert_eq!(parsed.sections.len(), 1);
+        assert!(cache.read(&CacheManager::parsed_key(&doc)).is_some());
+
+        // Changing the content invalidates the stored parse result
+        cache
+            .store_document(
+                &doc,
+                Format::Text,
+                "1.  Overview\n\n   Text.\n\n2.  Streams\n\n   Text.\n",
+            )
+            .unwrap();
+        let parsed = cache.parsed_document(&doc).unwrap().unwrap();
+        assert_eq!(parsed.sections.len(), 2);
+        assert_eq!(parsed.toc.flatten().len(), 2);
+
+        // Removing the document also removes its parse result
+        cache.remove(&doc).unwrap();
+        assert!(cache.read(&CacheManager::parsed_key(&doc)).is_none());
+    }
+
+    #[test]
+    fn test_metadata() {
+        let (cache, temp) = test_cache();
+        let doc = DocumentType::Draft("draft-ietf-quic-qlog".to_string());
+        let metadata: DocumentMetadata = serde_json::from_value(serde_json::json!({
+            "name": "draft-ietf-quic-qlog",
+            "doc_type": { "Draft": "draft-ietf-quic-qlog" },
+            "title": "qlog",
+            "authors": [],
+        }))
+        .unwrap();
+
+        assert!(cache.get_metadata(&doc).is_none());
+        cache.store_metadata(&doc, &metadata).unwrap();
+        assert_eq!(cache.get_metadata(&doc), Some(metadata.clone()));
+
+        // Metadata older than a day is queried again
+        let file = fs::File::options()
+            .write(true)
+            .open(temp.path().join("metadata/draft-ietf-quic-qlog.json"))
+            .unwrap();
+        file.set_modified(std::time::SystemTime::now() - METADATA_TTL * 2)
+            .unwrap();
+        assert!(cache.get_metadata(&doc).is_none());
+
+        cache.store_metadata(&doc, &metadata).unwrap();
+        cache.remove(&doc).unwrap();
+        assert!(cache.get_metadata(&doc).is_none());
+    }
+
+    #[test]
+    fn test_errata() {
+        let (cache, temp) = test_cache();
+        let erratum = Erratum {
+            id: 7392,
+            rfc: 9000,
+            kind: crate::models::ErratumType::Technical,
+            status: crate::models::ErratumStatus::Verified,
+            section: Some("19.8".to_string()),
+            original_text: None,
+            corrected_text: None,
+            notes: None,
+            submitted: None,
+            submitter: None,
+        };
+
+        assert!(cache.get_errata(9000).is_none());
+        cache
+            .store_errata(9000, std::slice::from_ref(&erratum))
+            .unwrap();
+        assert_eq!(cache.get_errata(9000), Some(vec![erratum.clone()]));
+        assert_eq!(cache.get_fresh_errata(9000), Some(vec![erratum.clone()]));
+
+        // Errata older than a day are still there, but not fresh
+        let file = fs::File::options()
+            .write(true)
+            .open(temp.path().join("errata/rfc9000.json"))
+            .unwrap();
+        file.set_modified(std::time::SystemTime::now() - ERRATA_TTL * 2)
+            .unwrap();
+        assert!(cache.get_fresh_errata(9000).is_none());
+        assert_eq!(cache.get_errata(9000), Some(vec![erratum]));
+
+        cache.remove(&DocumentType::Rfc(9000)).unwrap();
+        assert!(cache.get_errata(9000).is_none());
+    }
+
+    #[test]
+    fn test_bibxml() {
+        let (cache, _temp) = test_cache();
+        let doc = DocumentType::Rfc(9000);
+        let xml = "<reference anchor=\"RFC9000\"/>";
+
+        assert!(cache.get_bibxml(&doc).is_none());
+        cache.store_bibxml(&doc, xml).unwrap();
+        assert_eq!(cache.get_bibxml(&doc).as_deref(), Some(xml));
+
+        cache.remove(&doc).unwrap();
+        assert!(cache.get_bibxml(&doc).is_none());
+    }
+
+    #[test]
+    fn test_translations() {
+        let (cache, _temp) = test_cache();
+        let doc = DocumentType::Rfc(9000);
+
+        assert!(cache.get_translation(&doc, "ja").is_none());
+        cache
+            .store_translation(&doc, "ja", Format::Html, "<p>QUIC</p>")
+            .unwrap();
+        assert_eq!(
+            cache.get_translation(&doc, "JA"),
+            Some(("<p>QUIC</p>".to_string(), Format::Html))
+        );
+        assert!(cache.get_translation(&doc, "de").is_none());
+        assert!(cache
+            .store_translation(&doc, "../ja", Format::Text, "x")
+            .is_err());
+
+        cache.remove(&doc).unwrap();
+        assert!(cache.get_translation(&doc, "ja").is_none());
+    }
+
+    #[test]
+    fn test_custom_store() {
+        let (cache, temp) = test_cache();
+        let cache = cache.with_store(MemoryStore::new());
+        let doc = DocumentType::Rfc(9000);
+
+        cache.store_document(&doc, Format::Text, "QUIC").unwrap();
+        cache.store_errata(9000, &[]).unwrap();
+        assert_eq!(cache.get_document(&doc, Format::Text).unwrap(), "QUIC");
+        assert_eq!(cache.list_cached(), vec![doc.clone()]);
+        assert_eq!(
+            cache.store().list("").unwrap(),
+            vec!["documents/rfc9000.txt", "errata/rfc9000.json"]
+        );
+
+        // Streamed documents are staged in the store too
+        let mut staged = cache.stage(&doc, Format::Html, 0).unwrap();
+        staged.write_all(b"<p>QUIC</p>").unwrap();
+        cache.install(staged).unwrap();
+        assert_eq!(
+            cache.get_document(&doc, Format::Html).unwrap(),
+            "<p>QUIC</p>"
+        );
+        // Nothing but bookkeeping is written to the cache directory
+        assert!(!temp.path().join("documents").exists());
+
+        assert!(cache.remove(&doc).unwrap());
+        assert!(cache.store().list("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_memory_layer() {
+        let (cache, temp) = test_cache();
+        let cache = cache.with_memory_limit(1024 * 1024);
+        let doc = DocumentType::Rfc(9000);
+
+        cache
+            .store_document(&doc, Format::Text, "1. Introduction\n")
+            .unwrap();
+        assert_eq!(cache.memory_size(), 0);
+        assert!(cache.parsed_document(&doc).unwrap().is_some());
+        // The text and the serialized size of its parse result
+        let parsed_size = cache.read(&CacheManager::parsed_key(&doc)).unwrap().len() as u64;
+        assert_eq!(cache.memory_size(), 16 + parsed_size);
+        assert_eq!(
+            cache.get_document(&doc, Format::Text).unwrap(),
+            "1. Introduction\n"
+        );
+
+        // A copy replaced behind the manager's back is read again
+        fs::write(temp.path().join("documents/rfc9000.txt"), "2. Overview\n").unwrap();
+        assert_eq!(
+            cache.get_document(&doc, Format::Text).unwrap(),
+            "2. Overview\n"
+        );
+        let parsed = cache.parsed_document(&doc).unwrap().unwrap();
+        assert_eq!(parsed, ParsedDocument::parse("2. Overview\n"));
+
+        // Writes through the manager replace the copy in memory
+        cache
+            .store_document(&doc, Format::Text, "3. Details\n")
+            .unwrap();
+        assert_eq!(
+            cache.get_document(&doc, Format::Text).unwrap(),
+            "3. Details\n"
+        );
+
+        cache.remove(&doc).unwrap();
+        assert_eq!(cache.memory_size(), 0);
+        assert!(cache.get_document(&doc, Format::Text).is_none());
+    }
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(content_hash(""), "cbf29ce484222325");
+        assert_eq!(content_hash("a"), "af63dc4c8601ec8c");
+        assert_ne!(content_hash("rfc9000"), content_hash("rfc9001"));
+    }
+
     #[test]
     fn test_list_cached_with_drafts() {
         let (cache, _temp) = test_cache();
@@ -0,0 +1,475 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::{Cursor, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use anyhow::Context;
+use tempfile::NamedTempFile;
+
+use crate::Result;
+
+/// File marking a directory as a cache, see <https://bford.info/cachedir/>
+const CACHE_TAG: &str = "CACHEDIR.TAG";
+
+const CACHE_TAG_CONTENT: &str = "Signature: 8a477f597d28d172789f06886806bc55
+# This directory is a document cache of rfc; clearing the cache removes
+# everything in it but this file.
+";
+
+/// Create a directory and mark it as a cache
+pub(super) fn tag_dir(dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir).context("Failed to create cache directory")?;
+    let tag = dir.join(CACHE_TAG);
+    if !tag.exists() {
+        fs::write(&tag, CACHE_TAG_CONTENT).context("Failed to mark cache directory")?;
+    }
+    Ok(())
+}
+
+/// Create a directory and mark it as a cache if it is new or empty
+pub(super) fn claim_dir(dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir).context("Failed to create cache directory")?;
+    if is_empty(dir)? {
+        tag_dir(dir)?;
+    }
+    Ok(())
+}
+
+/// Fail unless a directory is missing, empty or marked as a cache
+pub(super) fn check_tag(dir: &Path) -> Result<()> {
+    if !dir.exists() || dir.join(CACHE_TAG).is_file() || is_empty(dir)? {
+        return Ok(());
+    }
+    Err(anyhow::anyhow!(
+        "Refusing to clear {}: it isn't marked as a cache (no {})",
+        dir.display(),
+        CACHE_TAG
+    )
+    .into())
+}
+
+/// Remove everything in a directory marked as a cache, except the mark
+pub(super) fn clear_dir(dir: &Path) -> Result<()> {
+    check_tag(dir)?;
+    if !dir.exists() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir).context("Failed to clear cache")? {
+        let entry = entry.context("Failed to clear cache")?;
+        if entry.file_name() == CACHE_TAG {
+            continue;
+        }
+        let path = entry.path();
+        let removed = if entry.file_type().context("Failed to clear cache")?.is_dir() {
+            fs::remove_dir_all(&path)
+        } else {
+            fs::remove_file(&path)
+        };
+        match removed {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(anyhow::Error::new(e)
+                    .context(format!("Failed to remove {}", path.display()))
+                    .into())
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_empty(dir: &Path) -> Result<bool> {
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read cache directory {}", dir.display()))?;
+    Ok(entries.next().is_none())
+}
+
+/// Size and modification time of a stored entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntryMetadata {
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+/// An entry being written, see [`CacheStore::stage`]
+#[derive(Debug)]
+pub enum StagedEntry {
+    /// A temporary file next to the entry, moved into place when committed
+    File(NamedTempFile),
+    /// Content buffered in memory, for stores without local files
+    Memory(Vec<u8>),
+}
+
+impl StagedEntry {
+    /// Read back what was written so far
+    pub fn content(&self) -> Result<Vec<u8>> {
+        match self {
+            StagedEntry::File(file) => {
+                Ok(fs::read(file.path()).context("Failed to read staged entry")?)
+            }
+            StagedEntry::Memory(content) => Ok(content.clone()),
+        }
+    }
+
+    fn into_content(self) -> Result<Vec<u8>> {
+        match self {
+            StagedEntry::File(file) => {
+                Ok(fs::read(file.path()).context("Failed to read staged entry")?)
+            }
+            StagedEntry::Memory(content) => Ok(content),
+        }
+    }
+}
+
+impl Write for StagedEntry {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            StagedEntry::File(file) => file.write(buf),
+            StagedEntry::Memory(content) => content.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            StagedEntry::File(file) => file.flush(),
+            StagedEntry::Memory(_) => Ok(()),
+        }
+    }
+}
+
+/// Where a [`CacheManager`](super::CacheManager) keeps documents and their
+/// derived artifacts (errata, bibxml references, translations, parse results)
+///
+/// Keys are relative paths with `/` separators, such as
+/// "documents/rfc9000.txt" or "errata/rfc9000.json". Implement this to keep
+/// the cache in S3, on a shared network store or in memory; bookkeeping
+/// (journal, access times, HTTP validators, provenance) stays in the cache
+/// directory.
+///
+/// Entries must be replaced as a whole, never rewritten in place.
+pub trait CacheStore: fmt::Debug + Send + Sync {
+    /// Get the content of an entry, or None if there is none
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Create or replace an entry
+    fn put(&self, key: &str, content: &[u8]) -> Result<()>;
+
+    /// Remove an entry; returns false if there was none
+    fn delete(&self, key: &str) -> Result<bool>;
+
+    /// List the keys starting with `prefix` (e.g., "documents/"), sorted
+    fn list(&self, prefix: &str) -> Result<Vec<String>>;
+
+    /// Get the size and modification time of an entry, or None if there is none
+    fn metadata(&self, key: &str) -> Result<Option<EntryMetadata>>;
+
+    /// Start writing an entry, to be put in place with [`commit`](Self::commit)
+    ///
+    /// Content is buffered in memory unless the store stages it elsewhere,
+    /// e.g. in a temporary file next to the entry.
+    fn stage(&self, _key: &str) -> Result<StagedEntry> {
+        Ok(StagedEntry::Memory(Vec::new()))
+    }
+
+    /// Create or replace an entry with staged content
+    fn commit(&self, key: &str, staged: StagedEntry) -> Result<()> {
+        self.put(key, &staged.into_content()?)
+    }
+
+    /// Open an entry for reading, or None if there is none
+    ///
+    /// Stores keeping entries as files read them as they go instead of
+    /// loading them whole.
+    fn open(&self, key: &str) -> Result<Option<Box<dyn Read + Send>>> {
+        Ok(self
+            .get(key)?
+            .map(|content| Box::new(Cursor::new(content)) as Box<dyn Read + Send>))
+    }
+
+    /// Get the local file of an entry, for stores keeping entries as files
+    ///
+    /// The file may be memory-mapped, so a store returning one must replace
+    /// entries by renaming a new file over them, never by writing in place.
+    fn local_path(&self, _key: &str) -> Option<PathBuf> {
+        None
+    }
+
+    /// Remove every entry
+    fn clear(&self) -> Result<()> {
+        for key in self.list("")? {
+            self.delete(&key)?;
+        }
+        Ok(())
+    }
+}
+
+/// Entries as files under a directory; the default store of the cache
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    root: PathBuf,
+}
+
+impl FileStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Get the directory holding the entries
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+
+    /// Create the directory of an entry, marking a new root as a cache
+    fn create_parent(&self, path: &Path) -> Result<()> {
+        if !self.root.exists() {
+            claim_dir(&self.root)?;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create cache directory")?;
+        }
+        Ok(())
+    }
+}
+
+impl CacheStore for FileStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        match fs::read(self.path(key)) {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(anyhow::Error::new(e)
+                .context(format!("Failed to read {} from cache", key))
+                .into()),
+        }
+    }
+
+    fn put(&self, key: &str, content: &[u8]) -> Result<()> {
+        let path = self.path(key);
+        self.create_parent(&path)?;
+        // Write next to the entry and rename over it, so readers (and memory
+        // mappings) of the previous content are never handed a partial entry
+        let dir = path.parent().unwrap_or(&self.root);
+        let mut temp = NamedTempFile::new_in(dir).context("Failed to write to cache")?;
+        temp.write_all(content)
+            .context("Failed to write to cache")?;
+        self.commit(key, StagedEntry::File(temp))
+    }
+
+    fn stage(&self, key: &str) -> Result<StagedEntry> {
+        let path = self.path(key);
+        self.create_parent(&path)?;
+        let dir = path.parent().unwrap_or(&self.root);
+        let temp = NamedTempFile::new_in(dir).context("Failed to write to cache")?;
+        Ok(StagedEntry::File(temp))
+    }
+
+    fn commit(&self, key: &str, staged: StagedEntry) -> Result<()> {
+        let mut file = match staged {
+            StagedEntry::File(file) => file,
+            StagedEntry::Memory(content) => return self.put(key, &content),
+        };
+        let path = self.path(key);
+        self.create_parent(&path)?;
+        file.flush().context("Failed to write to cache")?;
+        file.persist(&path)
+            .with_context(|| format!("Failed to write {} to cache", key))?;
+        Ok(())
+    }
+
+    fn open(&self, key: &str) -> Result<Option<Box<dyn Read + Send>>> {
+        match fs::File::open(self.path(key)) {
+            Ok(file) => Ok(Some(Box::new(file))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(anyhow::Error::new(e)
+                .context(format!("Failed to read {} from cache", key))
+                .into()),
+        }
+    }
+
+    fn delete(&self, key: &str) -> Result<bool> {
+        match fs::remove_file(self.path(key)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(anyhow::Error::new(e)
+                .context(format!("Failed to remove {} from cache", key))
+                .into()),
+        }
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        fn walk(dir: &Path, relative: &str, keys: &mut Vec<String>) {
+            for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                let key = format!("{}{}", relative, name);
+                if key == CACHE_TAG {
+                    continue;
+                }
+                match entry.file_type() {
+                    Ok(kind) if kind.is_dir() => walk(&entry.path(), &format!("{}/", key), keys),
+                    Ok(_) => keys.push(key),
+                    Err(_) => {}
+                }
+            }
+        }
+        // Only walk the directory the prefix is in
+        let dir = prefix.rfind('/').map_or("", |i| &prefix[..=i]);
+        let mut keys = Vec::new();
+        walk(&self.path(dir), dir, &mut keys);
+        keys.retain(|key| key.starts_with(prefix));
+        keys.sort();
+        Ok(keys)
+    }
+
+    fn metadata(&self, key: &str) -> Result<Option<EntryMetadata>> {
+        match fs::metadata(self.path(key)) {
+            Ok(metadata) => Ok(Some(EntryMetadata {
+                size: metadata.len(),
+                modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            })),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(anyhow::Error::new(e)
+                .context(format!("Failed to read metadata of {}", key))
+                .into()),
+        }
+    }
+
+    fn local_path(&self, key: &str) -> Option<PathBuf> {
+        Some(self.path(key))
+    }
+
+    fn clear(&self) -> Result<()> {
+        clear_dir(&self.root)
+    }
+}
+
+/// Entries in memory, e.g. for tests; nothing outlives the store
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    entries: Mutex<BTreeMap<String, (Vec<u8>, SystemTime)>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CacheStore for MemoryStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let entries = self.entries.lock().unwrap();
+        Ok(entries.get(key).map(|(content, _)| content.clone()))
+    }
+
+    fn put(&self, key: &str, content: &[u8]) -> Result<()> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(key.to_string(), (content.to_vec(), SystemTime::now()));
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<bool> {
+        Ok(self.entries.lock().unwrap().remove(key).is_some())
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        let entries = self.entries.lock().unwrap();
+        Ok(entries
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect())
+    }
+
+    fn metadata(&self, key: &str) -> Result<Option<EntryMetadata>> {
+        let entries = self.entries.lock().unwrap();
+        Ok(entries.get(key).map(|(content, modified)| EntryMetadata {
+            size: content.len() as u64,
+            modified: *modified,
+        }))
+    }
+
+    fn clear(&self) -> Result<()> {
+        self.entries.lock().unwrap().clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Exercise the behavior every store must share
+    fn check_store(store: &dyn CacheStore) {
+        assert_eq!(store.get("documents/rfc9000.txt").unwrap(), None);
+        store.put("documents/rfc9000.txt", b"QUIC").unwrap();
+        store.put("documents/rfc9000.html", b"<p>QUIC</p>").unwrap();
+        store.put("errata/rfc9000.json", b"[]").unwrap();
+        store.put("documents/rfc9000.txt", b"QUIC v1").unwrap();
+
+        assert_eq!(
+            store.get("documents/rfc9000.txt").unwrap().as_deref(),
+            Some(&b"QUIC v1"[..])
+        );
+        assert_eq!(
+            store.list("documents/").unwrap(),
+            vec!["documents/rfc9000.html", "documents/rfc9000.txt"]
+        );
+        assert_eq!(
+            store.list("errata/rfc9").unwrap(),
+            vec!["errata/rfc9000.json"]
+        );
+        assert_eq!(store.list("").unwrap().len(), 3);
+        assert_eq!(
+            store
+                .metadata("documents/rfc9000.txt")
+                .unwrap()
+                .unwrap()
+                .size,
+            7
+        );
+        assert!(store.metadata("documents/rfc1.txt").unwrap().is_none());
+
+        let mut staged = store.stage("documents/rfc9001.txt").unwrap();
+        staged.write_all(b"QUIC-TLS").unwrap();
+        assert_eq!(staged.content().unwrap(), b"QUIC-TLS");
+        assert!(store.get("documents/rfc9001.txt").unwrap().is_none());
+        store.commit("documents/rfc9001.txt", staged).unwrap();
+        let mut opened = String::new();
+        store
+            .open("documents/rfc9001.txt")
+            .unwrap()
+            .unwrap()
+            .read_to_string(&mut opened)
+            .unwrap();
+        assert_eq!(opened, "QUIC-TLS");
+        assert!(store.open("documents/rfc1.txt").unwrap().is_none());
+        assert!(store.delete("documents/rfc9001.txt").unwrap());
+
+        assert!(store.delete("documents/rfc9000.html").unwrap());
+        assert!(!store.delete("documents/rfc9000.html").unwrap());
+        store.clear().unwrap();
+        assert!(store.list("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_file_store() {
+        let temp = TempDir::new().unwrap();
+        let store = FileStore::new(temp.path().join("cache"));
+        check_store(&store);
+        assert_eq!(
+            store.local_path("documents/rfc9000.txt"),
+            Some(temp.path().join("cache/documents/rfc9000.txt"))
+        );
+    }
+
+    #[test]
+    fn test_memory_store() {
+        check_store(&MemoryStore::new());
+    }
+}
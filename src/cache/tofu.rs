@@ -0,0 +1,232 @@
+use std::fmt;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::bundle::sha256_hex;
+use super::{CacheManager, StagedDocument};
+use crate::models::{DocumentType, Format};
+
+/// Removed and added lines shown in the diff of a changed document
+const DIFF_LINES: usize = 20;
+
+/// Check if a document never changes once published, so a re-fetch must match the first copy
+///
+/// The plain text of an RFC is immutable; HTML and XML renderings are
+/// regenerated by the RFC Editor from time to time, and drafts get new
+/// revisions under the same name.
+pub fn is_immutable(doc: &DocumentType, format: Format) -> bool {
+    matches!((doc, format), (DocumentType::Rfc(_), Format::Text))
+}
+
+/// An immutable document whose content differs from the copy first fetched
+///
+/// Points at a broken mirror, a proxy rewriting responses or tampering.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentChange {
+    pub doc: DocumentType,
+    pub format: Format,
+    /// URL the changed copy was fetched from
+    pub url: String,
+    /// SHA-256 of the copy trusted on first use, in hex
+    pub trusted_sha256: String,
+    /// SHA-256 of the changed copy, in hex
+    pub sha256: String,
+    /// Changed lines against the cached copy, if the trusted copy is still cached
+    pub diff: Option<String>,
+}
+
+impl fmt::Display for ContentChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} from {} differs from the copy first fetched (SHA-256 {} instead of {}); \
+             published RFCs never change, so the source may be broken or tampered with",
+            self.doc, self.url, self.sha256, self.trusted_sha256
+        )
+    }
+}
+
+impl CacheManager {
+    /// Get the checksum a re-fetch of an immutable document has to match
+    ///
+    /// This is the checksum of the first copy recorded in the document's
+    /// provenance log, or of the last changed copy accepted with
+    /// [`accept_change`](Self::accept_change). Returns `None` for documents
+    /// that may change and for documents that were never fetched.
+    pub fn pinned_checksum(&self, doc: &DocumentType, format: Format) -> Option<String> {
+        if !is_immutable(doc, format) {
+            return None;
+        }
+        let log: Vec<_> = self
+            .provenance(doc)
+            .ok()?
+            .into_iter()
+            .filter(|record| record.format == format)
+            .collect();
+        log.iter()
+            .rfind(|record| record.accepted)
+            .or(log.first())
+            .map(|record| record.sha256.clone())
+    }
+
+    /// Check if the cached copy of a document is the pinned one
+    pub(crate) fn holds_pinned(&self, doc: &DocumentType, format: Format) -> bool {
+        match (
+            self.pinned_checksum(doc, format),
+            self.read_shared(doc, format),
+        ) {
+            (Some(pinned), Some(cached)) => sha256_hex(&cached) == pinned,
+            _ => false,
+        }
+    }
+
+    /// Put a changed copy of an immutable document aside, keeping the pinned copy
+    pub fn quarantine(
+        &self,
+        doc: &DocumentType,
+        format: Format,
+        content: &[u8],
+    ) -> crate::Result<()> {
+        Ok(self
+            .store()
+            .put(&Self::quarantine_key(doc, format), content)
+            .context("Failed to quarantine changed document")?)
+    }
+
+    /// Put a staged changed copy aside instead of installing it
+    pub(crate) fn quarantine_staged(&self, staged: StagedDocument) -> Result<()> {
+        let key = Self::quarantine_key(staged.doc(), staged.format());
+        self.store()
+            .commit(&key, staged.into_entry())
+            .context("Failed to quarantine changed document")
+    }
+
+    /// Get the changed copy of a document that was put aside, if any
+    pub fn quarantined(&self, doc: &DocumentType, format: Format) -> Option<Vec<u8>> {
+        self.store()
+            .get(&Self::quarantine_key(doc, format))
+            .ok()
+            .flatten()
+    }
+
+    /// Replace the pinned copy of a document with its quarantined changed copy
+    ///
+    /// The accepted copy becomes the one later fetches have to match.
+    /// Returns false if no changed copy was quarantined.
+    pub fn accept_change(&self, doc: &DocumentType, format: Format) -> crate::Result<bool> {
+        let Some(content) = self.quarantined(doc, format) else {
+            return Ok(false);
+        };
+        let sha256 = sha256_hex(&content);
+        let mut record = self
+            .provenance(doc)?
+            .into_iter()
+            .rfind(|record| record.format == format && record.sha256 == sha256)
+            .context("The quarantined copy has no provenance record")?;
+        record.time = chrono::Utc::now();
+        record.accepted = true;
+        self.store_bytes(doc, format, &content)?;
+        self.record_provenance(doc, &record)?;
+        self.store()
+            .delete(&Self::quarantine_key(doc, format))
+            .context("Failed to remove quarantined document")?;
+        Ok(true)
+    }
+
+    /// Get the store key of a quarantined copy (e.g., "quarantine/rfc9000.txt")
+    fn quarantine_key(doc: &DocumentType, format: Format) -> String {
+        format!("quarantine/{}.{}", doc.name(), format.extension())
+    }
+
+    /// Compare a freshly fetched copy of an immutable document with the pinned checksum
+    ///
+    /// Call this before the fetch is recorded and the copy stored, so the
+    /// diff is made against the trusted copy. Returns `None` if the copy
+    /// matches or nothing was pinned yet.
+    pub fn check_content(
+        &self,
+        doc: &DocumentType,
+        format: Format,
+        url: &str,
+        content: &[u8],
+    ) -> Option<ContentChange> {
+        let trusted_sha256 = self.pinned_checksum(doc, format)?;
+        let sha256 = sha256_hex(content);
+        if sha256 == trusted_sha256 {
+            return None;
+        }
+        let diff = self
+            .read_shared(doc, format)
+            .filter(|cached| sha256_hex(cached) == trusted_sha256)
+            .map(|cached| {
+                crate::analyze::diff_lines(
+                    &String::from_utf8_lossy(&cached),
+                    &String::from_utf8_lossy(content),
+                    DIFF_LINES,
+                )
+            });
+        Some(ContentChange {
+            doc: doc.clone(),
+            format,
+            url: url.to_string(),
+            trusted_sha256,
+            sha256,
+            diff,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{Provenance, Validators};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_check_content() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let doc = DocumentType::Rfc(9000);
+        let original = "QUIC\nA UDP-Based Multiplexed and Secure Transport\n";
+
+        assert_eq!(cache.pinned_checksum(&doc, Format::Text), None);
+        assert_eq!(cache.check_content(&doc, Format::Text, "u", b"x"), None);
+
+        cache.store_document(&doc, Format::Text, original).unwrap();
+        assert_eq!(cache.pinned_checksum(&doc, Format::Text), None);
+        let validators = Validators {
+            url: "https://www.rfc-editor.org/rfc/rfc9000.txt".to_string(),
+            etag: None,
+            last_modified: None,
+        };
+        let record = Provenance::new(Format::Text, 200, &validators, original.as_bytes());
+        cache.record_provenance(&doc, &record).unwrap();
+
+        let url = "https://mirror.example/rfc9000.txt";
+        assert_eq!(
+            cache.check_content(&doc, Format::Text, url, original.as_bytes()),
+            None
+        );
+        let altered = "QUIC\nA TCP-Based Transport\n";
+        let change = cache
+            .check_content(&doc, Format::Text, url, altered.as_bytes())
+            .unwrap();
+        assert_eq!(change.trusted_sha256, record.sha256);
+        assert_eq!(
+            change.diff.as_deref(),
+            Some(
+                "@@ -2,1 +2,1 @@\n-A UDP-Based Multiplexed and Secure Transport\n\
+                 +A TCP-Based Transport\n"
+            )
+        );
+        assert!(change
+            .to_string()
+            .starts_with("RFC 9000 from https://mirror"));
+
+        // Renderings and drafts may change
+        let draft = DocumentType::Draft("draft-ietf-quic-qlog".to_string());
+        assert!(!is_immutable(&draft, Format::Text));
+        assert!(!is_immutable(&doc, Format::Html));
+    }
+}
@@ -0,0 +1,92 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::CacheManager;
+use crate::models::{DocumentType, Format};
+
+/// HTTP validators of a cached document, used for conditional requests
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Validators {
+    /// URL the document was downloaded from
+    pub url: String,
+    /// Value of the ETag response header
+    pub etag: Option<String>,
+    /// Value of the Last-Modified response header
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    /// Whether there is anything to revalidate with
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+impl CacheManager {
+    /// Get the HTTP validators stored for a cached document
+    pub fn get_validators(&self, doc: &DocumentType, format: Format) -> Option<Validators> {
+        let json = fs::read_to_string(self.validators_path(doc, format)).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// Store the HTTP validators of a downloaded document
+    pub fn store_validators(
+        &self,
+        doc: &DocumentType,
+        format: Format,
+        validators: &Validators,
+    ) -> Result<()> {
+        let path = self.validators_path(doc, format);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create HTTP metadata directory")?;
+        }
+        let json = serde_json::to_string(validators).context("Failed to serialize validators")?;
+        fs::write(&path, json).context("Failed to write HTTP metadata")?;
+        #[cfg(feature = "index")]
+        self.index()?
+            .set_source(doc, format, &validators.url, validators.etag.as_deref())?;
+        Ok(())
+    }
+
+    /// Get the path of a document's HTTP metadata, kept apart from the documents
+    pub(super) fn validators_path(&self, doc: &DocumentType, format: Format) -> PathBuf {
+        self.cache_dir()
+            .join("http")
+            .join(format!("{}.{}.json", doc.name(), format.extension()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validators_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let doc = DocumentType::Rfc(9000);
+        let validators = Validators {
+            url: "https://www.rfc-editor.org/rfc/rfc9000.txt".to_string(),
+            etag: Some("\"5e3a-5c4f\"".to_string()),
+            last_modified: Some("Thu, 27 May 2021 19:42:12 GMT".to_string()),
+        };
+
+        assert!(cache.get_validators(&doc, Format::Text).is_none());
+        cache
+            .store_validators(&doc, Format::Text, &validators)
+            .unwrap();
+        assert_eq!(cache.get_validators(&doc, Format::Text), Some(validators));
+        assert!(cache.get_validators(&doc, Format::Html).is_none());
+
+        // Metadata is not a cached document
+        cache.store_document(&doc, Format::Text, "QUIC").unwrap();
+        assert_eq!(cache.list_cached(), vec![doc.clone()]);
+
+        cache.remove(&doc).unwrap();
+        assert!(cache.get_validators(&doc, Format::Text).is_none());
+    }
+}
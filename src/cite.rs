@@ -0,0 +1,510 @@
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Context;
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+
+use crate::api::{DataTrackerClient, DocumentFetcher, RfcIndex};
+use crate::cache::CacheManager;
+use crate::models::{Author, Document, DocumentType, RfcMetadata};
+use crate::Result;
+
+/// Month names as used in RFC Editor publication dates
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Look up a document and format its citation as a BibTeX entry
+///
+/// The entry matches those of the RFC Editor and Datatracker citation
+/// services; see [`Citation::lookup`] for where the metadata comes from.
+pub async fn bibtex(doc: &DocumentType) -> Result<String> {
+    let tracker = DataTrackerClient::new()?;
+    let fetcher = DocumentFetcher::new()?;
+    let cache = CacheManager::new()?;
+    Ok(Citation::lookup(doc, &tracker, &fetcher, &cache)
+        .await?
+        .to_bibtex())
+}
+
+/// Bibliographic data of one document, ready to be written as a citation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Citation {
+    pub doc: DocumentType,
+    pub title: String,
+    pub authors: Vec<Author>,
+    /// Publication month, 1-12
+    pub month: Option<u32>,
+    pub year: Option<i32>,
+    pub pages: Option<u32>,
+    pub doi: Option<String>,
+}
+
+impl Citation {
+    /// Create a citation with nothing but the document's label as title
+    pub fn new(doc: DocumentType) -> Self {
+        Self {
+            title: doc.to_string(),
+            doc,
+            authors: Vec::new(),
+            month: None,
+            year: None,
+            pages: None,
+            doi: None,
+        }
+    }
+
+    /// Create a citation from RFC Editor metadata
+    pub fn from_rfc(meta: &RfcMetadata) -> Self {
+        let (month, year) = meta
+            .pub_date
+            .as_deref()
+            .map(parse_pub_date)
+            .unwrap_or_default();
+        Self {
+            doc: DocumentType::Rfc(meta.number),
+            title: meta.title.clone(),
+            authors: meta.authors.clone(),
+            month,
+            year,
+            pages: meta.pages,
+            doi: Some(meta.doi()),
+        }
+    }
+
+    /// Create a citation from Datatracker metadata
+    pub fn from_document(doc: &Document) -> Self {
+        // Drafts are cited by revision
+        let doc_type = match (&doc.doc_type, &doc.rev) {
+            (DocumentType::Draft(name), Some(rev)) if doc.doc_type.draft_revision().is_none() => {
+                DocumentType::Draft(format!("{}-{}", name, rev))
+            }
+            (doc_type, _) => doc_type.clone(),
+        };
+        Self {
+            doi: match doc_type {
+                DocumentType::Rfc(number) => Some(
+                    RfcMetadata {
+                        number,
+                        ..Default::default()
+                    }
+                    .doi(),
+                ),
+                _ => None,
+            },
+            doc: doc_type,
+            title: doc.title.clone(),
+            authors: doc.authors.clone(),
+            month: doc.published.map(|d| d.month()),
+            year: doc.published.map(|d| d.year()),
+            pages: doc.pages,
+        }
+    }
+
+    /// Look up the metadata of a document and build its citation
+    ///
+    /// RFCs are cited from the RFC index synchronized into the cache, then
+    /// from the RFC Editor, then from the Datatracker; drafts and subseries
+    /// from the Datatracker. External specifications are cited by name.
+    pub async fn lookup(
+        doc: &DocumentType,
+        tracker: &DataTrackerClient,
+        fetcher: &DocumentFetcher,
+        cache: &CacheManager,
+    ) -> Result<Self> {
+        match doc {
+            DocumentType::Rfc(number) => {
+                let indexed = RfcIndex::cached(cache).and_then(|i| i.lookup(*number).cloned());
+                if let Some(meta) = indexed {
+                    return Ok(Self::from_rfc(&meta));
+                }
+                match fetcher.rfc_metadata(*number).await {
+                    Ok(meta) => Ok(Self::from_rfc(&meta)),
+                    Err(e) => match tracker.document(doc).await {
+                        Ok(document) => Ok(Self::from_document(&document)),
+                        Err(_) => Err(e),
+                    },
+                }
+            }
+            DocumentType::Draft(_) | DocumentType::Subseries(..) => {
+                Ok(Self::from_document(&tracker.document(doc).await?))
+            }
+            DocumentType::External { .. } => Ok(Self::new(doc.clone())),
+        }
+    }
+
+    /// Get the anchor used by xml2rfc (e.g., "RFC9000", "I-D.ietf-quic-transport")
+    pub fn anchor(&self) -> String {
+        match &self.doc {
+            DocumentType::Rfc(number) => format!("RFC{}", number),
+            DocumentType::Draft(_) => {
+                let name = self.draft_base().unwrap_or_default();
+                format!("I-D.{}", name.strip_prefix("draft-").unwrap_or(name))
+            }
+            other => other.name(),
+        }
+    }
+
+    /// Get the BibTeX key (e.g., "rfc9000", "ietf-quic-transport-34")
+    pub fn key(&self) -> String {
+        match &self.doc {
+            DocumentType::Draft(name) => name.strip_prefix("draft-").unwrap_or(name).to_string(),
+            other => other.name(),
+        }
+    }
+
+    /// Get the URL a citation points readers to
+    pub fn url(&self) -> String {
+        match &self.doc {
+            DocumentType::Rfc(number) => format!("https://www.rfc-editor.org/info/rfc{}", number),
+            DocumentType::Draft(name) => {
+                format!("https://datatracker.ietf.org/doc/html/{}", name)
+            }
+            other => other
+                .external_url()
+                .unwrap_or_else(|| other.datatracker_url()),
+        }
+    }
+
+    /// Format as a BibTeX entry, in the style of the RFC Editor's citations
+    pub fn to_bibtex(&self) -> String {
+        let mut fields: Vec<(&str, String)> = Vec::new();
+        let kind = match &self.doc {
+            DocumentType::Rfc(number) => {
+                fields.push(("series", "{Request for Comments}".to_string()));
+                fields.push(("number", number.to_string()));
+                fields.push(("howpublished", format!("{{RFC {}}}", number)));
+                fields.push(("publisher", "{RFC Editor}".to_string()));
+                "misc"
+            }
+            DocumentType::Draft(name) => {
+                fields.push(("number", format!("{{{}}}", name)));
+                fields.push(("type", "{Internet-Draft}".to_string()));
+                fields.push((
+                    "institution",
+                    "{Internet Engineering Task Force}".to_string(),
+                ));
+                fields.push(("publisher", "{Internet Engineering Task Force}".to_string()));
+                fields.push(("note", "{Work in Progress}".to_string()));
+                "techreport"
+            }
+            _ => "misc",
+        };
+        if let Some(doi) = &self.doi {
+            fields.push(("doi", format!("{{{}}}", doi)));
+        }
+        // The Datatracker cites drafts by the page of their revision
+        let url = match (&self.doc, self.doc.draft_revision()) {
+            (DocumentType::Draft(_), Some((base, rev))) => {
+                format!("https://datatracker.ietf.org/doc/{}/{:02}/", base, rev)
+            }
+            (DocumentType::Draft(name), None) => {
+                format!("https://datatracker.ietf.org/doc/{}/", name)
+            }
+            _ => self.url(),
+        };
+        fields.push(("url", format!("{{{}}}", url)));
+        if !self.authors.is_empty() {
+            let authors: Vec<String> = self
+                .authors
+                .iter()
+                .map(|a| bibtex_escape(bibtex_name(&a.name)))
+                .collect();
+            fields.push(("author", format!("{{{}}}", authors.join(" and "))));
+        }
+        // Double braces keep the capitalization of the title
+        fields.push(("title", format!("{{{{{}}}}}", bibtex_escape(&self.title))));
+        if let Some(pages) = self.pages {
+            fields.push(("pagetotal", pages.to_string()));
+        }
+        if let Some(year) = self.year {
+            fields.push(("year", year.to_string()));
+        }
+        if let Some(month) = self.month.and_then(month_name) {
+            fields.push(("month", month[..3].to_ascii_lowercase()));
+        }
+
+        let mut entry = format!("@{}{{{},\n", kind, self.key());
+        for (name, value) in fields {
+            entry.push_str(&format!("  {:<12} = {},\n", name, value));
+        }
+        entry.push('}');
+        entry
+    }
+
+    /// Format as an xml2rfc `<reference>` element, like the IETF bibxml service
+    pub fn to_bibxml(&self) -> String {
+        let mut xml = format!(
+            "<reference anchor=\"{}\" target=\"{}\">\n  <front>\n    <title>{}</title>\n",
+            xml_escape(&self.anchor()),
+            xml_escape(&self.url()),
+            xml_escape(&self.title)
+        );
+        for author in &self.authors {
+            xml.push_str(&format!(
+                "    <author fullname=\"{}\"/>\n",
+                xml_escape(&author.name)
+            ));
+        }
+        let month = self
+            .month
+            .and_then(month_name)
+            .map(|m| format!(" month=\"{}\"", m));
+        let year = self.year.map(|y| format!(" year=\"{}\"", y));
+        xml.push_str(&format!(
+            "    <date{}{}/>\n  </front>\n",
+            month.unwrap_or_default(),
+            year.unwrap_or_default()
+        ));
+        match &self.doc {
+            DocumentType::Rfc(number) => xml.push_str(&format!(
+                "  <seriesInfo name=\"RFC\" value=\"{}\"/>\n",
+                number
+            )),
+            DocumentType::Draft(name) => xml.push_str(&format!(
+                "  <seriesInfo name=\"Internet-Draft\" value=\"{}\"/>\n",
+                xml_escape(name)
+            )),
+            _ => {}
+        }
+        if let Some(doi) = &self.doi {
+            xml.push_str(&format!(
+                "  <seriesInfo name=\"DOI\" value=\"{}\"/>\n",
+                xml_escape(doi)
+            ));
+        }
+        xml.push_str("</reference>");
+        xml
+    }
+
+    /// Get the draft name without its revision
+    fn draft_base(&self) -> Option<&str> {
+        let DocumentType::Draft(name) = &self.doc else {
+            return None;
+        };
+        Some(
+            self.doc
+                .draft_revision()
+                .map_or(name.as_str(), |(base, _)| base),
+        )
+    }
+}
+
+/// File formats of a bibliography
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BibliographyFormat {
+    /// BibTeX entries, for LaTeX papers
+    Bibtex,
+    /// A `<references>` element of xml2rfc `<reference>`s, for draft builds
+    BibXml,
+}
+
+impl BibliographyFormat {
+    /// Choose the format by file extension: `.bib` or `.xml`
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "bib" => Some(BibliographyFormat::Bibtex),
+            "xml" => Some(BibliographyFormat::BibXml),
+            _ => None,
+        }
+    }
+}
+
+/// Write every citation into a single bibliography file
+pub fn write_bibliography<W: Write>(
+    citations: &[Citation],
+    format: BibliographyFormat,
+    mut out: W,
+) -> Result<()> {
+    match format {
+        BibliographyFormat::Bibtex => {
+            for citation in citations {
+                writeln!(out, "{}\n", citation.to_bibtex())
+                    .context("Failed to write bibliography")?;
+            }
+        }
+        BibliographyFormat::BibXml => {
+            writeln!(
+                out,
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<references>"
+            )
+            .context("Failed to write bibliography")?;
+            for citation in citations {
+                for line in citation.to_bibxml().lines() {
+                    writeln!(out, "  {}", line).context("Failed to write bibliography")?;
+                }
+            }
+            writeln!(out, "</references>").context("Failed to write bibliography")?;
+        }
+    }
+    out.flush().context("Failed to write bibliography")?;
+    Ok(())
+}
+
+fn month_name(month: u32) -> Option<&'static str> {
+    MONTHS.get((month as usize).checked_sub(1)?).copied()
+}
+
+/// Parse an RFC Editor publication date like "May 2021"
+fn parse_pub_date(date: &str) -> (Option<u32>, Option<i32>) {
+    let mut month = None;
+    let mut year = None;
+    for word in date.split_whitespace() {
+        if let Some(i) = MONTHS.iter().position(|m| m.eq_ignore_ascii_case(word)) {
+            month = Some(i as u32 + 1);
+        } else if let Ok(y) = word.parse() {
+            year = Some(y);
+        }
+    }
+    (month, year)
+}
+
+/// Drop the role suffix of an RFC Editor author ("J. Iyengar, Ed."), which
+/// BibTeX would read as "Last, First"
+fn bibtex_name(name: &str) -> &str {
+    name.strip_suffix(", Ed.")
+        .or_else(|| name.strip_suffix(", Eds."))
+        .unwrap_or(name)
+        .trim()
+}
+
+fn bibtex_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quic() -> Citation {
+        Citation::from_rfc(&RfcMetadata {
+            number: 9000,
+            title: "QUIC: A UDP-Based Multiplexed and Secure Transport".to_string(),
+            authors: vec![
+                Author::new("J. Iyengar, Ed."),
+                Author::new("M. Thomson, Ed."),
+            ],
+            pub_date: Some("May 2021".to_string()),
+            pages: Some(151),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_bibtex() {
+        assert_eq!(
+            quic().to_bibtex(),
+            "@misc{rfc9000,
+  series       = {Request for Comments},
+  number       = 9000,
+  howpublished = {RFC 9000},
+  publisher    = {RFC Editor},
+  doi          = {10.17487/RFC9000},
+  url          = {https://www.rfc-editor.org/info/rfc9000},
+  author       = {J. Iyengar and M. Thomson},
+  title        = {{QUIC: A UDP-Based Multiplexed and Secure Transport}},
+  pagetotal    = 151,
+  year         = 2021,
+  month        = may,
+}"
+        );
+    }
+
+    #[test]
+    fn test_bibtex_escape() {
+        assert_eq!(bibtex_escape("TCP & UDP_2"), "TCP \\& UDP\\_2");
+        assert_eq!(
+            bibtex_escape("{a}\\b~c^d"),
+            "\\{a\\}\\textbackslash{}b\\textasciitilde{}c\\textasciicircum{}d"
+        );
+    }
+
+    #[test]
+    fn test_bibxml() {
+        assert_eq!(
+            quic().to_bibxml(),
+            r#"<reference anchor="RFC9000" target="https://www.rfc-editor.org/info/rfc9000">
+  <front>
+    <title>QUIC: A UDP-Based Multiplexed and Secure Transport</title>
+    <author fullname="J. Iyengar, Ed."/>
+    <author fullname="M. Thomson, Ed."/>
+    <date month="May" year="2021"/>
+  </front>
+  <seriesInfo name="RFC" value="9000"/>
+  <seriesInfo name="DOI" value="10.17487/RFC9000"/>
+</reference>"#
+        );
+    }
+
+    #[test]
+    fn test_draft_citation() {
+        let mut doc = Document::new(
+            "draft-ietf-quic-qlog-main-schema".to_string(),
+            "qlog: Structured Logging for Network Protocols".to_string(),
+            DocumentType::Draft("draft-ietf-quic-qlog-main-schema".to_string()),
+        );
+        doc.rev = Some("09".to_string());
+        let citation = Citation::from_document(&doc);
+
+        assert_eq!(citation.key(), "ietf-quic-qlog-main-schema-09");
+        assert_eq!(citation.anchor(), "I-D.ietf-quic-qlog-main-schema");
+        assert!(citation.to_bibxml().contains(
+            r#"<seriesInfo name="Internet-Draft" value="draft-ietf-quic-qlog-main-schema-09"/>"#
+        ));
+        let bibtex = citation.to_bibtex();
+        assert!(bibtex.starts_with("@techreport{ietf-quic-qlog-main-schema-09,"));
+        assert!(bibtex.contains(
+            "  url          = {https://datatracker.ietf.org/doc/draft-ietf-quic-qlog-main-schema/09/},"
+        ));
+        assert!(bibtex.contains("  publisher    = {Internet Engineering Task Force},"));
+    }
+
+    #[test]
+    fn test_write_bibliography() {
+        let citations = [quic(), Citation::new(DocumentType::Rfc(2119))];
+        let mut out = Vec::new();
+        write_bibliography(&citations, BibliographyFormat::BibXml, &mut out).unwrap();
+        let xml = String::from_utf8(out).unwrap();
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<references>\n  <reference anchor=\"RFC9000\""));
+        assert!(xml.contains("  <reference anchor=\"RFC2119\""));
+        assert!(xml.ends_with("</references>\n"));
+
+        assert_eq!(
+            BibliographyFormat::from_path(Path::new("refs.BIB")),
+            Some(BibliographyFormat::Bibtex)
+        );
+        assert_eq!(BibliographyFormat::from_path(Path::new("refs.txt")), None);
+    }
+}
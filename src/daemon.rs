@@ -0,0 +1,376 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use serde_json::{json, Value};
+
+use crate::api::DocumentSource;
+use crate::cache::{CacheManager, EntryMetadata};
+use crate::models::{DocumentType, Format};
+use crate::search::{LocalHit, LocalIndex};
+use crate::service::LookupService;
+
+/// Socket the daemon listens on, in the cache directory it owns
+const SOCKET_FILE: &str = "daemon.sock";
+/// Lock held by the running daemon, so only one owns a cache; holds its process id
+const LOCK_FILE: &str = "daemon.lock";
+/// How often the daemon checks the cache for added, changed or removed documents
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A long-running owner of the cache, its full-text index and the polling loop
+///
+/// Serves the [`LookupService`] JSON-RPC protocol on a Unix socket in the cache
+/// directory, one request per line and any number of clients at once. Only one
+/// daemon runs per cache; tools find it with [`DaemonClient::connect`] and
+/// share its warm index instead of building their own.
+///
+/// Only full-text search is delegated: [`search_local`] and the `search` of a
+/// [`LookupService`] ask the daemon when it is running, while fetches and
+/// other cache reads and writes still happen in the calling process, on the
+/// shared cache directory. The index follows the cache by polling it.
+pub struct Daemon {
+    cache: CacheManager,
+    service: Arc<LookupService>,
+    poll_interval: Duration,
+}
+
+impl Daemon {
+    /// Create a daemon fetching documents missing from `cache` from `source`
+    pub fn new(cache: CacheManager, source: impl DocumentSource + 'static) -> Self {
+        Self {
+            service: Arc::new(LookupService::new(cache.clone(), source).without_delegation()),
+            cache,
+            poll_interval: POLL_INTERVAL,
+        }
+    }
+
+    /// Check the cache for changed documents at this interval
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval.max(Duration::from_millis(10));
+        self
+    }
+
+    /// Get the socket a daemon owning `cache` listens on
+    pub fn socket_path(cache: &CacheManager) -> PathBuf {
+        cache.cache_dir().join(SOCKET_FILE)
+    }
+
+    /// Serve clients until interrupted with Ctrl-C
+    pub async fn serve(self) -> crate::Result<()> {
+        self.serve_until(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+    }
+
+    /// Serve clients until `shutdown` completes
+    ///
+    /// Fails if another daemon already owns the cache.
+    #[cfg(unix)]
+    pub async fn serve_until(self, shutdown: impl Future<Output = ()>) -> crate::Result<()> {
+        use fs2::FileExt;
+        use std::io::Write;
+        use tokio::io::BufReader;
+        use tokio::net::UnixListener;
+
+        // Not truncated before it is locked, to keep a running daemon's process id
+        let mut lock = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.cache.cache_dir().join(LOCK_FILE))
+            .context("Failed to create daemon lock")?;
+        if lock.try_lock_exclusive().is_err() {
+            return Err(anyhow!(
+                "A daemon is already running for {}",
+                self.cache.cache_dir().display()
+            )
+            .into());
+        }
+        lock.set_len(0)
+            .and_then(|()| write!(lock, "{}", std::process::id()))
+            .context("Failed to write daemon lock")?;
+
+        // A socket left behind by a daemon that didn't shut down cleanly
+        let socket = Self::socket_path(&self.cache);
+        let _ = std::fs::remove_file(&socket);
+        let listener = UnixListener::bind(&socket)
+            .with_context(|| format!("Failed to listen on {}", socket.display()))?;
+
+        let poller = tokio::spawn(poll_index(
+            self.cache.clone(),
+            self.service.clone(),
+            self.poll_interval,
+        ));
+        tokio::pin!(shutdown);
+        let result = loop {
+            tokio::select! {
+                accepted = listener.accept() => {
+                    let stream = match accepted {
+                        Ok((stream, _)) => stream,
+                        Err(e) => break Err(e).context("Failed to accept connection"),
+                    };
+                    let service = self.service.clone();
+                    tokio::spawn(async move {
+                        let (reader, writer) = stream.into_split();
+                        // A client hanging up mid-request only ends its own connection
+                        let _ = service.run(BufReader::new(reader), writer).await;
+                    });
+                }
+                _ = &mut shutdown => break Ok(()),
+            }
+        };
+
+        poller.abort();
+        let _ = std::fs::remove_file(&socket);
+        drop(lock);
+        Ok(result?)
+    }
+
+    /// Serve clients until `shutdown` completes
+    #[cfg(not(unix))]
+    pub async fn serve_until(self, _shutdown: impl Future<Output = ()>) -> crate::Result<()> {
+        Err(anyhow!("Daemon mode is only supported on Unix").into())
+    }
+}
+
+/// Update the service's index whenever documents are added to, changed in or removed from the cache
+async fn poll_index(cache: CacheManager, service: Arc<LookupService>, interval: Duration) {
+    let mut index = LocalIndex::new();
+    let mut known = HashMap::new();
+    let mut published = false;
+    let mut ticks = tokio::time::interval(interval);
+    loop {
+        ticks.tick().await;
+        let snapshot = cache.clone();
+        let (mut taken, mut seen) = (std::mem::take(&mut index), std::mem::take(&mut known));
+        let refreshed = tokio::task::spawn_blocking(move || {
+            let changed = refresh_index(&snapshot, &mut taken, &mut seen);
+            (taken, seen, changed)
+        })
+        .await;
+        // A failed refresh starts over from an empty index on the next poll
+        let Ok((refreshed, seen, changed)) = refreshed else {
+            continue;
+        };
+        (index, known) = (refreshed, seen);
+        if changed || !published {
+            service.set_index(index.clone());
+            published = true;
+        }
+    }
+}
+
+/// Bring an index of the cache up to date, returning whether it changed
+///
+/// Only documents whose text was added, removed or replaced since `known`
+/// was taken (by its size and modification time) are read again.
+fn refresh_index(
+    cache: &CacheManager,
+    index: &mut LocalIndex,
+    known: &mut HashMap<DocumentType, EntryMetadata>,
+) -> bool {
+    let current: HashMap<DocumentType, EntryMetadata> = cache
+        .list_cached()
+        .into_iter()
+        .filter_map(|doc| {
+            let stored = cache.document_metadata(&doc, Format::Text)?;
+            Some((doc, stored))
+        })
+        .collect();
+    let mut changed = false;
+    known.retain(|doc, _| {
+        let cached = current.contains_key(doc);
+        if !cached {
+            index.remove(doc);
+            changed = true;
+        }
+        cached
+    });
+    let mut stale = Vec::new();
+    for (doc, stored) in current {
+        if known.get(&doc) != Some(&stored) {
+            index.remove(&doc);
+            // Taken before reading, so a concurrent write is noticed on the next poll
+            known.insert(doc.clone(), stored);
+            stale.push(doc);
+        }
+    }
+    stale.sort();
+    index.add_cached(cache, &stale);
+    changed || !stale.is_empty()
+}
+
+/// A connection to the daemon owning a cache
+#[cfg(unix)]
+pub struct DaemonClient {
+    reader: tokio::io::Lines<tokio::io::BufReader<tokio::net::unix::OwnedReadHalf>>,
+    writer: tokio::net::unix::OwnedWriteHalf,
+    next_id: u64,
+}
+
+#[cfg(unix)]
+impl DaemonClient {
+    /// Connect to the daemon owning `cache`, if one is running
+    pub async fn connect(cache: &CacheManager) -> Option<Self> {
+        use tokio::io::{AsyncBufReadExt, BufReader};
+
+        let stream = tokio::net::UnixStream::connect(Daemon::socket_path(cache))
+            .await
+            .ok()?;
+        let (reader, writer) = stream.into_split();
+        Some(Self {
+            reader: BufReader::new(reader).lines(),
+            writer,
+            next_id: 1,
+        })
+    }
+
+    /// Call a method of the lookup service and return its result
+    pub async fn call(&mut self, method: &str, params: Value) -> crate::Result<Value> {
+        use tokio::io::AsyncWriteExt;
+
+        let id = self.next_id;
+        self.next_id += 1;
+        let mut request =
+            json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }).to_string();
+        request.push('\n');
+        self.writer
+            .write_all(request.as_bytes())
+            .await
+            .context("Failed to send request to daemon")?;
+
+        let line = self
+            .reader
+            .next_line()
+            .await
+            .context("Failed to read daemon response")?
+            .context("Daemon closed the connection")?;
+        let mut response: Value = serde_json::from_str(&line).context("Invalid daemon response")?;
+        if let Some(message) = response["error"]["message"].as_str() {
+            return Err(anyhow!("{}", message).into());
+        }
+        Ok(response["result"].take())
+    }
+
+    /// Search the daemon's index of the cache
+    pub async fn search(&mut self, query: &str, limit: usize) -> crate::Result<Vec<LocalHit>> {
+        let hits = self
+            .call("search", json!({ "query": query, "limit": limit }))
+            .await?;
+        Ok(serde_json::from_value(hits).context("Invalid search results from daemon")?)
+    }
+}
+
+/// Search the text of cached documents, through a running daemon if there is one
+///
+/// Without a daemon (or if it fails), the index is built in the calling process.
+pub async fn search_local(cache: &CacheManager, query: &str, limit: usize) -> Vec<LocalHit> {
+    #[cfg(unix)]
+    if let Some(mut client) = DaemonClient::connect(cache).await {
+        if let Ok(hits) = client.search(query, limit).await {
+            return hits;
+        }
+    }
+    let cache = cache.clone();
+    tokio::task::spawn_blocking(move || LocalIndex::build(&cache))
+        .await
+        .map(|index| index.search(query, limit))
+        .unwrap_or_default()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::api::FixtureSource;
+    use tempfile::TempDir;
+
+    /// Wait until a search of the daemon's index finds `expected` documents
+    async fn wait_for_hits(client: &mut DaemonClient, query: &str, expected: usize) {
+        for _ in 0..200 {
+            if client.search(query, 10).await.unwrap().len() == expected {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("'{}' never found {} documents", query, expected);
+    }
+
+    #[tokio::test]
+    async fn test_clients_share_one_daemon() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        cache
+            .store_document(
+                &DocumentType::Rfc(9000),
+                Format::Text,
+                "1.  Introduction\n\n   QUIC is a transport protocol.\n",
+            )
+            .unwrap();
+        assert!(DaemonClient::connect(&cache).await.is_none());
+
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let daemon = Daemon::new(cache.clone(), FixtureSource::new())
+            .with_poll_interval(Duration::from_millis(20));
+        let server = tokio::spawn(daemon.serve_until(async {
+            let _ = stopped.await;
+        }));
+
+        let mut client = loop {
+            if let Some(client) = DaemonClient::connect(&cache).await {
+                break client;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+        let hits = client.search("quic", 10).await.unwrap();
+        assert_eq!(hits[0].doc, DocumentType::Rfc(9000));
+        assert_eq!(search_local(&cache, "transport", 10).await.len(), 1);
+        let pid = std::fs::read_to_string(temp.path().join(LOCK_FILE)).unwrap();
+        assert_eq!(pid, std::process::id().to_string());
+
+        // Polls pick up added, replaced and removed documents
+        let http3 = DocumentType::Rfc(9114);
+        cache
+            .store_document(&http3, Format::Text, "HTTP/3 runs over a transport.\n")
+            .unwrap();
+        wait_for_hits(&mut client, "transport", 2).await;
+        cache
+            .store_document(&http3, Format::Text, "HTTP/3 semantics.\n")
+            .unwrap();
+        wait_for_hits(&mut client, "transport", 1).await;
+        assert_eq!(client.search("semantics", 10).await.unwrap()[0].doc, http3);
+        cache.remove(&http3).unwrap();
+        wait_for_hits(&mut client, "semantics", 0).await;
+
+        // Another lookup service searches the daemon's index instead of its
+        // own, which would be empty with its documents kept in memory
+        let elsewhere = cache.clone().with_store(crate::cache::MemoryStore::new());
+        let service = LookupService::new(elsewhere, FixtureSource::new());
+        let search = r#"{"jsonrpc":"2.0","id":1,"method":"search","params":{"query":"quic"}}"#;
+        let result = service.handle_line(search).await.result.unwrap();
+        let hits: Vec<LocalHit> = serde_json::from_value(result).unwrap();
+        assert_eq!(hits[0].doc, DocumentType::Rfc(9000));
+
+        let resolved = client
+            .call("resolve", json!({ "document": "9000" }))
+            .await
+            .unwrap();
+        assert_eq!(resolved["label"], "RFC 9000");
+        let err = client.call("hover", json!({})).await.unwrap_err();
+        assert_eq!(err.to_string(), "Unknown method 'hover'");
+
+        // Only one daemon may own the cache
+        let second = Daemon::new(cache.clone(), FixtureSource::new());
+        let err = second.serve_until(async {}).await.unwrap_err();
+        assert!(err.to_string().starts_with("A daemon is already running"));
+        let pid = std::fs::read_to_string(temp.path().join(LOCK_FILE)).unwrap();
+        assert_eq!(pid, std::process::id().to_string());
+
+        stop.send(()).unwrap();
+        server.await.unwrap().unwrap();
+        assert!(!Daemon::socket_path(&cache).exists());
+    }
+}
@@ -0,0 +1,340 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::api::{OfflineError, SchemaError};
+use crate::cache::InsufficientSpace;
+use crate::models::DocumentType;
+
+/// Result of the library's public API
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Why a call to the library failed
+///
+/// Match on the variant to tell failure causes apart. Every variant keeps
+/// the context chain of the failure: `context` holds it for the typed
+/// variants when the error was wrapped on its way up, and the `Network`,
+/// `CacheIo`, `Parse` and `Other` variants keep the underlying error.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The document doesn't exist upstream (HTTP 404)
+    NotFound {
+        doc: DocumentType,
+        context: Option<anyhow::Error>,
+    },
+    /// The server asked to slow down (HTTP 429) and retrying didn't help
+    RateLimited {
+        /// How long the server asked to wait, if it said
+        retry_after: Option<Duration>,
+        context: Option<anyhow::Error>,
+    },
+    /// The document had to be fetched while offline (see [`crate::api::OfflineMode`])
+    Offline {
+        error: OfflineError,
+        context: Option<anyhow::Error>,
+    },
+    /// The Datatracker API changed upstream
+    Schema {
+        error: SchemaError,
+        context: Option<anyhow::Error>,
+    },
+    /// The cache's file system has no room for a write
+    InsufficientSpace {
+        error: InsufficientSpace,
+        context: Option<anyhow::Error>,
+    },
+    /// The request failed or got an unexpected response
+    Network(anyhow::Error),
+    /// Reading or writing the cache failed
+    CacheIo(anyhow::Error),
+    /// A response or document couldn't be read
+    Parse(anyhow::Error),
+    /// Any other failure, e.g. an invalid argument or configuration
+    Other(anyhow::Error),
+}
+
+impl Error {
+    /// A document that doesn't exist upstream
+    pub fn not_found(doc: DocumentType) -> Self {
+        Self::NotFound { doc, context: None }
+    }
+
+    /// A request the server kept rate limiting
+    pub fn rate_limited(retry_after: Option<Duration>) -> Self {
+        Self::RateLimited {
+            retry_after,
+            context: None,
+        }
+    }
+
+    /// Get the underlying error, or the context chain of a typed variant
+    fn inner(&self) -> Option<&anyhow::Error> {
+        match self {
+            Self::Network(e) | Self::CacheIo(e) | Self::Parse(e) | Self::Other(e) => Some(e),
+            Self::NotFound { context, .. }
+            | Self::RateLimited { context, .. }
+            | Self::Offline { context, .. }
+            | Self::Schema { context, .. }
+            | Self::InsufficientSpace { context, .. } => context.as_ref(),
+        }
+    }
+
+    /// Copy the error for another caller of a shared request; underlying
+    /// errors are kept as their message and context chain
+    pub(crate) fn replicate(&self) -> Self {
+        let message = |e: &anyhow::Error| anyhow::anyhow!("{:#}", e);
+        let context = self.inner().map(message);
+        match self {
+            Self::NotFound { doc, .. } => Self::NotFound {
+                doc: doc.clone(),
+                context,
+            },
+            Self::RateLimited { retry_after, .. } => Self::RateLimited {
+                retry_after: *retry_after,
+                context,
+            },
+            Self::Offline { error, .. } => Self::Offline {
+                error: error.clone(),
+                context,
+            },
+            Self::Schema { error, .. } => Self::Schema {
+                error: error.clone(),
+                context,
+            },
+            Self::InsufficientSpace { error, .. } => Self::InsufficientSpace {
+                error: *error,
+                context,
+            },
+            Self::Network(e) => Self::Network(message(e)),
+            Self::CacheIo(e) => Self::CacheIo(message(e)),
+            Self::Parse(e) => Self::Parse(message(e)),
+            Self::Other(e) => Self::Other(message(e)),
+        }
+    }
+
+    /// Classify a failure by the first typed error in its chain
+    ///
+    /// A typed error found below some context keeps the whole chain.
+    fn classify(err: anyhow::Error) -> Self {
+        enum Kind {
+            Network,
+            CacheIo,
+            Parse,
+        }
+        // An error that was converted without adding context is returned as is
+        if err.chain().next().is_some_and(|e| e.is::<Error>()) {
+            return err
+                .downcast::<Error>()
+                .expect("the outermost error is an Error");
+        }
+        let mut kind = None;
+        let mut typed = None;
+        for cause in err.chain() {
+            if let Some(e) = cause.downcast_ref::<Error>() {
+                match e {
+                    Self::NotFound { doc, .. } => {
+                        typed = Some(Self::not_found(doc.clone()));
+                        break;
+                    }
+                    Self::RateLimited { retry_after, .. } => {
+                        typed = Some(Self::rate_limited(*retry_after));
+                        break;
+                    }
+                    Self::Offline { error, .. } => {
+                        typed = Some(error.clone().into());
+                        break;
+                    }
+                    Self::Schema { error, .. } => {
+                        typed = Some(error.clone().into());
+                        break;
+                    }
+                    Self::InsufficientSpace { error, .. } => {
+                        typed = Some((*error).into());
+                        break;
+                    }
+                    Self::Network(_) => kind = kind.or(Some(Kind::Network)),
+                    Self::CacheIo(_) => kind = kind.or(Some(Kind::CacheIo)),
+                    Self::Parse(_) => kind = kind.or(Some(Kind::Parse)),
+                    Self::Other(_) => {}
+                }
+            } else if let Some(e) = cause.downcast_ref::<OfflineError>() {
+                typed = Some(e.clone().into());
+                break;
+            } else if let Some(e) = cause.downcast_ref::<SchemaError>() {
+                typed = Some(e.clone().into());
+                break;
+            } else if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+                // A body that isn't the expected JSON is a parse error
+                kind = kind.or(Some(if e.is_decode() {
+                    Kind::Parse
+                } else {
+                    Kind::Network
+                }));
+            } else if let Some(e) = cause.downcast_ref::<InsufficientSpace>() {
+                typed = Some((*e).into());
+                break;
+            } else if cause.is::<std::io::Error>() {
+                kind = kind.or(Some(Kind::CacheIo));
+            } else if cause.is::<serde_json::Error>()
+                || cause.is::<std::string::FromUtf8Error>()
+                || cause.is::<std::str::Utf8Error>()
+            {
+                kind = kind.or(Some(Kind::Parse));
+            }
+        }
+        if let Some(mut typed) = typed {
+            if let Self::NotFound { context, .. }
+            | Self::RateLimited { context, .. }
+            | Self::Offline { context, .. }
+            | Self::Schema { context, .. }
+            | Self::InsufficientSpace { context, .. } = &mut typed
+            {
+                *context = Some(err);
+            }
+            return typed;
+        }
+        match kind {
+            Some(Kind::Network) => Self::Network(err),
+            Some(Kind::CacheIo) => Self::CacheIo(err),
+            Some(Kind::Parse) => Self::Parse(err),
+            None => Self::Other(err),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(e) = self.inner() {
+            return e.fmt(f);
+        }
+        match self {
+            Self::NotFound { doc, .. } => write!(f, "{} not found", doc),
+            Self::RateLimited {
+                retry_after: Some(delay),
+                ..
+            } => write!(
+                f,
+                "Rate limited by the server; retry in {} seconds",
+                delay.as_secs()
+            ),
+            Self::RateLimited {
+                retry_after: None, ..
+            } => f.write_str("Rate limited by the server"),
+            Self::Offline { error, .. } => error.fmt(f),
+            Self::Schema { error, .. } => error.fmt(f),
+            Self::InsufficientSpace { error, .. } => error.fmt(f),
+            Self::Network(_) | Self::CacheIo(_) | Self::Parse(_) | Self::Other(_) => {
+                unreachable!("the underlying error is formatted above")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        // Transparent: the chain continues with the underlying error's causes
+        self.inner()?.source()
+    }
+}
+
+impl From<anyhow::Error> for Error {
+    fn from(err: anyhow::Error) -> Self {
+        Self::classify(err)
+    }
+}
+
+impl From<OfflineError> for Error {
+    fn from(error: OfflineError) -> Self {
+        Self::Offline {
+            error,
+            context: None,
+        }
+    }
+}
+
+impl From<SchemaError> for Error {
+    fn from(error: SchemaError) -> Self {
+        Self::Schema {
+            error,
+            context: None,
+        }
+    }
+}
+
+impl From<InsufficientSpace> for Error {
+    fn from(error: InsufficientSpace) -> Self {
+        Self::InsufficientSpace {
+            error,
+            context: None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::CacheIo(err.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_classify() {
+        let not_found = anyhow::Error::new(Error::not_found(DocumentType::Rfc(99999)))
+            .context("Failed to fetch document");
+        let err = Error::from(not_found);
+        assert!(matches!(
+            err,
+            Error::NotFound {
+                doc: DocumentType::Rfc(99999),
+                ..
+            }
+        ));
+        // The context chain is kept
+        assert_eq!(
+            format!("{:#}", err),
+            "Failed to fetch document: RFC 99999 not found"
+        );
+        let bare = Error::from(anyhow::Error::new(Error::not_found(DocumentType::Rfc(1))));
+        assert_eq!(bare.to_string(), "RFC 1 not found");
+        assert!(matches!(bare, Error::NotFound { context: None, .. }));
+
+        let offline = anyhow::Error::new(OfflineError {
+            requested: "RFC 9000".to_string(),
+            cached: Vec::new(),
+        });
+        assert!(matches!(Error::from(offline), Error::Offline { .. }));
+
+        let io = std::fs::read("/nonexistent/rfc9000.txt")
+            .context("Failed to read cached document")
+            .unwrap_err();
+        let err = Error::from(io);
+        assert!(matches!(err, Error::CacheIo(_)));
+        // The context chain is kept
+        assert!(format!("{:#}", err).starts_with("Failed to read cached document: "));
+
+        let parse = serde_json::from_str::<u32>("{").context("Invalid JSON");
+        assert!(matches!(Error::from(parse.unwrap_err()), Error::Parse(_)));
+        let space = anyhow::Error::new(InsufficientSpace {
+            required: 10,
+            available: 5,
+        })
+        .context("Failed to store document");
+        let err = Error::from(space);
+        let Error::InsufficientSpace { error, .. } = &err else {
+            panic!("not classified as insufficient space: {:?}", err);
+        };
+        assert_eq!((error.required, error.available), (10, 5));
+        assert!(format!("{:#}", err).starts_with("Failed to store document: Not enough disk space"));
+
+        let utf8 = String::from_utf8(vec![0xff]).context("Document is not valid UTF-8");
+        assert!(matches!(Error::from(utf8.unwrap_err()), Error::Parse(_)));
+        assert!(matches!(
+            Error::from(anyhow::anyhow!("Unknown")),
+            Error::Other(_)
+        ));
+    }
+}
@@ -0,0 +1,319 @@
+use std::io::Write;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::analyze::Requirement;
+use crate::models::{Document, DocumentType, SearchResult};
+
+/// Machine-readable formats for search results and listings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Comma-separated values with a header row (RFC 4180)
+    Csv,
+    /// One JSON object per line
+    JsonLines,
+}
+
+impl ExportFormat {
+    /// Parse a format name ("csv", "jsonl")
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "csv" => Some(ExportFormat::Csv),
+            "jsonl" | "json-lines" | "ndjson" => Some(ExportFormat::JsonLines),
+            _ => None,
+        }
+    }
+}
+
+/// Flat record of a document, shared by both formats
+#[derive(Debug, Serialize)]
+struct DocumentRecord<'a> {
+    name: String,
+    title: &'a str,
+    status: Option<&'a str>,
+    published: Option<String>,
+    stream: Option<String>,
+    wg: Option<&'a str>,
+    rev: Option<&'a str>,
+    state: Option<&'a str>,
+    pages: Option<u32>,
+    authors: String,
+}
+
+impl<'a> From<&'a Document> for DocumentRecord<'a> {
+    fn from(doc: &'a Document) -> Self {
+        Self {
+            name: doc.doc_type.name(),
+            title: &doc.title,
+            status: doc.status.as_deref(),
+            published: doc.published.map(|d| d.format("%Y-%m-%d").to_string()),
+            stream: doc.stream.map(|s| s.to_string()),
+            wg: doc.wg.as_deref(),
+            rev: doc.rev.as_deref(),
+            state: doc.state.as_deref(),
+            pages: doc.pages,
+            authors: doc
+                .authors
+                .iter()
+                .map(|a| a.name.as_str())
+                .collect::<Vec<_>>()
+                .join("; "),
+        }
+    }
+}
+
+impl DocumentRecord<'_> {
+    const HEADER: &'static [&'static str] = &[
+        "name",
+        "title",
+        "status",
+        "published",
+        "stream",
+        "wg",
+        "rev",
+        "state",
+        "pages",
+        "authors",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.title.to_string(),
+            self.status.unwrap_or_default().to_string(),
+            self.published.clone().unwrap_or_default(),
+            self.stream.clone().unwrap_or_default(),
+            self.wg.unwrap_or_default().to_string(),
+            self.rev.unwrap_or_default().to_string(),
+            self.state.unwrap_or_default().to_string(),
+            self.pages.map(|p| p.to_string()).unwrap_or_default(),
+            self.authors.clone(),
+        ]
+    }
+}
+
+/// Record of a bare document listing (e.g., the cache contents)
+#[derive(Debug, Serialize)]
+struct ListingRecord {
+    name: String,
+    label: String,
+}
+
+/// Write documents in the given format
+pub fn export_documents<W: Write>(
+    docs: &[Document],
+    format: ExportFormat,
+    mut out: W,
+) -> crate::Result<()> {
+    let records: Vec<DocumentRecord> = docs.iter().map(DocumentRecord::from).collect();
+    match format {
+        ExportFormat::Csv => {
+            write_csv_row(&mut out, DocumentRecord::HEADER)?;
+            for record in &records {
+                write_csv_row(&mut out, &record.fields())?;
+            }
+        }
+        ExportFormat::JsonLines => write_json_lines(&mut out, &records)?,
+    }
+    Ok(out.flush().context("Failed to write export")?)
+}
+
+/// Write a listing of document identifiers in the given format
+pub fn export_listing<W: Write>(
+    docs: &[DocumentType],
+    format: ExportFormat,
+    mut out: W,
+) -> crate::Result<()> {
+    let records: Vec<ListingRecord> = docs
+        .iter()
+        .map(|d| ListingRecord {
+            name: d.name(),
+            label: d.to_string(),
+        })
+        .collect();
+    match format {
+        ExportFormat::Csv => {
+            write_csv_row(&mut out, &["name", "label"])?;
+            for record in &records {
+                write_csv_row(&mut out, &[&record.name, &record.label])?;
+            }
+        }
+        ExportFormat::JsonLines => write_json_lines(&mut out, &records)?,
+    }
+    Ok(out.flush().context("Failed to write export")?)
+}
+
+/// Flat record of a BCP 14 requirement, one row of a conformance checklist
+#[derive(Debug, Serialize)]
+struct RequirementRecord<'a> {
+    section: String,
+    title: &'a str,
+    level: String,
+    keywords: String,
+    line: usize,
+    sentence: &'a str,
+}
+
+impl RequirementRecord<'_> {
+    const HEADER: &'static [&'static str] =
+        &["section", "title", "level", "keywords", "line", "sentence"];
+}
+
+/// Write requirements in the given format, e.g. as a conformance checklist
+pub fn export_requirements<W: Write>(
+    requirements: &[Requirement],
+    format: ExportFormat,
+    mut out: W,
+) -> crate::Result<()> {
+    let records: Vec<RequirementRecord> = requirements
+        .iter()
+        .map(|r| RequirementRecord {
+            section: r
+                .section
+                .as_ref()
+                .and_then(|s| s.number.clone())
+                .unwrap_or_default(),
+            title: r.section.as_ref().map_or("", |s| s.title.as_str()),
+            level: r.level.to_string(),
+            keywords: r.keywords.join("; "),
+            // Lines are 1-based for people reading the document
+            line: r.line + 1,
+            sentence: &r.sentence,
+        })
+        .collect();
+    match format {
+        ExportFormat::Csv => {
+            write_csv_row(&mut out, RequirementRecord::HEADER)?;
+            for record in &records {
+                write_csv_row(
+                    &mut out,
+                    &[
+                        record.section.as_str(),
+                        record.title,
+                        &record.level,
+                        &record.keywords,
+                        &record.line.to_string(),
+                        record.sentence,
+                    ],
+                )?;
+            }
+        }
+        ExportFormat::JsonLines => write_json_lines(&mut out, &records)?,
+    }
+    Ok(out.flush().context("Failed to write export")?)
+}
+
+impl SearchResult {
+    /// Write the matching documents in the given format
+    pub fn export<W: Write>(&self, format: ExportFormat, out: W) -> crate::Result<()> {
+        export_documents(&self.documents, format, out)
+    }
+}
+
+fn write_json_lines<W: Write, T: Serialize>(out: &mut W, records: &[T]) -> Result<()> {
+    for record in records {
+        serde_json::to_writer(&mut *out, record).context("Failed to serialize record")?;
+        writeln!(out).context("Failed to write export")?;
+    }
+    Ok(())
+}
+
+fn write_csv_row<W: Write, S: AsRef<str>>(out: &mut W, fields: &[S]) -> Result<()> {
+    let row: Vec<String> = fields.iter().map(|f| csv_field(f.as_ref())).collect();
+    write!(out, "{}\r\n", row.join(",")).context("Failed to write export")
+}
+
+/// Quote a CSV field if it contains separators, quotes or line breaks
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Stream;
+
+    fn sample() -> Vec<Document> {
+        let mut doc = Document::new(
+            "rfc9000".to_string(),
+            "QUIC: A UDP-Based \"Multiplexed\" Transport".to_string(),
+            DocumentType::Rfc(9000),
+        );
+        doc.authors = vec!["J. Iyengar".into(), "M. Thomson".into()];
+        doc.stream = Some(Stream::Ietf);
+        doc.wg = Some("quic".to_string());
+        vec![doc]
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let mut out = Vec::new();
+        export_documents(&sample(), ExportFormat::Csv, &mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let rows: Vec<&str> = csv.split("\r\n").collect();
+
+        assert_eq!(
+            rows[0],
+            "name,title,status,published,stream,wg,rev,state,pages,authors"
+        );
+        assert_eq!(
+            rows[1],
+            "rfc9000,\"QUIC: A UDP-Based \"\"Multiplexed\"\" Transport\",,,IETF,quic,,,,J. Iyengar; M. Thomson"
+        );
+    }
+
+    #[test]
+    fn test_json_lines_export() {
+        let mut out = Vec::new();
+        export_listing(
+            &[
+                DocumentType::Rfc(9000),
+                DocumentType::Draft("draft-ietf-quic-tls".to_string()),
+            ],
+            ExportFormat::JsonLines,
+            &mut out,
+        )
+        .unwrap();
+        let lines: Vec<&str> = std::str::from_utf8(&out).unwrap().lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], r#"{"name":"rfc9000","label":"RFC 9000"}"#);
+
+        let mut out = Vec::new();
+        export_documents(&sample(), ExportFormat::JsonLines, &mut out).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(value["wg"], "quic");
+        assert_eq!(value["published"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_requirements_csv() {
+        let text = "1.  Streams\n\n   Endpoints MUST NOT send data, but MAY close.\n";
+        let mut out = Vec::new();
+        export_requirements(
+            &crate::analyze::requirements(text),
+            ExportFormat::Csv,
+            &mut out,
+        )
+        .unwrap();
+        let csv = String::from_utf8(out).unwrap();
+
+        assert_eq!(
+            csv,
+            "section,title,level,keywords,line,sentence\r\n\
+             1,Streams,MUST NOT,MUST NOT; MAY,3,\"Endpoints MUST NOT send data, but MAY close.\"\r\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(ExportFormat::parse("CSV"), Some(ExportFormat::Csv));
+        assert_eq!(ExportFormat::parse("ndjson"), Some(ExportFormat::JsonLines));
+        assert_eq!(ExportFormat::parse("xml"), None);
+    }
+}
@@ -0,0 +1,273 @@
+use std::fmt;
+use std::process::Command;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+
+use crate::cache::ContentChange;
+use crate::models::{DocumentType, Format};
+use crate::tasks::TaskHook;
+
+/// Something that happened to a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookEvent {
+    /// A document was downloaded (not revalidated) from `url`
+    Fetched {
+        doc: DocumentType,
+        format: Format,
+        url: String,
+    },
+    /// A draft has a newer revision than any seen before
+    NewRevision {
+        /// The draft name without revision
+        doc: DocumentType,
+        previous: u32,
+        revision: u32,
+    },
+    /// A document was evicted to stay within a size limit or free disk space
+    Evicted { doc: DocumentType },
+    /// A published RFC was fetched with different content than the copy first fetched
+    ContentChanged { change: ContentChange },
+}
+
+impl HookEvent {
+    /// Get the name of the event (e.g., "fetched", "new-revision")
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookEvent::Fetched { .. } => "fetched",
+            HookEvent::NewRevision { .. } => "new-revision",
+            HookEvent::Evicted { .. } => "evicted",
+            HookEvent::ContentChanged { .. } => "content-changed",
+        }
+    }
+
+    /// Get the document the event is about
+    pub fn document(&self) -> &DocumentType {
+        match self {
+            HookEvent::Fetched { doc, .. }
+            | HookEvent::NewRevision { doc, .. }
+            | HookEvent::Evicted { doc } => doc,
+            HookEvent::ContentChanged { change } => &change.doc,
+        }
+    }
+}
+
+/// A callback fired on document events
+pub trait Hook: Send + Sync {
+    fn on_event(&self, event: &HookEvent) -> Result<()>;
+}
+
+impl<F> Hook for F
+where
+    F: Fn(&HookEvent) -> Result<()> + Send + Sync,
+{
+    fn on_event(&self, event: &HookEvent) -> Result<()> {
+        self(event)
+    }
+}
+
+/// Runs a shell command for every event
+///
+/// The event is passed in the environment: `RFC_EVENT` (e.g., "fetched"),
+/// `RFC_DOCUMENT` (e.g., "rfc9000") and, depending on the event, `RFC_FORMAT`,
+/// `RFC_URL`, `RFC_PREVIOUS_REVISION`, `RFC_REVISION`, `RFC_TRUSTED_SHA256`
+/// and `RFC_SHA256`. The command runs in the background, so a slow hook
+/// doesn't hold up fetching; if it fails, that is reported on stderr.
+#[derive(Debug, Clone)]
+pub struct CommandHook {
+    command: String,
+}
+
+impl CommandHook {
+    pub fn new(command: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+        }
+    }
+
+    /// Run the command for an event and wait for it to finish
+    pub fn run(&self, event: &HookEvent) -> crate::Result<()> {
+        let status = self
+            .command(event)
+            .status()
+            .with_context(|| format!("Failed to run hook '{}'", self.command))?;
+        if !status.success() {
+            return Err(anyhow::anyhow!("Hook '{}' failed: {}", self.command, status).into());
+        }
+        Ok(())
+    }
+
+    /// Build the command for an event
+    fn command(&self, event: &HookEvent) -> Command {
+        let mut command = if cfg!(windows) {
+            let mut command = Command::new("cmd");
+            command.arg("/C").arg(&self.command);
+            command
+        } else {
+            let mut command = Command::new("sh");
+            command.arg("-c").arg(&self.command);
+            command
+        };
+        command
+            .env("RFC_EVENT", event.name())
+            .env("RFC_DOCUMENT", event.document().name());
+        match event {
+            HookEvent::Fetched { format, url, .. } => {
+                command.env("RFC_FORMAT", format.name()).env("RFC_URL", url);
+            }
+            HookEvent::NewRevision {
+                previous, revision, ..
+            } => {
+                command
+                    .env("RFC_PREVIOUS_REVISION", format!("{:02}", previous))
+                    .env("RFC_REVISION", format!("{:02}", revision));
+            }
+            HookEvent::Evicted { .. } => {}
+            HookEvent::ContentChanged { change } => {
+                command
+                    .env("RFC_FORMAT", change.format.name())
+                    .env("RFC_URL", &change.url)
+                    .env("RFC_TRUSTED_SHA256", &change.trusted_sha256)
+                    .env("RFC_SHA256", &change.sha256);
+            }
+        }
+        command
+    }
+}
+
+impl Hook for CommandHook {
+    fn on_event(&self, event: &HookEvent) -> Result<()> {
+        let mut child = self
+            .command(event)
+            .spawn()
+            .with_context(|| format!("Failed to run hook '{}'", self.command))?;
+        let name = self.command.clone();
+        std::thread::spawn(move || match child.wait() {
+            Ok(status) if status.success() => {}
+            Ok(status) => eprintln!("Warning: hook '{}' failed: {}", name, status),
+            Err(e) => eprintln!("Warning: hook '{}' failed: {}", name, e),
+        });
+        Ok(())
+    }
+}
+
+/// The hooks registered with a fetcher or cache
+///
+/// Cloning shares the registered hooks. A failing hook never fails the
+/// operation that fired it; its error is reported on stderr instead.
+#[derive(Clone, Default)]
+pub struct Hooks {
+    hooks: Vec<Arc<dyn Hook>>,
+}
+
+impl Hooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the hooks configured in the environment
+    ///
+    /// `RFC_HOOK` names a command to run and `RFC_TASKS` a task list to
+    /// append tasks to (see `TaskHook`).
+    pub fn from_env() -> Self {
+        let mut hooks = Self::new();
+        if let Some(command) = std::env::var("RFC_HOOK")
+            .ok()
+            .filter(|c| !c.trim().is_empty())
+        {
+            hooks.register(CommandHook::new(command));
+        }
+        if let Some(path) = std::env::var_os("RFC_TASKS").filter(|p| !p.is_empty()) {
+            hooks.register(TaskHook::new(path));
+        }
+        hooks
+    }
+
+    /// Add a hook, fired after those registered before it
+    pub fn register(&mut self, hook: impl Hook + 'static) {
+        self.hooks.push(Arc::new(hook));
+    }
+
+    /// Fire an event, reporting failed hooks on stderr and returning their errors
+    pub fn emit(&self, event: &HookEvent) -> Vec<anyhow::Error> {
+        let errors: Vec<_> = self
+            .hooks
+            .iter()
+            .filter_map(|hook| hook.on_event(event).err())
+            .collect();
+        for error in &errors {
+            eprintln!(
+                "Warning: {} hook for {} failed: {:#}",
+                event.name(),
+                event.document(),
+                error
+            );
+        }
+        errors
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hooks")
+            .field("count", &self.hooks.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_callbacks_receive_events() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut hooks = Hooks::new();
+        let recorder = seen.clone();
+        hooks.register(move |event: &HookEvent| {
+            recorder.lock().unwrap().push(event.name());
+            Ok(())
+        });
+        hooks.register(|_: &HookEvent| anyhow::bail!("notification failed"));
+
+        let event = HookEvent::Evicted {
+            doc: DocumentType::Rfc(9000),
+        };
+        let errors = hooks.emit(&event);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(*seen.lock().unwrap(), vec!["evicted"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_hook_environment() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let out = temp.path().join("event");
+        let hook = CommandHook::new(format!(
+            "echo \"$RFC_EVENT $RFC_DOCUMENT $RFC_PREVIOUS_REVISION $RFC_REVISION\" > {}",
+            out.display()
+        ));
+        hook.run(&HookEvent::NewRevision {
+            doc: DocumentType::Draft("draft-ietf-quic-qlog".to_string()),
+            previous: 8,
+            revision: 9,
+        })
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(out).unwrap(),
+            "new-revision draft-ietf-quic-qlog 08 09\n"
+        );
+
+        let evicted = HookEvent::Evicted {
+            doc: DocumentType::Rfc(1),
+        };
+        assert!(CommandHook::new("exit 3").run(&evicted).is_err());
+        // In the background, only failing to start the command is an error
+        assert!(CommandHook::new("exit 3").on_event(&evicted).is_ok());
+    }
+}
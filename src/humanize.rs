@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+
+/// Format a size in bytes (e.g., "512 B", "1.5 KB", "2.0 MB")
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Describe a point in time relative to now (e.g., "3 years ago", "in 12 days")
+pub fn relative_time(when: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (when - now).num_seconds();
+    let span = span(seconds.unsigned_abs());
+    match span {
+        None => "just now".to_string(),
+        Some(span) if seconds < 0 => format!("{} ago", span),
+        Some(span) => format!("in {}", span),
+    }
+}
+
+/// Describe a duration in its largest whole unit; None below one minute
+fn span(seconds: u64) -> Option<String> {
+    const UNITS: [(u64, &str); 5] = [
+        (365 * 24 * 60 * 60, "year"),
+        (30 * 24 * 60 * 60, "month"),
+        (24 * 60 * 60, "day"),
+        (60 * 60, "hour"),
+        (60, "minute"),
+    ];
+
+    UNITS
+        .iter()
+        .find(|(unit, _)| seconds >= *unit)
+        .map(|(unit, name)| {
+            let count = seconds / unit;
+            format!("{} {}{}", count, name, if count == 1 { "" } else { "s" })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(2 * 1024 * 1024), "2.0 MB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn test_relative_time() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+
+        assert_eq!(relative_time(now - Duration::seconds(30), now), "just now");
+        assert_eq!(relative_time(now - Duration::hours(1), now), "1 hour ago");
+        assert_eq!(relative_time(now + Duration::days(12), now), "in 12 days");
+        assert_eq!(relative_time(now - Duration::days(45), now), "1 month ago");
+        assert_eq!(
+            relative_time(Utc.with_ymd_and_hms(2021, 5, 27, 0, 0, 0).unwrap(), now),
+            "3 years ago"
+        );
+    }
+}
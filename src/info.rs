@@ -0,0 +1,257 @@
+use crate::api::{DataTrackerClient, DocumentFetcher, RfcIndex};
+use crate::cache::CacheManager;
+use crate::models::{DocumentInfo, DocumentType, Format, Relations};
+use crate::profile;
+use crate::render::{DraftStatus, StaleBanner};
+use crate::Result;
+
+/// Gather everything known about a document into a single `DocumentInfo`
+///
+/// Datatracker metadata, RFC Editor metadata and errata are queried
+/// concurrently; RFC metadata falls back to the RFC index synchronized into
+/// the cache. The license of an RFC is read from its cached text, if any.
+/// Only a failure of every remote lookup is reported as an error; individual
+/// lookups that fail simply leave their fields empty.
+pub async fn info(
+    doc: &DocumentType,
+    tracker: &DataTrackerClient,
+    fetcher: &DocumentFetcher,
+    cache: &CacheManager,
+) -> Result<DocumentInfo> {
+    let (info, timings) = profile::measured(gather(doc, tracker, fetcher, cache)).await;
+    let mut info = info?;
+    info.timings = timings;
+    Ok(info)
+}
+
+async fn gather(
+    doc: &DocumentType,
+    tracker: &DataTrackerClient,
+    fetcher: &DocumentFetcher,
+    cache: &CacheManager,
+) -> Result<DocumentInfo> {
+    let mut info = DocumentInfo::new(doc.clone());
+    info.cached_formats = cache.cached_formats(doc);
+
+    let (document, rfc, errata_count) = match doc {
+        DocumentType::Rfc(num) => {
+            let (document, rfc, errata) = tokio::join!(
+                tracker.document(doc),
+                fetcher.rfc_metadata(*num),
+                fetcher.errata_cached(*num, cache)
+            );
+            // Fall back to the synchronized RFC index when offline
+            let rfc = rfc.or_else(|e| {
+                RfcIndex::cached(cache)
+                    .and_then(|index| index.lookup(*num).cloned())
+                    .ok_or(e)
+            });
+            (document, Some(rfc), errata.ok().map(|e| e.len()))
+        }
+        DocumentType::Draft(_) | DocumentType::Subseries(..) => {
+            (tracker.document(doc).await, None, None)
+        }
+        // The IETF services know nothing about external specifications
+        DocumentType::External { .. } => return Ok(info),
+    };
+
+    let rfc = match rfc {
+        Some(Ok(mut rfc)) => {
+            rfc.license = cache
+                .get_document(doc, Format::Text)
+                .and_then(|text| crate::parse::license(&text));
+            Some(rfc)
+        }
+        Some(Err(rfc_err)) if document.is_err() => return Err(rfc_err),
+        _ => None,
+    };
+    let document = match document {
+        Ok(document) => Some(document),
+        Err(e) if rfc.is_none() => return Err(e),
+        Err(_) => None,
+    };
+
+    if let DocumentType::Draft(_) = doc {
+        info.latest_revision = document.as_ref().and_then(|d| d.rev.clone());
+    }
+    info.document = document;
+    info.rfc = rfc;
+    info.errata_count = errata_count;
+
+    Ok(info)
+}
+
+/// Get what a document obsoletes and updates, and what obsoletes and updates it
+///
+/// The RFC index synchronized into the cache is consulted first, then the RFC
+/// Editor. Only RFCs have relations; other documents get empty ones.
+pub async fn relations(
+    doc: &DocumentType,
+    fetcher: &DocumentFetcher,
+    cache: &CacheManager,
+) -> Result<Relations> {
+    let DocumentType::Rfc(number) = doc else {
+        return Ok(Relations::default());
+    };
+    if let Some(meta) = RfcIndex::cached(cache).and_then(|index| index.lookup(*number).cloned()) {
+        return Ok(meta.relations());
+    }
+    Ok(fetcher.rfc_metadata(*number).await?.relations())
+}
+
+/// Follow obsoleted-by links from a document to its newest successor
+///
+/// Returns the document itself if it hasn't been obsoleted.
+pub async fn latest(
+    doc: &DocumentType,
+    fetcher: &DocumentFetcher,
+    cache: &CacheManager,
+) -> Result<DocumentType> {
+    let mut current = doc.clone();
+    // Chains are short; the bound only guards against cycles in bad data
+    for _ in 0..16 {
+        match relations(&current, fetcher, cache).await?.successor() {
+            Some(next) if *next != current => current = next.clone(),
+            _ => break,
+        }
+    }
+    Ok(current)
+}
+
+/// Get the status banner to show above a draft that is out of date
+///
+/// A draft published as an RFC is recognized from the RFC index synchronized
+/// into the cache without going online; otherwise the Datatracker metadata of
+/// the draft decides whether it was replaced or has expired. Metadata cached
+/// less than a day ago is used as is; without a `tracker` nothing else is
+/// looked up. Returns `None` for current drafts, other documents, and when
+/// nothing can be looked up.
+pub async fn stale_banner(
+    doc: &DocumentType,
+    tracker: Option<&DataTrackerClient>,
+    cache: &CacheManager,
+) -> Option<StaleBanner> {
+    let DocumentType::Draft(name) = doc else {
+        return None;
+    };
+    let base = doc.draft_revision().map_or(name.as_str(), |(base, _)| base);
+
+    let published = RfcIndex::cached(cache).and_then(|index| {
+        index
+            .iter()
+            .find(|rfc| rfc.draft.as_deref().is_some_and(|d| draft_base(d) == base))
+            .map(|rfc| rfc.number)
+    });
+    if let Some(rfc) = published {
+        return Some(StaleBanner::new(
+            doc.clone(),
+            DraftStatus::Published { rfc },
+        ));
+    }
+
+    let latest = DocumentType::Draft(base.to_string());
+    let meta = match cache.get_metadata(&latest) {
+        Some(meta) => meta,
+        None => {
+            let meta = tracker?.metadata(&latest).await.ok()?;
+            // Only saves the next view a request, so a failed write is ignored
+            let _ = cache.store_metadata(&latest, &meta);
+            meta
+        }
+    };
+    StaleBanner::for_draft(doc, &meta, chrono::Utc::now())
+}
+
+/// Strip the revision from a draft name
+fn draft_base(name: &str) -> &str {
+    match name.rsplit_once('-') {
+        Some((base, rev)) if !rev.is_empty() && rev.chars().all(|c| c.is_ascii_digit()) => base,
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_latest_follows_the_cached_index() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let xml = "\
+<rfc-entry><doc-id>RFC2616</doc-id><title>HTTP/1.1</title>\
+<obsoleted-by><doc-id>RFC7230</doc-id><doc-id>RFC7231</doc-id></obsoleted-by></rfc-entry>\
+<rfc-entry><doc-id>RFC7231</doc-id><title>HTTP/1.1 Semantics</title>\
+<obsoleted-by><doc-id>RFC9110</doc-id></obsoleted-by></rfc-entry>\
+<rfc-entry><doc-id>RFC9110</doc-id><title>HTTP Semantics</title></rfc-entry>";
+        RfcIndex::parse(xml).store(&cache).unwrap();
+        let fetcher = DocumentFetcher::new().unwrap();
+
+        let http = relations(&DocumentType::Rfc(2616), &fetcher, &cache)
+            .await
+            .unwrap();
+        assert_eq!(
+            http.obsoleted_by,
+            vec![DocumentType::Rfc(7230), DocumentType::Rfc(7231)]
+        );
+        assert_eq!(
+            latest(&DocumentType::Rfc(2616), &fetcher, &cache)
+                .await
+                .unwrap(),
+            DocumentType::Rfc(9110)
+        );
+        assert!(
+            relations(&DocumentType::Draft("draft-x".into()), &fetcher, &cache)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stale_banner_from_the_cached_index() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let xml = "<rfc-entry><doc-id>RFC9000</doc-id><title>QUIC</title>\
+<draft>draft-ietf-quic-transport-34</draft></rfc-entry>";
+        RfcIndex::parse(xml).store(&cache).unwrap();
+        let tracker = DataTrackerClient::new()
+            .unwrap()
+            .with_offline_mode(crate::api::OfflineMode::Offline);
+
+        let draft = DocumentType::Draft("draft-ietf-quic-transport-29".to_string());
+        let banner = stale_banner(&draft, Some(&tracker), &cache).await.unwrap();
+        assert_eq!(banner.status, DraftStatus::Published { rfc: 9000 });
+        assert_eq!(banner.recommended(), Some(DocumentType::Rfc(9000)));
+
+        let unknown = DocumentType::Draft("draft-ietf-quic-qlog".to_string());
+        assert_eq!(stale_banner(&unknown, Some(&tracker), &cache).await, None);
+        assert_eq!(
+            stale_banner(&DocumentType::Rfc(9000), Some(&tracker), &cache).await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stale_banner_from_cached_metadata() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let draft = DocumentType::Draft("draft-thomson-quic-tls-01".to_string());
+        assert_eq!(stale_banner(&draft, None, &cache).await, None);
+
+        // Metadata is cached for the latest revision
+        let latest = DocumentType::Draft("draft-thomson-quic-tls".to_string());
+        let meta: crate::models::DocumentMetadata = serde_json::from_value(serde_json::json!({
+            "name": "draft-thomson-quic-tls",
+            "doc_type": { "Draft": "draft-thomson-quic-tls" },
+            "title": "Porting QUIC to TLS",
+            "authors": [],
+            "state": "Replaced",
+        }))
+        .unwrap();
+        cache.store_metadata(&latest, &meta).unwrap();
+        let banner = stale_banner(&draft, None, &cache).await.unwrap();
+        assert_eq!(banner.status, DraftStatus::Replaced { by: Vec::new() });
+    }
+}
@@ -1,7 +1,40 @@
+pub mod abnf;
+pub mod analyze;
 pub mod api;
+pub mod blocking;
+pub mod bookmarks;
 pub mod cache;
+pub mod cite;
+pub mod daemon;
+mod error;
+pub mod export;
+pub mod hooks;
+pub mod humanize;
+pub mod info;
+pub mod lint;
+pub mod messages;
 pub mod models;
+pub mod parse;
+pub mod paths;
+pub mod plugin;
+pub mod profile;
+pub mod queue;
+pub mod render;
+pub mod response;
+pub mod search;
+pub mod service;
+pub mod tasks;
+pub mod workspace;
 
 pub use api::{DataTrackerClient, DocumentFetcher};
 pub use cache::CacheManager;
-pub use models::{Document, DocumentType, Format, SearchFilter, SearchResult};
+pub use error::{Error, Result};
+pub use info::{info, relations};
+pub use messages::{Locale, Message};
+pub use models::{
+    Author, Cluster, Document, DocumentInfo, DocumentMetadata, DocumentType, DraftRevision,
+    Erratum, Format, FormatPreference, FormatStrategy, License, ParseError, Relations, RfcMetadata,
+    SearchFilter, SearchResult, SectionMatch, Stream, Subseries,
+};
+pub use plugin::PluginRegistry;
+pub use workspace::Workspace;
@@ -0,0 +1,249 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::analyze::{check_references, ReferenceIssueKind};
+use crate::api::DocumentFetcher;
+use crate::models::DocumentType;
+use crate::parse::{boilerplate, citations, sections, BoilerplateKind};
+
+/// Maximum line length allowed by the RFC style guide
+const MAX_LINE_LENGTH: usize = 72;
+
+/// BCP 14 keywords whose use requires the requirements language paragraph
+pub(crate) const BCP14_KEYWORDS: &[&str] = &[
+    "MUST",
+    "MUST NOT",
+    "REQUIRED",
+    "SHALL",
+    "SHALL NOT",
+    "SHOULD",
+    "SHOULD NOT",
+    "RECOMMENDED",
+    "NOT RECOMMENDED",
+    "MAY",
+    "OPTIONAL",
+];
+
+/// Kind of problem found by a lint check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LintKind {
+    /// A required boilerplate section is missing
+    MissingBoilerplate,
+    /// BCP 14 keywords are used without the requirements language paragraph
+    MissingBcp14,
+    /// A line exceeds 72 characters
+    LineTooLong,
+    /// A citation has no entry in the References sections
+    UnlistedReference,
+    /// A References entry is never cited
+    UncitedReference,
+    /// A cited RFC has been obsoleted
+    ObsoleteCitation,
+}
+
+/// How serious a lint issue is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl LintKind {
+    /// Get the severity idnits assigns to this kind of issue
+    pub fn severity(&self) -> Severity {
+        match self {
+            LintKind::MissingBoilerplate | LintKind::LineTooLong => Severity::Error,
+            LintKind::MissingBcp14
+            | LintKind::UnlistedReference
+            | LintKind::UncitedReference
+            | LintKind::ObsoleteCitation => Severity::Warning,
+        }
+    }
+}
+
+/// A problem found in a document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintIssue {
+    pub kind: LintKind,
+    /// Line of the problem (0-based), if it has one
+    pub line: Option<usize>,
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Run the offline checks against a plain text draft
+pub fn lint(text: &str) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    issues.extend(check_boilerplate(text));
+    issues.extend(check_line_lengths(text));
+    issues.extend(check_citations(text));
+    issues.sort_by_key(|i| i.line);
+    issues
+}
+
+/// Run all checks, including those that look up cited RFCs
+pub async fn lint_online(text: &str, fetcher: &DocumentFetcher) -> crate::Result<Vec<LintIssue>> {
+    let mut issues = lint(text);
+    issues.extend(obsolete_citations(text, fetcher).await?);
+    issues.sort_by_key(|i| i.line);
+    Ok(issues)
+}
+
+/// Check that Abstract, Status of This Memo, Copyright Notice and, if keywords
+/// are used, the BCP 14 paragraph are present
+fn check_boilerplate(text: &str) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+    let found = boilerplate(text);
+    let has = |kind| found.iter().any(|b| b.kind == kind);
+
+    if !sections(text).iter().any(|s| s.title == "Abstract") {
+        issues.push(missing("Abstract"));
+    }
+    for kind in [
+        BoilerplateKind::StatusOfMemo,
+        BoilerplateKind::CopyrightNotice,
+    ] {
+        if !has(kind) {
+            issues.push(missing(kind.label()));
+        }
+    }
+
+    if !has(BoilerplateKind::Bcp14) {
+        if let Some((line, keyword)) = first_keyword(text) {
+            issues.push(LintIssue {
+                kind: LintKind::MissingBcp14,
+                line: Some(line),
+                message: format!(
+                    "'{}' is used but the BCP 14 requirements language paragraph is missing",
+                    keyword
+                ),
+            });
+        }
+    }
+
+    issues
+}
+
+fn missing(section: &str) -> LintIssue {
+    LintIssue {
+        kind: LintKind::MissingBoilerplate,
+        line: None,
+        message: format!("Missing '{}' section", section),
+    }
+}
+
+/// Find the first line using a BCP 14 keyword
+fn first_keyword(text: &str) -> Option<(usize, &'static str)> {
+    text.lines().enumerate().find_map(|(i, line)| {
+        line.split(|c: char| !c.is_ascii_alphabetic())
+            .find_map(|word| BCP14_KEYWORDS.iter().find(|k| **k == word).copied())
+            .map(|k| (i, k))
+    })
+}
+
+/// Check that no line exceeds 72 characters
+fn check_line_lengths(text: &str) -> Vec<LintIssue> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let length = line.trim_end().chars().count();
+            (length > MAX_LINE_LENGTH).then(|| LintIssue {
+                kind: LintKind::LineTooLong,
+                line: Some(i),
+                message: format!(
+                    "Line is {} characters long (max {})",
+                    length, MAX_LINE_LENGTH
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Check that citations and References entries match up
+fn check_citations(text: &str) -> Vec<LintIssue> {
+    check_references(text)
+        .into_iter()
+        .map(|issue| match issue.kind {
+            ReferenceIssueKind::Unlisted => LintIssue {
+                kind: LintKind::UnlistedReference,
+                line: Some(issue.line),
+                message: format!(
+                    "[{}] is cited but not listed in the References",
+                    issue.anchor
+                ),
+            },
+            ReferenceIssueKind::Uncited => LintIssue {
+                kind: LintKind::UncitedReference,
+                line: Some(issue.line),
+                message: format!(
+                    "[{}] is listed in the References but never cited",
+                    issue.anchor
+                ),
+            },
+        })
+        .collect()
+}
+
+/// Warn about citations of RFCs that have been obsoleted
+async fn obsolete_citations(text: &str, fetcher: &DocumentFetcher) -> Result<Vec<LintIssue>> {
+    let mut issues = Vec::new();
+    let mut checked: Vec<u32> = Vec::new();
+
+    for citation in citations(text) {
+        let DocumentType::Rfc(number) = citation.document else {
+            continue;
+        };
+        if checked.contains(&number) {
+            continue;
+        }
+        checked.push(number);
+
+        let metadata = fetcher.rfc_metadata(number).await?;
+        if metadata.is_obsoleted() {
+            let by: Vec<String> = metadata
+                .obsoleted_by
+                .iter()
+                .map(|d| d.to_string())
+                .collect();
+            issues.push(LintIssue {
+                kind: LintKind::ObsoleteCitation,
+                line: Some(citation.line),
+                message: format!("[{}] is obsoleted by {}", citation.anchor, by.join(", ")),
+            });
+        }
+    }
+
+    Ok(issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DRAFT: &str = "Abstract\n\n   A protocol.\n\n\
+Status of This Memo\n\n   This Internet-Draft is submitted in full conformance.\n\n\
+1.  Introduction\n\n   Clients MUST use TLS [RFC8446], per Section 4 of [RFC9001].\n\
+   This line is much too long for an RFC because it goes well past the limit.\n\n\
+2.  Normative References\n\n   [RFC8446]  Rescorla, E., \"The Transport Layer Security (TLS) Protocol\n              Version 1.3\", RFC 8446, August 2018.\n";
+
+    #[test]
+    fn test_lint() {
+        let issues = lint(DRAFT);
+        let kinds: Vec<LintKind> = issues.iter().map(|i| i.kind).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                LintKind::MissingBoilerplate,
+                LintKind::MissingBcp14,
+                LintKind::UnlistedReference,
+                LintKind::LineTooLong,
+            ]
+        );
+        assert_eq!(issues[0].message, "Missing 'Copyright Notice' section");
+        assert_eq!(issues[1].line, Some(10));
+        assert!(issues[2].message.starts_with("[RFC9001]"));
+        assert_eq!(issues[3].line, Some(11));
+        assert_eq!(issues[3].kind.severity(), Severity::Error);
+    }
+}
@@ -1,10 +1,20 @@
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches, Parser};
 use std::env;
 use std::io::Write;
 use std::process::{Command, Stdio};
-
-use rfc::{CacheManager, DataTrackerClient, DocumentFetcher, DocumentType, Format, SearchFilter};
+use std::sync::{Arc, OnceLock};
+
+use chrono::Utc;
+use rfc::export::ExportFormat;
+use rfc::hooks::HookEvent;
+use rfc::humanize::{format_size, relative_time};
+use rfc::render::{BoilerplateMode, RenderOptions, Theme};
+use rfc::response::{CacheListing, CacheRemoval, Envelope, FetchResponse, Response};
+use rfc::{
+    CacheManager, DataTrackerClient, Document, DocumentFetcher, DocumentInfo, DocumentType, Format,
+    FormatPreference, Locale, Message, PluginRegistry, SearchFilter, Stream,
+};
 
 #[derive(Parser)]
 #[command(name = "rfc")]
@@ -19,6 +29,14 @@ struct Cli {
     #[arg(short, long, value_name = "QUERY")]
     search: Option<String>,
 
+    /// Search the text of cached documents instead of the Datatracker (with -s)
+    #[arg(long, requires = "search", conflicts_with = "author")]
+    local: bool,
+
+    /// Only find documents written by an author (with -s, or alone for all of them)
+    #[arg(long, value_name = "NAME")]
+    author: Option<String>,
+
     /// Use PAGER instead of EDITOR
     #[arg(short, long)]
     pager: bool,
@@ -31,10 +49,131 @@ struct Cli {
     #[arg(short, long)]
     fresh: bool,
 
+    /// Never use the network; serve only cached documents
+    #[arg(long, conflicts_with = "fresh")]
+    offline: bool,
+
+    /// Print the time spent on network, parsing, rendering and cache IO
+    #[arg(long)]
+    profile: bool,
+
     /// Open document in web browser (IETF Datatracker)
     #[arg(short = 'w', long, conflicts_with_all = ["pager", "open_with", "fresh"])]
     web: bool,
 
+    /// Render for screen readers (no ASCII art, explicit section labels)
+    #[arg(long, conflicts_with = "web")]
+    accessible: bool,
+
+    /// Style headings, links, page headers and BCP 14 keywords for the terminal
+    #[arg(long, conflicts_with_all = ["web", "accessible"])]
+    color: bool,
+
+    /// How to render standard boilerplate: show, fold or skip
+    #[arg(long, value_name = "MODE", conflicts_with = "web")]
+    boilerplate: Option<String>,
+
+    /// Read the newest RFC that obsoletes the document instead
+    #[arg(long, conflicts_with = "web")]
+    latest: bool,
+
+    /// Read a community translation (e.g., "ja"); RFC_TRANSLATION_URL overrides the source
+    #[arg(long, value_name = "LANG", conflicts_with = "web")]
+    lang: Option<String>,
+
+    /// Show document metadata, status and relations instead of viewing it
+    #[arg(short, long, conflicts_with_all = ["pager", "open_with", "fresh", "web"])]
+    info: bool,
+
+    /// Print a BibTeX entry citing the document
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "fresh", "web", "info"])]
+    bibtex: bool,
+
+    /// Print the document's xml2rfc reference from the IETF bibxml service
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info", "bibtex"])]
+    bibxml: bool,
+
+    /// Print the document's ABNF grammar with imported rules resolved
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info"])]
+    abnf: bool,
+
+    /// Print only the document's own ABNF rules as one grammar, noting the section
+    /// each comes from (DOCUMENT may be a local file)
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info", "abnf"])]
+    collected_abnf: bool,
+
+    /// Check the document's ABNF for syntax errors and undefined rules
+    /// (DOCUMENT may be a local file)
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info", "abnf"])]
+    validate_abnf: bool,
+
+    /// Run idnits-style checks (DOCUMENT may be a local file)
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info", "abnf", "validate_abnf"])]
+    lint: bool,
+
+    /// Print the document as Markdown (DOCUMENT may be a local text or HTML file)
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info", "abnf", "validate_abnf", "lint"])]
+    markdown: bool,
+
+    /// Print the document's table of contents (DOCUMENT may be a local file)
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info", "abnf", "validate_abnf", "lint", "markdown"])]
+    toc: bool,
+
+    /// List the document's figures, diagrams and code samples (DOCUMENT may be a
+    /// local text or XML file)
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info", "abnf", "toc", "markdown"])]
+    artwork: bool,
+
+    /// Only list artwork of one type, e.g. "json" or "ascii-art" (with --artwork)
+    #[arg(long, value_name = "TYPE", requires = "artwork")]
+    artwork_type: Option<String>,
+
+    /// List every MUST/SHOULD/MAY sentence with its section, e.g. for a conformance
+    /// checklist (DOCUMENT may be a local file; see --export)
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info", "abnf", "validate_abnf", "lint", "markdown", "toc"])]
+    requirements: bool,
+
+    /// Summarize the registry actions of the document's IANA Considerations
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info", "abnf"])]
+    iana: bool,
+
+    /// List the errata reported against an RFC
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info", "abnf", "iana"])]
+    errata: bool,
+
+    /// List the documents published in the same RFC Editor cluster
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info", "abnf", "iana"])]
+    cluster: bool,
+
+    /// List every published revision of a draft with its date
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info", "abnf", "iana"])]
+    revisions: bool,
+
+    /// Report the sections added, removed, renamed or modified since OLD
+    /// (DOCUMENT and OLD may be local files)
+    #[arg(long, value_name = "OLD", conflicts_with_all = ["pager", "open_with", "web", "info", "abnf", "iana"])]
+    diff: Option<String>,
+
+    /// Print only the Security Considerations section, with its references resolved
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info", "abnf", "iana"])]
+    security: bool,
+
+    /// Print only one section, given by number, anchor or title, with its references resolved
+    #[arg(long, value_name = "SECTION", conflicts_with_all = ["pager", "open_with", "web", "info", "abnf", "iana", "security"])]
+    section: Option<String>,
+
+    /// List the document's references with the documents they point to
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info", "abnf", "iana", "security"])]
+    references: bool,
+
+    /// Download every document the document references into the cache
+    #[arg(long, conflicts_with_all = ["pager", "open_with", "web", "info", "abnf", "iana", "security", "references"])]
+    fetch_references: bool,
+
+    /// Only use Normative References (with --references or --fetch-references)
+    #[arg(long)]
+    normative: bool,
+
     /// Only show drafts (with -s)
     #[arg(short, long, conflicts_with = "all")]
     drafts: bool,
@@ -43,10 +182,51 @@ struct Cli {
     #[arg(short, long, conflicts_with = "drafts")]
     all: bool,
 
+    /// List documents of an IRTF research group
+    #[arg(long, value_name = "ACRONYM")]
+    rg: Option<String>,
+
+    /// List the active drafts and published RFCs of an IETF working group
+    #[arg(long, value_name = "ACRONYM", conflicts_with = "rg")]
+    wg: Option<String>,
+
     /// Limit search results (with -s)
     #[arg(short, long)]
     limit: Option<usize>,
 
+    /// Continue a search at the offset printed with the previous page (with -s)
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    offset: u64,
+
+    /// Open the Nth search result (with -s), scrolled to the section that matched
+    #[arg(long, value_name = "N", conflicts_with = "local")]
+    open_hit: Option<usize>,
+
+    /// Print a JSON envelope instead of human-readable output (search, info,
+    /// fetch and cache operations)
+    #[arg(long)]
+    json: bool,
+
+    /// Print the JSON Schema of the --json envelope
+    #[arg(long, conflicts_with = "json")]
+    json_schema: bool,
+
+    /// Look up a term in the Terminology sections of DOCUMENT (or all cached documents)
+    #[arg(long, value_name = "TERM")]
+    define: Option<String>,
+
+    /// Serve JSON-RPC lookups (resolve, fetch, section, citation) on stdin/stdout
+    #[arg(long)]
+    lookup_service: bool,
+
+    /// Own the cache and a warm search index, serving other rfc processes until Ctrl-C
+    #[arg(long)]
+    daemon: bool,
+
+    /// Write search results and listings as csv or jsonl instead of text
+    #[arg(long, value_name = "FORMAT")]
+    export: Option<String>,
+
     /// List cached documents
     #[arg(long)]
     list_cache: bool,
@@ -62,15 +242,213 @@ struct Cli {
     /// Remove a document from cache
     #[arg(long, value_name = "DOC")]
     uncache: Option<String>,
+
+    /// Update the cache metadata index from the cached files
+    #[cfg(feature = "index")]
+    #[arg(long)]
+    reindex: bool,
+
+    /// Fetch every document listed in a TOML or JSON manifest (default: the workspace's) into the cache
+    #[arg(long, value_name = "MANIFEST", num_args = 0..=1)]
+    sync: Option<Option<std::path::PathBuf>>,
+
+    /// Download the index of all RFCs for offline metadata lookups
+    #[arg(long)]
+    sync_index: bool,
+
+    /// Download a comma-separated list or range of documents (e.g., 9000-9114) into the cache
+    #[arg(long, value_name = "DOCS")]
+    prefetch: Option<String>,
+
+    /// Maximum number of concurrent downloads for --prefetch
+    #[arg(long, value_name = "N", default_value_t = rfc::api::DEFAULT_PARALLELISM)]
+    jobs: usize,
+
+    /// Write a checksummed bundle of the cache (or of the --sync manifest) to DIR
+    ///
+    /// A path ending in .tar.gz or .tgz writes a single portable archive instead.
+    #[arg(long, value_name = "DIR")]
+    export_bundle: Option<std::path::PathBuf>,
+
+    /// Write a citation for every cached document (or the --sync manifest's) to FILE
+    ///
+    /// A path ending in .bib writes BibTeX; one ending in .xml writes bibxml
+    /// references for xml2rfc.
+    #[arg(long, value_name = "FILE")]
+    bibliography: Option<std::path::PathBuf>,
+
+    /// Only cite the documents of this manifest collection (with --bibliography and --sync)
+    #[arg(long, value_name = "NAME", requires_all = ["bibliography", "sync"])]
+    collection: Option<String>,
+
+    /// Verify a bundle (directory or .tar.gz archive) and copy its documents into the cache
+    #[arg(long, value_name = "DIR", conflicts_with = "export_bundle")]
+    import_bundle: Option<std::path::PathBuf>,
+
+    /// Compare the cached text and HTML renderings of a document
+    #[arg(long, value_name = "DOC")]
+    verify_cache: Option<String>,
+
+    /// Show where and when each cached copy of a document was fetched
+    #[arg(long, value_name = "DOC")]
+    provenance: Option<String>,
+
+    /// Replace the pinned copy of an RFC with the changed copy put aside on refetch
+    #[arg(long, value_name = "DOC")]
+    accept_change: Option<String>,
+
+    /// Bookmark a document or one of its sections (e.g., "RFC 9110 §15.5.4")
+    #[arg(long, value_name = "TARGET")]
+    bookmark: Option<String>,
+
+    /// Note to keep with the --bookmark
+    #[arg(long, value_name = "TEXT", requires = "bookmark")]
+    note: Option<String>,
+
+    /// List bookmarks
+    #[arg(long)]
+    bookmarks: bool,
+
+    /// Remove a bookmark
+    #[arg(long, value_name = "TARGET")]
+    unbookmark: Option<String>,
+
+    /// Open the Nth bookmark (as numbered by --bookmarks), scrolled to its section
+    #[arg(long, value_name = "N")]
+    open_bookmark: Option<usize>,
+
+    /// Put a document on the reading list, or update its state with --state
+    #[arg(long, value_name = "DOC")]
+    queue: Option<String>,
+
+    /// Reading state for --queue: to-read, in-progress or done
+    #[arg(long, value_name = "STATE", requires = "queue")]
+    state: Option<String>,
+
+    /// Show the reading list by state
+    #[arg(long)]
+    reading_list: bool,
+
+    /// Take a document off the reading list
+    #[arg(long, value_name = "DOC")]
+    dequeue: Option<String>,
+
+    /// Write the reading list to FILE, for --import-queue on another device
+    #[arg(long, value_name = "FILE")]
+    export_queue: Option<std::path::PathBuf>,
+
+    /// Merge a reading list written by --export-queue; newer changes win
+    #[arg(long, value_name = "FILE", conflicts_with = "export_queue")]
+    import_queue: Option<std::path::PathBuf>,
+
+    /// Write the reading list to FILE as tasks (todo.txt, or org-mode/Markdown by extension)
+    #[arg(long, value_name = "FILE")]
+    export_tasks: Option<std::path::PathBuf>,
+
+    /// Task format for --export-tasks: todo.txt, org or markdown
+    #[arg(long, value_name = "FORMAT", requires = "export_tasks")]
+    task_format: Option<String>,
+}
+
+/// Options that `json_response` honours; any other option is rejected with --json
+const JSON_SUPPORTED: [&str; 16] = [
+    "document",
+    "search",
+    "author",
+    "fresh",
+    "offline",
+    "profile",
+    "info",
+    "drafts",
+    "all",
+    "limit",
+    "offset",
+    "json",
+    "list_cache",
+    "clear_cache",
+    "cache_info",
+    "uncache",
+];
+
+/// Parse the command line, rejecting options that have no JSON response under --json
+fn parse_cli<I, T>(args: I) -> std::result::Result<Cli, clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let mut command = Cli::command();
+    let matches = command.try_get_matches_from_mut(args)?;
+    if matches.get_flag("json") {
+        let unsupported = command.get_arguments().find(|arg| {
+            let id = arg.get_id().as_str();
+            !JSON_SUPPORTED.contains(&id)
+                && matches.value_source(id) == Some(clap::parser::ValueSource::CommandLine)
+        });
+        if let Some(arg) = unsupported {
+            let name = arg.get_long().unwrap_or(arg.get_id().as_str());
+            return Err(command.error(
+                clap::error::ErrorKind::ArgumentConflict,
+                format!("the argument '--json' cannot be used with '--{}'", name),
+            ));
+        }
+    }
+    Cli::from_arg_matches(&matches)
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let cli = parse_cli(std::env::args_os()).unwrap_or_else(|err| err.exit());
+    if cli.offline {
+        rfc::api::OfflineMode::force_offline();
+    }
+    if cli.profile {
+        rfc::profile::enable();
+    }
+    let export =
+        match &cli.export {
+            Some(format) => Some(ExportFormat::parse(format).with_context(|| {
+                format!("Invalid export format '{}' (use csv or jsonl)", format)
+            })?),
+            None => None,
+        };
+
+    if cli.json_schema {
+        print!("{}", rfc::response::RESPONSE_SCHEMA);
+        return Ok(());
+    }
+    if cli.json {
+        return print_json(&cli).await;
+    }
+    let (result, timings) = rfc::profile::measured(run(&cli, export)).await;
+    if let Some(timings) = timings {
+        eprintln!("Profile: {}", timings);
+    }
+    result
+}
+
+/// Run the operation requested on the command line
+async fn run(cli: &Cli, export: Option<ExportFormat>) -> Result<()> {
+    if cli.lookup_service {
+        let service =
+            rfc::service::LookupService::new(CacheManager::new()?, DocumentFetcher::new()?);
+        let stdin = tokio::io::BufReader::new(tokio::io::stdin());
+        return Ok(service.run(stdin, tokio::io::stdout()).await?);
+    }
+    if cli.daemon {
+        let cache = CacheManager::new()?;
+        eprintln!(
+            "Serving {} on {}",
+            cache.cache_dir().display(),
+            rfc::daemon::Daemon::socket_path(&cache).display()
+        );
+        return Ok(rfc::daemon::Daemon::new(cache, DocumentFetcher::new()?)
+            .serve()
+            .await?);
+    }
 
     // Handle cache operations first
     if cli.list_cache {
-        return list_cache();
+        return list_cache(export);
     }
     if cli.clear_cache {
         return clear_cache();
@@ -81,9 +459,103 @@ async fn main() -> Result<()> {
     if let Some(doc) = &cli.uncache {
         return uncache_document(doc);
     }
+    #[cfg(feature = "index")]
+    if cli.reindex {
+        return reindex();
+    }
+    if let Some(doc) = &cli.verify_cache {
+        return verify_cache(doc);
+    }
+    if let Some(doc) = &cli.provenance {
+        return show_provenance(doc);
+    }
+    if let Some(doc) = &cli.accept_change {
+        return accept_change(doc);
+    }
+    if let Some(target) = &cli.bookmark {
+        return add_bookmark(target, cli.note.as_deref());
+    }
+    if cli.bookmarks {
+        return list_bookmarks();
+    }
+    if let Some(target) = &cli.unbookmark {
+        return remove_bookmark(target);
+    }
+    if let Some(n) = cli.open_bookmark {
+        return open_bookmark(n, cli.pager, cli.open_with.as_deref(), cli.fresh).await;
+    }
+    if let Some(doc) = &cli.queue {
+        return queue_document(doc, cli.state.as_deref());
+    }
+    if cli.reading_list {
+        return show_reading_list();
+    }
+    if let Some(doc) = &cli.dequeue {
+        return dequeue_document(doc);
+    }
+    if let Some(path) = &cli.export_queue {
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        rfc::queue::ReadingQueue::open()?.export(std::io::BufWriter::new(file))?;
+        println!("Wrote reading list to {}", path.display());
+        return Ok(());
+    }
+    if let Some(path) = &cli.import_queue {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut queue = rfc::queue::ReadingQueue::open()?;
+        let changed = queue.import(std::io::BufReader::new(file))?;
+        queue.save()?;
+        println!(
+            "Merged {} reading list entries from {}",
+            changed,
+            path.display()
+        );
+        return Ok(());
+    }
+    if let Some(path) = &cli.export_tasks {
+        return export_tasks(path, cli.task_format.as_deref());
+    }
+    let manifest = cli
+        .sync
+        .as_ref()
+        .map(|path| load_manifest(path.as_deref()))
+        .transpose()?;
+    if let Some(path) = &cli.bibliography {
+        return write_bibliography(path, manifest.as_ref(), cli.collection.as_deref()).await;
+    }
+    if let Some(dir) = &cli.export_bundle {
+        return export_bundle(dir, manifest.as_ref()).await;
+    }
+    if let Some(dir) = &cli.import_bundle {
+        let cache = CacheManager::new()?;
+        let count = if rfc::cache::is_archive(dir) {
+            cache.import(dir)?
+        } else {
+            cache.import_bundle(dir)?
+        };
+        println!("Imported {} documents from {}", count, dir.display());
+        return Ok(());
+    }
+    if cli.sync_index {
+        let cache = CacheManager::new()?;
+        let index = rfc::api::RfcIndex::sync(&DocumentFetcher::new()?, &cache).await?;
+        println!("Indexed {} RFCs", index.len());
+        return Ok(());
+    }
+    if let Some(spec) = &cli.prefetch {
+        return prefetch(spec, cli.jobs).await;
+    }
+    if let Some(manifest) = &manifest {
+        return sync(manifest).await;
+    }
 
     // Handle search
-    if let Some(query) = &cli.search {
+    if let (Some(query), true) = (&cli.search, cli.local) {
+        return search_local(query, cli.limit.unwrap_or(20)).await;
+    }
+    if cli.search.is_some() || cli.author.is_some() {
+        let query = cli.search.as_deref().unwrap_or_default();
         let filter = if cli.drafts {
             SearchFilter::DraftsOnly
         } else if cli.all {
@@ -91,17 +563,128 @@ async fn main() -> Result<()> {
         } else {
             SearchFilter::RfcsOnly
         };
-        return search_documents(query, cli.limit.unwrap_or(100), filter).await;
+        if let Some(n) = cli.open_hit {
+            let author = cli.author.as_deref();
+            let open_with = cli.open_with.as_deref();
+            return open_search_hit(query, author, filter, n, cli.pager, open_with, cli.fresh)
+                .await;
+        }
+        return search_documents(
+            query,
+            cli.author.as_deref(),
+            cli.limit.unwrap_or(100),
+            cli.offset,
+            filter,
+            export,
+        )
+        .await;
+    }
+    if let Some(term) = &cli.define {
+        return define_term(term, cli.document.as_deref(), cli.fresh).await;
+    }
+    if let Some(acronym) = &cli.rg {
+        return list_rg_documents(acronym, cli.limit.unwrap_or(100), export).await;
+    }
+    if let Some(acronym) = &cli.wg {
+        return list_wg_documents(acronym, export).await;
     }
 
     // Default: view document
     if let Some(document) = &cli.document {
+        if cli.info {
+            return show_info(document).await;
+        }
+        if cli.bibtex {
+            let doc_type = parse_document(document)?;
+            println!("{}", rfc::cite::bibtex(&doc_type).await?);
+            return Ok(());
+        }
+        if cli.bibxml {
+            return print_bibxml(document, cli.fresh).await;
+        }
+        if cli.abnf {
+            return print_abnf(document, cli.fresh).await;
+        }
+        if cli.collected_abnf {
+            return print_collected_abnf(document, cli.fresh).await;
+        }
+        if cli.validate_abnf {
+            return validate_abnf(document, cli.fresh).await;
+        }
+        if cli.lint {
+            return lint_document(document, cli.fresh).await;
+        }
+        if cli.markdown {
+            return print_markdown(document, cli.fresh).await;
+        }
+        if cli.toc {
+            return print_toc(document, cli.fresh).await;
+        }
+        if cli.artwork {
+            return print_artwork(document, cli.artwork_type.as_deref()).await;
+        }
+        if cli.requirements {
+            return print_requirements(document, cli.fresh, export).await;
+        }
+        if cli.iana {
+            return show_iana(document, cli.fresh).await;
+        }
+        if cli.errata {
+            return show_errata(document, cli.fresh).await;
+        }
+        if cli.cluster {
+            return show_cluster(document).await;
+        }
+        if cli.revisions {
+            return show_revisions(document).await;
+        }
+        if let Some(old) = &cli.diff {
+            return show_diff(old, document, cli.fresh).await;
+        }
+        if cli.security {
+            return show_security(document, cli.fresh).await;
+        }
+        if let Some(section) = &cli.section {
+            return show_section(document, section, cli.fresh).await;
+        }
+        if cli.references || cli.fetch_references {
+            return show_references(
+                document,
+                cli.normative,
+                cli.fetch_references,
+                cli.jobs,
+                cli.fresh,
+            )
+            .await;
+        }
+
+        let document = if cli.latest {
+            latest_successor(document).await?
+        } else {
+            document.clone()
+        };
+        if let Some(lang) = &cli.lang {
+            return view_translation(
+                &document,
+                lang,
+                cli.pager,
+                cli.open_with.as_deref(),
+                cli.fresh,
+            )
+            .await;
+        }
         return view_document(
-            document,
+            &document,
             cli.pager,
             cli.open_with.as_deref(),
             cli.fresh,
             cli.web,
+            cli.accessible,
+            RenderOptions {
+                boilerplate: parse_boilerplate(cli.boilerplate.as_deref())?,
+                banner: None,
+                theme: cli.color.then(Theme::default),
+            },
         )
         .await;
     }
@@ -109,10 +692,38 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Render a message in the locale selected by the environment
+fn localize(message: Message) -> String {
+    message.localized(Locale::from_env())
+}
+
+/// Get the renderers and document sources documents are resolved, fetched and rendered with
+fn plugins() -> &'static Arc<PluginRegistry> {
+    static PLUGINS: OnceLock<Arc<PluginRegistry>> = OnceLock::new();
+    PLUGINS.get_or_init(|| Arc::new(PluginRegistry::with_builtin_renderers()))
+}
+
+/// Create a fetcher backed by the cache that warns when a published RFC changed
+fn cached_fetcher(cache: &CacheManager) -> Result<DocumentFetcher> {
+    Ok(DocumentFetcher::new()?
+        .with_cache(cache.clone())
+        .with_plugins(plugins().clone())
+        .with_hook(|event: &HookEvent| {
+            if let HookEvent::ContentChanged { change } = event {
+                let message = Message::ContentChanged(change.doc.clone(), change.url.clone());
+                eprintln!("{}", localize(message));
+                if let Some(diff) = &change.diff {
+                    eprint!("{}", diff);
+                }
+            }
+            Ok(())
+        }))
+}
+
 /// Parse document identifier into DocumentType
 fn parse_document(doc: &str) -> Result<DocumentType> {
-    // First try the standard parser
-    if let Some(doc_type) = DocumentType::parse(doc) {
+    // First try the standard parser, with the schemes of registered sources
+    if let Some(doc_type) = plugins().resolve(doc) {
         return Ok(doc_type);
     }
 
@@ -126,6 +737,69 @@ fn parse_document(doc: &str) -> Result<DocumentType> {
     Ok(DocumentType::Draft(draft_name))
 }
 
+/// Resolve a document to the newest RFC that obsoletes it
+async fn latest_successor(document: &str) -> Result<String> {
+    let doc_type = parse_document(document)?;
+    let cache = CacheManager::new()?;
+    let fetcher = DocumentFetcher::new()?;
+    let latest = rfc::info::latest(&doc_type, &fetcher, &cache).await?;
+    if latest != doc_type {
+        eprintln!(
+            "{}",
+            localize(Message::ObsoletedBy(doc_type, vec![latest.clone()]))
+        );
+    }
+    Ok(latest.name())
+}
+
+/// View a community translation of a document using EDITOR or PAGER
+async fn view_translation(
+    document: &str,
+    lang: &str,
+    use_pager: bool,
+    open_with: Option<&str>,
+    fresh: bool,
+) -> Result<()> {
+    let doc_type = parse_document(document)?;
+    let cache = CacheManager::new()?;
+    let cached = if fresh {
+        None
+    } else {
+        cache.get_translation(&doc_type, lang)
+    };
+    let (content, format) = match cached {
+        Some(translation) => translation,
+        None => {
+            let mut fetcher = DocumentFetcher::new()?;
+            if let Ok(template) = env::var("RFC_TRANSLATION_URL") {
+                fetcher = fetcher.with_translation_source(lang, &template);
+            }
+            eprintln!("{}", localize(Message::Fetching(doc_type.clone())));
+            let (content, format) = fetcher.fetch_translation(&doc_type, lang).await?;
+            cache.store_translation(&doc_type, lang, format, &content)?;
+            (content, format)
+        }
+    };
+    let text = match format {
+        Format::Html => html_to_text(&content),
+        _ => content,
+    };
+    open_in_viewer(&text, use_pager, open_with)
+}
+
+/// Parse the --boilerplate mode, showing boilerplate if none is given
+fn parse_boilerplate(mode: Option<&str>) -> Result<BoilerplateMode> {
+    match mode {
+        Some(mode) => BoilerplateMode::parse(mode).with_context(|| {
+            format!(
+                "Invalid boilerplate mode '{}' (use show, fold or skip)",
+                mode
+            )
+        }),
+        None => Ok(BoilerplateMode::Show),
+    }
+}
+
 /// View a document using EDITOR or PAGER
 async fn view_document(
     document: &str,
@@ -133,6 +807,8 @@ async fn view_document(
     open_with: Option<&str>,
     fresh: bool,
     web: bool,
+    accessible: bool,
+    mut options: RenderOptions,
 ) -> Result<()> {
     let doc_type = parse_document(document)?;
 
@@ -142,19 +818,47 @@ async fn view_document(
     }
 
     let cache = CacheManager::new()?;
-    let rfc_editor = DocumentFetcher::new()?;
+    let rfc_editor = cached_fetcher(&cache)?;
+
+    // Only the synchronized index is consulted, so viewing stays offline-capable
+    if let DocumentType::Rfc(number) = doc_type {
+        let meta = rfc::api::RfcIndex::cached(&cache).and_then(|i| i.lookup(number).cloned());
+        if let Some(successor) = meta
+            .as_ref()
+            .and_then(|m| m.relations().successor().cloned())
+        {
+            let by = meta.map(|m| m.obsoleted_by).unwrap_or_default();
+            eprintln!("{}", localize(Message::ObsoletedBy(doc_type.clone(), by)));
+            eprintln!("{}", localize(Message::ReadLatest(successor)));
+        }
+    }
 
-    // Check cache first (unless fresh requested)
-    let content = if !fresh {
-        if let Some(cached) = cache.get_document(&doc_type, Format::Text) {
-            eprintln!("Using cached copy of {}", doc_type);
-            cached
-        } else {
-            fetch_and_cache(&doc_type, &cache, &rfc_editor).await?
+    // Drafts that were published, replaced or expired get a status banner. The
+    // Datatracker isn't asked about a fresh cached copy, or while offline.
+    let cached = !fresh
+        && cache.cached_formats(&doc_type).contains(&Format::Text)
+        && !cache.is_stale(&doc_type);
+    let online = !cached && !rfc::api::OfflineMode::from_env().is_offline();
+    let tracker = match (&doc_type, online) {
+        (DocumentType::Draft(_), true) => Some(DataTrackerClient::new()?),
+        _ => None,
+    };
+    let (content, banner) = tokio::join!(
+        load_document(&doc_type, &cache, &rfc_editor, fresh),
+        rfc::info::stale_banner(&doc_type, tracker.as_ref(), &cache)
+    );
+    let content = match content {
+        Ok(content) => content,
+        Err(err) => {
+            suggest_similar(&doc_type, &cache);
+            return Err(err);
         }
-    } else {
-        fetch_and_cache(&doc_type, &cache, &rfc_editor).await?
     };
+    options.banner = banner;
+    let content = options.apply(&content);
+
+    let renderer = if accessible { "accessible" } else { "plain" };
+    let content = plugins().render(renderer, &content, Format::Text)?;
 
     // Open in editor or pager
     open_in_viewer(&content, use_pager, open_with)?;
@@ -162,24 +866,180 @@ async fn view_document(
     Ok(())
 }
 
+/// Point out cached or workspace documents named like one that could not be loaded
+fn suggest_similar(doc: &DocumentType, cache: &CacheManager) {
+    let mut known = cache.list_cached();
+    if let Some(workspace) = rfc::Workspace::current().ok().flatten() {
+        known.extend(
+            workspace
+                .documents()
+                .iter()
+                .filter_map(|entry| entry.resolve().ok())
+                .map(|(doc, _)| doc),
+        );
+    }
+    let suggestions = rfc::search::fuzzy::suggest_documents(doc, &known, 3);
+    if !suggestions.is_empty() {
+        eprintln!("{}", localize(Message::DidYouMean(suggestions)));
+    }
+}
+
+/// Run the requested operation and print its result as a JSON envelope
+async fn print_json(cli: &Cli) -> Result<()> {
+    let envelope = Envelope::from(json_response(cli).await);
+    println!("{}", envelope.to_json()?);
+    if !envelope.ok {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Run the requested operation, returning its typed response
+async fn json_response(cli: &Cli) -> Result<Response> {
+    let cache = CacheManager::new()?;
+
+    if cli.list_cache || cli.cache_info {
+        return Ok(Response::CacheList(CacheListing {
+            directory: cache.cache_dir().to_path_buf(),
+            documents: cache.list_cached(),
+            size: cache.total_size(),
+        }));
+    }
+    if cli.clear_cache {
+        cache.clear_cache()?;
+        return Ok(Response::CacheClear);
+    }
+    if let Some(document) = &cli.uncache {
+        let doc_type = parse_document(document)?;
+        let removed = cache.remove(&doc_type)?;
+        return Ok(Response::CacheRemove(CacheRemoval {
+            document: doc_type,
+            removed,
+        }));
+    }
+    if cli.search.is_some() || cli.author.is_some() {
+        let query = cli.search.as_deref().unwrap_or_default();
+        let filter = if cli.drafts {
+            SearchFilter::DraftsOnly
+        } else if cli.all {
+            SearchFilter::Both
+        } else {
+            SearchFilter::RfcsOnly
+        };
+        let client = DataTrackerClient::new()?;
+        let results = client
+            .search_page(
+                query,
+                cli.author.as_deref(),
+                filter,
+                cli.limit.unwrap_or(100) as u32,
+                cli.offset,
+            )
+            .await?
+            .with_cached_sections(&cache);
+        return Ok(Response::Search(results));
+    }
+
+    let Some(document) = &cli.document else {
+        anyhow::bail!("--json supports viewing, searching, --info and cache operations");
+    };
+    let doc_type = parse_document(document)?;
+    let fetcher = cached_fetcher(&cache)?;
+
+    if cli.info {
+        let tracker = DataTrackerClient::new()?;
+        let info = rfc::info(&doc_type, &tracker, &fetcher, &cache).await?;
+        return Ok(Response::Info(Box::new(info)));
+    }
+
+    let (fetched, timings) = rfc::profile::measured(async {
+        let cached = if cli.fresh {
+            None
+        } else {
+            cache.get_fresh(&doc_type, Format::Text)
+        };
+        anyhow::Ok(match cached {
+            Some(content) => (true, content),
+            None => (false, fetch_and_cache(&doc_type, &cache, &fetcher).await?),
+        })
+    })
+    .await;
+    let (cached, content) = fetched?;
+    let response = FetchResponse {
+        name: doc_type.name(),
+        format: Format::Text,
+        cached,
+        content,
+        timings,
+        document: doc_type,
+    };
+    Ok(Response::Fetch(response))
+}
+
+/// Read a local file, or load the document it names; returns a display name and the text
+async fn load_file_or_document(
+    document: &str,
+    fetcher: &DocumentFetcher,
+    fresh: bool,
+) -> Result<(String, String)> {
+    match std::fs::read_to_string(document) {
+        Ok(text) => Ok((document.to_string(), text)),
+        Err(_) => {
+            let doc_type = parse_document(document)?;
+            let cache = CacheManager::new()?;
+            let text = load_document(&doc_type, &cache, fetcher, fresh).await?;
+            Ok((doc_type.to_string(), text))
+        }
+    }
+}
+
+/// Get a document's text from the cache, fetching it if missing or if fresh is requested
+async fn load_document(
+    doc_type: &DocumentType,
+    cache: &CacheManager,
+    rfc_editor: &DocumentFetcher,
+    fresh: bool,
+) -> Result<String> {
+    if !fresh {
+        if let Some(cached) = cache.get_fresh(doc_type, Format::Text) {
+            eprintln!("{}", localize(Message::UsingCached(doc_type.clone())));
+            return Ok(cached);
+        }
+    }
+    match fetch_and_cache(doc_type, cache, rfc_editor).await {
+        Ok(text) => Ok(text),
+        // Serve an expired copy rather than nothing when the refetch fails
+        Err(err) => match cache.get_document(doc_type, Format::Text) {
+            Some(stale) => {
+                let err = format!("{:#}", err);
+                eprintln!("{}", localize(Message::UsingStale(doc_type.clone(), err)));
+                Ok(stale)
+            }
+            None => Err(err),
+        },
+    }
+}
+
 /// Fetch document and store in cache
 async fn fetch_and_cache(
     doc_type: &DocumentType,
     cache: &CacheManager,
     rfc_editor: &DocumentFetcher,
 ) -> Result<String> {
-    eprintln!("Fetching {}...", doc_type);
+    eprintln!("{}", localize(Message::Fetching(doc_type.clone())));
 
-    // Try text first, fall back to HTML
-    let (content, format) = rfc_editor.fetch(doc_type).await?;
+    // Follow the configured order of formats, leaving out those that can't be displayed
+    let preference = displayable(&rfc_editor.format_preference(doc_type))?;
+    let (content, format) = rfc_editor.fetch_preferring(doc_type, &preference).await?;
 
     // Convert HTML to text if needed
     let text = match format {
         Format::Text => content,
         Format::Html => {
-            eprintln!("Plain text not available, converting from HTML...");
+            eprintln!("{}", localize(Message::ConvertingHtml));
             html_to_text(&content)
         }
+        Format::Xml | Format::Pdf => rfc::render::to_text(content, format, 80)?,
     };
 
     // Cache the text content
@@ -188,12 +1048,26 @@ async fn fetch_and_cache(
     Ok(text)
 }
 
+/// Drop the formats that can't be displayed (xml2rfc sources) from a format preference
+fn displayable(preference: &FormatPreference) -> Result<FormatPreference> {
+    let formats = preference
+        .formats()
+        .iter()
+        .copied()
+        .filter(|format| matches!(format, Format::Text | Format::Html));
+    let displayable = FormatPreference::new(formats);
+    if displayable.formats().is_empty() {
+        anyhow::bail!("RFC_FORMAT_PREFERENCE lists no format that can be displayed (text, html)");
+    }
+    Ok(displayable)
+}
+
 /// Convert HTML to plain text
 fn html_to_text(html: &str) -> String {
-    html2text::from_read(html.as_bytes(), 80).unwrap_or_else(|e| {
+    rfc::render::html_to_text(html, 80).unwrap_or_else(|e| {
         eprintln!(
-            "Warning: HTML to text conversion failed ({}), displaying raw HTML",
-            e
+            "{}",
+            localize(Message::HtmlConversionFailed(format!("{:#}", e)))
         );
         html.to_string()
     })
@@ -202,13 +1076,23 @@ fn html_to_text(html: &str) -> String {
 /// Open a document in the default web browser
 fn open_in_browser(doc_type: &DocumentType) -> Result<()> {
     let url = doc_type.datatracker_url();
-    eprintln!("Opening {} in browser...", doc_type);
+    eprintln!("{}", localize(Message::OpeningBrowser(doc_type.clone())));
     opener::open(&url).with_context(|| format!("Failed to open URL: {}", url))?;
     Ok(())
 }
 
 /// Open text in EDITOR or PAGER
 fn open_in_viewer(text: &str, use_pager: bool, open_with: Option<&str>) -> Result<()> {
+    open_in_viewer_at(text, use_pager, open_with, None)
+}
+
+/// Open text in the viewer, scrolled to a line (1-based) if the viewer supports "+LINE"
+fn open_in_viewer_at(
+    text: &str,
+    use_pager: bool,
+    open_with: Option<&str>,
+    line: Option<usize>,
+) -> Result<()> {
     let viewer = if let Some(program) = open_with {
         program.to_string()
     } else if use_pager {
@@ -226,10 +1110,28 @@ fn open_in_viewer(text: &str, use_pager: bool, open_with: Option<&str>) -> Resul
         || viewer.contains("less")
         || viewer.contains("more");
 
+    let program = std::path::Path::new(&viewer)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let jump = line
+        .filter(|_| {
+            [
+                "less", "more", "most", "vi", "vim", "nvim", "view", "nano", "emacs", "micro",
+            ]
+            .contains(&program.as_str())
+        })
+        .map(|line| format!("+{}", line));
+
     if is_pager {
         // Pipe to pager
-        let mut child = Command::new(&viewer)
-            .stdin(Stdio::piped())
+        let mut command = Command::new(&viewer);
+        command.args(&jump).stdin(Stdio::piped());
+        // less shows escape sequences literally unless told otherwise
+        if text.contains('\x1b') && env::var_os("LESS").is_none() {
+            command.env("LESS", "-R");
+        }
+        let mut child = command
             .spawn()
             .with_context(|| format!("Failed to start pager: {}", viewer))?;
 
@@ -245,6 +1147,7 @@ fn open_in_viewer(text: &str, use_pager: bool, open_with: Option<&str>) -> Resul
         temp_file.flush()?;
 
         let status = Command::new(&viewer)
+            .args(&jump)
             .arg(temp_file.path())
             .status()
             .with_context(|| format!("Failed to start editor: {}", viewer))?;
@@ -257,46 +1160,833 @@ fn open_in_viewer(text: &str, use_pager: bool, open_with: Option<&str>) -> Resul
     Ok(())
 }
 
+/// Search the text of cached documents
+async fn search_local(query: &str, limit: usize) -> Result<()> {
+    let cache = CacheManager::new()?;
+    eprintln!("{}", localize(Message::Searching(query.to_string())));
+
+    let hits = rfc::daemon::search_local(&cache, query, limit).await;
+    if hits.is_empty() {
+        println!("{}", localize(Message::NoResults(query.to_string())));
+        return Ok(());
+    }
+
+    println!("\n{}\n", localize(Message::FoundResults(hits.len())));
+    for (i, hit) in hits.iter().enumerate() {
+        match &hit.section {
+            Some(section) => println!("{}. {} ({})", i + 1, hit.doc, section),
+            None => println!("{}. {}", i + 1, hit.doc),
+        }
+        println!("   {}", hit.snippet);
+    }
+    println!("\n{}", localize(Message::ReadHint));
+    Ok(())
+}
+
 /// Search for documents
-async fn search_documents(query: &str, limit: usize, filter: SearchFilter) -> Result<()> {
+async fn search_documents(
+    query: &str,
+    author: Option<&str>,
+    limit: usize,
+    offset: u64,
+    filter: SearchFilter,
+    export: Option<ExportFormat>,
+) -> Result<()> {
     let client = DataTrackerClient::new()?;
+    let label = match author {
+        Some(author) if query.is_empty() => format!("author: {}", author),
+        Some(author) => format!("{} (author: {})", query, author),
+        None => query.to_string(),
+    };
 
-    eprintln!("Searching for '{}'...", query);
+    eprintln!("{}", localize(Message::Searching(label.clone())));
 
-    let results = client.search(query, filter, limit as u32).await?;
+    let results = client
+        .search_page(query, author, filter, limit as u32, offset)
+        .await?
+        .with_cached_sections(&CacheManager::new()?);
+    if let Some(format) = export {
+        return Ok(results.export(format, std::io::stdout().lock())?);
+    }
 
     if results.is_empty() {
-        println!("No results found for '{}'", query);
+        println!("{}", localize(Message::NoResults(label)));
         return Ok(());
     }
 
     let shown = results.len();
-    println!("\nFound {} results:\n", shown);
+    println!("\n{}\n", localize(Message::FoundResults(shown)));
 
     for (i, doc) in results.documents.iter().enumerate() {
-        println!("{}. {} - {}", i + 1, doc.doc_type, doc.title);
+        println!("{}. {}", i + 1, listing_line(doc));
+        if let Some(hit) = doc.matches.first() {
+            match &hit.snippet {
+                Some(snippet) => println!("   {}: {}", hit.anchor, snippet),
+                None => println!("   {}", hit.anchor),
+            }
+        }
     }
 
-    if results.has_more {
-        println!("\n(More results available. Use -l to show more.)");
+    if let Some(next) = results.next_offset {
+        println!("\n{}", localize(Message::MoreResults));
+        println!("{}", localize(Message::NextPage(next)));
     }
 
-    println!("\nUse 'rfc <document>' to read a document");
+    println!("\n{}", localize(Message::ReadHint));
 
     Ok(())
 }
 
+/// List the documents of an IRTF research group
+async fn list_rg_documents(
+    acronym: &str,
+    limit: usize,
+    export: Option<ExportFormat>,
+) -> Result<()> {
+    let client = DataTrackerClient::new()?;
+    let documents = client.rg_documents(acronym, limit as u32).await?;
+    if let Some(format) = export {
+        return Ok(rfc::export::export_documents(
+            &documents,
+            format,
+            std::io::stdout().lock(),
+        )?);
+    }
+
+    if documents.is_empty() {
+        println!("No documents found for research group '{}'", acronym);
+        return Ok(());
+    }
+
+    println!(
+        "Documents of {} ({}):\n",
+        acronym.to_uppercase(),
+        documents.len()
+    );
+    for doc in &documents {
+        println!("  {}", listing_line(doc));
+    }
+
+    Ok(())
+}
+
+/// List the drafts and RFCs of an IETF working group with their state
+async fn list_wg_documents(acronym: &str, export: Option<ExportFormat>) -> Result<()> {
+    let client = DataTrackerClient::new()?;
+    let documents = client.wg_documents(acronym).await?;
+    if let Some(format) = export {
+        return Ok(rfc::export::export_documents(
+            &documents,
+            format,
+            std::io::stdout().lock(),
+        )?);
+    }
+
+    if documents.is_empty() {
+        println!("No documents found for working group '{}'", acronym);
+        return Ok(());
+    }
+
+    println!(
+        "Documents of {} ({}):\n",
+        acronym.to_uppercase(),
+        documents.len()
+    );
+    for doc in &documents {
+        let revision = doc.rev.as_deref().map(|r| format!("-{}", r));
+        let state = doc.state.as_deref().unwrap_or("-");
+        println!(
+            "  {:<7} {}{} - {}",
+            state,
+            doc.name,
+            revision.unwrap_or_default(),
+            doc.title
+        );
+    }
+
+    Ok(())
+}
+
+/// Format a document for listings, labeling documents outside the IETF stream
+fn listing_line(doc: &Document) -> String {
+    match doc.stream {
+        Some(stream) if stream != Stream::Ietf => {
+            format!("{} - {} [{}]", doc.doc_type, doc.title, stream)
+        }
+        _ => format!("{} - {}", doc.doc_type, doc.title),
+    }
+}
+
+/// Print a document's ABNF with imported rules resolved
+async fn print_abnf(document: &str, fresh: bool) -> Result<()> {
+    let doc_type = parse_document(document)?;
+    let cache = CacheManager::new()?;
+    let fetcher = cached_fetcher(&cache)?;
+
+    let text = load_document(&doc_type, &cache, &fetcher, fresh).await?;
+    let grammar = rfc::abnf::resolve_grammar(&text, &fetcher, &[]).await?;
+    warn_failed_imports(&grammar);
+
+    if grammar.rules.is_empty() {
+        println!("No ABNF rules found in {}", doc_type);
+        return Ok(());
+    }
+
+    print!("{}", grammar.to_abnf());
+    if !grammar.is_complete() {
+        eprintln!(
+            "Warning: unresolved rules: {}",
+            grammar.unresolved.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Print a document, or a local text or HTML file, as Markdown
+async fn print_markdown(document: &str, fresh: bool) -> Result<()> {
+    let fetcher = DocumentFetcher::new()?;
+    let (_, content) = load_file_or_document(document, &fetcher, fresh).await?;
+    let format = std::path::Path::new(document)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(Format::from_extension)
+        .filter(|_| std::path::Path::new(document).is_file())
+        .unwrap_or(Format::Text);
+    print!("{}", rfc::render::to_markdown(&content, format)?);
+    Ok(())
+}
+
+/// Print the section tree of a document or a local file
+async fn print_toc(document: &str, fresh: bool) -> Result<()> {
+    let fetcher = DocumentFetcher::new()?;
+    let (name, text) = load_file_or_document(document, &fetcher, fresh).await?;
+    let toc = rfc::parse::toc(&text);
+    if toc.is_empty() {
+        println!("No sections found in {}", name);
+        return Ok(());
+    }
+    print!("{}", toc);
+    Ok(())
+}
+
+/// Print the figures and code samples of a document or a local file
+async fn print_artwork(document: &str, content_type: Option<&str>) -> Result<()> {
+    let path = std::path::Path::new(document);
+    let (name, artwork) = if path.is_file() {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", document))?;
+        let artwork = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("xml") => rfc::parse::xml_artwork(&content),
+            _ => rfc::parse::artwork(&content),
+        };
+        (document.to_string(), artwork)
+    } else {
+        let doc_type = parse_document(document)?;
+        let cache = CacheManager::new()?;
+        let fetcher = cached_fetcher(&cache)?;
+        (doc_type.to_string(), fetcher.artwork(&doc_type).await?)
+    };
+
+    let artwork: Vec<_> = artwork
+        .into_iter()
+        .filter(|a| content_type.is_none_or(|t| a.content_type.as_deref() == Some(t)))
+        .collect();
+    if artwork.is_empty() {
+        println!("No artwork found in {}", name);
+        return Ok(());
+    }
+    for (i, art) in artwork.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let content_type = art.content_type.as_deref().unwrap_or("unknown");
+        println!(
+            "{} [{}, {}, line {}]",
+            art.label(),
+            art.kind,
+            content_type,
+            art.line + 1
+        );
+        println!();
+        for line in art.content.lines() {
+            println!("   {}", line);
+        }
+    }
+    Ok(())
+}
+
+/// Print the BCP 14 requirements of a document or a local file as a checklist
+async fn print_requirements(
+    document: &str,
+    fresh: bool,
+    export: Option<ExportFormat>,
+) -> Result<()> {
+    let fetcher = DocumentFetcher::new()?;
+    let (name, text) = load_file_or_document(document, &fetcher, fresh).await?;
+    let requirements = rfc::analyze::requirements(&text);
+    if let Some(format) = export {
+        return Ok(rfc::export::export_requirements(
+            &requirements,
+            format,
+            std::io::stdout().lock(),
+        )?);
+    }
+    if requirements.is_empty() {
+        println!("No BCP 14 requirements found in {}", name);
+        return Ok(());
+    }
+
+    let mut section = None;
+    for requirement in &requirements {
+        if requirement.section.as_ref() != section {
+            if section.is_some() {
+                println!();
+            }
+            section = requirement.section.as_ref();
+            if let Some(section) = section {
+                println!("{}", section);
+            }
+        }
+        println!("- [ ] {}: {}", requirement.level, requirement.sentence);
+    }
+    Ok(())
+}
+
+/// Print the ABNF rules of a document or a local file, with a comment naming each section
+async fn print_collected_abnf(document: &str, fresh: bool) -> Result<()> {
+    let fetcher = DocumentFetcher::new()?;
+    let (name, text) = load_file_or_document(document, &fetcher, fresh).await?;
+    let grammar = rfc::abnf::collect_grammar(&text);
+    if grammar.is_empty() {
+        println!("No ABNF rules found in {}", name);
+        return Ok(());
+    }
+
+    let mut section = None;
+    for (i, line) in grammar.abnf.lines().enumerate() {
+        let location = grammar.locate(i).map(|l| l.section.as_deref());
+        if let Some(Some(label)) = location.filter(|l| *l != section) {
+            if i > 0 {
+                println!();
+            }
+            println!("; {}", label);
+        }
+        if let Some(current) = location {
+            section = current;
+        }
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// Warn about RFCs whose rules couldn't be imported into a grammar
+fn warn_failed_imports(grammar: &rfc::abnf::Grammar) {
+    for failed in &grammar.failed_imports {
+        eprintln!(
+            "Warning: could not import rules from RFC {}: {}",
+            failed.number, failed.error
+        );
+    }
+}
+
+/// Validate a document's ABNF, resolving imports from referenced RFCs
+async fn validate_abnf(document: &str, fresh: bool) -> Result<()> {
+    let fetcher = DocumentFetcher::new()?;
+    let (name, text) = load_file_or_document(document, &fetcher, fresh).await?;
+
+    let grammar = rfc::abnf::resolve_grammar(&text, &fetcher, &[]).await?;
+    warn_failed_imports(&grammar);
+    if grammar.rules.is_empty() {
+        println!("No ABNF rules found in {}", name);
+        return Ok(());
+    }
+
+    let diagnostics = grammar.validate();
+    for diagnostic in &diagnostics {
+        // Imported rules are located in the RFC they come from
+        let location = diagnostic
+            .origin
+            .as_ref()
+            .map_or_else(|| name.clone(), |doc| doc.name());
+        println!(
+            "{}:{}: {} (in rule '{}')",
+            location,
+            diagnostic.line + 1,
+            diagnostic.message,
+            diagnostic.rule
+        );
+    }
+
+    if diagnostics.is_empty() {
+        println!("{}: {} rules, no problems found", name, grammar.rules.len());
+        Ok(())
+    } else {
+        anyhow::bail!("{} problems found in {}", diagnostics.len(), name)
+    }
+}
+
+/// Run the lint checks against a local file or document and print the issues found
+async fn lint_document(document: &str, fresh: bool) -> Result<()> {
+    let fetcher = DocumentFetcher::new()?;
+    let (name, text) = load_file_or_document(document, &fetcher, fresh).await?;
+
+    let issues = match rfc::lint::lint_online(&text, &fetcher).await {
+        Ok(issues) => issues,
+        Err(e) => {
+            eprintln!("Skipping obsolete citation check: {:#}", e);
+            rfc::lint::lint(&text)
+        }
+    };
+
+    for issue in &issues {
+        let severity = match issue.kind.severity() {
+            rfc::lint::Severity::Error => "error",
+            rfc::lint::Severity::Warning => "warning",
+        };
+        match issue.line {
+            Some(line) => println!("{}:{}: {}: {}", name, line + 1, severity, issue.message),
+            None => println!("{}: {}: {}", name, severity, issue.message),
+        }
+    }
+
+    let errors = issues
+        .iter()
+        .filter(|i| i.kind.severity() == rfc::lint::Severity::Error)
+        .count();
+    if errors > 0 {
+        anyhow::bail!(
+            "{} errors, {} warnings in {}",
+            errors,
+            issues.len() - errors,
+            name
+        )
+    }
+    if issues.is_empty() {
+        println!("{}: no problems found", name);
+    }
+    Ok(())
+}
+
+/// Print the IANA actions requested by a document
+async fn show_iana(document: &str, fresh: bool) -> Result<()> {
+    let doc_type = parse_document(document)?;
+    let cache = CacheManager::new()?;
+    let fetcher = cached_fetcher(&cache)?;
+    let text = load_document(&doc_type, &cache, &fetcher, fresh).await?;
+
+    let Some(iana) = rfc::analyze::iana_considerations(&text) else {
+        println!("{} has no IANA Considerations section", doc_type);
+        return Ok(());
+    };
+    if iana.no_actions || iana.actions.is_empty() {
+        println!("{} requests no IANA actions", doc_type);
+        return Ok(());
+    }
+
+    for action in &iana.actions {
+        let section = action.section.as_deref().unwrap_or("-");
+        println!("Section {}: {}", section, action.title);
+        println!("  Action:   {:?}", action.kind);
+        if let Some(registry) = &action.registry {
+            println!("  Registry: {}", registry);
+        }
+        if let Some(policy) = &action.policy {
+            println!("  Policy:   {}", policy);
+        }
+        for entry in &action.entries {
+            let fields: Vec<String> = entry
+                .fields
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect();
+            println!("  - {}", fields.join("; "));
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Print the section-level changes between two documents
+async fn show_diff(old: &str, new: &str, fresh: bool) -> Result<()> {
+    use rfc::analyze::SectionChange;
+
+    let fetcher = DocumentFetcher::new()?;
+    let (old_name, old_text) = load_file_or_document(old, &fetcher, fresh).await?;
+    let (new_name, new_text) = load_file_or_document(new, &fetcher, fresh).await?;
+
+    let changes = rfc::analyze::diff_sections(&old_text, &new_text);
+    if changes.is_empty() {
+        println!("No section changes between {} and {}", old_name, new_name);
+        return Ok(());
+    }
+    for change in &changes {
+        match change {
+            SectionChange::Added(section) => println!("+ {}", section),
+            SectionChange::Removed(section) => println!("- {}", section),
+            SectionChange::Modified(section) => println!("~ {}", section),
+            SectionChange::Renamed { old, new, modified } => {
+                let marker = if *modified { "~" } else { " " };
+                println!("{} {} (was {})", marker, new, old);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Print the definitions of a term found in a document or in the cache
+async fn define_term(term: &str, document: Option<&str>, fresh: bool) -> Result<()> {
+    let cache = CacheManager::new()?;
+    let fetcher = cached_fetcher(&cache)?;
+
+    let mut texts = Vec::new();
+    match document {
+        Some(document) => {
+            let doc_type = parse_document(document)?;
+            let text = load_document(&doc_type, &cache, &fetcher, fresh).await?;
+            texts.push((doc_type, text));
+        }
+        None => {
+            for doc_type in cache.list_cached() {
+                if let Some(text) = cache.peek_document(&doc_type, Format::Text) {
+                    texts.push((doc_type, text));
+                }
+            }
+        }
+    }
+
+    let definitions = rfc::analyze::define(term, texts.iter().map(|(d, t)| (d, t.as_str())));
+    if definitions.is_empty() {
+        println!("No definition of '{}' found", term);
+        return Ok(());
+    }
+    for definition in &definitions {
+        println!(
+            "{} ({}, {}):",
+            definition.term, definition.document, definition.section
+        );
+        println!("  {}\n", definition.definition);
+    }
+    Ok(())
+}
+
+/// Print the cluster a document was published in
+async fn show_cluster(document: &str) -> Result<()> {
+    let doc_type = parse_document(document)?;
+    let fetcher = DocumentFetcher::new()?;
+
+    let Some(cluster) = fetcher.cluster_of(&doc_type).await? else {
+        println!("{} is not part of a cluster", doc_type);
+        return Ok(());
+    };
+    println!("Cluster {} ({})", cluster.id, cluster.url());
+    for doc in &cluster.documents {
+        println!("  {}", doc);
+    }
+    Ok(())
+}
+
+/// Print the published revisions of a draft
+async fn show_revisions(document: &str) -> Result<()> {
+    let DocumentType::Draft(name) = parse_document(document)? else {
+        anyhow::bail!("Revisions are only listed for Internet-Drafts");
+    };
+    let revisions = DocumentFetcher::new()?.draft_revisions(&name).await?;
+
+    let now = Utc::now();
+    for revision in &revisions {
+        match revision.published {
+            Some(published) => println!(
+                "  {}  {} ({})",
+                revision.document(),
+                published.format("%Y-%m-%d"),
+                relative_time(published, now)
+            ),
+            None => println!("  {}", revision.document()),
+        }
+    }
+    if revisions.is_empty() {
+        println!("No revisions of {} found", name);
+    }
+    Ok(())
+}
+
+/// Print the errata of an RFC
+async fn show_errata(document: &str, fresh: bool) -> Result<()> {
+    let DocumentType::Rfc(number) = parse_document(document)? else {
+        anyhow::bail!("Errata are only published for RFCs");
+    };
+    let cache = CacheManager::new()?;
+    let fetcher = cached_fetcher(&cache)?;
+
+    let errata = if fresh {
+        let errata = fetcher.errata(number).await?;
+        cache.store_errata(number, &errata)?;
+        errata
+    } else {
+        fetcher.errata_cached(number, &cache).await?
+    };
+
+    if errata.is_empty() {
+        println!("No errata reported for RFC {}", number);
+        return Ok(());
+    }
+    for erratum in &errata {
+        let section = erratum.section.as_deref().unwrap_or("-");
+        println!(
+            "Erratum {} ({:?}, {}), section {}",
+            erratum.id,
+            erratum.kind,
+            erratum.status.label(),
+            section
+        );
+        println!("  {}", erratum.url());
+        if let Some(url) = erratum.section_url() {
+            println!("  {}", url);
+        }
+        if let Some(corrected) = &erratum.corrected_text {
+            for line in corrected.lines() {
+                println!("    {}", line);
+            }
+        }
+        println!();
+    }
+    Ok(())
+}
+
+/// Print a document's Security Considerations and the references it makes
+async fn show_security(document: &str, fresh: bool) -> Result<()> {
+    let doc_type = parse_document(document)?;
+    let cache = CacheManager::new()?;
+    let fetcher = cached_fetcher(&cache)?;
+    let text = load_document(&doc_type, &cache, &fetcher, fresh).await?;
+
+    let parsed = rfc::parse::ParsedDocument::parse(&text);
+    let Some(section) = parsed.security_considerations() else {
+        println!("{} has no Security Considerations section", doc_type);
+        return Ok(());
+    };
+    print_excerpt(&parsed.excerpt(&text, section));
+    Ok(())
+}
+
+/// Print a single section of a document and the references it makes
+async fn show_section(document: &str, section: &str, fresh: bool) -> Result<()> {
+    let doc_type = parse_document(document)?;
+    let cache = CacheManager::new()?;
+    let fetcher = cached_fetcher(&cache)?;
+    let text = load_document(&doc_type, &cache, &fetcher, fresh).await?;
+
+    let parsed = rfc::parse::ParsedDocument::parse(&text);
+    let found = parsed
+        .find_section(section)
+        .with_context(|| format!("{} has no section '{}'", doc_type, section.trim()))?;
+    print_excerpt(&parsed.excerpt(&text, found));
+    Ok(())
+}
+
+/// List the References of a document, or download the documents they point to
+async fn show_references(
+    document: &str,
+    normative_only: bool,
+    fetch: bool,
+    jobs: usize,
+    fresh: bool,
+) -> Result<()> {
+    let doc_type = parse_document(document)?;
+    let cache = CacheManager::new()?;
+    let fetcher = cached_fetcher(&cache)?.with_parallelism(jobs);
+    let text = load_document(&doc_type, &cache, &fetcher, fresh).await?;
+
+    let parsed = cache.parsed_document(&doc_type)?;
+    let references: Vec<_> = parsed
+        .map(|parsed| parsed.references)
+        .unwrap_or_else(|| rfc::parse::references(&text))
+        .into_iter()
+        .filter(|r| r.normative || !normative_only)
+        .collect();
+    if references.is_empty() {
+        println!("{} has no references", doc_type);
+        return Ok(());
+    }
+
+    if fetch {
+        let mut docs: Vec<DocumentType> = Vec::new();
+        for target in references.iter().filter_map(|r| r.target.clone()) {
+            if !docs.contains(&target) {
+                docs.push(target);
+            }
+        }
+        let report = fetcher.prefetch(&docs, &cache).await;
+        print_report(&report);
+        if !report.is_complete() {
+            anyhow::bail!("Fetching references incomplete");
+        }
+        return Ok(());
+    }
+
+    for (normative, heading) in [
+        (true, "Normative References"),
+        (false, "Informative References"),
+    ] {
+        let group: Vec<_> = references
+            .iter()
+            .filter(|r| r.normative == normative)
+            .collect();
+        if group.is_empty() {
+            continue;
+        }
+        println!("{}:", heading);
+        for reference in group {
+            let target = match (&reference.target, &reference.url) {
+                (Some(doc), _) => doc.to_string(),
+                (None, Some(url)) => url.clone(),
+                (None, None) => "-".to_string(),
+            };
+            match &reference.title {
+                Some(title) => println!("  [{}] {}: {}", reference.anchor, target, title),
+                None => println!("  [{}] {}", reference.anchor, target),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Print the text of a section, followed by the sections and documents it refers to
+fn print_excerpt(excerpt: &rfc::parse::SectionExcerpt) {
+    println!("{}", excerpt.text);
+    if !excerpt.references.is_empty() {
+        println!("\nReferences:");
+        for reference in &excerpt.references {
+            match (&reference.title, &reference.document) {
+                (Some(title), _) => println!("  {}: {}", reference.label, title),
+                (None, Some(doc)) if reference.section.is_none() => {
+                    println!("  {}: {}", reference.label, doc)
+                }
+                _ => println!("  {}", reference.label),
+            }
+        }
+    }
+}
+
+/// Show aggregated document info
+async fn show_info(document: &str) -> Result<()> {
+    let doc_type = parse_document(document)?;
+    let tracker = DataTrackerClient::new()?;
+    let fetcher = DocumentFetcher::new()?;
+    let cache = CacheManager::new()?;
+
+    eprintln!("{}", localize(Message::LookingUp(doc_type.clone())));
+    let info = rfc::info(&doc_type, &tracker, &fetcher, &cache).await?;
+
+    print_info(&info);
+    if let Some(warning) = info.obsolescence_warning() {
+        println!("\n{}", localize(warning));
+    }
+    Ok(())
+}
+
+/// Print a `DocumentInfo` in human-readable form
+fn print_info(info: &DocumentInfo) {
+    fn doc_list(docs: &[DocumentType]) -> String {
+        docs.iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    println!("{}", info.doc_type);
+    if let Some(title) = info.title() {
+        println!("  Title:        {}", title);
+    }
+    if let Some(status) = info.status() {
+        println!("  Status:       {}", status);
+    }
+    if let Some(doc) = &info.document {
+        if !doc.authors.is_empty() {
+            let authors: Vec<_> = doc.authors.iter().map(|a| a.to_string()).collect();
+            println!("  Authors:      {}", authors.join(", "));
+        }
+        if let Some(wg) = &doc.wg {
+            println!("  Group:        {}", wg);
+        }
+        if let Some(stream) = doc.stream {
+            println!("  Stream:       {}", stream);
+        }
+        if let Some(state) = &doc.state {
+            println!("  State:        {}", state);
+        }
+        if let Some(expiry) = doc.expiry_relative(Utc::now()) {
+            println!("  Expiry:       {}", expiry);
+        }
+    }
+    if let Some(rev) = &info.latest_revision {
+        println!("  Latest rev:   {}", rev);
+    }
+    if let Some(date) = info.rfc.as_ref().and_then(|r| r.pub_date.as_ref()) {
+        println!("  Published:    {}", date);
+    } else if let Some(date) = info.document.as_ref().and_then(|d| d.published) {
+        println!(
+            "  Published:    {} ({})",
+            date.format("%Y-%m-%d"),
+            relative_time(date, Utc::now())
+        );
+    }
+    if !info.obsoletes().is_empty() {
+        println!("  Obsoletes:    {}", doc_list(info.obsoletes()));
+    }
+    if !info.obsoleted_by().is_empty() {
+        println!("  Obsoleted by: {}", doc_list(info.obsoleted_by()));
+    }
+    if !info.updates().is_empty() {
+        println!("  Updates:      {}", doc_list(info.updates()));
+    }
+    if !info.updated_by().is_empty() {
+        println!("  Updated by:   {}", doc_list(info.updated_by()));
+    }
+    if let Some(rfc) = &info.rfc {
+        println!("  DOI:          {}", rfc.doi());
+        println!("  URN:          {}", rfc.urn());
+        println!("  ISSN:         {}", rfc.issn());
+        if let Some(license) = &rfc.license {
+            println!("  License:      {}", license);
+        }
+    }
+    if let Some(count) = info.errata_count {
+        println!("  Errata:       {}", count);
+    }
+    if info.is_cached() {
+        let formats: Vec<_> = info.cached_formats.iter().map(|f| f.extension()).collect();
+        println!("  Cached:       yes ({})", formats.join(", "));
+    } else {
+        println!("  Cached:       no");
+    }
+}
+
 /// List cached documents
-fn list_cache() -> Result<()> {
+fn list_cache(export: Option<ExportFormat>) -> Result<()> {
     let cache = CacheManager::new()?;
     let cached = cache.list_cached();
+    if let Some(format) = export {
+        return Ok(rfc::export::export_listing(
+            &cached,
+            format,
+            std::io::stdout().lock(),
+        )?);
+    }
 
     if cached.is_empty() {
-        println!("Cache is empty");
+        println!("{}", localize(Message::CacheEmpty));
     } else {
-        println!("Cached documents ({}):\n", cached.len());
-        for doc_type in cached {
-            println!("  {}", doc_type);
+        println!("{}\n", localize(Message::CachedDocuments(cached.len())));
+        // Titles come from the synchronized RFC index, looked up in one pass
+        let index = rfc::api::RfcIndex::cached(&cache).unwrap_or_default();
+        for (doc_type, meta) in cached.iter().zip(index.metadata_many(&cached)) {
+            match meta {
+                Some(meta) => println!("  {:<10} {}", doc_type.to_string(), meta.title),
+                None => println!("  {}", doc_type),
+            }
         }
     }
 
@@ -307,7 +1997,7 @@ fn list_cache() -> Result<()> {
 fn clear_cache() -> Result<()> {
     let cache = CacheManager::new()?;
     cache.clear_cache()?;
-    println!("Cache cleared");
+    println!("{}", localize(Message::CacheCleared));
     Ok(())
 }
 
@@ -320,25 +2010,189 @@ fn cache_info() -> Result<()> {
     println!("Cache directory: {}", path.display());
     println!("Cached documents: {}", cached.len());
 
-    // Calculate total size
-    if let Ok(entries) = std::fs::read_dir(path) {
-        let total_size: u64 = entries
-            .filter_map(|e| e.ok())
-            .filter_map(|e| e.metadata().ok())
-            .map(|m| m.len())
-            .sum();
-
-        let size_str = if total_size < 1024 {
-            format!("{} B", total_size)
-        } else if total_size < 1024 * 1024 {
-            format!("{:.1} KB", total_size as f64 / 1024.0)
-        } else {
-            format!("{:.1} MB", total_size as f64 / (1024.0 * 1024.0))
-        };
+    println!("Total size: {}", format_size(cache.total_size()));
+
+    Ok(())
+}
+
+/// Update the cache metadata index, showing progress on stderr
+#[cfg(feature = "index")]
+fn reindex() -> Result<()> {
+    let cache = CacheManager::new()?;
+    let report = cache.index()?.rebuild_with_progress(&cache, |progress| {
+        eprint!("\rIndexed {}/{} documents", progress.done, progress.total);
+    })?;
+    eprintln!();
+    println!(
+        "{} added, {} updated, {} removed, {} unchanged",
+        report.added, report.updated, report.removed, report.unchanged
+    );
+    Ok(())
+}
+
+/// Load a manifest file, or the manifest of the working directory's workspace
+fn load_manifest(path: Option<&std::path::Path>) -> Result<rfc::cache::Manifest> {
+    Ok(match path {
+        Some(path) => rfc::cache::Manifest::load(path)?,
+        None => rfc::Workspace::current()?
+            .with_context(|| {
+                format!(
+                    "No manifest given and no {} found",
+                    rfc::workspace::WORKSPACE_FILE
+                )
+            })?
+            .manifest()?,
+    })
+}
+
+/// Cache everything listed in a manifest
+async fn sync(manifest: &rfc::cache::Manifest) -> Result<()> {
+    let cache = CacheManager::new()?;
+    let fetcher = cached_fetcher(&cache)?;
 
-        println!("Total size: {}", size_str);
+    let report = rfc::cache::sync_manifest(manifest, &cache, &fetcher).await?;
+    print_report(&report);
+    if !report.is_complete() {
+        anyhow::bail!("Manifest sync incomplete");
     }
+    Ok(())
+}
+
+/// Download a list or range of documents into the cache
+async fn prefetch(spec: &str, jobs: usize) -> Result<()> {
+    let docs = DocumentType::parse_list(spec)?;
+    let cache = CacheManager::new()?;
+    let fetcher = cached_fetcher(&cache)?.with_parallelism(jobs);
+
+    let report = fetcher.prefetch(&docs, &cache).await;
+    print_report(&report);
+    if !report.is_complete() {
+        anyhow::bail!("Prefetch incomplete");
+    }
+    Ok(())
+}
 
+/// Print the fetched and failed documents of a sync or prefetch
+fn print_report(report: &rfc::cache::SyncReport) {
+    for (doc, format) in &report.fetched {
+        println!("Fetched {} ({})", doc, format.extension());
+    }
+    for (doc, format, error) in &report.failed {
+        eprintln!("Failed {} ({}): {}", doc, format.extension(), error);
+    }
+    println!(
+        "{} fetched, {} already cached, {} failed",
+        report.fetched.len(),
+        report.present.len(),
+        report.failed.len()
+    );
+}
+
+/// Bundle the cache, or sync a manifest and bundle exactly its documents
+async fn export_bundle(
+    dir: &std::path::Path,
+    manifest: Option<&rfc::cache::Manifest>,
+) -> Result<()> {
+    let selected = match manifest {
+        Some(manifest) => {
+            sync(manifest).await?;
+            manifest.entries()?
+        }
+        None => Vec::new(),
+    };
+
+    let cache = CacheManager::new()?;
+    let index = if rfc::cache::is_archive(dir) {
+        cache.export_archive(&selected, dir)?
+    } else {
+        cache.export_bundle(&selected, dir)?
+    };
+    println!(
+        "Wrote {} documents to {}",
+        index.documents.len(),
+        dir.display()
+    );
+    Ok(())
+}
+
+/// Print a document's bibxml reference, from the cache unless fresh is requested
+async fn print_bibxml(document: &str, fresh: bool) -> Result<()> {
+    let doc_type = parse_document(document)?;
+    let cache = CacheManager::new()?;
+    let fetcher = DocumentFetcher::new()?;
+    let xml = if fresh {
+        let xml = fetcher.bibxml_reference(&doc_type).await?;
+        cache.store_bibxml(&doc_type, &xml)?;
+        xml
+    } else {
+        fetcher.bibxml_reference_cached(&doc_type, &cache).await?
+    };
+    print!("{}", xml);
+    if !xml.ends_with('\n') {
+        println!();
+    }
+    Ok(())
+}
+
+/// Write citations for the cache, a manifest or one of its collections to a file
+async fn write_bibliography(
+    path: &std::path::Path,
+    manifest: Option<&rfc::cache::Manifest>,
+    collection: Option<&str>,
+) -> Result<()> {
+    use rfc::cite::{BibliographyFormat, Citation};
+
+    let format = BibliographyFormat::from_path(path)
+        .context("Bibliography file must end in .bib or .xml")?;
+    let cache = CacheManager::new()?;
+    let mut docs: Vec<DocumentType> = match manifest {
+        Some(manifest) => {
+            let entries = match collection {
+                Some(name) => manifest.collection(name)?,
+                None => manifest.entries()?,
+            };
+            entries.into_iter().map(|(doc, _)| doc).collect()
+        }
+        None => cache.list_cached(),
+    };
+    let mut seen = std::collections::HashSet::new();
+    docs.retain(|doc| seen.insert(doc.clone()));
+
+    // RFC metadata comes from the synchronized index, everything else from
+    // one bulk Datatracker query
+    let index = rfc::api::RfcIndex::cached(&cache).unwrap_or_default();
+    let mut citations: Vec<Option<Citation>> = index
+        .metadata_many(&docs)
+        .into_iter()
+        .map(|meta| meta.map(Citation::from_rfc))
+        .collect();
+    let missing: Vec<DocumentType> = docs
+        .iter()
+        .zip(&citations)
+        .filter(|(_, c)| c.is_none())
+        .map(|(doc, _)| doc.clone())
+        .collect();
+    if !missing.is_empty() {
+        match DataTrackerClient::new()?.documents(&missing).await {
+            Ok(found) => {
+                let mut found = found.into_iter();
+                for citation in citations.iter_mut().filter(|c| c.is_none()) {
+                    *citation = found.next().flatten().map(|d| Citation::from_document(&d));
+                }
+            }
+            Err(e) => eprintln!("Citing without Datatracker metadata: {:#}", e),
+        }
+    }
+    let citations: Vec<Citation> = docs
+        .into_iter()
+        .zip(citations)
+        .map(|(doc, citation)| citation.unwrap_or_else(|| Citation::new(doc)))
+        .collect();
+
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    rfc::cite::write_bibliography(&citations, format, std::io::BufWriter::new(file))?;
+    println!("Wrote {} citations to {}", citations.len(), path.display());
     Ok(())
 }
 
@@ -348,10 +2202,399 @@ fn uncache_document(document: &str) -> Result<()> {
     let doc_type = parse_document(document)?;
 
     if cache.remove(&doc_type)? {
-        println!("Removed {} from cache", doc_type);
+        println!("{}", localize(Message::Removed(doc_type)));
+    } else {
+        println!("{}", localize(Message::NotCached(doc_type)));
+    }
+
+    Ok(())
+}
+
+/// Parse a bookmark target, failing with a hint on the accepted forms
+fn parse_bookmark_target(
+    target: &str,
+) -> Result<(DocumentType, Option<rfc::parse::SectionAnchor>)> {
+    rfc::bookmarks::Bookmark::parse_target(target).with_context(|| {
+        format!(
+            "Invalid bookmark '{}' (use e.g. \"RFC 9110\" or \"RFC 9110 §15.5.4\")",
+            target
+        )
+    })
+}
+
+/// Bookmark a document or section
+fn add_bookmark(target: &str, note: Option<&str>) -> Result<()> {
+    let (doc, section) = parse_bookmark_target(target)?;
+    let mut bookmark = rfc::bookmarks::Bookmark::new(doc, section);
+    if let Some(note) = note {
+        bookmark = bookmark.with_note(note);
+    }
+    let label = bookmark.target();
+    let mut bookmarks = rfc::bookmarks::Bookmarks::open()?;
+    if bookmarks.add(bookmark) {
+        println!("Bookmarked {}", label);
     } else {
-        println!("{} was not in cache", doc_type);
+        println!("Updated bookmark {}", label);
     }
+    Ok(bookmarks.save()?)
+}
 
+/// List bookmarks, numbered for --open-bookmark
+fn list_bookmarks() -> Result<()> {
+    let bookmarks = rfc::bookmarks::Bookmarks::open()?;
+    if bookmarks.entries().is_empty() {
+        println!("No bookmarks");
+        return Ok(());
+    }
+    for (i, bookmark) in bookmarks.entries().iter().enumerate() {
+        match &bookmark.note {
+            Some(note) => println!("{}. {} - {}", i + 1, bookmark.target(), note),
+            None => println!("{}. {}", i + 1, bookmark.target()),
+        }
+    }
     Ok(())
 }
+
+/// Remove a bookmark
+fn remove_bookmark(target: &str) -> Result<()> {
+    let (doc, section) = parse_bookmark_target(target)?;
+    let mut bookmarks = rfc::bookmarks::Bookmarks::open()?;
+    match bookmarks.remove(&doc, section.as_ref()) {
+        Some(bookmark) => {
+            bookmarks.save()?;
+            println!("Removed bookmark {}", bookmark.target());
+        }
+        None => println!("No bookmark for {}", target),
+    }
+    Ok(())
+}
+
+/// Open a bookmarked document at its section
+async fn open_bookmark(
+    n: usize,
+    use_pager: bool,
+    open_with: Option<&str>,
+    fresh: bool,
+) -> Result<()> {
+    let bookmarks = rfc::bookmarks::Bookmarks::open()?;
+    let bookmark = n
+        .checked_sub(1)
+        .and_then(|i| bookmarks.entries().get(i))
+        .with_context(|| format!("No bookmark {} (see --bookmarks)", n))?;
+
+    let section = bookmark.section.as_ref();
+    open_at_section(&bookmark.doc, section, use_pager, open_with, fresh).await
+}
+
+/// Run a search and open its Nth result at the section that matched
+async fn open_search_hit(
+    query: &str,
+    author: Option<&str>,
+    filter: SearchFilter,
+    n: usize,
+    use_pager: bool,
+    open_with: Option<&str>,
+    fresh: bool,
+) -> Result<()> {
+    let client = DataTrackerClient::new()?;
+    let limit = u32::try_from(n).unwrap_or(u32::MAX);
+    let results = client.search_page(query, author, filter, limit, 0).await?;
+    let doc = n
+        .checked_sub(1)
+        .and_then(|i| results.documents.get(i))
+        .with_context(|| format!("No search result {} ({} found)", n, results.len()))?;
+
+    // The hit is read to find the section matching the query, and cached for the viewer
+    let cache = CacheManager::new()?;
+    let text = load_document(&doc.doc_type, &cache, &cached_fetcher(&cache)?, fresh).await?;
+    let matches = rfc::search::section_matches(&text, query);
+    let section = matches.first().map(|m| &m.anchor);
+    open_at_section(&doc.doc_type, section, use_pager, open_with, false).await
+}
+
+/// Open a document in the viewer, scrolled to a section if given
+async fn open_at_section(
+    doc: &DocumentType,
+    section: Option<&rfc::parse::SectionAnchor>,
+    use_pager: bool,
+    open_with: Option<&str>,
+    fresh: bool,
+) -> Result<()> {
+    let cache = CacheManager::new()?;
+    let fetcher = cached_fetcher(&cache)?;
+    let content = load_document(doc, &cache, &fetcher, fresh).await?;
+
+    let line = match section {
+        Some(anchor) => {
+            let parsed = rfc::parse::ParsedDocument::parse(&content);
+            let section = parsed
+                .section(anchor.number())
+                .with_context(|| format!("{} has no {}", doc, anchor))?;
+            Some(section.start_line + 1)
+        }
+        None => None,
+    };
+    open_in_viewer_at(&content, use_pager, open_with, line)
+}
+
+/// Queue a document, or move it to another reading state
+fn queue_document(document: &str, state: Option<&str>) -> Result<()> {
+    use rfc::queue::{ReadingQueue, ReadingState};
+
+    let doc_type = parse_document(document)?;
+    let mut queue = ReadingQueue::open()?;
+    match state {
+        Some(name) => {
+            let state = ReadingState::parse(name).with_context(|| {
+                format!(
+                    "Invalid reading state '{}' (use to-read, in-progress or done)",
+                    name
+                )
+            })?;
+            queue.set_state(&doc_type, state);
+            println!("Marked {} as {}", doc_type, state);
+        }
+        None if queue.add(doc_type.clone()) => println!("Queued {} to read", doc_type),
+        None => println!("{} is already on the reading list", doc_type),
+    }
+    Ok(queue.save()?)
+}
+
+/// Print the reading list, grouped by state
+fn show_reading_list() -> Result<()> {
+    use rfc::queue::{ReadingQueue, ReadingState};
+
+    let queue = ReadingQueue::open()?;
+    if queue.entries().is_empty() {
+        println!("Reading list is empty");
+        return Ok(());
+    }
+    for state in ReadingState::ALL {
+        let entries: Vec<_> = queue.in_state(state).collect();
+        if entries.is_empty() {
+            continue;
+        }
+        println!("{} ({}):", state, entries.len());
+        for entry in entries {
+            let since = match state {
+                ReadingState::ToRead => entry.added,
+                ReadingState::InProgress => entry.started.unwrap_or(entry.updated),
+                ReadingState::Done => entry.finished.unwrap_or(entry.updated),
+            };
+            println!("  {}  ({})", entry.doc, relative_time(since, Utc::now()));
+        }
+    }
+    Ok(())
+}
+
+/// Write the reading list as a task list
+fn export_tasks(path: &std::path::Path, format: Option<&str>) -> Result<()> {
+    use rfc::tasks::TaskFormat;
+
+    let format = match format {
+        Some(name) => TaskFormat::parse(name).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown task format '{}', expected todo.txt, org or markdown",
+                name
+            )
+        })?,
+        None => TaskFormat::from_path(path),
+    };
+    let tasks = rfc::queue::ReadingQueue::open()?.tasks();
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    rfc::tasks::export_tasks(&tasks, format, std::io::BufWriter::new(file))?;
+    println!("Wrote {} tasks to {}", tasks.len(), path.display());
+    Ok(())
+}
+
+/// Take a document off the reading list
+fn dequeue_document(document: &str) -> Result<()> {
+    let doc_type = parse_document(document)?;
+    let mut queue = rfc::queue::ReadingQueue::open()?;
+    match queue.remove(&doc_type) {
+        Some(_) => {
+            queue.save()?;
+            println!("Removed {} from the reading list", doc_type);
+        }
+        None => println!("{} is not on the reading list", doc_type),
+    }
+    Ok(())
+}
+
+/// Print the fetch history of a document
+fn show_provenance(document: &str) -> Result<()> {
+    let cache = CacheManager::new()?;
+    let doc_type = parse_document(document)?;
+
+    let records = cache.provenance(&doc_type)?;
+    if records.is_empty() {
+        println!("No fetches of {} recorded", doc_type);
+        return Ok(());
+    }
+    for record in &records {
+        println!(
+            "{}  {} {} ({} bytes)",
+            record.time.format("%Y-%m-%d %H:%M:%S UTC"),
+            record.status,
+            record.url,
+            record.size
+        );
+        println!("  sha256: {}", record.sha256);
+        if let Some(etag) = &record.etag {
+            println!("  etag:   {}", etag);
+        }
+        if let Some(last_modified) = &record.last_modified {
+            println!("  last-modified: {}", last_modified);
+        }
+    }
+    Ok(())
+}
+
+/// Install the changed copies of a document that were quarantined on refetch
+fn accept_change(document: &str) -> Result<()> {
+    let cache = CacheManager::new()?;
+    let doc_type = parse_document(document)?;
+
+    let mut accepted = false;
+    for format in Format::ALL {
+        if cache.accept_change(&doc_type, format)? {
+            println!("Accepted the changed {} of {}", format.name(), doc_type);
+            accepted = true;
+        }
+    }
+    if !accepted {
+        println!("No changed copy of {} is waiting to be accepted", doc_type);
+    }
+    Ok(())
+}
+
+/// Compare the cached renderings of a document
+fn verify_cache(document: &str) -> Result<()> {
+    let cache = CacheManager::new()?;
+    let doc_type = parse_document(document)?;
+
+    let Some(report) = cache.check_consistency(&doc_type)? else {
+        println!("{} is not cached in both text and HTML form", doc_type);
+        return Ok(());
+    };
+
+    println!(
+        "Text: {} words ({:.1}% found in HTML)",
+        report.text_words,
+        report.text_coverage * 100.0
+    );
+    println!(
+        "HTML: {} words ({:.1}% found in text)",
+        report.html_words,
+        report.html_coverage * 100.0
+    );
+
+    if let Some(format) = report.truncated {
+        println!(
+            "Warning: the {} rendering appears truncated",
+            format.extension()
+        );
+    }
+    for divergence in &report.divergences {
+        println!(
+            "  only in {} at word {} ({} words): {}...",
+            divergence.present_in.extension(),
+            divergence.word_offset,
+            divergence.word_count,
+            divergence.excerpt
+        );
+    }
+
+    if report.is_consistent() {
+        println!("{}: renderings are consistent", doc_type);
+    } else {
+        println!("{}: renderings diverge", doc_type);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_displayable_preference() {
+        let preference = FormatPreference::new([Format::Xml, Format::Text, Format::Html]);
+        assert_eq!(
+            displayable(&preference).unwrap().formats(),
+            [Format::Text, Format::Html]
+        );
+        assert!(displayable(&FormatPreference::new([Format::Xml])).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_load_stale_copy() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let fetcher = DocumentFetcher::new()
+            .unwrap()
+            .with_offline_mode(rfc::api::OfflineMode::Offline);
+        let draft = DocumentType::Draft("draft-ietf-quic-transport".to_string());
+        assert!(load_document(&draft, &cache, &fetcher, true).await.is_err());
+
+        // A failed refetch falls back to the cached copy
+        cache
+            .store_document(&draft, Format::Text, "QUIC Transport")
+            .unwrap();
+        let text = load_document(&draft, &cache, &fetcher, true).await.unwrap();
+        assert_eq!(text, "QUIC Transport");
+    }
+
+    #[test]
+    fn test_json_unsupported() {
+        Cli::command().debug_assert();
+        assert!(parse_cli(["rfc", "--json", "9000"]).is_ok());
+        assert!(parse_cli(["rfc", "--json", "--info", "9000"]).is_ok());
+        let err = parse_cli(["rfc", "--json", "--bibtex", "9000"]).err();
+        assert_eq!(
+            err.map(|e| e.kind()),
+            Some(clap::error::ErrorKind::ArgumentConflict)
+        );
+        assert!(parse_cli(["rfc", "--json", "--bookmarks"]).is_err());
+    }
+
+    #[test]
+    fn test_json_covers_every_option() {
+        let command = Cli::command();
+        let ids: Vec<_> = command
+            .get_arguments()
+            .map(|a| a.get_id().as_str())
+            .collect();
+        for id in JSON_SUPPORTED {
+            assert!(ids.contains(&id), "unknown option {} in JSON_SUPPORTED", id);
+        }
+        // Every other option is rejected rather than silently ignored
+        for arg in command.get_arguments() {
+            let Some(long) = arg.get_long() else { continue };
+            if JSON_SUPPORTED.contains(&arg.get_id().as_str()) {
+                continue;
+            }
+            let flag = format!("--{}", long);
+            let mut args = vec!["rfc", "--json", flag.as_str()];
+            if arg.get_action().takes_values() {
+                args.push("1");
+            }
+            args.push("9000");
+            let kind = parse_cli(args).err().map(|e| e.kind());
+            assert!(
+                matches!(
+                    kind,
+                    Some(
+                        clap::error::ErrorKind::ArgumentConflict
+                            | clap::error::ErrorKind::MissingRequiredArgument
+                    )
+                ),
+                "--{} is accepted with --json: {:?}",
+                long,
+                kind
+            );
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use std::fmt;
+
+use crate::models::DocumentType;
+
+/// Supported message locales
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    English,
+    German,
+}
+
+impl Locale {
+    /// Parse a POSIX locale or language tag (e.g., "de_AT.UTF-8", "de-DE", "en")
+    /// Returns None for unsupported languages
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match language.as_str() {
+            "en" | "c" | "posix" => Some(Locale::English),
+            "de" => Some(Locale::German),
+            _ => None,
+        }
+    }
+
+    /// Select a locale from LC_ALL, LC_MESSAGES or LANG, falling back to English
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::from_tag(&value))
+            .unwrap_or_default()
+    }
+}
+
+/// A user-facing message; render through `localized` to follow the user's locale
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// A document is being downloaded
+    Fetching(DocumentType),
+    /// A document is served from the local cache
+    UsingCached(DocumentType),
+    /// A document couldn't be refetched, so an expired cached copy is served
+    UsingStale(DocumentType, String),
+    /// Only an HTML rendering was available
+    ConvertingHtml,
+    /// HTML conversion failed and raw HTML is shown instead
+    HtmlConversionFailed(String),
+    /// A document is being opened in the browser
+    OpeningBrowser(DocumentType),
+    /// Metadata for a document is being looked up
+    LookingUp(DocumentType),
+    /// A search is in progress
+    Searching(String),
+    /// A search returned no results
+    NoResults(String),
+    /// A search returned results
+    FoundResults(usize),
+    /// A search was cut off by the result limit
+    MoreResults,
+    /// The next page of a search starts at this offset
+    NextPage(u64),
+    /// Hint on how to read a search result
+    ReadHint,
+    /// A document has been obsoleted by newer documents
+    ObsoletedBy(DocumentType, Vec<DocumentType>),
+    /// Hint on how to read the successor of an obsoleted document
+    ReadLatest(DocumentType),
+    /// The cache holds no documents
+    CacheEmpty,
+    /// The cache was cleared
+    CacheCleared,
+    /// Heading for the list of cached documents
+    CachedDocuments(usize),
+    /// A document was removed from the cache
+    Removed(DocumentType),
+    /// A document to be removed was not cached
+    NotCached(DocumentType),
+    /// A document could not be found, but similarly named ones are known
+    DidYouMean(Vec<DocumentType>),
+    /// A published RFC fetched from a URL differs from the copy first fetched
+    ContentChanged(DocumentType, String),
+}
+
+impl Message {
+    /// Render this message in the given locale
+    pub fn localized(&self, locale: Locale) -> String {
+        match locale {
+            Locale::English => self.english(),
+            Locale::German => self.german(),
+        }
+    }
+
+    fn english(&self) -> String {
+        match self {
+            Message::Fetching(doc) => format!("Fetching {}...", doc),
+            Message::UsingCached(doc) => format!("Using cached copy of {}", doc),
+            Message::UsingStale(doc, err) => format!(
+                "Warning: failed to refresh {} ({}), using a stale cached copy",
+                doc, err
+            ),
+            Message::ConvertingHtml => {
+                "Plain text not available, converting from HTML...".to_string()
+            }
+            Message::HtmlConversionFailed(err) => format!(
+                "Warning: HTML to text conversion failed ({}), displaying raw HTML",
+                err
+            ),
+            Message::OpeningBrowser(doc) => format!("Opening {} in browser...", doc),
+            Message::LookingUp(doc) => format!("Looking up {}...", doc),
+            Message::Searching(query) => format!("Searching for '{}'...", query),
+            Message::NoResults(query) => format!("No results found for '{}'", query),
+            Message::FoundResults(count) => format!("Found {} results:", count),
+            Message::MoreResults => "(More results available. Use -l to show more.)".to_string(),
+            Message::NextPage(offset) => format!("(Next page: --offset {})", offset),
+            Message::ReadHint => "Use 'rfc <document>' to read a document".to_string(),
+            Message::ObsoletedBy(doc, by) => {
+                format!("Warning: {} is obsoleted by {}", doc, join(by))
+            }
+            Message::ReadLatest(doc) => format!("Use --latest to read {}", doc),
+            Message::CacheEmpty => "Cache is empty".to_string(),
+            Message::CacheCleared => "Cache cleared".to_string(),
+            Message::CachedDocuments(count) => format!("Cached documents ({}):", count),
+            Message::Removed(doc) => format!("Removed {} from cache", doc),
+            Message::NotCached(doc) => format!("{} was not in cache", doc),
+            Message::DidYouMean(docs) => format!("Did you mean {}?", join(docs)),
+            Message::ContentChanged(doc, url) => format!(
+                "Warning: {} from {} differs from the copy first fetched; \
+                 published RFCs never change, so the source may be broken or tampered with. \
+                 The cached copy was kept; `rfc --accept-change {}` installs the new one",
+                doc,
+                url,
+                doc.name()
+            ),
+        }
+    }
+
+    fn german(&self) -> String {
+        match self {
+            Message::Fetching(doc) => format!("Lade {}...", doc),
+            Message::UsingCached(doc) => format!("Verwende zwischengespeicherte Kopie von {}", doc),
+            Message::UsingStale(doc, err) => format!(
+                "Warnung: {} konnte nicht aktualisiert werden ({}), verwende veraltete Kopie",
+                doc, err
+            ),
+            Message::ConvertingHtml => {
+                "Kein Klartext verfügbar, konvertiere aus HTML...".to_string()
+            }
+            Message::HtmlConversionFailed(err) => format!(
+                "Warnung: HTML-Konvertierung fehlgeschlagen ({}), zeige rohes HTML an",
+                err
+            ),
+            Message::OpeningBrowser(doc) => format!("Öffne {} im Browser...", doc),
+            Message::LookingUp(doc) => format!("Suche Informationen zu {}...", doc),
+            Message::Searching(query) => format!("Suche nach '{}'...", query),
+            Message::NoResults(query) => format!("Keine Ergebnisse für '{}'", query),
+            Message::FoundResults(count) => format!("{} Ergebnisse gefunden:", count),
+            Message::MoreResults => {
+                "(Weitere Ergebnisse verfügbar. Mit -l mehr anzeigen.)".to_string()
+            }
+            Message::NextPage(offset) => format!("(Nächste Seite: --offset {})", offset),
+            Message::ReadHint => "Mit 'rfc <dokument>' ein Dokument lesen".to_string(),
+            Message::ObsoletedBy(doc, by) => {
+                format!("Warnung: {} wurde durch {} ersetzt", doc, join(by))
+            }
+            Message::ReadLatest(doc) => format!("Mit --latest {} lesen", doc),
+            Message::CacheEmpty => "Cache ist leer".to_string(),
+            Message::CacheCleared => "Cache geleert".to_string(),
+            Message::CachedDocuments(count) => {
+                format!("Zwischengespeicherte Dokumente ({}):", count)
+            }
+            Message::Removed(doc) => format!("{} aus dem Cache entfernt", doc),
+            Message::NotCached(doc) => format!("{} war nicht im Cache", doc),
+            Message::DidYouMean(docs) => format!("Meinten Sie {}?", join(docs)),
+            Message::ContentChanged(doc, url) => format!(
+                "Warnung: {} von {} weicht von der zuerst geladenen Kopie ab; \
+                 veröffentlichte RFCs ändern sich nie, die Quelle ist also defekt oder manipuliert. \
+                 Die Kopie im Cache bleibt erhalten; `rfc --accept-change {}` übernimmt die neue",
+                doc, url, doc.name()
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.english())
+    }
+}
+
+/// Join a list of documents for display
+fn join(docs: &[DocumentType]) -> String {
+    docs.iter()
+        .map(|d| d.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_from_tag() {
+        assert_eq!(Locale::from_tag("de_AT.UTF-8"), Some(Locale::German));
+        assert_eq!(Locale::from_tag("de-DE"), Some(Locale::German));
+        assert_eq!(Locale::from_tag("en_US.UTF-8"), Some(Locale::English));
+        assert_eq!(Locale::from_tag("C"), Some(Locale::English));
+        assert_eq!(Locale::from_tag("ja_JP.UTF-8"), None);
+        assert_eq!(Locale::from_tag(""), None);
+    }
+
+    #[test]
+    fn test_localized_messages() {
+        let msg = Message::ObsoletedBy(
+            DocumentType::Rfc(2616),
+            vec![DocumentType::Rfc(7230), DocumentType::Rfc(7231)],
+        );
+
+        assert_eq!(
+            msg.localized(Locale::English),
+            "Warning: RFC 2616 is obsoleted by RFC 7230, RFC 7231"
+        );
+        assert_eq!(
+            msg.localized(Locale::German),
+            "Warnung: RFC 2616 wurde durch RFC 7230, RFC 7231 ersetzt"
+        );
+        assert_eq!(msg.to_string(), msg.localized(Locale::English));
+    }
+}
@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+
+/// A document author, with the ASCII form of the name if it has non-ASCII characters
+///
+/// xml2rfc v3 sources carry names as written (`fullname="Jürgen Schönwälder"`)
+/// and optionally an ASCII rendering (`asciiFullname="Juergen Schoenwaelder"`).
+/// Serialized as a plain string when there is no ASCII form.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "AuthorRepr", into = "AuthorRepr")]
+pub struct Author {
+    /// Name as written by the author
+    pub name: String,
+    /// ASCII rendering of the name, if it differs
+    pub ascii_name: Option<String>,
+}
+
+impl Author {
+    /// Create an author without a separate ASCII name
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ascii_name: None,
+        }
+    }
+
+    /// Create an author with an ASCII rendering, dropped if it equals the name
+    pub fn with_ascii(name: impl Into<String>, ascii_name: impl Into<String>) -> Self {
+        let name = name.into();
+        let ascii_name = ascii_name.into();
+        let ascii_name = (!ascii_name.is_empty() && ascii_name != name).then_some(ascii_name);
+        Self { name, ascii_name }
+    }
+
+    /// Get the name in ASCII, falling back to the name as written
+    pub fn ascii(&self) -> &str {
+        self.ascii_name.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Check if a query matches the name as written or its ASCII form
+    ///
+    /// Matching is case-insensitive and ignores diacritics, so "schonwalder",
+    /// "Schoenwaelder" and "Schönwälder" all match "Jürgen Schönwälder".
+    pub fn matches(&self, query: &str) -> bool {
+        let query = fold(query);
+        if query.is_empty() {
+            return false;
+        }
+        [Some(self.name.as_str()), self.ascii_name.as_deref()]
+            .into_iter()
+            .flatten()
+            .any(|name| fold(name).contains(&query) || transliterate(name).contains(&query))
+    }
+}
+
+impl std::fmt::Display for Author {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl From<&str> for Author {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for Author {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+/// Serialized form of an author: a plain name or a name with its ASCII form
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum AuthorRepr {
+    Name(String),
+    Full {
+        name: String,
+        ascii_name: Option<String>,
+    },
+}
+
+impl From<AuthorRepr> for Author {
+    fn from(repr: AuthorRepr) -> Self {
+        match repr {
+            AuthorRepr::Name(name) => Self::new(name),
+            AuthorRepr::Full { name, ascii_name } => Self { name, ascii_name },
+        }
+    }
+}
+
+impl From<Author> for AuthorRepr {
+    fn from(author: Author) -> Self {
+        match author.ascii_name {
+            None => AuthorRepr::Name(author.name),
+            ascii_name => AuthorRepr::Full {
+                name: author.name,
+                ascii_name,
+            },
+        }
+    }
+}
+
+/// Lowercase a name and strip diacritics from Latin letters ("Schönwälder" -> "schonwalder")
+fn fold(s: &str) -> String {
+    s.to_lowercase().chars().map(base_letter).collect()
+}
+
+/// Lowercase a name and spell out letters the way ASCII renderings usually do
+/// ("Schönwälder" -> "schoenwaelder", "Straße" -> "strasse")
+fn transliterate(s: &str) -> String {
+    let mut out = String::new();
+    for c in s.to_lowercase().chars() {
+        match c {
+            'ä' => out.push_str("ae"),
+            'ö' | 'ø' => out.push_str("oe"),
+            'ü' => out.push_str("ue"),
+            'ß' => out.push_str("ss"),
+            'æ' => out.push_str("ae"),
+            'å' => out.push_str("aa"),
+            c => out.push(base_letter(c)),
+        }
+    }
+    out
+}
+
+fn base_letter(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => 'a',
+        'ç' | 'ć' | 'č' => 'c',
+        'ď' | 'đ' => 'd',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' => 'e',
+        'ğ' => 'g',
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'ı' => 'i',
+        'ł' | 'ľ' => 'l',
+        'ñ' | 'ń' | 'ň' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => 'o',
+        'ř' => 'r',
+        'ś' | 'š' | 'ş' => 's',
+        'ť' | 'ţ' => 't',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        c => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_either_form() {
+        let author = Author::with_ascii("Jürgen Schönwälder", "Juergen Schoenwaelder");
+
+        assert!(author.matches("Schönwälder"));
+        assert!(author.matches("schoenwaelder"));
+        assert!(author.matches("schonwalder"));
+        assert!(!author.matches("Thomson"));
+        assert!(!author.matches(""));
+        assert_eq!(author.ascii(), "Juergen Schoenwaelder");
+
+        // Transliterated queries match even without an explicit ASCII form
+        assert!(Author::new("Jürgen Schönwälder").matches("Schoenwaelder"));
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let plain = Author::new("M. Thomson");
+        assert_eq!(serde_json::to_string(&plain).unwrap(), "\"M. Thomson\"");
+
+        let full = Author::with_ascii("中村 直人", "N. Nakamura");
+        let json = serde_json::to_string(&full).unwrap();
+        assert_eq!(serde_json::from_str::<Author>(&json).unwrap(), full);
+        assert_eq!(
+            serde_json::from_str::<Author>("\"M. Thomson\"").unwrap(),
+            plain
+        );
+
+        assert_eq!(
+            Author::with_ascii("M. Thomson", "M. Thomson").ascii_name,
+            None
+        );
+    }
+}
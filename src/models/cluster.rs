@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+use super::DocumentType;
+
+/// A set of documents the RFC Editor processes and publishes together
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cluster {
+    /// Cluster identifier (e.g., "C310")
+    pub id: String,
+    /// Member documents, as RFCs once published and as drafts before
+    pub documents: Vec<DocumentType>,
+}
+
+impl Cluster {
+    /// Get the RFC Editor page describing the cluster
+    pub fn url(&self) -> String {
+        format!(
+            "https://www.rfc-editor.org/cluster_info.php?cid={}",
+            self.id
+        )
+    }
+
+    /// Check if a document is a member, ignoring draft revisions
+    pub fn contains(&self, doc: &DocumentType) -> bool {
+        let base = |d: &DocumentType| match d.draft_revision() {
+            Some((base, _)) => base.to_string(),
+            None => d.name(),
+        };
+        self.documents
+            .iter()
+            .any(|member| base(member) == base(doc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains() {
+        let cluster = Cluster {
+            id: "C310".to_string(),
+            documents: vec![
+                DocumentType::Rfc(9000),
+                DocumentType::Draft("draft-ietf-quic-http".to_string()),
+            ],
+        };
+
+        assert!(cluster.contains(&DocumentType::Rfc(9000)));
+        assert!(cluster.contains(&DocumentType::Draft("draft-ietf-quic-http-34".to_string())));
+        assert!(!cluster.contains(&DocumentType::Rfc(9001)));
+        assert_eq!(
+            cluster.url(),
+            "https://www.rfc-editor.org/cluster_info.php?cid=C310"
+        );
+    }
+}
@@ -1,23 +1,118 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::{Author, SectionMatch, Stream};
 use crate::api::DATATRACKER_BASE_URL;
+use crate::humanize::relative_time;
+use crate::parse::SectionAnchor;
 
-/// The type of document - either an RFC or an Internet-Draft
+/// How long a draft revision stays active after it is posted
+const DRAFT_LIFETIME: chrono::Duration = chrono::Duration::days(185);
+
+/// Source identifier for W3C Technical Reports
+pub const W3C: &str = "w3c";
+/// Source identifier for WHATWG Living Standards
+pub const WHATWG: &str = "whatwg";
+
+/// External sources recognized by `DocumentType::parse`
+const EXTERNAL_SOURCES: &[&str] = &[W3C, WHATWG];
+
+/// Hosts whose document URLs `DocumentType::parse` resolves, current and retired
+const LEGACY_HOSTS: &[&str] = &[
+    "tools.ietf.org",
+    "datatracker.ietf.org",
+    "www.ietf.org",
+    "ietf.org",
+    "www.rfc-editor.org",
+    "rfc-editor.org",
+    "doi.org",
+    "dx.doi.org",
+];
+
+/// File extensions stripped from the last segment of a document URL
+const URL_EXTENSIONS: &[&str] = &[".txt", ".html", ".htm", ".pdf", ".xml", ".ps"];
+
+/// An RFC subseries grouping RFCs under a stable number (e.g., BCP 14)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Subseries {
+    /// Best Current Practice
+    Bcp,
+    /// Internet Standard
+    Std,
+    /// For Your Information (closed in 2011)
+    Fyi,
+}
+
+impl Subseries {
+    /// All subseries, in the order they are tried when parsing
+    pub const ALL: [Subseries; 3] = [Subseries::Bcp, Subseries::Std, Subseries::Fyi];
+
+    /// Get the lowercase prefix used in names and URLs (e.g., "bcp")
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Subseries::Bcp => "bcp",
+            Subseries::Std => "std",
+            Subseries::Fyi => "fyi",
+        }
+    }
+
+    /// Get the uppercase label (e.g., "BCP")
+    pub fn label(&self) -> &'static str {
+        match self {
+            Subseries::Bcp => "BCP",
+            Subseries::Std => "STD",
+            Subseries::Fyi => "FYI",
+        }
+    }
+}
+
+/// The type of document - an RFC, an Internet-Draft, or an external specification
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DocumentType {
     /// An RFC document with its number
     Rfc(u32),
     /// An Internet-Draft with its name
     Draft(String),
+    /// A subseries number (e.g., BCP 14), standing for one or more RFCs
+    Subseries(Subseries, u32),
+    /// A specification published outside the IETF (e.g., by the W3C or WHATWG)
+    External {
+        /// Lowercase source identifier (e.g., "w3c", "whatwg")
+        source: String,
+        /// Short name within the source (e.g., "webauthn-2", "fetch")
+        name: String,
+    },
 }
 
 impl DocumentType {
     /// Parse a document type from a string
-    /// Handles formats like "rfc9000", "RFC 9000", "9000", or draft names
+    /// Handles formats like "rfc9000", "RFC 9000", "9000", draft names,
+    /// subseries like "BCP 14" or "std90", or external specifications like
+    /// "w3c:webauthn-2" and "whatwg:fetch". Legacy forms resolve as well:
+    /// tools.ietf.org, datatracker and rfc-editor.org URLs, RFC DOIs, URNs
+    /// ("urn:ietf:rfc:9000") and xml2rfc anchors ("I-D.ietf-quic-transport")
     pub fn parse(s: &str) -> Option<Self> {
         let s = s.trim().to_lowercase();
 
+        if let Some(doc) = Self::parse_legacy(&s) {
+            return Some(doc);
+        }
+
+        // Try to parse as an external specification
+        for source in EXTERNAL_SOURCES {
+            if let Some(rest) = s.strip_prefix(source) {
+                if let Some(name) = rest.strip_prefix([':', '-', ' ']) {
+                    let name = name.trim();
+                    if !name.is_empty() {
+                        return Some(DocumentType::External {
+                            source: source.to_string(),
+                            name: name.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
         // Try to parse as RFC number
         if let Some(num_str) = s.strip_prefix("rfc") {
             let num_str = num_str.trim();
@@ -26,6 +121,15 @@ impl DocumentType {
             }
         }
 
+        // Try to parse as a subseries number ("bcp14", "BCP 14", "BCP0014")
+        for series in Subseries::ALL {
+            if let Some(num_str) = s.strip_prefix(series.prefix()) {
+                if let Ok(num) = num_str.trim().parse::<u32>() {
+                    return Some(DocumentType::Subseries(series, num));
+                }
+            }
+        }
+
         // Try to parse as plain number (assumed RFC)
         if let Ok(num) = s.parse::<u32>() {
             return Some(DocumentType::Rfc(num));
@@ -39,11 +143,78 @@ impl DocumentType {
         None
     }
 
+    /// Resolve a lowercase URL, URN, DOI or citation anchor
+    fn parse_legacy(s: &str) -> Option<Self> {
+        if let Some(name) = s.strip_prefix("i-d.") {
+            let name = name.strip_prefix("draft-").unwrap_or(name);
+            return (!name.is_empty()).then(|| DocumentType::Draft(format!("draft-{}", name)));
+        }
+        if let Some(number) = s.strip_prefix("urn:ietf:rfc:") {
+            return number.parse().ok().map(DocumentType::Rfc);
+        }
+        if let Some(rfc) = s.strip_prefix("10.17487/") {
+            return Self::parse(rfc);
+        }
+
+        let url = s.split_once("://").map_or(s, |(_, rest)| rest);
+        let url = url.split(['#', '?']).next().unwrap_or_default();
+        let (host, path) = url.split_once('/')?;
+        if !LEGACY_HOSTS.contains(&host) {
+            return None;
+        }
+        // The document is the last segment naming one (".../doc/rfc9000/history/")
+        path.rsplit('/')
+            .filter(|segment| !segment.is_empty() && !segment.chars().all(|c| c.is_ascii_digit()))
+            .find_map(|segment| {
+                let segment = URL_EXTENSIONS
+                    .iter()
+                    .find_map(|ext| segment.strip_suffix(ext))
+                    .unwrap_or(segment);
+                match Self::parse(segment)? {
+                    DocumentType::External { .. } => None,
+                    doc => Some(doc),
+                }
+            })
+    }
+
+    /// Parse a comma-separated list of documents and RFC ranges (e.g., "9000-9002, bcp14")
+    pub fn parse_list(s: &str) -> crate::Result<Vec<Self>> {
+        let rfc_number = |s: &str| {
+            let s = s.trim().to_ascii_lowercase();
+            s.strip_prefix("rfc")
+                .unwrap_or(&s)
+                .trim()
+                .parse::<u32>()
+                .ok()
+        };
+
+        let mut docs = Vec::new();
+        for item in s.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+            let range = item
+                .split_once('-')
+                .and_then(|(first, last)| Some((rfc_number(first)?, rfc_number(last)?)));
+            match range {
+                Some((first, last)) if first <= last => {
+                    docs.extend((first..=last).map(DocumentType::Rfc))
+                }
+                Some(_) => {
+                    return Err(
+                        anyhow::Error::new(ParseError::InvalidRange(item.to_string())).into(),
+                    )
+                }
+                None => docs.push(item.parse::<Self>().map_err(anyhow::Error::new)?),
+            }
+        }
+        Ok(docs)
+    }
+
     /// Get the canonical name for this document
     pub fn name(&self) -> String {
         match self {
             DocumentType::Rfc(num) => format!("rfc{}", num),
             DocumentType::Draft(name) => name.clone(),
+            DocumentType::Subseries(series, num) => format!("{}{}", series.prefix(), num),
+            DocumentType::External { source, name } => format!("{}-{}", source, name),
         }
     }
 
@@ -52,16 +223,104 @@ impl DocumentType {
         match self {
             DocumentType::Rfc(num) => format!("RFC {}", num),
             DocumentType::Draft(name) => name.clone(),
+            DocumentType::Subseries(series, num) => format!("{} {}", series.label(), num),
+            DocumentType::External { source, name } => {
+                format!("{} {}", source.to_uppercase(), name)
+            }
         }
     }
 
     /// Get the IETF Datatracker URL for this document
+    /// External specifications link to their publisher's page instead
     pub fn datatracker_url(&self) -> String {
         match self {
             DocumentType::Rfc(num) => format!("{}/doc/rfc{}/", DATATRACKER_BASE_URL, num),
             DocumentType::Draft(name) => format!("{}/doc/{}/", DATATRACKER_BASE_URL, name),
+            DocumentType::Subseries(..) => {
+                format!("{}/doc/{}/", DATATRACKER_BASE_URL, self.name())
+            }
+            DocumentType::External { .. } => self.external_url().unwrap_or_default(),
+        }
+    }
+
+    /// Get the publisher URL of an external specification
+    /// Returns None for IETF documents and unknown sources
+    pub fn external_url(&self) -> Option<String> {
+        let DocumentType::External { source, name } = self else {
+            return None;
+        };
+        match source.as_str() {
+            W3C => Some(format!("https://www.w3.org/TR/{}/", name)),
+            WHATWG => Some(format!("https://{}.spec.whatwg.org/", name)),
+            _ => None,
         }
     }
+
+    /// Check if this is an IETF document (RFC, subseries or Internet-Draft)
+    #[must_use]
+    pub fn is_ietf(&self) -> bool {
+        !matches!(self, DocumentType::External { .. })
+    }
+
+    /// Split a draft name into its base name and revision number
+    /// Returns None for other documents and drafts without a revision suffix
+    pub fn draft_revision(&self) -> Option<(&str, u32)> {
+        let DocumentType::Draft(name) = self else {
+            return None;
+        };
+        let (base, rev) = name.rsplit_once('-')?;
+        if rev.is_empty() || !rev.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        Some((base, rev.parse().ok()?))
+    }
+
+    /// Check if this document was published after `other`, going by number or revision
+    ///
+    /// Any RFC with a higher number is newer than one with a lower number,
+    /// whether or not one obsoletes the other, and a higher draft revision is
+    /// newer than a lower revision of the same draft. Drafts of different
+    /// names, and documents of different kinds, are never newer.
+    #[must_use]
+    pub fn is_newer_than(&self, other: &DocumentType) -> bool {
+        match (self, other) {
+            (DocumentType::Rfc(a), DocumentType::Rfc(b)) => a > b,
+            (DocumentType::Draft(_), DocumentType::Draft(_)) => {
+                match (self.draft_revision(), other.draft_revision()) {
+                    (Some((base, a)), Some((other_base, b))) => base == other_base && a > b,
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Key for ordering: kind first, then number or name and revision
+    fn sort_key(&self) -> (u8, u32, &str, Option<u32>, &str) {
+        match self {
+            DocumentType::Rfc(num) => (0, *num, "", None, ""),
+            DocumentType::Subseries(series, num) => (1 + *series as u8, *num, "", None, ""),
+            DocumentType::Draft(name) => match self.draft_revision() {
+                Some((base, rev)) => (4, 0, base, Some(rev), name),
+                None => (4, 0, name, None, name),
+            },
+            DocumentType::External { source, name } => (5, 0, source, None, name),
+        }
+    }
+}
+
+/// RFCs sort by number, then subseries, then drafts by name and numeric
+/// revision, then external specifications
+impl Ord for DocumentType {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl PartialOrd for DocumentType {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl std::fmt::Display for DocumentType {
@@ -70,22 +329,213 @@ impl std::fmt::Display for DocumentType {
     }
 }
 
+impl std::str::FromStr for DocumentType {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| ParseError::UnknownDocument(s.to_string()))
+    }
+}
+
+/// A document, RFC range or format name that couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// Not the name of a document (e.g., "not a document")
+    UnknownDocument(String),
+    /// An RFC range ending before it starts (e.g., "9114-9000")
+    InvalidRange(String),
+    /// Not the name of a format (e.g., "epub")
+    UnknownFormat(String),
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownDocument(s) => write!(f, "Unrecognized document '{}'", s),
+            Self::InvalidRange(s) => write!(f, "Invalid RFC range '{}'", s),
+            Self::UnknownFormat(s) => write!(f, "Unknown format '{}'", s),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 /// Document content format
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Format {
     Html,
     Text,
+    /// xml2rfc v3 source
+    Xml,
+    Pdf,
 }
 
 impl Format {
+    /// Every format, in the order they are preferred for reading
+    pub const ALL: [Format; 4] = [Format::Text, Format::Html, Format::Xml, Format::Pdf];
+
     pub fn extension(&self) -> &'static str {
         match self {
             Format::Html => "html",
             Format::Text => "txt",
+            Format::Xml => "xml",
+            Format::Pdf => "pdf",
+        }
+    }
+
+    /// Get the format stored under a file extension
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext {
+            "html" => Some(Format::Html),
+            "txt" => Some(Format::Text),
+            "xml" => Some(Format::Xml),
+            "pdf" => Some(Format::Pdf),
+            _ => None,
+        }
+    }
+
+    /// Get the canonical name of the format (e.g., "text")
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Html => "html",
+            Format::Text => "text",
+            Format::Xml => "xml",
+            Format::Pdf => "pdf",
+        }
+    }
+
+    /// Parse a format name or file extension, case-insensitively
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "text" => Some(Format::Text),
+            other => Self::from_extension(other),
+        }
+    }
+
+    /// Whether content in this format is binary rather than UTF-8 text
+    pub fn is_binary(&self) -> bool {
+        matches!(self, Format::Pdf)
+    }
+}
+
+/// First RFC published in the xml2rfc v3 format, whose text rendering loses
+/// tables and SVG artwork
+pub const FIRST_V3_RFC: u32 = 8650;
+
+/// How the fetcher picks the format of a document
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FormatStrategy {
+    /// Plain text first, HTML if no text is published
+    #[default]
+    TextFirst,
+    /// HTML first for v3-native RFCs (RFC 8650 and later), text first otherwise
+    Auto,
+}
+
+impl FormatStrategy {
+    /// Parse a strategy name ("text" or "auto"), case-insensitively
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "text" | "text-first" => Some(FormatStrategy::TextFirst),
+            "auto" => Some(FormatStrategy::Auto),
+            _ => None,
+        }
+    }
+
+    /// Get the strategy selected by `RFC_FORMAT_STRATEGY`, defaulting to text first
+    pub fn from_env() -> Self {
+        std::env::var("RFC_FORMAT_STRATEGY")
+            .ok()
+            .and_then(|name| Self::parse(&name))
+            .unwrap_or_default()
+    }
+
+    /// Get the format to try first for a document
+    pub fn preferred(&self, doc: &DocumentType) -> Format {
+        match (self, doc) {
+            (FormatStrategy::Auto, DocumentType::Rfc(number)) if *number >= FIRST_V3_RFC => {
+                Format::Html
+            }
+            _ => Format::Text,
+        }
+    }
+
+    /// Get the order in which `fetch` tries the formats of a document
+    pub fn preference(&self, doc: &DocumentType) -> FormatPreference {
+        match self.preferred(doc) {
+            Format::Html => FormatPreference::new([Format::Html, Format::Text]),
+            _ => FormatPreference::new([Format::Text, Format::Html]),
         }
     }
 }
 
+/// Formats `fetch` tries in order, e.g. `[Xml, Text, Html]` for the xml2rfc source first
+///
+/// Binary formats and repeated formats are dropped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<Format>")]
+pub struct FormatPreference(Vec<Format>);
+
+impl TryFrom<Vec<Format>> for FormatPreference {
+    type Error = String;
+
+    fn try_from(formats: Vec<Format>) -> Result<Self, Self::Error> {
+        let preference = Self::new(formats);
+        if preference.0.is_empty() {
+            return Err("a format preference needs at least one text format".to_string());
+        }
+        Ok(preference)
+    }
+}
+
+impl FormatPreference {
+    /// Create a preference trying `formats` in order, without binary and repeated formats
+    pub fn new(formats: impl IntoIterator<Item = Format>) -> Self {
+        let mut order = Vec::new();
+        for format in formats {
+            if !format.is_binary() && !order.contains(&format) {
+                order.push(format);
+            }
+        }
+        Self(order)
+    }
+
+    /// Parse a comma-separated list of formats (e.g., "xml,text,html")
+    pub fn parse(list: &str) -> Option<Self> {
+        let formats = list
+            .split(',')
+            .map(Format::parse)
+            .collect::<Option<Vec<_>>>()?;
+        let preference = Self::new(formats);
+        (!preference.0.is_empty()).then_some(preference)
+    }
+
+    /// Get the preference set by `RFC_FORMAT_PREFERENCE`, if any
+    pub fn from_env() -> Option<Self> {
+        Self::parse(&std::env::var("RFC_FORMAT_PREFERENCE").ok()?)
+    }
+
+    /// Get the formats, most preferred first
+    pub fn formats(&self) -> &[Format] {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl std::str::FromStr for Format {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| ParseError::UnknownFormat(s.to_string()))
+    }
+}
+
 /// An IETF document (RFC or Internet-Draft)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Document {
@@ -104,11 +554,18 @@ pub struct Document {
     /// Document status (e.g., "Standards Track", "Informational")
     pub status: Option<String>,
     /// List of authors
-    pub authors: Vec<String>,
-    /// Stream (e.g., "IETF", "IAB", "IRTF")
-    pub stream: Option<String>,
+    pub authors: Vec<Author>,
+    /// Publication stream (e.g., IETF, IAB, IRTF)
+    pub stream: Option<Stream>,
     /// Working group
     pub wg: Option<String>,
+    /// Current revision (drafts only, e.g., "34")
+    pub rev: Option<String>,
+    /// Datatracker state (e.g., "Active", "Expired", "RFC")
+    pub state: Option<String>,
+    /// Sections that matched a search, best first (search results only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matches: Vec<SectionMatch>,
 }
 
 impl Document {
@@ -125,9 +582,23 @@ impl Document {
             authors: Vec::new(),
             stream: None,
             wg: None,
+            rev: None,
+            state: None,
+            matches: Vec::new(),
         }
     }
 
+    /// Get the section a search matched best, to open the document at
+    pub fn matched_section(&self) -> Option<&SectionAnchor> {
+        self.matches.first().map(|m| &m.anchor)
+    }
+
+    /// Check if this document belongs to the IRTF stream
+    #[must_use]
+    pub fn is_irtf(&self) -> bool {
+        self.stream == Some(Stream::Irtf)
+    }
+
     /// Get a short display title (truncated if necessary)
     pub fn short_title(&self, max_len: usize) -> String {
         if self.title.chars().count() <= max_len {
@@ -137,11 +608,209 @@ impl Document {
             format!("{}...", truncated)
         }
     }
+
+    /// Check if an author's name, as written or in ASCII, matches a query
+    pub fn has_author(&self, query: &str) -> bool {
+        self.authors.iter().any(|author| author.matches(query))
+    }
+
+    /// Describe when the document was published (e.g., "published 3 years ago")
+    pub fn published_relative(&self, now: DateTime<Utc>) -> Option<String> {
+        self.published
+            .map(|date| format!("published {}", relative_time(date, now)))
+    }
+
+    /// Get the date an active draft revision expires
+    pub fn expires(&self) -> Option<DateTime<Utc>> {
+        let active = self.state.as_deref().is_none_or(|s| s == "Active");
+        match self.doc_type {
+            DocumentType::Draft(_) if active => self.published.map(|p| p + DRAFT_LIFETIME),
+            _ => None,
+        }
+    }
+
+    /// Describe when an active draft expires (e.g., "draft expires in 12 days")
+    pub fn expiry_relative(&self, now: DateTime<Utc>) -> Option<String> {
+        self.expires().map(|date| {
+            let verb = if date > now { "expires" } else { "expired" };
+            format!("draft {} {}", verb, relative_time(date, now))
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_format_strategy() {
+        let auto = FormatStrategy::Auto;
+        assert_eq!(auto.preferred(&DocumentType::Rfc(9000)), Format::Html);
+        assert_eq!(auto.preferred(&DocumentType::Rfc(8650)), Format::Html);
+        assert_eq!(auto.preferred(&DocumentType::Rfc(8649)), Format::Text);
+        assert_eq!(
+            auto.preferred(&DocumentType::Draft("draft-ietf-quic-qlog".to_string())),
+            Format::Text
+        );
+        assert_eq!(
+            FormatStrategy::TextFirst.preferred(&DocumentType::Rfc(9000)),
+            Format::Text
+        );
+        assert_eq!(FormatStrategy::parse("AUTO"), Some(FormatStrategy::Auto));
+        assert_eq!(FormatStrategy::parse("pdf"), None);
+    }
+
+    #[test]
+    fn test_format_preference() {
+        let preference = FormatPreference::parse("xml, Text,html,text,pdf").unwrap();
+        assert_eq!(
+            preference.formats(),
+            [Format::Xml, Format::Text, Format::Html]
+        );
+        assert_eq!(FormatPreference::parse("pdf"), None);
+        assert_eq!(FormatPreference::parse("xml,docx"), None);
+
+        // Deserializing filters like `new`
+        let parsed: FormatPreference =
+            serde_json::from_str(r#"["Xml","Pdf","Xml","Text"]"#).unwrap();
+        assert_eq!(parsed.formats(), [Format::Xml, Format::Text]);
+        assert!(serde_json::from_str::<FormatPreference>(r#"["Pdf"]"#).is_err());
+        assert_eq!(
+            FormatStrategy::Auto
+                .preference(&DocumentType::Rfc(9000))
+                .formats(),
+            [Format::Html, Format::Text]
+        );
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        let docs = [
+            DocumentType::Rfc(9000),
+            DocumentType::Draft("draft-ietf-quic-transport-34".to_string()),
+            DocumentType::Subseries(Subseries::Bcp, 14),
+            DocumentType::External {
+                source: WHATWG.to_string(),
+                name: "fetch".to_string(),
+            },
+        ];
+        for doc in docs {
+            assert_eq!(doc.to_string().parse::<DocumentType>().unwrap(), doc);
+        }
+        assert_eq!(DocumentType::Rfc(9000).to_string(), "RFC 9000");
+        assert_eq!(
+            "not a document".parse::<DocumentType>(),
+            Err(ParseError::UnknownDocument("not a document".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_format_display_roundtrip() {
+        for format in Format::ALL {
+            assert_eq!(format.to_string().parse::<Format>().unwrap(), format);
+        }
+        assert_eq!(Format::Text.to_string(), "text");
+        assert_eq!("TXT".parse::<Format>().unwrap(), Format::Text);
+        assert_eq!(
+            "epub".parse::<Format>(),
+            Err(ParseError::UnknownFormat("epub".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_draft_revision() {
+        let draft = DocumentType::Draft("draft-ietf-quic-transport-34".to_string());
+        assert_eq!(
+            draft.draft_revision(),
+            Some(("draft-ietf-quic-transport", 34))
+        );
+        assert_eq!(
+            DocumentType::Draft("draft-ietf-quic-transport".to_string()).draft_revision(),
+            None
+        );
+        assert_eq!(DocumentType::Rfc(9000).draft_revision(), None);
+    }
+
+    #[test]
+    fn test_is_newer_than() {
+        let draft = |name: &str| DocumentType::Draft(name.to_string());
+
+        assert!(DocumentType::Rfc(9000).is_newer_than(&DocumentType::Rfc(8999)));
+        assert!(!DocumentType::Rfc(8999).is_newer_than(&DocumentType::Rfc(9000)));
+        // Unrelated RFCs compare by number too
+        assert!(DocumentType::Rfc(9000).is_newer_than(&DocumentType::Rfc(1)));
+        assert!(draft("draft-foo-10").is_newer_than(&draft("draft-foo-09")));
+        assert!(!draft("draft-foo-09").is_newer_than(&draft("draft-foo-09")));
+        assert!(!draft("draft-bar-10").is_newer_than(&draft("draft-foo-09")));
+        assert!(!draft("draft-foo").is_newer_than(&draft("draft-foo-09")));
+        assert!(!DocumentType::Rfc(9000).is_newer_than(&draft("draft-foo-09")));
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut docs = [
+            DocumentType::Draft("draft-foo-10".to_string()),
+            DocumentType::External {
+                source: W3C.to_string(),
+                name: "webauthn-2".to_string(),
+            },
+            DocumentType::Draft("draft-foo-9".to_string()),
+            DocumentType::Subseries(Subseries::Std, 90),
+            DocumentType::Rfc(9000),
+            DocumentType::Subseries(Subseries::Bcp, 14),
+            DocumentType::Draft("draft-foo".to_string()),
+            DocumentType::Rfc(800),
+        ];
+        docs.sort();
+
+        let names: Vec<_> = docs.iter().map(DocumentType::name).collect();
+        assert_eq!(
+            names,
+            [
+                "rfc800",
+                "rfc9000",
+                "bcp14",
+                "std90",
+                "draft-foo",
+                "draft-foo-9",
+                "draft-foo-10",
+                "w3c-webauthn-2"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_relative_dates() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let mut draft = Document::new(
+            "draft-foo-01".to_string(),
+            "Foo".to_string(),
+            DocumentType::Draft("draft-foo-01".to_string()),
+        );
+        assert_eq!(draft.published_relative(now), None);
+        assert_eq!(draft.expiry_relative(now), None);
+
+        draft.published = Some(now - chrono::Duration::days(173));
+        draft.state = Some("Active".to_string());
+        assert_eq!(
+            draft.published_relative(now).unwrap(),
+            "published 5 months ago"
+        );
+        assert_eq!(
+            draft.expiry_relative(now).unwrap(),
+            "draft expires in 12 days"
+        );
+
+        draft.published = Some(now - chrono::Duration::days(188));
+        assert_eq!(
+            draft.expiry_relative(now).unwrap(),
+            "draft expired 3 days ago"
+        );
+
+        draft.state = Some("RFC".to_string());
+        assert_eq!(draft.expires(), None);
+    }
 
     #[test]
     fn test_parse_rfc_number() {
@@ -170,6 +839,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            DocumentType::parse_list("9000-9002, rfc8446, draft-ietf-quic-qlog").unwrap(),
+            vec![
+                DocumentType::Rfc(9000),
+                DocumentType::Rfc(9001),
+                DocumentType::Rfc(9002),
+                DocumentType::Rfc(8446),
+                DocumentType::Draft("draft-ietf-quic-qlog".to_string()),
+            ]
+        );
+        assert_eq!(
+            DocumentType::parse_list("RFC9114-RFC9114").unwrap(),
+            vec![DocumentType::Rfc(9114)]
+        );
+        assert!(DocumentType::parse_list("9114-9000").is_err());
+    }
+
+    #[test]
+    fn test_parse_subseries() {
+        assert_eq!(
+            DocumentType::parse("BCP 14"),
+            Some(DocumentType::Subseries(Subseries::Bcp, 14))
+        );
+        assert_eq!(
+            DocumentType::parse("std90"),
+            Some(DocumentType::Subseries(Subseries::Std, 90))
+        );
+        assert_eq!(
+            DocumentType::parse("FYI0036"),
+            Some(DocumentType::Subseries(Subseries::Fyi, 36))
+        );
+        assert_eq!(DocumentType::parse("bcp"), None);
+
+        let bcp = DocumentType::Subseries(Subseries::Bcp, 14);
+        assert_eq!(bcp.name(), "bcp14");
+        assert_eq!(bcp.to_string(), "BCP 14");
+        assert_eq!(DocumentType::parse(&bcp.name()), Some(bcp));
+    }
+
     #[test]
     fn test_document_type_display() {
         assert_eq!(DocumentType::Rfc(9000).to_string(), "RFC 9000");
@@ -212,6 +922,122 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_legacy() {
+        let rfc = Some(DocumentType::Rfc(9000));
+        for form in [
+            "https://tools.ietf.org/html/rfc9000",
+            "http://tools.ietf.org/rfc/rfc9000.txt",
+            "tools.ietf.org/pdf/rfc9000.pdf",
+            "https://www.rfc-editor.org/info/rfc9000",
+            "https://www.rfc-editor.org/rfc/rfc9000.html#section-5.2",
+            "https://www.rfc-editor.org/rfc/inline-errata/rfc9000.html",
+            "https://datatracker.ietf.org/doc/html/rfc9000",
+            "https://datatracker.ietf.org/doc/rfc9000/history/",
+            "https://www.ietf.org/rfc/rfc9000.txt",
+            "https://doi.org/10.17487/RFC9000",
+            "10.17487/RFC9000",
+            "urn:ietf:rfc:9000",
+            "RFC9000",
+        ] {
+            assert_eq!(DocumentType::parse(form), rfc, "{}", form);
+        }
+
+        let draft = |name: &str| Some(DocumentType::Draft(name.to_string()));
+        assert_eq!(
+            DocumentType::parse("I-D.ietf-quic-transport"),
+            draft("draft-ietf-quic-transport")
+        );
+        assert_eq!(
+            DocumentType::parse("I-D.draft-ietf-quic-transport-34"),
+            draft("draft-ietf-quic-transport-34")
+        );
+        assert_eq!(
+            DocumentType::parse("https://tools.ietf.org/html/draft-ietf-quic-transport-34"),
+            draft("draft-ietf-quic-transport-34")
+        );
+        assert_eq!(
+            DocumentType::parse("https://tools.ietf.org/id/draft-ietf-quic-transport-34.txt"),
+            draft("draft-ietf-quic-transport-34")
+        );
+        assert_eq!(
+            DocumentType::parse("https://tools.ietf.org/wg/quic/draft-ietf-quic-transport/"),
+            draft("draft-ietf-quic-transport")
+        );
+        assert_eq!(
+            DocumentType::parse("https://www.rfc-editor.org/info/bcp14"),
+            Some(DocumentType::Subseries(Subseries::Bcp, 14))
+        );
+
+        // Other hosts and pages without a document are not guessed at
+        assert_eq!(DocumentType::parse("https://example.org/rfc9000"), None);
+        assert_eq!(
+            DocumentType::parse("https://www.rfc-editor.org/errata/eid5000"),
+            None
+        );
+        assert_eq!(DocumentType::parse("I-D."), None);
+    }
+
+    #[test]
+    fn test_parse_external() {
+        let webauthn = DocumentType::External {
+            source: W3C.to_string(),
+            name: "webauthn-2".to_string(),
+        };
+        assert_eq!(
+            DocumentType::parse("w3c:webauthn-2"),
+            Some(webauthn.clone())
+        );
+        assert_eq!(
+            DocumentType::parse("W3C webauthn-2"),
+            Some(webauthn.clone())
+        );
+
+        // Canonical and display names round-trip
+        assert_eq!(webauthn.name(), "w3c-webauthn-2");
+        assert_eq!(webauthn.to_string(), "W3C webauthn-2");
+        assert_eq!(
+            DocumentType::parse(&webauthn.name()),
+            Some(webauthn.clone())
+        );
+        assert_eq!(
+            DocumentType::parse(&webauthn.to_string()),
+            Some(webauthn.clone())
+        );
+
+        // External names containing "draft" are not Internet-Drafts
+        assert_eq!(
+            DocumentType::parse("w3c:css-draft"),
+            Some(DocumentType::External {
+                source: W3C.to_string(),
+                name: "css-draft".to_string(),
+            })
+        );
+
+        assert_eq!(DocumentType::parse("w3c:"), None);
+        assert_eq!(DocumentType::parse("w3cfoo"), None);
+    }
+
+    #[test]
+    fn test_external_urls() {
+        let fetch = DocumentType::parse("whatwg:fetch").unwrap();
+        assert_eq!(
+            fetch.external_url().as_deref(),
+            Some("https://fetch.spec.whatwg.org/")
+        );
+        assert_eq!(fetch.datatracker_url(), "https://fetch.spec.whatwg.org/");
+        assert!(!fetch.is_ietf());
+
+        let webauthn = DocumentType::parse("w3c:webauthn-2").unwrap();
+        assert_eq!(
+            webauthn.external_url().as_deref(),
+            Some("https://www.w3.org/TR/webauthn-2/")
+        );
+
+        assert_eq!(DocumentType::Rfc(9000).external_url(), None);
+        assert!(DocumentType::Rfc(9000).is_ietf());
+    }
+
     #[test]
     fn test_short_title() {
         let doc = Document::new(
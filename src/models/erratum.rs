@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+use crate::parse::SectionAnchor;
+
+/// Kind of erratum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErratumType {
+    Technical,
+    Editorial,
+    Other,
+}
+
+impl ErratumType {
+    /// Parse the RFC Editor's type code ("Technical", "Editorial")
+    pub fn parse(s: &str) -> Self {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "technical" => ErratumType::Technical,
+            "editorial" => ErratumType::Editorial,
+            _ => ErratumType::Other,
+        }
+    }
+}
+
+/// Review status of an erratum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErratumStatus {
+    Reported,
+    Verified,
+    HeldForDocumentUpdate,
+    Rejected,
+}
+
+impl ErratumStatus {
+    /// Parse the RFC Editor's status code; unknown codes count as reported
+    pub fn parse(s: &str) -> Self {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "verified" => ErratumStatus::Verified,
+            "held for document update" | "hold for document update" => {
+                ErratumStatus::HeldForDocumentUpdate
+            }
+            "rejected" => ErratumStatus::Rejected,
+            _ => ErratumStatus::Reported,
+        }
+    }
+
+    /// Get a human-readable name
+    pub fn label(&self) -> &'static str {
+        match self {
+            ErratumStatus::Reported => "Reported",
+            ErratumStatus::Verified => "Verified",
+            ErratumStatus::HeldForDocumentUpdate => "Held for Document Update",
+            ErratumStatus::Rejected => "Rejected",
+        }
+    }
+}
+
+/// An erratum reported against an RFC
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Erratum {
+    /// Errata ID (e.g., 7392)
+    pub id: u32,
+    /// RFC the erratum applies to
+    pub rfc: u32,
+    pub kind: ErratumType,
+    pub status: ErratumStatus,
+    /// Affected section as given by the submitter (e.g., "4.1", "GLOBAL")
+    pub section: Option<String>,
+    pub original_text: Option<String>,
+    pub corrected_text: Option<String>,
+    pub notes: Option<String>,
+    /// Submission date (e.g., "2021-06-02")
+    pub submitted: Option<String>,
+    pub submitter: Option<String>,
+}
+
+impl Erratum {
+    /// Get the RFC Editor page of this erratum
+    pub fn url(&self) -> String {
+        format!("https://www.rfc-editor.org/errata/eid{}", self.id)
+    }
+
+    /// Get the location of the affected section; None for "GLOBAL" or free-form text
+    pub fn anchor(&self) -> Option<SectionAnchor> {
+        SectionAnchor::parse(self.section.as_deref()?)
+    }
+
+    /// Get a deep link to the affected section in the HTML rendering of the RFC
+    pub fn section_url(&self) -> Option<String> {
+        self.anchor().map(|a| a.rfc_url(self.rfc))
+    }
+
+    /// Check if the erratum is still relevant (not rejected)
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        self.status != ErratumStatus::Rejected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_codes() {
+        assert_eq!(ErratumType::parse("Technical"), ErratumType::Technical);
+        assert_eq!(ErratumType::parse("?"), ErratumType::Other);
+        assert_eq!(
+            ErratumStatus::parse("Held for Document Update"),
+            ErratumStatus::HeldForDocumentUpdate
+        );
+        assert_eq!(ErratumStatus::parse("Reported"), ErratumStatus::Reported);
+        assert_eq!(ErratumStatus::parse("rejected"), ErratumStatus::Rejected);
+    }
+
+    #[test]
+    fn test_section_url() {
+        let mut erratum = Erratum {
+            id: 7392,
+            rfc: 9000,
+            kind: ErratumType::Editorial,
+            status: ErratumStatus::Verified,
+            section: Some("Appendix A.2".to_string()),
+            original_text: None,
+            corrected_text: None,
+            notes: None,
+            submitted: None,
+            submitter: None,
+        };
+        assert_eq!(
+            erratum.section_url().as_deref(),
+            Some("https://www.rfc-editor.org/rfc/rfc9000.html#appendix-A.2")
+        );
+
+        erratum.section = Some("GLOBAL".to_string());
+        assert!(erratum.anchor().is_none());
+    }
+}
@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Document, DocumentType, Format, RfcMetadata};
+use crate::messages::Message;
+use crate::profile::Timings;
+
+/// Aggregated information about a single document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentInfo {
+    /// The document this information describes
+    pub doc_type: DocumentType,
+    /// Datatracker metadata (title, authors, abstract, ...)
+    pub document: Option<Document>,
+    /// RFC Editor publication metadata (RFCs only)
+    pub rfc: Option<RfcMetadata>,
+    /// Number of reported errata (RFCs only)
+    pub errata_count: Option<usize>,
+    /// Latest published revision (drafts only)
+    pub latest_revision: Option<String>,
+    /// Formats currently present in the local cache
+    pub cached_formats: Vec<Format>,
+    /// Time spent gathering the information, if profiling is on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<Timings>,
+}
+
+impl DocumentInfo {
+    /// Create an empty info record for a document
+    pub fn new(doc_type: DocumentType) -> Self {
+        Self {
+            doc_type,
+            document: None,
+            rfc: None,
+            errata_count: None,
+            latest_revision: None,
+            cached_formats: Vec::new(),
+            timings: None,
+        }
+    }
+
+    /// Get the best known title for the document
+    pub fn title(&self) -> Option<&str> {
+        self.document
+            .as_ref()
+            .map(|d| d.title.as_str())
+            .or_else(|| self.rfc.as_ref().map(|r| r.title.as_str()))
+    }
+
+    /// Get the best known status for the document
+    pub fn status(&self) -> Option<&str> {
+        self.rfc
+            .as_ref()
+            .and_then(|r| r.status.as_deref())
+            .or_else(|| self.document.as_ref().and_then(|d| d.status.as_deref()))
+    }
+
+    /// Documents this document obsoletes
+    pub fn obsoletes(&self) -> &[DocumentType] {
+        self.rfc.as_ref().map_or(&[], |r| &r.obsoletes)
+    }
+
+    /// Documents that obsolete this document
+    pub fn obsoleted_by(&self) -> &[DocumentType] {
+        self.rfc.as_ref().map_or(&[], |r| &r.obsoleted_by)
+    }
+
+    /// Documents this document updates
+    pub fn updates(&self) -> &[DocumentType] {
+        self.rfc.as_ref().map_or(&[], |r| &r.updates)
+    }
+
+    /// Documents that update this document
+    pub fn updated_by(&self) -> &[DocumentType] {
+        self.rfc.as_ref().map_or(&[], |r| &r.updated_by)
+    }
+
+    /// Get a warning if the document has been obsoleted
+    pub fn obsolescence_warning(&self) -> Option<Message> {
+        let by = self.obsoleted_by();
+        (!by.is_empty()).then(|| Message::ObsoletedBy(self.doc_type.clone(), by.to_vec()))
+    }
+
+    /// Check if any format of the document is cached locally
+    #[must_use]
+    pub fn is_cached(&self) -> bool {
+        !self.cached_formats.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_info() {
+        let info = DocumentInfo::new(DocumentType::Rfc(9000));
+
+        assert_eq!(info.title(), None);
+        assert_eq!(info.status(), None);
+        assert!(info.obsoletes().is_empty());
+        assert!(info.obsoleted_by().is_empty());
+        assert!(!info.is_cached());
+    }
+
+    #[test]
+    fn test_info_prefers_rfc_status() {
+        let mut info = DocumentInfo::new(DocumentType::Rfc(9000));
+        let mut doc = Document::new(
+            "rfc9000".to_string(),
+            "QUIC".to_string(),
+            DocumentType::Rfc(9000),
+        );
+        doc.status = Some("Proposed Standard".to_string());
+        info.document = Some(doc);
+
+        assert_eq!(info.title(), Some("QUIC"));
+        assert_eq!(info.status(), Some("Proposed Standard"));
+
+        info.rfc = Some(RfcMetadata {
+            number: 9000,
+            title: "QUIC: A UDP-Based Multiplexed and Secure Transport".to_string(),
+            status: Some("PROPOSED STANDARD".to_string()),
+            updated_by: vec![DocumentType::Rfc(9369)],
+            ..Default::default()
+        });
+
+        assert_eq!(info.title(), Some("QUIC"));
+        assert_eq!(info.status(), Some("PROPOSED STANDARD"));
+        assert_eq!(info.updated_by(), &[DocumentType::Rfc(9369)]);
+        assert_eq!(info.obsolescence_warning(), None);
+    }
+}
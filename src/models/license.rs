@@ -0,0 +1,105 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// License of code components extracted from a document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CodeLicense {
+    /// BSD License, as referenced by TLP 2.0 to 4.0 (the "Simplified" 2-clause text)
+    SimplifiedBsd,
+    /// Revised (3-clause) BSD License, referenced since TLP 5.0
+    RevisedBsd,
+}
+
+impl CodeLicense {
+    /// Get a human-readable name
+    pub fn label(&self) -> &'static str {
+        match self {
+            CodeLicense::SimplifiedBsd => "Simplified BSD License",
+            CodeLicense::RevisedBsd => "Revised BSD License",
+        }
+    }
+}
+
+/// Copyright and licensing terms of a document, as stated in its boilerplate
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct License {
+    /// Copyright holder (e.g., "IETF Trust" or "The Internet Society")
+    pub holder: Option<String>,
+    /// Year of the copyright notice
+    pub year: Option<u16>,
+    /// Major version of the IETF Trust's Legal Provisions the notice refers to (1 to 5)
+    ///
+    /// `None` for documents published before the TLP (November 2008).
+    pub tlp: Option<u8>,
+    /// License of code components, if the notice grants one
+    pub code_license: Option<CodeLicense>,
+    /// Carries the disclaimer for material published before RFC 5378 (November 10, 2008),
+    /// whose contributors may not have allowed modifications outside the IETF
+    pub pre5378: bool,
+    /// The document may not be modified or have derivative works created
+    pub no_derivatives: bool,
+}
+
+impl License {
+    /// Check if excerpts of the document may be modified and used in other works
+    /// under the Trust Legal Provisions
+    ///
+    /// Documents before the TLP, with the pre-5378 disclaimer or with a
+    /// no-derivatives clause need permission from the original contributors.
+    pub fn allows_derivative_works(&self) -> bool {
+        self.tlp.is_some() && !self.pre5378 && !self.no_derivatives
+    }
+}
+
+impl fmt::Display for License {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.holder, self.year) {
+            (Some(holder), Some(year)) => write!(f, "(c) {} {}", year, holder)?,
+            (Some(holder), None) => write!(f, "(c) {}", holder)?,
+            _ => f.write_str("No copyright notice")?,
+        }
+        if let Some(tlp) = self.tlp {
+            write!(f, ", TLP {}.0", tlp)?;
+        }
+        if let Some(code) = self.code_license {
+            write!(f, ", code under the {}", code.label())?;
+        }
+        if self.pre5378 {
+            f.write_str(", pre-5378 material")?;
+        }
+        if self.no_derivatives {
+            f.write_str(", no derivative works")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_license_display() {
+        let license = License {
+            holder: Some("IETF Trust".to_string()),
+            year: Some(2021),
+            tlp: Some(5),
+            code_license: Some(CodeLicense::RevisedBsd),
+            ..Default::default()
+        };
+        assert!(license.allows_derivative_works());
+        assert_eq!(
+            license.to_string(),
+            "(c) 2021 IETF Trust, TLP 5.0, code under the Revised BSD License"
+        );
+
+        let legacy = License {
+            pre5378: true,
+            ..license
+        };
+        assert!(!legacy.allows_derivative_works());
+        assert!(legacy.to_string().ends_with(", pre-5378 material"));
+        assert!(!License::default().allows_derivative_works());
+    }
+}
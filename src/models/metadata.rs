@@ -0,0 +1,182 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Author, Document, DocumentType, License, Relations, Stream};
+
+/// ISSN of the RFC series
+pub const RFC_SERIES_ISSN: &str = "2070-1721";
+
+/// DOI prefix assigned to the RFC Editor
+const RFC_DOI_PREFIX: &str = "10.17487";
+
+/// Publication metadata for an RFC as reported by the RFC Editor
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RfcMetadata {
+    /// RFC number
+    pub number: u32,
+    /// Human-readable title
+    pub title: String,
+    /// List of authors
+    pub authors: Vec<Author>,
+    /// Publication date as published by the RFC Editor (e.g., "May 2021")
+    pub pub_date: Option<String>,
+    /// Current status (e.g., "PROPOSED STANDARD")
+    pub status: Option<String>,
+    /// Status at the time of publication
+    pub pub_status: Option<String>,
+    /// Originating working group or stream (e.g., "QUIC", "IAB")
+    pub source: Option<String>,
+    /// Number of pages
+    pub pages: Option<u32>,
+    /// Abstract text
+    pub abstract_text: Option<String>,
+    /// The draft this RFC was published from
+    pub draft: Option<String>,
+    /// Documents this RFC obsoletes
+    pub obsoletes: Vec<DocumentType>,
+    /// Documents that obsolete this RFC
+    pub obsoleted_by: Vec<DocumentType>,
+    /// Documents this RFC updates
+    pub updates: Vec<DocumentType>,
+    /// Documents that update this RFC
+    pub updated_by: Vec<DocumentType>,
+    /// Link to the errata page, if any errata exist
+    pub errata_url: Option<String>,
+    /// Digital Object Identifier as published by the RFC Editor (e.g., "10.17487/RFC9000")
+    pub doi: Option<String>,
+    /// Copyright and licensing terms, read from the boilerplate of the text when available
+    #[serde(default)]
+    pub license: Option<License>,
+}
+
+impl RfcMetadata {
+    /// Check if this RFC has been obsoleted by a later document
+    #[must_use]
+    pub fn is_obsoleted(&self) -> bool {
+        !self.obsoleted_by.is_empty()
+    }
+
+    /// Get the obsoletes/updates relations of the RFC
+    pub fn relations(&self) -> Relations {
+        Relations::from(self)
+    }
+
+    /// Get the DOI of the RFC, derived from its number if not published
+    pub fn doi(&self) -> String {
+        self.doi
+            .clone()
+            .unwrap_or_else(|| format!("{}/RFC{:04}", RFC_DOI_PREFIX, self.number))
+    }
+
+    /// Get the resolvable URL of the DOI
+    pub fn doi_url(&self) -> String {
+        format!("https://doi.org/{}", self.doi())
+    }
+
+    /// Get the URN of the RFC (RFC 2648), e.g., "urn:ietf:rfc:9000"
+    pub fn urn(&self) -> String {
+        format!("urn:ietf:rfc:{}", self.number)
+    }
+
+    /// Get the ISSN of the RFC series
+    pub fn issn(&self) -> &'static str {
+        RFC_SERIES_ISSN
+    }
+}
+
+/// Full metadata of an RFC or Internet-Draft as reported by the Datatracker
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentMetadata {
+    /// Internal name (e.g., "rfc9000" or "draft-ietf-quic-transport")
+    pub name: String,
+    /// Document type
+    pub doc_type: DocumentType,
+    /// Human-readable title
+    pub title: String,
+    /// List of authors
+    pub authors: Vec<Author>,
+    /// Abstract text
+    pub abstract_text: Option<String>,
+    /// Time of the latest revision or publication
+    pub published: Option<DateTime<Utc>>,
+    /// Expiry of the latest revision (drafts only)
+    pub expires: Option<DateTime<Utc>>,
+    /// Number of pages
+    pub pages: Option<u32>,
+    /// Current revision (drafts only, e.g., "34")
+    pub rev: Option<String>,
+    /// Publication stream (e.g., IETF, IAB, IRTF)
+    pub stream: Option<Stream>,
+    /// Standards level of a published RFC (e.g., "Proposed Standard")
+    pub std_level: Option<String>,
+    /// Standards level a draft is intended for
+    pub intended_std_level: Option<String>,
+    /// Datatracker state (e.g., "Active", "Expired", "RFC")
+    pub state: Option<String>,
+    /// Working group acronym (e.g., "quic")
+    pub wg: Option<String>,
+    /// Working group name (e.g., "QUIC")
+    pub wg_name: Option<String>,
+}
+
+impl DocumentMetadata {
+    /// Get the standards level, falling back to the intended one
+    pub fn status(&self) -> Option<&str> {
+        self.std_level
+            .as_deref()
+            .or(self.intended_std_level.as_deref())
+    }
+}
+
+impl From<DocumentMetadata> for Document {
+    fn from(meta: DocumentMetadata) -> Self {
+        let status = meta.status().map(str::to_string);
+        Self {
+            name: meta.name,
+            title: meta.title,
+            doc_type: meta.doc_type,
+            abstract_text: meta.abstract_text,
+            pages: meta.pages,
+            published: meta.published,
+            status,
+            authors: meta.authors,
+            stream: meta.stream,
+            wg: meta.wg,
+            rev: meta.rev,
+            state: meta.state,
+            matches: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_obsoleted() {
+        let mut meta = RfcMetadata {
+            number: 2616,
+            ..Default::default()
+        };
+        assert!(!meta.is_obsoleted());
+
+        meta.obsoleted_by.push(DocumentType::Rfc(7230));
+        assert!(meta.is_obsoleted());
+    }
+
+    #[test]
+    fn test_identifiers() {
+        let mut meta = RfcMetadata {
+            number: 791,
+            ..Default::default()
+        };
+        assert_eq!(meta.doi(), "10.17487/RFC0791");
+        assert_eq!(meta.doi_url(), "https://doi.org/10.17487/RFC0791");
+        assert_eq!(meta.urn(), "urn:ietf:rfc:791");
+        assert_eq!(meta.issn(), "2070-1721");
+
+        meta.doi = Some("10.17487/rfc0791".to_string());
+        assert_eq!(meta.doi(), "10.17487/rfc0791");
+    }
+}
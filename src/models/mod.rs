@@ -1,5 +1,26 @@
+mod author;
+mod cluster;
 mod document;
+mod erratum;
+mod info;
+mod license;
+mod metadata;
+mod relations;
+mod revision;
 mod search;
+mod stream;
 
-pub use document::{Document, DocumentType, Format};
-pub use search::{SearchFilter, SearchResult};
+pub use author::Author;
+pub use cluster::Cluster;
+pub use document::{
+    Document, DocumentType, Format, FormatPreference, FormatStrategy, ParseError, Subseries,
+    FIRST_V3_RFC, W3C, WHATWG,
+};
+pub use erratum::{Erratum, ErratumStatus, ErratumType};
+pub use info::DocumentInfo;
+pub use license::{CodeLicense, License};
+pub use metadata::{DocumentMetadata, RfcMetadata, RFC_SERIES_ISSN};
+pub use relations::Relations;
+pub use revision::DraftRevision;
+pub use search::{SearchFilter, SearchResult, SectionMatch};
+pub use stream::Stream;
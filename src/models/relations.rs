@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+use super::{DocumentType, RfcMetadata};
+
+/// How a document relates to the documents that replace or amend it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Relations {
+    /// Documents this document obsoletes
+    pub obsoletes: Vec<DocumentType>,
+    /// Documents that obsolete this document
+    pub obsoleted_by: Vec<DocumentType>,
+    /// Documents this document updates
+    pub updates: Vec<DocumentType>,
+    /// Documents that update this document
+    pub updated_by: Vec<DocumentType>,
+}
+
+impl Relations {
+    /// Check if the document has been obsoleted
+    #[must_use]
+    pub fn is_obsoleted(&self) -> bool {
+        !self.obsoleted_by.is_empty()
+    }
+
+    /// Check if no relations are known
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.obsoletes.is_empty()
+            && self.obsoleted_by.is_empty()
+            && self.updates.is_empty()
+            && self.updated_by.is_empty()
+    }
+
+    /// Get the most recent document that obsoletes this one
+    pub fn successor(&self) -> Option<&DocumentType> {
+        self.obsoleted_by.iter().max()
+    }
+}
+
+impl From<&RfcMetadata> for Relations {
+    fn from(meta: &RfcMetadata) -> Self {
+        Self {
+            obsoletes: meta.obsoletes.clone(),
+            obsoleted_by: meta.obsoleted_by.clone(),
+            updates: meta.updates.clone(),
+            updated_by: meta.updated_by.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_successor() {
+        let meta = RfcMetadata {
+            number: 2616,
+            obsoleted_by: vec![DocumentType::Rfc(7231), DocumentType::Rfc(7230)],
+            updates: vec![DocumentType::Rfc(2068)],
+            ..Default::default()
+        };
+        let relations = Relations::from(&meta);
+
+        assert!(relations.is_obsoleted());
+        assert_eq!(relations.successor(), Some(&DocumentType::Rfc(7231)));
+        assert!(Relations::default().is_empty());
+        assert!(Relations::default().successor().is_none());
+    }
+}
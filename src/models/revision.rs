@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::DocumentType;
+
+/// A published revision of an Internet-Draft
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DraftRevision {
+    /// Draft name without revision (e.g., "draft-ietf-quic-transport")
+    pub name: String,
+    /// Revision number (e.g., 7 for "-07")
+    pub rev: u32,
+    /// When the revision was posted
+    pub published: Option<DateTime<Utc>>,
+}
+
+impl DraftRevision {
+    /// Get the revision suffix as written in draft names (e.g., "07")
+    pub fn label(&self) -> String {
+        format!("{:02}", self.rev)
+    }
+
+    /// Get the document of this exact revision
+    pub fn document(&self) -> DocumentType {
+        DocumentType::Draft(format!("{}-{}", self.name, self.label()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revision_document() {
+        let revision = DraftRevision {
+            name: "draft-ietf-quic-transport".to_string(),
+            rev: 7,
+            published: None,
+        };
+        assert_eq!(revision.label(), "07");
+        assert_eq!(
+            revision.document(),
+            DocumentType::Draft("draft-ietf-quic-transport-07".to_string())
+        );
+    }
+}
@@ -1,9 +1,13 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use super::Document;
+use super::{Document, Format};
+use crate::cache::CacheManager;
+use crate::parse::SectionAnchor;
+use crate::profile::Timings;
+use crate::search::section_matches;
 
 /// Filter for search results
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SearchFilter {
     /// Only return RFCs
     RfcsOnly,
@@ -25,8 +29,17 @@ impl SearchFilter {
     }
 }
 
+/// A section of a document that matched a search
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionMatch {
+    pub anchor: SectionAnchor,
+    /// Line of the section with the most query words, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
 /// Search results from the API
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SearchResult {
     /// List of matching documents
     pub documents: Vec<Document>,
@@ -36,6 +49,15 @@ pub struct SearchResult {
     pub query: String,
     /// The filter that was applied
     pub filter: SearchFilter,
+    /// Offset of this page in the unfiltered Datatracker results
+    #[serde(default)]
+    pub offset: u64,
+    /// Offset to pass to `search_page` for the next page, if there is one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<u64>,
+    /// Time spent on the search, if profiling is on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<Timings>,
 }
 
 impl SearchResult {
@@ -46,6 +68,9 @@ impl SearchResult {
             has_more: false,
             query,
             filter,
+            offset: 0,
+            next_offset: None,
+            timings: None,
         }
     }
 
@@ -60,11 +85,31 @@ impl SearchResult {
     pub fn len(&self) -> usize {
         self.documents.len()
     }
+
+    /// Keep only documents with an author matching a query in either name form
+    pub fn retain_author(&mut self, query: &str) {
+        self.documents.retain(|doc| doc.has_author(query));
+    }
+
+    /// Find the sections matching the query in documents cached as text
+    ///
+    /// Fills `matches` of each cached document (see
+    /// [`section_matches`](crate::search::section_matches)); documents that
+    /// aren't cached are left without matches rather than fetched.
+    pub fn with_cached_sections(mut self, cache: &CacheManager) -> Self {
+        for doc in &mut self.documents {
+            if let Some(text) = cache.peek_document(&doc.doc_type, Format::Text) {
+                doc.matches = section_matches(&text, &self.query);
+            }
+        }
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{Author, DocumentType};
 
     #[test]
     fn test_search_filter_api_param() {
@@ -73,6 +118,57 @@ mod tests {
         assert_eq!(SearchFilter::Both.api_param(), None);
     }
 
+    #[test]
+    fn test_retain_author() {
+        let mut doc = Document::new(
+            "rfc6241".to_string(),
+            "NETCONF".to_string(),
+            DocumentType::Rfc(6241),
+        );
+        doc.authors = vec![Author::with_ascii(
+            "Jürgen Schönwälder",
+            "Juergen Schoenwaelder",
+        )];
+        let mut result = SearchResult::empty("netconf".to_string(), SearchFilter::Both);
+        result.documents.push(doc);
+
+        result.retain_author("Schoenwaelder");
+        assert_eq!(result.len(), 1);
+        result.retain_author("Thomson");
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_with_cached_sections() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        cache
+            .store_document(
+                &DocumentType::Rfc(9000),
+                Format::Text,
+                "1.  Introduction\n\n   Overview.\n\n2.  Streams\n\n   Streams carry data.\n",
+            )
+            .unwrap();
+        let mut result = SearchResult::empty("streams".to_string(), SearchFilter::Both);
+        for (name, doc_type) in [
+            ("rfc9000", DocumentType::Rfc(9000)),
+            ("rfc9001", DocumentType::Rfc(9001)),
+        ] {
+            result
+                .documents
+                .push(Document::new(name.to_string(), String::new(), doc_type));
+        }
+
+        let result = result.with_cached_sections(&cache);
+        assert_eq!(
+            result.documents[0]
+                .matched_section()
+                .map(|anchor| anchor.number()),
+            Some("2")
+        );
+        assert!(result.documents[1].matches.is_empty());
+    }
+
     #[test]
     fn test_search_filter_default() {
         assert_eq!(SearchFilter::default(), SearchFilter::Both);
@@ -0,0 +1,106 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The stream through which a document is published
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Stream {
+    /// Internet Engineering Task Force
+    Ietf,
+    /// Internet Research Task Force
+    Irtf,
+    /// Internet Architecture Board
+    Iab,
+    /// Independent Submission Stream
+    Independent,
+    /// Editorial Stream
+    Editorial,
+    /// Documents published before streams existed
+    Legacy,
+}
+
+impl Stream {
+    /// Parse a stream from a name ("IRTF"), slug ("ise") or Datatracker API URI
+    /// ("/api/v1/name/streamname/irtf/")
+    pub fn parse(s: &str) -> Option<Self> {
+        let slug = s
+            .trim()
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match slug.as_str() {
+            "ietf" => Some(Stream::Ietf),
+            "irtf" => Some(Stream::Irtf),
+            "iab" => Some(Stream::Iab),
+            "ise" | "independent" => Some(Stream::Independent),
+            "editorial" => Some(Stream::Editorial),
+            "legacy" => Some(Stream::Legacy),
+            _ => None,
+        }
+    }
+
+    /// Infer the stream from a draft name (e.g., "draft-irtf-cfrg-..." is IRTF)
+    /// Individual drafts carry no stream in their name, so None is returned
+    pub fn from_draft_name(name: &str) -> Option<Self> {
+        let rest = name.strip_prefix("draft-")?;
+        match rest.split('-').next()? {
+            "ietf" => Some(Stream::Ietf),
+            "irtf" => Some(Stream::Irtf),
+            "iab" => Some(Stream::Iab),
+            "rfc-editor" | "rswg" => Some(Stream::Editorial),
+            _ => None,
+        }
+    }
+
+    /// Get the short label used in listings
+    pub fn label(&self) -> &'static str {
+        match self {
+            Stream::Ietf => "IETF",
+            Stream::Irtf => "IRTF",
+            Stream::Iab => "IAB",
+            Stream::Independent => "ISE",
+            Stream::Editorial => "Editorial",
+            Stream::Legacy => "Legacy",
+        }
+    }
+}
+
+impl fmt::Display for Stream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stream() {
+        assert_eq!(Stream::parse("IRTF"), Some(Stream::Irtf));
+        assert_eq!(Stream::parse("ise"), Some(Stream::Independent));
+        assert_eq!(
+            Stream::parse("/api/v1/name/streamname/irtf/"),
+            Some(Stream::Irtf)
+        );
+        assert_eq!(Stream::parse(""), None);
+        assert_eq!(Stream::parse("unknown"), None);
+    }
+
+    #[test]
+    fn test_from_draft_name() {
+        assert_eq!(
+            Stream::from_draft_name("draft-irtf-cfrg-hpke-12"),
+            Some(Stream::Irtf)
+        );
+        assert_eq!(
+            Stream::from_draft_name("draft-ietf-quic-transport-34"),
+            Some(Stream::Ietf)
+        );
+        assert_eq!(Stream::from_draft_name("draft-smith-foo-00"), None);
+        assert_eq!(Stream::from_draft_name("rfc9000"), None);
+    }
+}
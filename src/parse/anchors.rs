@@ -0,0 +1,167 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::sections::is_section_number;
+use super::Section;
+
+/// A section location that can be expressed in every format of a document
+///
+/// Plain text uses section numbers ("5.2", "A.1"), the HTML rendering uses
+/// anchors ("#section-5.2", "#appendix-A.1") and prepped xml2rfc v3 sources use
+/// `pn` identifiers ("section-5.2", "section-appendix.a", "section-a.1").
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SectionAnchor {
+    /// Canonical section number, appendix letters in upper case (e.g., "A.1")
+    number: String,
+}
+
+impl SectionAnchor {
+    /// Parse a section number, text label, HTML anchor, XML `pn` or URL fragment
+    ///
+    /// Accepts forms like "5.2", "Section 5.2.", "Appendix A", "#section-5.2",
+    /// "appendix-A.1", "section-a.1", "section-appendix.a" and
+    /// "https://www.rfc-editor.org/rfc/rfc9000.html#section-5.2".
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let s = s.rsplit_once('#').map_or(s, |(_, fragment)| fragment);
+        let lower = s.to_ascii_lowercase();
+
+        let (rest, appendix) = if let Some(rest) = strip_any(&lower, &["section-appendix."]) {
+            (rest, true)
+        } else if let Some(rest) = strip_any(&lower, &["appendix-", "appendix "]) {
+            (rest, true)
+        } else if let Some(rest) = strip_any(&lower, &["section-", "section ", "sec. ", "§"]) {
+            (rest, false)
+        } else {
+            (lower.as_str(), false)
+        };
+
+        let rest = rest.trim().trim_end_matches(['.', ':']);
+        let number = match rest.split_once('.') {
+            Some((first, tail)) => format!("{}.{}", first.to_ascii_uppercase(), tail),
+            None => rest.to_ascii_uppercase(),
+        };
+        let is_letter = number.starts_with(|c: char| c.is_ascii_uppercase());
+        if !is_section_number(&number) || (appendix && !is_letter) {
+            return None;
+        }
+        Some(Self { number })
+    }
+
+    /// Get the anchor of a detected section; unnumbered sections have none
+    pub fn of(section: &Section) -> Option<Self> {
+        section.number.as_deref().and_then(Self::parse)
+    }
+
+    /// Get the section number as used in plain text (e.g., "5.2", "A.1")
+    pub fn number(&self) -> &str {
+        &self.number
+    }
+
+    /// Check if the anchor points into an appendix
+    #[must_use]
+    pub fn is_appendix(&self) -> bool {
+        self.number.starts_with(|c: char| c.is_ascii_uppercase())
+    }
+
+    /// Get the `id` of the section in the HTML rendering (e.g., "section-5.2")
+    pub fn html_anchor(&self) -> String {
+        if self.is_appendix() {
+            format!("appendix-{}", self.number)
+        } else {
+            format!("section-{}", self.number)
+        }
+    }
+
+    /// Get the `pn` of the section in a prepped XML source (e.g., "section-a.1")
+    pub fn xml_pn(&self) -> String {
+        let lower = self.number.to_ascii_lowercase();
+        // Only top-level appendices carry the "appendix." prefix
+        if self.is_appendix() && !lower.contains('.') {
+            format!("section-appendix.{}", lower)
+        } else {
+            format!("section-{}", lower)
+        }
+    }
+
+    /// Get a deep link into the HTML rendering of an RFC
+    pub fn rfc_url(&self, rfc: u32) -> String {
+        format!(
+            "https://www.rfc-editor.org/rfc/rfc{}.html#{}",
+            rfc,
+            self.html_anchor()
+        )
+    }
+}
+
+impl fmt::Display for SectionAnchor {
+    /// Format as a plain text label (e.g., "Section 5.2", "Appendix A")
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_appendix() {
+            write!(f, "Appendix {}", self.number)
+        } else {
+            write!(f, "Section {}", self.number)
+        }
+    }
+}
+
+fn strip_any<'a>(s: &'a str, prefixes: &[&str]) -> Option<&'a str> {
+    prefixes.iter().find_map(|p| s.strip_prefix(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anchor(s: &str) -> SectionAnchor {
+        SectionAnchor::parse(s).unwrap_or_else(|| panic!("no anchor in {:?}", s))
+    }
+
+    #[test]
+    fn test_forms_resolve_to_the_same_anchor() {
+        let forms = [
+            "5.2",
+            "5.2.",
+            "Section 5.2",
+            "§5.2",
+            "#section-5.2",
+            "https://www.rfc-editor.org/rfc/rfc9000.html#section-5.2",
+        ];
+        for form in forms {
+            assert_eq!(anchor(form).number(), "5.2", "{}", form);
+        }
+
+        let forms = ["A.1", "Appendix A.1", "appendix-A.1", "section-a.1"];
+        for form in forms {
+            assert_eq!(anchor(form).number(), "A.1", "{}", form);
+        }
+        assert_eq!(anchor("section-appendix.b").number(), "B");
+    }
+
+    #[test]
+    fn test_format_identifiers() {
+        let section = anchor("5.2");
+        assert_eq!(section.html_anchor(), "section-5.2");
+        assert_eq!(section.xml_pn(), "section-5.2");
+        assert_eq!(section.to_string(), "Section 5.2");
+        assert_eq!(
+            section.rfc_url(9000),
+            "https://www.rfc-editor.org/rfc/rfc9000.html#section-5.2"
+        );
+
+        let appendix = anchor("Appendix A");
+        assert_eq!(appendix.html_anchor(), "appendix-A");
+        assert_eq!(appendix.xml_pn(), "section-appendix.a");
+        assert_eq!(appendix.to_string(), "Appendix A");
+        assert_eq!(anchor("A.1").xml_pn(), "section-a.1");
+    }
+
+    #[test]
+    fn test_rejects_non_sections() {
+        assert!(SectionAnchor::parse("GLOBAL").is_none());
+        assert!(SectionAnchor::parse("").is_none());
+        assert!(SectionAnchor::parse("appendix-5").is_none());
+        assert!(SectionAnchor::parse("section-abstract").is_none());
+    }
+}
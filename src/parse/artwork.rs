@@ -0,0 +1,599 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use super::xml::{attribute, unescape};
+use super::{is_page_artifact, sections};
+
+/// Fraction of drawing characters above which a block is treated as ASCII art
+const ART_DENSITY: f64 = 0.3;
+
+/// Indentation from which a block that doesn't read as prose is taken as an example
+const EXAMPLE_INDENT: usize = 5;
+
+/// Element an [`Artwork`] comes from, after xml2rfc v3
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtworkKind {
+    /// Diagrams and other drawings (`<artwork>`)
+    Artwork,
+    /// Code and data samples (`<sourcecode>`)
+    SourceCode,
+}
+
+impl fmt::Display for ArtworkKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Artwork => "artwork",
+            Self::SourceCode => "sourcecode",
+        })
+    }
+}
+
+/// A figure, diagram or code sample of a document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Artwork {
+    pub kind: ArtworkKind,
+    /// Content type (e.g., "json", "abnf", "ascii-art", "packet"); from the
+    /// `type` attribute in XML, guessed from the content in text
+    pub content_type: Option<String>,
+    /// File name, from a `name` attribute or a `<CODE BEGINS> file "..."` marker
+    pub name: Option<String>,
+    /// Number of the enclosing figure
+    pub figure: Option<u32>,
+    /// Caption of the enclosing figure, without the "Figure N:" prefix
+    pub caption: Option<String>,
+    /// The content, without its common indentation
+    pub content: String,
+    /// Line the content starts on (0-based)
+    pub line: usize,
+}
+
+impl Artwork {
+    /// Get a label for listings (e.g., "Figure 3: Packet Layout", "quic.yang")
+    pub fn label(&self) -> String {
+        match (self.figure, &self.caption, &self.name) {
+            (Some(n), Some(caption), _) => format!("Figure {}: {}", n, caption),
+            (Some(n), None, _) => format!("Figure {}", n),
+            (None, Some(caption), _) => caption.clone(),
+            (None, None, Some(name)) => name.clone(),
+            (None, None, None) => self.kind.to_string(),
+        }
+    }
+}
+
+/// Find the figures, diagrams and code samples of a plain text document
+///
+/// `<CODE BEGINS>` blocks, blocks followed by a "Figure N: ..." caption,
+/// ASCII art and ABNF are found, as are indented blocks that don't read as
+/// prose. Blocks separated only by blank lines are joined until a caption
+/// closes the figure.
+pub fn artwork(text: &str) -> Vec<Artwork> {
+    let lines: Vec<&str> = text.lines().collect();
+    let found = sections(text);
+    let headings: HashSet<usize> = found.iter().map(|s| s.start_line).collect();
+    let toc = found
+        .iter()
+        .find(|s| s.number.is_none() && s.title == "Table of Contents")
+        .map_or(0..0, |s| s.start_line..s.end_line);
+
+    let mut figures: Vec<Artwork> = Vec::new();
+    // Lines of a figure that may continue after blank lines, with its guessed type
+    let mut pending: Option<(Range<usize>, Option<Guess>)> = None;
+    let flush = |pending: &mut Option<(Range<usize>, Option<Guess>)>,
+                 figures: &mut Vec<Artwork>,
+                 caption: Option<(Option<u32>, String)>| {
+        if let Some((range, guess)) = pending.take() {
+            let mut figure = block(text_lines(&lines[range.clone()]), range.start);
+            if let Some((kind, content_type)) = guess {
+                (figure.kind, figure.content_type) = (kind, content_type);
+            }
+            // Samples split by blank lines are only recognizable as a whole
+            if figure.content_type.is_none() && is_json(&figure.content) {
+                figure.kind = ArtworkKind::SourceCode;
+                figure.content_type = Some("json".to_string());
+            }
+            if let Some((number, caption)) = caption {
+                (figure.figure, figure.caption) = (number, Some(caption));
+            }
+            figures.push(figure);
+        }
+    };
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if is_gap(line) {
+            i += 1;
+            continue;
+        }
+        if headings.contains(&i) || toc.contains(&i) {
+            flush(&mut pending, &mut figures, None);
+            i += 1;
+            continue;
+        }
+
+        if let Some(code) = code_block(&lines, i) {
+            flush(&mut pending, &mut figures, None);
+            let content_type = code.extension();
+            let name = code.name;
+            i = code.body.end + 1;
+            pending = Some((code.body, Some((ArtworkKind::SourceCode, content_type))));
+            let caption = caption_at(&lines, i).map(|(next, number, caption)| {
+                i = next;
+                (number, caption)
+            });
+            flush(&mut pending, &mut figures, caption);
+            if let Some(code) = figures.last_mut() {
+                code.name = name;
+            }
+            continue;
+        }
+
+        let end = (i..lines.len())
+            .find(|&n| is_gap(lines[n]) || (n > i && headings.contains(&n)))
+            .unwrap_or(lines.len());
+        let caption = caption_at(&lines, end);
+        let guess = classify_block(&lines[i..end]);
+        if guess.is_none() && caption.is_none() {
+            flush(&mut pending, &mut figures, None);
+            i = end;
+            continue;
+        }
+
+        match &mut pending {
+            Some((range, previous)) => {
+                range.end = end;
+                if previous.as_ref().is_none_or(|(_, t)| t.is_none()) && guess.is_some() {
+                    *previous = guess;
+                }
+            }
+            None => pending = Some((i..end, guess)),
+        }
+        i = end;
+        if let Some((next, number, caption)) = caption {
+            flush(&mut pending, &mut figures, Some((number, caption)));
+            i = next;
+        }
+    }
+    flush(&mut pending, &mut figures, None);
+    figures
+}
+
+/// Find the `<artwork>` and `<sourcecode>` elements of an xml2rfc v3 document
+///
+/// Each gets the number and `<name>` of its enclosing `<figure>`. SVG artwork
+/// is returned as markup; both forms of an `<artset>` are included.
+pub fn xml_artwork(xml: &str) -> Vec<Artwork> {
+    let mut figures: Vec<(usize, usize, Option<String>)> = Vec::new();
+    for start in element_starts(xml, "figure") {
+        let end = xml[start..]
+            .find("</figure>")
+            .map_or(xml.len(), |n| start + n);
+        let body = &xml[start..end];
+        let first_art = ["<artwork", "<sourcecode"]
+            .iter()
+            .filter_map(|tag| body.find(tag))
+            .min()
+            .unwrap_or(body.len());
+        let caption = body[..first_art]
+            .find("<name>")
+            .and_then(|n| {
+                let rest = &body[n + "<name>".len()..first_art];
+                rest.find("</name>").map(|e| &rest[..e])
+            })
+            .map(inline_text)
+            .filter(|c| !c.is_empty());
+        figures.push((start, end, caption));
+    }
+
+    let mut found: Vec<(usize, ArtworkKind)> = element_starts(xml, "artwork")
+        .into_iter()
+        .map(|p| (p, ArtworkKind::Artwork))
+        .chain(
+            element_starts(xml, "sourcecode")
+                .into_iter()
+                .map(|p| (p, ArtworkKind::SourceCode)),
+        )
+        .collect();
+    found.sort_by_key(|(p, _)| *p);
+
+    let mut artwork = Vec::new();
+    for (start, kind) in found {
+        let element = match kind {
+            ArtworkKind::Artwork => "artwork",
+            ArtworkKind::SourceCode => "sourcecode",
+        };
+        let tag_end = xml[start..].find('>').map_or(xml.len(), |n| start + n);
+        let tag = &xml[start..tag_end];
+        if tag.ends_with('/') {
+            continue;
+        }
+        let body_start = (tag_end + 1).min(xml.len());
+        let close = format!("</{}>", element);
+        let body_end = xml[body_start..]
+            .find(&close)
+            .map_or(xml.len(), |n| body_start + n);
+        let content = element_text(&xml[body_start..body_end]);
+        if content.trim().is_empty() {
+            continue;
+        }
+
+        let figure = figures
+            .iter()
+            .position(|(s, e, _)| *s < start && start < *e);
+        let leading = content.len() - content.trim_start_matches(['\n', '\r']).len();
+        artwork.push(Artwork {
+            kind,
+            content_type: attribute(tag, "type"),
+            name: attribute(tag, "name"),
+            figure: figure.map(|f| f as u32 + 1),
+            caption: figure.and_then(|f| figures[f].2.clone()),
+            content: trim_blank_lines(&content),
+            line: xml[..body_start].matches('\n').count()
+                + content[..leading].matches('\n').count(),
+        });
+    }
+    artwork
+}
+
+/// A `<CODE BEGINS>` block of a plain text document
+pub(crate) struct CodeBlock {
+    /// File name from a `<CODE BEGINS> file "..."` marker
+    pub name: Option<String>,
+    /// Lines between the markers; the `<CODE ENDS>` marker is at `body.end`
+    pub body: Range<usize>,
+}
+
+impl CodeBlock {
+    /// Get the extension of the file name (e.g., "yang" for "quic.yang")
+    pub fn extension(&self) -> Option<String> {
+        let (_, ext) = self.name.as_deref()?.rsplit_once('.')?;
+        (!ext.is_empty() && ext.chars().all(|c| c.is_ascii_alphanumeric())).then(|| ext.to_string())
+    }
+}
+
+/// Read the `<CODE BEGINS>` block starting at a line, if there is one
+///
+/// A block missing its `<CODE ENDS>` marker runs to the end of the document.
+pub(crate) fn code_block(lines: &[&str], at: usize) -> Option<CodeBlock> {
+    let marker = lines.get(at)?.trim_start().strip_prefix("<CODE BEGINS>")?;
+    let end = lines[at..]
+        .iter()
+        .position(|l| l.trim_start().starts_with("<CODE ENDS>"))
+        .map_or(lines.len(), |n| at + n);
+    Some(CodeBlock {
+        name: marker.split('"').nth(1).map(str::to_string),
+        body: at + 1..end,
+    })
+}
+
+/// Check if a block of lines is dominated by drawing characters
+pub(crate) fn is_art(block: &[&str]) -> bool {
+    let mut total = 0usize;
+    let mut drawing = 0usize;
+
+    for c in block
+        .iter()
+        .flat_map(|l| l.chars())
+        .filter(|c| !c.is_whitespace())
+    {
+        total += 1;
+        if is_drawing_char(c) {
+            drawing += 1;
+        }
+    }
+
+    total > 0 && drawing as f64 / total as f64 >= ART_DENSITY
+}
+
+/// Check for ASCII art and Unicode box-drawing characters
+fn is_drawing_char(c: char) -> bool {
+    matches!(
+        c,
+        '+' | '-' | '|' | '/' | '\\' | '_' | '=' | '*' | '<' | '>' | '^' | 'v' | '#'
+    ) || ('\u{2500}'..='\u{259f}').contains(&c)
+}
+
+/// Kind and content type guessed for a block
+pub(crate) type Guess = (ArtworkKind, Option<String>);
+
+/// Guess the kind and content type of a block; None if it reads as prose
+pub(crate) fn classify_block(block: &[&str]) -> Option<Guess> {
+    let first = block[0].trim_start();
+    if is_bullet(first) {
+        return None;
+    }
+    let joined = block
+        .iter()
+        .map(|l| l.trim())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if is_packet_diagram(block) {
+        return Some((ArtworkKind::Artwork, Some("packet".to_string())));
+    }
+    if is_art(block) {
+        return Some((ArtworkKind::Artwork, Some("ascii-art".to_string())));
+    }
+    if is_rule(first) {
+        return Some((ArtworkKind::SourceCode, Some("abnf".to_string())));
+    }
+    if is_json(&joined) {
+        return Some((ArtworkKind::SourceCode, Some("json".to_string())));
+    }
+    let indented = block.iter().all(|l| indent(l) >= EXAMPLE_INDENT);
+    if indented && !is_prose(block) {
+        let code = block
+            .iter()
+            .any(|l| l.trim_end().ends_with([';', '{', '}']));
+        let kind = match code {
+            true => ArtworkKind::SourceCode,
+            false => ArtworkKind::Artwork,
+        };
+        return Some((kind, None));
+    }
+    None
+}
+
+/// Check for the bit ruler or `+-+-+` borders of a packet diagram
+fn is_packet_diagram(block: &[&str]) -> bool {
+    block.iter().any(|l| {
+        let l = l.trim();
+        l.starts_with("+-+-+") && l.chars().all(|c| c == '+' || c == '-')
+            || l.starts_with("0 1 2 3 4 5 6 7 8 9")
+    })
+}
+
+/// Check for a JSON object, or an array of objects or strings
+fn is_json(text: &str) -> bool {
+    let text = text.trim();
+    (text.starts_with('{') && text.ends_with('}'))
+        || (text.starts_with('[') && text.ends_with(']') && text.contains(['{', '"']))
+}
+
+/// Check for an ABNF rule definition ("name = ..." or "name =/ ...")
+pub(crate) fn is_rule(line: &str) -> bool {
+    let Some((name, rest)) = line.split_once(char::is_whitespace) else {
+        return false;
+    };
+    let rest = rest.trim_start();
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && (rest.starts_with("= ") || rest.starts_with("=/ ") || rest == "=")
+}
+
+/// Check if a block reads as sentences: mostly words, ending in punctuation
+fn is_prose(block: &[&str]) -> bool {
+    let text: String = block.iter().map(|l| l.trim()).collect::<Vec<_>>().join(" ");
+    let words = text.split_whitespace().count();
+    let letters = text
+        .chars()
+        .filter(|c| c.is_alphabetic() || c.is_whitespace())
+        .count();
+    text.ends_with(['.', ':', '?', ')'])
+        && words >= 4 * block.len()
+        && letters as f64 >= 0.85 * text.chars().count() as f64
+}
+
+fn is_bullet(line: &str) -> bool {
+    ["o  ", "*  ", "-  ", "+  "]
+        .iter()
+        .any(|b| line.starts_with(b))
+        || line
+            .split_once(". ")
+            .is_some_and(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Read a "Figure N: ..." caption at the next non-blank line, returning the line after it
+fn caption_at(lines: &[&str], from: usize) -> Option<(usize, Option<u32>, String)> {
+    let at = (from..lines.len()).find(|&i| !is_gap(lines[i]))?;
+    let rest = lines[at].trim().strip_prefix("Figure ")?;
+    let (number, caption) = rest.split_once(':')?;
+    let number = number.trim().parse().ok()?;
+    Some((at + 1, Some(number), caption.trim().to_string()))
+}
+
+/// Build an artwork from the lines of a block, removing their common indentation
+fn block(lines: Vec<&str>, line: usize) -> Artwork {
+    let common = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| indent(l))
+        .min()
+        .unwrap_or(0);
+    let content: Vec<&str> = lines
+        .iter()
+        .map(|l| l.get(common..).unwrap_or("").trim_end())
+        .collect();
+    Artwork {
+        kind: ArtworkKind::Artwork,
+        content_type: None,
+        name: None,
+        figure: None,
+        caption: None,
+        content: trim_blank_lines(&content.join("\n")),
+        line,
+    }
+}
+
+/// Drop page headers and footers from the lines of a code block
+fn text_lines<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    lines
+        .iter()
+        .copied()
+        .filter(|l| !is_page_artifact(l) && l.trim() != "\u{c}")
+        .collect()
+}
+
+/// Check for lines that separate blocks: blank lines, form feeds and page headers and footers
+fn is_gap(line: &str) -> bool {
+    line.trim().trim_matches('\u{c}').is_empty() || is_page_artifact(line)
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn trim_blank_lines(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let first = lines.iter().position(|l| !l.trim().is_empty());
+    let last = lines.iter().rposition(|l| !l.trim().is_empty());
+    match (first, last) {
+        (Some(first), Some(last)) => lines[first..=last].join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// Find the positions of the start tags of an element
+fn element_starts(xml: &str, element: &str) -> Vec<usize> {
+    let open = format!("<{}", element);
+    xml.match_indices(&open)
+        .map(|(p, _)| p)
+        .filter(|&p| {
+            xml[p + open.len()..].starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        })
+        .collect()
+}
+
+/// Get the text of an element's content, keeping CDATA sections as written
+fn element_text(body: &str) -> String {
+    let mut out = String::new();
+    let mut rest = body;
+    while let Some(start) = rest.find("<![CDATA[") {
+        out.push_str(&unescape(&rest[..start]));
+        rest = &rest[start + "<![CDATA[".len()..];
+        let end = rest.find("]]>").unwrap_or(rest.len());
+        out.push_str(&rest[..end]);
+        rest = rest.get(end + "]]>".len()..).unwrap_or("");
+    }
+    // Markup outside CDATA (e.g., SVG) is kept as is
+    out.push_str(&unescape(rest));
+    out
+}
+
+/// Get the text of inline markup like a figure `<name>`, dropping its tags
+fn inline_text(markup: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in markup.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    unescape(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "\
+1.  Packets
+
+   Every packet starts with a header.
+
+    0                   1
+    0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5
+   +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+   |  Type (8)     |  Length (8)   |
+   +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+
+                    Figure 1: Packet Header
+
+   A sample message:
+
+      {
+        \"type\": \"hello\",
+
+        \"id\": 7
+      }
+
+   The C equivalent:
+
+      struct hello {
+          uint8_t type;
+      };
+
+      Type:  The type of the packet, which
+         is one of the values defined below.
+
+   <CODE BEGINS> file \"example.yang\"
+   module example {
+   }
+   <CODE ENDS>
+";
+
+    #[test]
+    fn test_artwork_in_text() {
+        let found = artwork(TEXT);
+
+        assert_eq!(found.len(), 4);
+        let header = &found[0];
+        assert_eq!(header.kind, ArtworkKind::Artwork);
+        assert_eq!(header.content_type.as_deref(), Some("packet"));
+        assert_eq!(header.label(), "Figure 1: Packet Header");
+        assert_eq!(header.line, 4);
+        assert!(header.content.starts_with(" 0                   1\n 0 1 2"));
+
+        // Blocks separated by a blank line form one sample
+        assert_eq!(found[1].content_type.as_deref(), Some("json"));
+        assert_eq!(
+            found[1].content,
+            "{\n  \"type\": \"hello\",\n\n  \"id\": 7\n}"
+        );
+        assert_eq!(found[2].kind, ArtworkKind::SourceCode);
+        assert_eq!(found[2].content_type, None);
+
+        let code = &found[3];
+        assert_eq!(code.name.as_deref(), Some("example.yang"));
+        assert_eq!(code.content_type.as_deref(), Some("yang"));
+        assert_eq!(code.content, "module example {\n}");
+        assert_eq!(code.label(), "example.yang");
+    }
+
+    #[test]
+    fn test_xml_artwork() {
+        let xml = r#"<rfc version="3">
+<middle>
+  <figure anchor="fig-header">
+    <name>Header of a <tt>HELLO</tt> Packet</name>
+    <artset>
+      <artwork type="svg"><svg xmlns="http://www.w3.org/2000/svg"/></artwork>
+      <artwork type="ascii-art"><![CDATA[
++------+
+| Type |
++------+
+]]></artwork>
+    </artset>
+  </figure>
+  <sourcecode type="json" name="hello.json">
+{"type": "hello" &amp; "id": 7}
+  </sourcecode>
+  <artwork src="diagram.svg"/>
+</middle>
+</rfc>"#;
+        let found = xml_artwork(xml);
+
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0].content_type.as_deref(), Some("svg"));
+        assert!(found[0].content.starts_with("<svg"));
+        assert_eq!(found[1].label(), "Figure 1: Header of a HELLO Packet");
+        assert_eq!(found[1].content, "+------+\n| Type |\n+------+");
+        assert_eq!(found[1].line, 7);
+
+        let code = &found[2];
+        assert_eq!(code.kind, ArtworkKind::SourceCode);
+        assert_eq!(code.name.as_deref(), Some("hello.json"));
+        assert_eq!(code.figure, None);
+        assert_eq!(code.content.trim(), r#"{"type": "hello" & "id": 7}"#);
+    }
+}
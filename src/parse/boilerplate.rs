@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+use super::sections;
+use crate::profile::{self, Phase};
+
+/// Standard text that appears in (nearly) every RFC and draft
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoilerplateKind {
+    StatusOfMemo,
+    CopyrightNotice,
+    /// The BCP 14 (RFC 2119/8174) requirements language paragraph
+    Bcp14,
+}
+
+impl BoilerplateKind {
+    /// Get a human-readable name
+    pub fn label(&self) -> &'static str {
+        match self {
+            BoilerplateKind::StatusOfMemo => "Status of This Memo",
+            BoilerplateKind::CopyrightNotice => "Copyright Notice",
+            BoilerplateKind::Bcp14 => "BCP 14 requirements language",
+        }
+    }
+}
+
+/// A detected block of boilerplate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Boilerplate {
+    pub kind: BoilerplateKind,
+    /// First line of the block (0-based; the heading for sections)
+    pub start_line: usize,
+    /// Line after the last line of the block
+    pub end_line: usize,
+}
+
+/// Find the boilerplate blocks of a plain text document, in document order
+pub fn boilerplate(text: &str) -> Vec<Boilerplate> {
+    profile::time(Phase::Parse, || find_boilerplate(text))
+}
+
+fn find_boilerplate(text: &str) -> Vec<Boilerplate> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut found: Vec<Boilerplate> = sections(text)
+        .into_iter()
+        .filter(|s| s.number.is_none())
+        .filter_map(|s| {
+            let kind = match s.title.to_ascii_lowercase().as_str() {
+                "status of this memo" => BoilerplateKind::StatusOfMemo,
+                "copyright notice" | "copyright statement" => BoilerplateKind::CopyrightNotice,
+                _ => return None,
+            };
+            Some(Boilerplate {
+                kind,
+                start_line: s.start_line,
+                end_line: s.end_line,
+            })
+        })
+        .collect();
+
+    // Only the first requirements language paragraph is boilerplate
+    if let Some(start) = lines.iter().position(|l| l.contains("key words \"MUST")) {
+        let start = lines[..start]
+            .iter()
+            .rposition(|l| l.trim().is_empty())
+            .map_or(0, |n| n + 1);
+        let end = lines[start..]
+            .iter()
+            .position(|l| l.trim().is_empty())
+            .map_or(lines.len(), |n| start + n);
+        let paragraph = lines[start..end].join(" ");
+        if paragraph.contains("BCP 14") || paragraph.contains("RFC 2119") {
+            found.push(Boilerplate {
+                kind: BoilerplateKind::Bcp14,
+                start_line: start,
+                end_line: end,
+            });
+        }
+    }
+
+    found.sort_by_key(|b| b.start_line);
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "Abstract\n\n   A protocol.\n\n\
+Status of This Memo\n\n   This is an Internet Standards Track document.\n\n\
+Copyright Notice\n\n   Copyright (c) 2021 IETF Trust.\n\n\
+1.  Introduction\n\n   Text.\n\n\
+1.1.  Terms\n\n   The key words \"MUST\", \"MUST NOT\", \"REQUIRED\" in this\n   document are to be interpreted as described in BCP 14 [RFC2119].\n\n   More text.\n";
+
+    #[test]
+    fn test_boilerplate() {
+        let blocks = boilerplate(TEXT);
+        let lines: Vec<&str> = TEXT.lines().collect();
+
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].kind, BoilerplateKind::StatusOfMemo);
+        assert_eq!(lines[blocks[0].start_line], "Status of This Memo");
+        assert_eq!(lines[blocks[1].end_line], "1.  Introduction");
+        assert_eq!(blocks[2].kind, BoilerplateKind::Bcp14);
+        assert_eq!(blocks[2].end_line - blocks[2].start_line, 2);
+    }
+
+    #[test]
+    fn test_no_boilerplate() {
+        assert!(boilerplate("1.  Introduction\n\n   The key words are few.\n").is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    cross_references, references, section_text, sections, toc, CrossReference, Reference, Section,
+    SectionAnchor, Toc,
+};
+
+/// The parsed structure of a plain text document
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParsedDocument {
+    /// Detected sections in document order
+    pub sections: Vec<Section>,
+    /// Section tree, from the table of contents if the document has one
+    pub toc: Toc,
+    /// Entries of the References sections
+    pub references: Vec<Reference>,
+}
+
+/// The text of a single section together with its resolved cross-references
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionExcerpt {
+    pub section: Section,
+    /// Section text without page headers and footers
+    pub text: String,
+    /// References made by the section, with titles for sections of the same document
+    pub references: Vec<CrossReference>,
+}
+
+impl ParsedDocument {
+    /// Parse the structure of a plain text document
+    pub fn parse(text: &str) -> Self {
+        Self {
+            sections: sections(text),
+            toc: toc(text),
+            references: references(text),
+        }
+    }
+
+    /// Find a section by its number (e.g., "5.2", "A.1")
+    pub fn section(&self, number: &str) -> Option<&Section> {
+        let number = number.trim().trim_end_matches('.');
+        self.sections
+            .iter()
+            .find(|s| s.number.as_deref() == Some(number))
+    }
+
+    /// Find a section by a number, HTML anchor or XML `pn` (e.g., "#section-5.2")
+    pub fn section_at(&self, anchor: &str) -> Option<&Section> {
+        self.section(SectionAnchor::parse(anchor)?.number())
+    }
+
+    /// Find a section by its title, ignoring case
+    pub fn section_titled(&self, title: &str) -> Option<&Section> {
+        self.sections
+            .iter()
+            .find(|s| s.title.eq_ignore_ascii_case(title.trim()))
+    }
+
+    /// Find a section by number, anchor or title (e.g., "5.2", "Appendix A", "#section-3", "Terms")
+    pub fn find_section(&self, query: &str) -> Option<&Section> {
+        self.section_at(query)
+            .or_else(|| self.section_titled(query))
+    }
+
+    /// Find the top-level Security Considerations section
+    pub fn security_considerations(&self) -> Option<&Section> {
+        self.sections.iter().filter(|s| s.level <= 1).find(|s| {
+            s.title
+                .to_ascii_lowercase()
+                .contains("security considerations")
+        })
+    }
+
+    /// Extract a section's text and resolve the cross-references it makes
+    pub fn excerpt(&self, text: &str, section: &Section) -> SectionExcerpt {
+        let body = section_text(text, section);
+        let references = cross_references(&body)
+            .into_iter()
+            .filter(|r| r.document.is_some() || r.section != section.number)
+            .map(|mut r| {
+                if r.document.is_none() {
+                    r.title = r
+                        .section
+                        .as_deref()
+                        .and_then(|n| self.section(n))
+                        .map(|s| s.title.clone());
+                }
+                r
+            })
+            .collect();
+
+        SectionExcerpt {
+            section: section.clone(),
+            text: body,
+            references,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_section_lookup() {
+        let text =
+            "Abstract\n\n   Text.\n\n1.  Introduction\n\n   Text.\n\n1.1.  Terms\n\n   Text.\n";
+        let parsed = ParsedDocument::parse(text);
+
+        assert_eq!(parsed.sections.len(), 3);
+        assert_eq!(parsed.section("1.1").unwrap().title, "Terms");
+        assert_eq!(parsed.section("1.").unwrap().title, "Introduction");
+        assert!(parsed.section("2").is_none());
+        assert_eq!(parsed.section_at("#section-1.1").unwrap().title, "Terms");
+        assert_eq!(parsed.section_titled("abstract").unwrap().number, None);
+        assert_eq!(parsed.find_section("Section 1.1").unwrap().title, "Terms");
+        assert_eq!(parsed.find_section(" terms ").unwrap().start_line, 8);
+        assert!(parsed.find_section("Appendix B").is_none());
+        assert_eq!(parsed.toc.flatten().len(), 3);
+        assert_eq!(parsed.toc.find("1.1").unwrap().title, "Terms");
+        assert!(parsed.references.is_empty());
+    }
+
+    #[test]
+    fn test_security_considerations_excerpt() {
+        let text = "1.  Introduction\n\n   Text.\n\n2.  Stream Limits\n\n   Text.\n\n\
+                    3.  Security Considerations\n\n   Limits from Section 2 apply, and the\n\
+                    \u{c}RFC 9999               Example Protocol                May 2021\n\
+                       handshake follows [RFC8446] and Section 7.\n\n\
+                    4.  IANA Considerations\n\n   None.\n";
+        let parsed = ParsedDocument::parse(text);
+        let section = parsed.security_considerations().unwrap();
+        let excerpt = parsed.excerpt(text, section);
+
+        assert_eq!(section.number.as_deref(), Some("3"));
+        assert!(excerpt.text.starts_with("3.  Security Considerations"));
+        assert!(!excerpt.text.contains("May 2021"));
+        assert!(!excerpt.text.contains("IANA"));
+        assert_eq!(excerpt.references.len(), 3);
+        assert_eq!(
+            excerpt.references[0].title.as_deref(),
+            Some("Stream Limits")
+        );
+        assert_eq!(
+            excerpt.references[1].document,
+            Some(crate::DocumentType::Rfc(8446))
+        );
+        assert_eq!(excerpt.references[2].label, "Section 7");
+        assert_eq!(excerpt.references[2].title, None);
+    }
+}
@@ -0,0 +1,160 @@
+use super::{boilerplate, BoilerplateKind};
+use crate::models::{CodeLicense, License};
+
+/// Read the copyright and licensing terms from the boilerplate of a plain text document
+///
+/// The TLP version is inferred from the wording of the copyright notice,
+/// which changed with every version of the Trust Legal Provisions. Returns
+/// `None` if the document has no copyright notice at all.
+pub fn license(text: &str) -> Option<License> {
+    let lines: Vec<&str> = text.lines().collect();
+    let blocks: Vec<String> = boilerplate(text)
+        .into_iter()
+        .filter(|b| b.kind != BoilerplateKind::Bcp14)
+        .map(|b| lines[b.start_line..b.end_line].join(" "))
+        .collect();
+    // Older documents keep their notices outside recognized sections
+    let notice = if blocks.is_empty() {
+        text.to_string()
+    } else {
+        blocks.join(" ")
+    };
+    let lower = notice
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+
+    let copyright = lower.find("copyright (c)");
+    let tlp = tlp_version(&lower);
+    if copyright.is_none() && tlp.is_none() {
+        return None;
+    }
+
+    let statement = copyright.map_or("", |at| {
+        &lower[at..floor_boundary(&lower, (at + 120).min(lower.len()))]
+    });
+    let holder = if statement.contains("ietf trust") {
+        Some("IETF Trust")
+    } else if statement.contains("internet society") {
+        Some("The Internet Society")
+    } else {
+        None
+    };
+
+    Some(License {
+        holder: holder.map(str::to_string),
+        year: year(statement),
+        tlp,
+        code_license: if lower.contains("revised bsd license") {
+            Some(CodeLicense::RevisedBsd)
+        } else if lower.contains("bsd license") {
+            Some(CodeLicense::SimplifiedBsd)
+        } else {
+            None
+        },
+        pre5378: lower.contains("before november 10, 2008"),
+        no_derivatives: lower.contains("derivative works of it may not be created"),
+    })
+}
+
+/// Infer the TLP version from the wording of a (lowercased) copyright notice
+fn tlp_version(notice: &str) -> Option<u8> {
+    if !notice.contains("legal provisions relating to ietf documents") {
+        return None;
+    }
+    let version = if notice.contains("revised bsd license") {
+        5
+    } else if notice.contains("without warranty as described in the simplified bsd license") {
+        4
+    } else if notice.contains("simplified bsd license") {
+        3
+    } else if notice.contains("bsd license") {
+        2
+    } else {
+        1
+    };
+    Some(version)
+}
+
+/// Find the first plausible year in a copyright statement
+fn year(statement: &str) -> Option<u16> {
+    statement
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|digits| digits.len() == 4)
+        .filter_map(|digits| digits.parse().ok())
+        .find(|year| (1969..=2999).contains(year))
+}
+
+/// Round a byte index down to a character boundary
+fn floor_boundary(s: &str, mut index: usize) -> usize {
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RFC_9000: &str = "\
+Status of This Memo
+
+   This is an Internet Standards Track document.
+
+Copyright Notice
+
+   Copyright (c) 2021 IETF Trust and the persons identified as the
+   document authors.  All rights reserved.
+
+   This document is subject to BCP 78 and the IETF Trust's Legal
+   Provisions Relating to IETF Documents
+   (https://trustee.ietf.org/license-info) in effect on the date of
+   publication of this document.  Code Components extracted from this
+   document must include Simplified BSD License text as described in
+   Section 4.e of the Trust Legal Provisions and are provided without
+   warranty as described in the Simplified BSD License.
+
+1.  Introduction
+
+   QUIC is a transport protocol.
+";
+
+    #[test]
+    fn test_tlp_license() {
+        let found = license(RFC_9000).unwrap();
+        assert_eq!(found.holder.as_deref(), Some("IETF Trust"));
+        assert_eq!(found.year, Some(2021));
+        assert_eq!(found.tlp, Some(4));
+        assert_eq!(found.code_license, Some(CodeLicense::SimplifiedBsd));
+        assert!(found.allows_derivative_works());
+
+        let revised = RFC_9000.replace("Simplified BSD", "Revised BSD");
+        assert_eq!(license(&revised).unwrap().tlp, Some(5));
+
+        let pre5378 = RFC_9000.replace(
+            "1.  Introduction",
+            "   This document may contain material from IETF Documents or IETF\n   \
+             Contributions published or made publicly available before November\n   \
+             10, 2008.\n\n1.  Introduction",
+        );
+        let legacy = license(&pre5378).unwrap();
+        assert!(legacy.pre5378);
+        assert!(!legacy.allows_derivative_works());
+    }
+
+    #[test]
+    fn test_internet_society_license() {
+        let text = "Status of this Memo\n\n   This memo provides information.\n\n\
+Copyright Notice\n\n   Copyright (C) The Internet Society (1999).  All Rights Reserved.\n\n\
+1.  Introduction\n";
+        let found = license(text).unwrap();
+        assert_eq!(found.holder.as_deref(), Some("The Internet Society"));
+        assert_eq!(found.year, Some(1999));
+        assert_eq!(found.tlp, None);
+        assert!(!found.allows_derivative_works());
+
+        assert_eq!(license("1.  Introduction\n\n   Text.\n"), None);
+    }
+}
@@ -0,0 +1,97 @@
+mod anchors;
+mod artwork;
+mod boilerplate;
+mod document;
+mod license;
+mod references;
+mod sections;
+mod table;
+mod toc;
+mod xml;
+mod xref;
+
+pub use anchors::SectionAnchor;
+pub use artwork::{artwork, xml_artwork, Artwork, ArtworkKind};
+pub(crate) use artwork::{classify_block, code_block, is_art, is_rule};
+pub use boilerplate::{boilerplate, Boilerplate, BoilerplateKind};
+pub use document::{ParsedDocument, SectionExcerpt};
+pub use license::license;
+pub use references::{normative_references, references, Reference};
+pub use sections::{headings, sections, Heading, Headings, Section};
+pub use table::{tables, Table};
+pub use toc::{toc, Toc, TocEntry, TocSource};
+pub(crate) use xml::unescape;
+pub use xml::xml_authors;
+pub use xref::{citations, cross_references, Citation, CrossReference};
+
+/// Version of the parsed structure; bump when parsing output changes so cached
+/// parse results are invalidated
+pub const PARSE_VERSION: u32 = 2;
+
+/// Iterate over the lines of a section (heading included), without page headers and footers
+pub fn section_lines<'a>(text: &'a str, section: &Section) -> impl Iterator<Item = &'a str> {
+    text.lines()
+        .skip(section.start_line)
+        .take(section.end_line - section.start_line)
+        .filter(|line| !is_page_artifact(line) && !line.trim().eq("\u{c}"))
+}
+
+/// Get the text of a section (heading included), without page headers and footers
+pub fn section_text(text: &str, section: &Section) -> String {
+    let mut out = String::new();
+    for (i, line) in section_lines(text, section).enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(line);
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// Find the innermost section containing a line
+pub fn section_at_line(sections: &[Section], line: usize) -> Option<&Section> {
+    sections
+        .iter()
+        .filter(|s| s.start_line <= line && line < s.end_line)
+        .max_by_key(|s| s.level)
+}
+
+/// Check if a line is a page footer ("... [Page 12]") or running header ("RFC 9000 ... May 2021")
+pub fn is_page_artifact(line: &str) -> bool {
+    let line = line.trim().trim_start_matches('\u{c}');
+    if line.ends_with(']') && line.contains("[Page ") {
+        return true;
+    }
+    // Running headers of drafts: "Internet-Draft    QUIC Transport    January 2021"
+    let Some(rest) = line
+        .strip_prefix("RFC ")
+        .or_else(|| line.strip_prefix("Internet-Draft "))
+    else {
+        return false;
+    };
+    let number_len = rest.chars().take_while(char::is_ascii_digit).count();
+    let year_len = line.chars().rev().take_while(char::is_ascii_digit).count();
+    (number_len > 0 || line.starts_with("Internet-Draft")) && year_len == 4 && line.contains("   ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_page_artifact() {
+        assert!(is_page_artifact(
+            "Iyengar & Thomson       Standards Track                   [Page 12]"
+        ));
+        assert!(is_page_artifact(
+            "\u{c}RFC 9000            QUIC Transport Protocol              May 2021"
+        ));
+        assert!(is_page_artifact(
+            "Internet-Draft           QUIC Transport                   January 2021"
+        ));
+
+        assert!(!is_page_artifact("RFC 9000 was published in 2021"));
+        assert!(!is_page_artifact("   See [RFC9000] for details."));
+    }
+}
@@ -0,0 +1,231 @@
+use serde::{Deserialize, Serialize};
+
+use super::xref::citation;
+use super::{is_page_artifact, sections};
+use crate::models::DocumentType;
+use crate::profile::{self, Phase};
+
+/// An entry of a document's References section
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reference {
+    /// Citation anchor without brackets (e.g., "RFC2119", "I-D.ietf-quic-tls")
+    pub anchor: String,
+    /// Whether the entry is listed under Normative References
+    pub normative: bool,
+    /// Line of the entry (0-based)
+    pub line: usize,
+    /// The referenced RFC or draft, from the anchor or the entry text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<DocumentType>,
+    /// Title of the referenced document, as quoted in the entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// URL given in the entry (e.g., "https://www.rfc-editor.org/info/rfc2119")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Get the documents listed under Normative References, to fetch a document's dependencies
+pub fn normative_references(text: &str) -> Vec<DocumentType> {
+    references(text)
+        .into_iter()
+        .filter(|r| r.normative)
+        .filter_map(|r| r.target)
+        .collect()
+}
+
+/// Extract the entries of all References sections of a plain text document
+pub fn references(text: &str) -> Vec<Reference> {
+    profile::time(Phase::Parse, || find_references(text))
+}
+
+fn find_references(text: &str) -> Vec<Reference> {
+    let mut refs = Vec::new();
+
+    let found = sections(text);
+    let reference_sections: Vec<_> = found
+        .iter()
+        .filter(|s| s.title.contains("References"))
+        .collect();
+
+    for section in &reference_sections {
+        // "References" parents are covered by their Normative/Informative subsections
+        let has_subsections = reference_sections
+            .iter()
+            .any(|s| s.start_line > section.start_line && s.start_line < section.end_line);
+        if has_subsections {
+            continue;
+        }
+
+        let normative = section.title.contains("Normative");
+        // Entries run on across page breaks, so page headers and footers are dropped first
+        let lines: Vec<(usize, &str)> = text
+            .lines()
+            .enumerate()
+            .take(section.end_line)
+            .skip(section.start_line + 1)
+            .filter(|(_, line)| !is_page_artifact(line) && line.trim() != "\u{c}")
+            .collect();
+        for (j, &(i, line)) in lines.iter().enumerate() {
+            let line = line.trim();
+            if !line.starts_with('[') {
+                continue;
+            }
+            let Some(end) = line.find(']') else {
+                continue;
+            };
+            let anchor = &line[1..end];
+            if anchor.is_empty() || refs.iter().any(|r: &Reference| r.anchor == anchor) {
+                continue;
+            }
+            let entry = entry_text(&line[end + 1..], &lines[j + 1..]);
+            refs.push(Reference {
+                anchor: anchor.to_string(),
+                normative,
+                line: i,
+                target: citation(&line[..=end]).or_else(|| mentioned_document(&entry)),
+                title: quoted_title(&entry),
+                url: entry_url(&entry),
+            });
+        }
+    }
+
+    refs
+}
+
+/// Join the text of an entry, which ends at a blank line or the next entry
+fn entry_text(first: &str, rest: &[(usize, &str)]) -> String {
+    let mut text = first.trim().to_string();
+    for (_, line) in rest {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('[') {
+            break;
+        }
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(line);
+    }
+    text
+}
+
+/// Find the first quoted string of an entry, which is the title
+fn quoted_title(entry: &str) -> Option<String> {
+    let (_, rest) = entry.split_once('"')?;
+    let (title, _) = rest.split_once('"')?;
+    let title = title.trim().trim_end_matches(',');
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Find the URL of an entry, written in angle brackets and possibly wrapped
+fn entry_url(entry: &str) -> Option<String> {
+    let start = entry.find("<http")?;
+    let end = entry[start..].find('>')? + start;
+    let url: String = entry[start + 1..end]
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    Some(url)
+}
+
+/// Find the RFC ("RFC 9001") or draft ("draft-ietf-quic-tls-34") an entry describes
+fn mentioned_document(entry: &str) -> Option<DocumentType> {
+    let words: Vec<&str> = entry
+        .split_whitespace()
+        .map(|w| w.trim_end_matches([',', '.', ';']))
+        .collect();
+    let rfc = words
+        .windows(2)
+        .find(|pair| pair[0] == "RFC")
+        .and_then(|pair| pair[1].parse().ok())
+        .map(DocumentType::Rfc);
+    rfc.or_else(|| {
+        words
+            .iter()
+            .find(|w| w.starts_with("draft-") && w.len() > "draft-".len())
+            .map(|w| DocumentType::Draft(w.to_string()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_references() {
+        let text = "1.  Introduction\n\n   See [RFC2119].\n\n\
+2.  References\n\n\
+2.1.  Normative References\n\n\
+   [RFC2119]  Bradner, S., \"Key words for use in RFCs to Indicate\n              Requirement Levels\", BCP 14, RFC 2119, March 1997.\n\n\
+2.2.  Informative References\n\n\
+   [I-D.ietf-quic-tls]\n              Thomson, M., \"Using TLS to Secure QUIC\", Work in Progress.\n";
+        let refs = references(text);
+
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs[0].anchor, "RFC2119");
+        assert!(refs[0].normative);
+        assert_eq!(refs[0].line, 8);
+        assert_eq!(refs[1].anchor, "I-D.ietf-quic-tls");
+        assert!(!refs[1].normative);
+
+        assert_eq!(refs[0].target, Some(DocumentType::Rfc(2119)));
+        assert_eq!(
+            refs[0].title.as_deref(),
+            Some("Key words for use in RFCs to Indicate Requirement Levels")
+        );
+        assert_eq!(
+            refs[1].target,
+            Some(DocumentType::Draft("draft-ietf-quic-tls".to_string()))
+        );
+        assert_eq!(refs[1].title.as_deref(), Some("Using TLS to Secure QUIC"));
+    }
+
+    #[test]
+    fn test_reference_targets() {
+        let text = "\
+9.  Normative References
+
+   [QUIC-TLS] Thomson, M., Ed. and S. Turner, Ed., \"Using TLS to Secure
+              QUIC\", RFC 9001, DOI 10.17487/RFC9001, May 2021,
+              <https://www.rfc-editor.org/info/
+              rfc9001>.
+
+Iyengar & Thomson            Standards Track                   [Page 51]
+\u{c}
+RFC 9000                    QUIC Transport                      May 2021
+
+
+   [QUIC-RECOVERY]
+              Iyengar, J., Ed. and I. Swett, Ed., \"QUIC Loss Detection
+              and Congestion Control\", Work in Progress, Internet-Draft,
+              draft-ietf-quic-recovery-34, 14 January 2021.
+
+   [SPEC]     \"A Specification\", <https://example.com/spec>.
+";
+        let refs = references(text);
+
+        assert_eq!(refs.len(), 3);
+        assert_eq!(refs[0].target, Some(DocumentType::Rfc(9001)));
+        assert_eq!(refs[0].title.as_deref(), Some("Using TLS to Secure QUIC"));
+        assert_eq!(
+            refs[0].url.as_deref(),
+            Some("https://www.rfc-editor.org/info/rfc9001")
+        );
+        assert_eq!(refs[1].line, 12);
+        assert_eq!(
+            refs[1].target,
+            Some(DocumentType::Draft(
+                "draft-ietf-quic-recovery-34".to_string()
+            ))
+        );
+        assert_eq!(refs[2].target, None);
+        assert_eq!(refs[2].url.as_deref(), Some("https://example.com/spec"));
+        assert_eq!(
+            normative_references(text),
+            vec![
+                DocumentType::Rfc(9001),
+                DocumentType::Draft("draft-ietf-quic-recovery-34".to_string())
+            ]
+        );
+    }
+}
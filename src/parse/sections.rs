@@ -0,0 +1,315 @@
+use serde::{Deserialize, Serialize};
+
+use super::is_page_artifact;
+use crate::profile::{self, Phase};
+
+/// Unnumbered headings that appear at the start of a line in RFCs and drafts
+pub(super) const UNNUMBERED_HEADINGS: &[&str] = &[
+    "Abstract",
+    "Status of This Memo",
+    "Status of this Memo",
+    "Copyright Notice",
+    "Copyright Statement",
+    "Table of Contents",
+    "Acknowledgments",
+    "Acknowledgements",
+    "Contributors",
+    "Index",
+    "Authors' Addresses",
+    "Author's Address",
+    "Full Copyright Statement",
+    "Intellectual Property",
+];
+
+/// A section of a plain text document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Section {
+    /// Section number (e.g., "5.2", "A.1"); None for unnumbered sections like "Abstract"
+    pub number: Option<String>,
+    /// Section title
+    pub title: String,
+    /// Nesting depth, starting at 1 for top-level sections
+    pub level: usize,
+    /// Line index of the heading
+    pub start_line: usize,
+    /// Line index one past the last line of the section, including subsections
+    pub end_line: usize,
+}
+
+impl Section {
+    /// Check if this section is an appendix
+    #[must_use]
+    pub fn is_appendix(&self) -> bool {
+        self.number
+            .as_ref()
+            .is_some_and(|n| n.starts_with(|c: char| c.is_ascii_uppercase()))
+    }
+
+    /// Get a label suitable for reading aloud (e.g., "Section 5.2", "Appendix A")
+    pub fn label(&self) -> String {
+        match &self.number {
+            Some(number) if self.is_appendix() => format!("Appendix {}", number),
+            Some(number) => format!("Section {}", number),
+            None => self.title.clone(),
+        }
+    }
+}
+
+/// A section heading, borrowed from the document text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Heading<'a> {
+    /// Section number (e.g., "5.2", "A.1"); None for unnumbered sections
+    pub number: Option<&'a str>,
+    pub title: &'a str,
+    /// Line index of the heading
+    pub line: usize,
+}
+
+impl Heading<'_> {
+    /// Nesting depth, starting at 1 for top-level sections
+    pub fn level(&self) -> usize {
+        self.number.map_or(1, |n| n.split('.').count())
+    }
+}
+
+/// Streaming iterator over the headings of a document, see [`headings`]
+pub struct Headings<'a> {
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+    in_toc: bool,
+}
+
+impl<'a> Iterator for Headings<'a> {
+    type Item = Heading<'a>;
+
+    fn next(&mut self) -> Option<Heading<'a>> {
+        for (i, line) in self.lines.by_ref() {
+            let Some((number, title)) = parse_heading(line) else {
+                continue;
+            };
+
+            // Skip table of contents entries, which repeat every heading
+            if number.is_none() {
+                self.in_toc = title == "Table of Contents";
+            } else if self.in_toc && is_toc_entry(line) {
+                continue;
+            } else {
+                self.in_toc = false;
+            }
+            return Some(Heading {
+                number,
+                title,
+                line: i,
+            });
+        }
+        None
+    }
+}
+
+/// Iterate over the section headings of a plain text document without copying it
+///
+/// Lines are read one at a time, so this is suitable for the largest documents.
+pub fn headings(text: &str) -> Headings<'_> {
+    Headings {
+        lines: text.lines().enumerate(),
+        in_toc: false,
+    }
+}
+
+/// Detect the section headings of a plain text RFC or draft
+pub fn sections(text: &str) -> Vec<Section> {
+    profile::time(Phase::Parse, || find_sections(text))
+}
+
+fn find_sections(text: &str) -> Vec<Section> {
+    let line_count = text.lines().count();
+    let mut sections: Vec<Section> = Vec::new();
+    // Sections whose end hasn't been seen yet, outermost first
+    let mut open: Vec<usize> = Vec::new();
+
+    for heading in headings(text) {
+        let level = heading.level();
+        // A section ends where the next section at the same or a shallower level begins
+        while let Some(&last) = open.last() {
+            if sections[last].level < level {
+                break;
+            }
+            sections[last].end_line = heading.line;
+            open.pop();
+        }
+        open.push(sections.len());
+        sections.push(Section {
+            number: heading.number.map(str::to_string),
+            title: heading.title.to_string(),
+            level,
+            start_line: heading.line,
+            end_line: line_count,
+        });
+    }
+
+    sections
+}
+
+/// Parse a heading line into its number (if any) and title
+fn parse_heading(line: &str) -> Option<(Option<&str>, &str)> {
+    if line.starts_with(char::is_whitespace) || is_page_artifact(line) {
+        return None;
+    }
+    let line = line.trim_end();
+
+    if UNNUMBERED_HEADINGS.contains(&line) {
+        return Some((None, line));
+    }
+
+    let line = line.strip_prefix("Appendix ").unwrap_or(line);
+    let (number, title) = line.split_once(char::is_whitespace)?;
+    let number = number.trim_end_matches(['.', ':']);
+    let title = title.trim();
+
+    if !is_section_number(number) || title.is_empty() {
+        return None;
+    }
+    // Headings are short and never end in a full stop, unlike numbered list items
+    if title.chars().count() > 72 || title.ends_with('.') {
+        return None;
+    }
+
+    Some((Some(number), strip_dot_leaders(title)))
+}
+
+/// Check for numbers like "5", "5.2", "A", "A.1"
+pub(super) fn is_section_number(number: &str) -> bool {
+    let mut parts = number.split('.');
+    let Some(first) = parts.next() else {
+        return false;
+    };
+    let first_ok = (!first.is_empty() && first.chars().all(|c| c.is_ascii_digit()))
+        || (first.len() == 1 && first.chars().all(|c| c.is_ascii_uppercase()));
+    first_ok && parts.all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Check if a line looks like a table of contents entry ("5.2.  Title ...... 12")
+fn is_toc_entry(line: &str) -> bool {
+    line.contains("..") || line.trim_end().ends_with(|c: char| c.is_ascii_digit())
+}
+
+/// Remove trailing dot leaders and page numbers from a heading
+fn strip_dot_leaders(title: &str) -> &str {
+    match title.find(" ..") {
+        Some(pos) => title[..pos].trim_end(),
+        None => title,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+Abstract
+
+   This document defines QUIC.
+
+Table of Contents
+
+   1.  Overview  . . . . . . . . . . . . . . . . . . . . . . . . .   4
+     1.1.  Terms . . . . . . . . . . . . . . . . . . . . . . . . .   5
+   2.  Streams . . . . . . . . . . . . . . . . . . . . . . . . . .   9
+
+1.  Overview
+
+   QUIC is a secure general-purpose transport protocol.
+
+1.1.  Terms
+
+   The following terms are used:
+
+   1.  An item in a numbered list.
+
+2.  Streams
+
+   Streams are ordered byte streams.
+
+Appendix A.  Pseudocode
+
+   Some code.
+";
+
+    #[test]
+    fn test_sections() {
+        let sections = sections(SAMPLE);
+        let labels: Vec<String> = sections.iter().map(|s| s.label()).collect();
+
+        assert_eq!(
+            labels,
+            vec![
+                "Abstract",
+                "Table of Contents",
+                "Section 1",
+                "Section 1.1",
+                "Section 2",
+                "Appendix A"
+            ]
+        );
+        assert_eq!(sections[2].title, "Overview");
+        assert_eq!(sections[3].level, 2);
+        assert_eq!(sections[5].title, "Pseudocode");
+    }
+
+    #[test]
+    fn test_section_ranges() {
+        let lines: Vec<&str> = SAMPLE.lines().collect();
+        let sections = sections(SAMPLE);
+
+        // Section 1 includes its subsection
+        let overview = &sections[2];
+        let streams = &sections[4];
+        assert_eq!(overview.end_line, streams.start_line);
+        assert_eq!(lines[overview.start_line], "1.  Overview");
+        assert_eq!(sections.last().unwrap().end_line, lines.len());
+    }
+
+    #[test]
+    fn test_headings_borrow_the_text() {
+        let headings: Vec<Heading> = headings(SAMPLE).collect();
+        assert_eq!(headings.len(), 6);
+        assert_eq!(headings[3].number, Some("1.1"));
+        assert_eq!(headings[3].level(), 2);
+        assert_eq!(headings[5].title, "Pseudocode");
+
+        // Titles point into the document instead of being copied
+        let range = SAMPLE.as_bytes().as_ptr_range();
+        assert!(range.contains(&headings[5].title.as_ptr()));
+    }
+
+    #[test]
+    fn test_unindented_toc() {
+        // Older RFCs put table of contents entries at the start of the line
+        let text = "\
+Table of Contents
+
+1 Introduction ....................................................7
+2 Notational Conventions .........................................14
+
+1 Introduction
+
+   Text.
+";
+        let sections = sections(text);
+
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[1].title, "Introduction");
+        assert_eq!(sections[1].start_line, 5);
+    }
+
+    #[test]
+    fn test_is_section_number() {
+        assert!(is_section_number("5"));
+        assert!(is_section_number("5.2.1"));
+        assert!(is_section_number("A.1"));
+
+        assert!(!is_section_number(""));
+        assert!(!is_section_number("RFC"));
+        assert!(!is_section_number("5."));
+        assert!(!is_section_number("AB.1"));
+    }
+}
@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+
+/// A table drawn with ASCII art (`+---+` borders and `|` column separators)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Table {
+    /// Line index of the first line of the table
+    pub start_line: usize,
+    /// Line index one past the last line of the table
+    pub end_line: usize,
+    /// Column headings
+    pub header: Vec<String>,
+    /// Data rows, one cell per column
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Find the index of a column by heading, ignoring case
+    pub fn column(&self, heading: &str) -> Option<usize> {
+        self.header
+            .iter()
+            .position(|h| h.eq_ignore_ascii_case(heading))
+    }
+}
+
+/// Find all ASCII art tables in a range of lines
+pub fn tables(lines: &[&str], offset: usize) -> Vec<Table> {
+    let mut tables = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        if !is_table_line(lines[i]) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < lines.len() && is_table_line(lines[i]) {
+            i += 1;
+        }
+        if let Some(mut table) = parse_table(&lines[start..i]) {
+            table.start_line = offset + start;
+            table.end_line = offset + i;
+            tables.push(table);
+        }
+    }
+
+    tables
+}
+
+/// Parse consecutive table lines into a header and rows
+fn parse_table(lines: &[&str]) -> Option<Table> {
+    // Group the lines between separators
+    let mut groups: Vec<Vec<Vec<String>>> = Vec::new();
+    let mut current: Vec<Vec<String>> = Vec::new();
+    for line in lines {
+        if is_separator(line) {
+            if !current.is_empty() {
+                groups.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(cells(line));
+        }
+    }
+    if !current.is_empty() {
+        groups.push(current);
+    }
+
+    let mut groups = groups.into_iter();
+    let header = merge_lines(groups.next()?);
+    let body: Vec<Vec<Vec<String>>> = groups.collect();
+
+    // Without separators between rows, every line is a row unless it continues
+    // the previous one (empty first cell)
+    let rows = if body.len() == 1 {
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        for line in body.into_iter().next().unwrap_or_default() {
+            match rows.last_mut() {
+                Some(last) if line.first().is_some_and(String::is_empty) => {
+                    append_cells(last, &line)
+                }
+                _ => rows.push(line),
+            }
+        }
+        rows
+    } else {
+        body.into_iter().map(merge_lines).collect()
+    };
+
+    (!header.is_empty()).then_some(Table {
+        start_line: 0,
+        end_line: 0,
+        header,
+        rows,
+    })
+}
+
+/// Merge the wrapped lines of a single row into one row
+fn merge_lines(lines: Vec<Vec<String>>) -> Vec<String> {
+    let mut lines = lines.into_iter();
+    let mut row = lines.next().unwrap_or_default();
+    for line in lines {
+        append_cells(&mut row, &line);
+    }
+    row
+}
+
+/// Append wrapped cell text to a row
+fn append_cells(row: &mut Vec<String>, line: &[String]) {
+    for (i, cell) in line.iter().enumerate() {
+        if cell.is_empty() {
+            continue;
+        }
+        match row.get_mut(i) {
+            Some(existing) if existing.is_empty() => existing.push_str(cell),
+            Some(existing) if existing.ends_with('-') => existing.push_str(cell),
+            Some(existing) => {
+                existing.push(' ');
+                existing.push_str(cell);
+            }
+            None => row.push(cell.clone()),
+        }
+    }
+}
+
+/// Split a table line into trimmed cells
+fn cells(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let inner = line.strip_prefix('|').unwrap_or(line);
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+    inner.split('|').map(|c| c.trim().to_string()).collect()
+}
+
+/// Check for a border line like `+-----+=====+`
+fn is_separator(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with('+') && line.chars().all(|c| matches!(c, '+' | '-' | '='))
+}
+
+/// Check if a line belongs to a table
+fn is_table_line(line: &str) -> bool {
+    let trimmed = line.trim();
+    is_separator(trimmed)
+        || (trimmed.starts_with('|') && trimmed.ends_with('|') && trimmed.len() > 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_with_row_separators() {
+        let text = "\
+   +-------+--------------+---------------+
+   | Value | Frame Type   | Spec          |
+   | Name  |              |               |
+   +=======+==============+===============+
+   | 0x00  | PADDING      | Section 19.1  |
+   +-------+--------------+---------------+
+   | 0x01  | PING         | Section 19.2  |
+   +-------+--------------+---------------+";
+        let lines: Vec<&str> = text.lines().collect();
+        let tables = tables(&lines, 10);
+
+        assert_eq!(tables.len(), 1);
+        let table = &tables[0];
+        assert_eq!(table.header, vec!["Value Name", "Frame Type", "Spec"]);
+        assert_eq!(table.rows.len(), 2);
+        assert_eq!(table.rows[1], vec!["0x01", "PING", "Section 19.2"]);
+        assert_eq!(table.start_line, 10);
+        assert_eq!(table.end_line, 18);
+        assert_eq!(table.column("frame type"), Some(1));
+    }
+
+    #[test]
+    fn test_table_without_row_separators() {
+        let text = "\
+   +------+-------------------+
+   | Code | Name              |
+   +------+-------------------+
+   | 1    | First             |
+   | 2    | Second with a     |
+   |      | wrapped name      |
+   +------+-------------------+
+
+   Not a table line.";
+        let lines: Vec<&str> = text.lines().collect();
+        let tables = tables(&lines, 0);
+
+        assert_eq!(tables.len(), 1);
+        assert_eq!(
+            tables[0].rows,
+            vec![
+                vec!["1".to_string(), "First".to_string()],
+                vec!["2".to_string(), "Second with a wrapped name".to_string()],
+            ]
+        );
+    }
+}
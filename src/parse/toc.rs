@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::sections::{is_section_number, UNNUMBERED_HEADINGS};
+use super::{headings, is_page_artifact, Heading};
+use crate::profile::{self, Phase};
+
+/// Title of the section holding the table of contents
+const TOC_TITLE: &str = "Table of Contents";
+
+/// Where the entries of a [`Toc`] come from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TocSource {
+    /// The document's own table of contents
+    TableOfContents,
+    /// The section headings, for documents without a table of contents
+    #[default]
+    Headings,
+}
+
+/// A section in a table of contents, with its subsections
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TocEntry {
+    /// Section number (e.g., "5.2", "A.1"); None for unnumbered sections
+    pub number: Option<String>,
+    pub title: String,
+    /// Nesting depth, starting at 1 for top-level sections
+    pub level: usize,
+    /// Page the section starts on, for paginated documents
+    pub page: Option<u32>,
+    /// Line index of the section heading, if it was found in the text
+    pub line: Option<usize>,
+    pub children: Vec<TocEntry>,
+}
+
+impl TocEntry {
+    fn new(number: Option<String>, title: String, page: Option<u32>) -> Self {
+        let level = number.as_deref().map_or(1, |n| n.split('.').count());
+        Self {
+            number,
+            title,
+            level,
+            page,
+            line: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Check if another entry is a subsection of this one
+    fn contains(&self, other: &TocEntry) -> bool {
+        match (&self.number, &other.number) {
+            (Some(parent), Some(child)) => child
+                .strip_prefix(parent.as_str())
+                .is_some_and(|rest| rest.starts_with('.')),
+            _ => false,
+        }
+    }
+}
+
+/// The section tree of a document
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Toc {
+    /// Top-level sections in document order
+    pub entries: Vec<TocEntry>,
+    pub source: TocSource,
+}
+
+impl Toc {
+    /// Get every entry, depth-first in document order
+    pub fn flatten(&self) -> Vec<&TocEntry> {
+        fn walk<'a>(entries: &'a [TocEntry], out: &mut Vec<&'a TocEntry>) {
+            for entry in entries {
+                out.push(entry);
+                walk(&entry.children, out);
+            }
+        }
+        let mut out = Vec::new();
+        walk(&self.entries, &mut out);
+        out
+    }
+
+    /// Find an entry by its section number (e.g., "5.2", "A.1")
+    pub fn find(&self, number: &str) -> Option<&TocEntry> {
+        let number = number.trim().trim_end_matches('.');
+        self.flatten()
+            .into_iter()
+            .find(|e| e.number.as_deref() == Some(number))
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl fmt::Display for Toc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in self.flatten() {
+            let indent = "  ".repeat(entry.level - 1);
+            match &entry.number {
+                Some(number) => write!(f, "{}{}. {}", indent, number, entry.title)?,
+                None => write!(f, "{}{}", indent, entry.title)?,
+            }
+            match entry.page {
+                Some(page) => writeln!(f, " ({})", page)?,
+                None => writeln!(f)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Extract the section tree of a plain text document
+///
+/// The document's table of contents is read if it has one, with the page
+/// numbers it gives; otherwise the tree is built from the detected headings,
+/// with pages counted from form feeds. Entries are matched to the heading
+/// lines either way.
+pub fn toc(text: &str) -> Toc {
+    profile::time(Phase::Parse, || find_toc(text))
+}
+
+fn find_toc(text: &str) -> Toc {
+    let all: Vec<Heading> = headings(text).collect();
+    let toc_at = all
+        .iter()
+        .position(|h| h.number.is_none() && h.title == TOC_TITLE);
+
+    let (entries, source, body) = match toc_at.map(|i| (i, read_toc(text, &all, i))) {
+        Some((i, entries)) if !entries.is_empty() => {
+            (entries, TocSource::TableOfContents, &all[i + 1..])
+        }
+        _ => (from_headings(text, &all), TocSource::Headings, &all[..]),
+    };
+
+    let mut by_number: HashMap<&str, usize> = HashMap::new();
+    let mut by_title: HashMap<&str, usize> = HashMap::new();
+    for heading in body.iter().rev() {
+        match heading.number {
+            Some(number) => by_number.insert(number, heading.line),
+            None => by_title.insert(heading.title, heading.line),
+        };
+    }
+
+    let mut tree: Vec<TocEntry> = Vec::new();
+    for mut entry in entries {
+        entry.line = match &entry.number {
+            Some(number) => by_number.get(number.as_str()).copied(),
+            None => by_title.get(entry.title.as_str()).copied(),
+        };
+        insert(&mut tree, entry);
+    }
+    Toc {
+        entries: tree,
+        source,
+    }
+}
+
+/// Read the entries between the table of contents heading and the next heading
+fn read_toc(text: &str, headings: &[Heading], at: usize) -> Vec<TocEntry> {
+    let start = headings[at].line + 1;
+    let end = headings.get(at + 1).map_or(usize::MAX, |h| h.line);
+    let mut entries: Vec<TocEntry> = Vec::new();
+
+    for line in text.lines().take(end).skip(start) {
+        if is_page_artifact(line) || line.trim().trim_matches('\u{c}').is_empty() {
+            continue;
+        }
+        let (body, page) = split_page(line.trim());
+        match parse_entry(body) {
+            Some((number, title)) => entries.push(TocEntry::new(number, title.to_string(), page)),
+            // Long titles wrap onto the next line, which carries the page number
+            None => {
+                if let Some(last) = entries.last_mut() {
+                    if !body.is_empty() {
+                        last.title.push(' ');
+                        last.title.push_str(body);
+                    }
+                    last.page = last.page.or(page);
+                }
+            }
+        }
+    }
+    entries
+}
+
+/// Split an entry into its text and its page number, dropping dot leaders
+fn split_page(line: &str) -> (&str, Option<u32>) {
+    let digits = line.len() - line.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    let before = line[..line.len() - digits].trim_end_matches(' ');
+    let has_leader = before.ends_with('.') || line.len() - digits - before.len() >= 2;
+    let page = (digits > 0 && has_leader)
+        .then(|| line[line.len() - digits..].parse().ok())
+        .flatten();
+    let body = match page {
+        Some(_) => before.trim_end_matches(['.', ' ']),
+        None => line,
+    };
+    (body, page)
+}
+
+/// Parse the number and title of an entry; None for the continuation of a wrapped title
+fn parse_entry(body: &str) -> Option<(Option<String>, &str)> {
+    if UNNUMBERED_HEADINGS.contains(&body) {
+        return Some((None, body));
+    }
+    let body = body.strip_prefix("Appendix ").unwrap_or(body);
+    let (number, title) = body.split_once(char::is_whitespace)?;
+    let number = number.trim_end_matches(['.', ':']);
+    let title = title.trim();
+    (is_section_number(number) && !title.is_empty()).then(|| (Some(number.to_string()), title))
+}
+
+/// Build entries from the detected headings, counting pages from form feeds
+fn from_headings(text: &str, headings: &[Heading]) -> Vec<TocEntry> {
+    let paginated = text.contains('\u{c}');
+    let mut pages = Vec::new();
+    if paginated {
+        let mut page = 1;
+        for line in text.lines() {
+            page += line.matches('\u{c}').count() as u32;
+            pages.push(page);
+        }
+    }
+    headings
+        .iter()
+        .filter(|h| !(h.number.is_none() && h.title == TOC_TITLE))
+        .map(|h| {
+            let page = pages.get(h.line).copied();
+            TocEntry::new(h.number.map(str::to_string), h.title.to_string(), page)
+        })
+        .collect()
+}
+
+/// Add an entry under the last entry it is a subsection of
+fn insert(entries: &mut Vec<TocEntry>, entry: TocEntry) {
+    match entries.last_mut() {
+        Some(last) if last.contains(&entry) => insert(&mut last.children, entry),
+        _ => entries.push(entry),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+Abstract
+
+   This document defines QUIC.
+
+Table of Contents
+
+   1.  Overview  . . . . . . . . . . . . . . . . . . . . . . . . .   4
+     1.1.  Terms . . . . . . . . . . . . . . . . . . . . . . . . .   5
+     1.2.  A Section Title That Is Long Enough to Wrap Onto a
+           Second Line . . . . . . . . . . . . . . . . . . . . . .   6
+   2.  Streams . . . . . . . . . . . . . . . . . . . . . . . . . .   9
+   Appendix A.  Pseudocode . . . . . . . . . . . . . . . . . . . .  12
+   Authors' Addresses  . . . . . . . . . . . . . . . . . . . . . .  14
+
+1.  Overview
+
+   QUIC is a secure general-purpose transport protocol.
+
+1.1.  Terms
+
+1.2.  A Section Title That Is Long Enough to Wrap Onto a Second Line
+
+2.  Streams
+
+Appendix A.  Pseudocode
+
+Authors' Addresses
+";
+
+    #[test]
+    fn test_toc_from_table_of_contents() {
+        let toc = toc(SAMPLE);
+        let lines: Vec<&str> = SAMPLE.lines().collect();
+
+        assert_eq!(toc.source, TocSource::TableOfContents);
+        assert_eq!(toc.entries.len(), 4);
+        let overview = &toc.entries[0];
+        assert_eq!(overview.number.as_deref(), Some("1"));
+        assert_eq!(overview.page, Some(4));
+        assert_eq!(lines[overview.line.unwrap()], "1.  Overview");
+        assert_eq!(overview.children.len(), 2);
+        assert_eq!(
+            overview.children[1].title,
+            "A Section Title That Is Long Enough to Wrap Onto a Second Line"
+        );
+        assert_eq!(overview.children[1].page, Some(6));
+        assert_eq!(overview.children[1].level, 2);
+
+        let appendix = toc.find("A").unwrap();
+        assert_eq!(appendix.title, "Pseudocode");
+        assert_eq!(appendix.page, Some(12));
+        let authors = &toc.entries[3];
+        assert_eq!(authors.number, None);
+        assert_eq!(lines[authors.line.unwrap()], "Authors' Addresses");
+        assert_eq!(toc.flatten().len(), 6);
+    }
+
+    #[test]
+    fn test_toc_from_headings() {
+        let text = "\
+Abstract
+
+   Text.
+
+1.  Introduction
+
+\u{c}
+2.  Terms
+
+2.1.  Roles
+";
+        let toc = toc(text);
+
+        assert_eq!(toc.source, TocSource::Headings);
+        assert_eq!(toc.entries.len(), 3);
+        assert_eq!(toc.entries[1].page, Some(1));
+        assert_eq!(toc.entries[2].page, Some(2));
+        assert_eq!(toc.entries[2].children[0].line, Some(9));
+        assert_eq!(
+            toc.to_string(),
+            "Abstract (1)\n1. Introduction (1)\n2. Terms (2)\n  2.1. Roles (2)\n"
+        );
+    }
+
+    #[test]
+    fn test_split_page() {
+        assert_eq!(
+            split_page("1.  Overview  . . . .   4"),
+            ("1.  Overview", Some(4))
+        );
+        assert_eq!(
+            split_page("1 Introduction ......7"),
+            ("1 Introduction", Some(7))
+        );
+        assert_eq!(
+            split_page("2.  Changes in Version 2"),
+            ("2.  Changes in Version 2", None)
+        );
+    }
+}
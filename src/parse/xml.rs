@@ -0,0 +1,155 @@
+use crate::models::Author;
+
+/// Extract the authors of an xml2rfc v3 document
+///
+/// Only the document's own `<front>` is read, not those of its references.
+/// Names come from `fullname`, or `initials` and `surname`; the ASCII forms
+/// from `asciiFullname`, or `asciiInitials` and `asciiSurname`.
+pub fn xml_authors(xml: &str) -> Vec<Author> {
+    let front = match (xml.find("<front"), xml.find("</front>")) {
+        (Some(start), Some(end)) if start < end => &xml[start..end],
+        _ => return Vec::new(),
+    };
+
+    let mut authors = Vec::new();
+    let mut rest = front;
+    while let Some(start) = rest.find("<author") {
+        rest = &rest[start + "<author".len()..];
+        // Skip other elements sharing the prefix
+        if !rest.starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/') {
+            continue;
+        }
+        let tag = &rest[..rest.find('>').unwrap_or(rest.len())];
+
+        let name = attribute(tag, "fullname")
+            .or_else(|| join_name(attribute(tag, "initials"), attribute(tag, "surname")));
+        let ascii = attribute(tag, "asciiFullname").or_else(|| {
+            join_name(
+                attribute(tag, "asciiInitials"),
+                attribute(tag, "asciiSurname"),
+            )
+        });
+        if let Some(name) = name {
+            authors.push(match ascii {
+                Some(ascii) => Author::with_ascii(name, ascii),
+                None => Author::new(name),
+            });
+        }
+    }
+    authors
+}
+
+/// Join initials and surname ("M." and "Thomson" -> "M. Thomson")
+fn join_name(initials: Option<String>, surname: Option<String>) -> Option<String> {
+    match (initials, surname) {
+        (Some(initials), Some(surname)) => Some(format!("{} {}", initials, surname)),
+        (None, Some(surname)) => Some(surname),
+        _ => None,
+    }
+}
+
+/// Get the unescaped value of an attribute within a start tag
+pub(super) fn attribute(tag: &str, name: &str) -> Option<String> {
+    let mut rest = tag;
+    loop {
+        let pos = rest.find(name)?;
+        let preceded = rest[..pos].ends_with(char::is_whitespace);
+        rest = &rest[pos + name.len()..];
+        let Some(value) = rest.trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let Some(quote) = value.chars().next().filter(|&c| c == '"' || c == '\'') else {
+            continue;
+        };
+        let value = &value[1..];
+        let end = value.find(quote)?;
+        if preceded {
+            let value = unescape(&value[..end]);
+            return (!value.trim().is_empty()).then(|| value.trim().to_string());
+        }
+    }
+}
+
+/// Resolve the predefined XML entities and numeric character references
+pub(crate) fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(semi) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..semi];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rfc number="9999" version="3">
+  <front>
+    <title>Example</title>
+    <author fullname="Jürgen Schönwälder" asciiFullname="Juergen Schoenwaelder" initials="J." surname="Schönwälder">
+      <organization>Constructor University</organization>
+    </author>
+    <author initials="M." surname="Thomson" fullname="Martin Thomson"/>
+    <author initials="N." surname="中村" asciiInitials="N." asciiSurname="Nakamura"/>
+    <author fullname="D. O&apos;Brien &#x26; Co"/>
+  </front>
+  <back>
+    <references>
+      <reference anchor="RFC2119">
+        <front><author fullname="Scott Bradner"/></front>
+      </reference>
+    </references>
+  </back>
+</rfc>"#;
+
+    #[test]
+    fn test_xml_authors() {
+        let authors = xml_authors(XML);
+
+        assert_eq!(
+            authors,
+            vec![
+                Author::with_ascii("Jürgen Schönwälder", "Juergen Schoenwaelder"),
+                Author::new("Martin Thomson"),
+                Author::with_ascii("N. 中村", "N. Nakamura"),
+                Author::new("D. O'Brien & Co"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_front() {
+        assert!(xml_authors("<rfc/>").is_empty());
+    }
+}
@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+
+use super::sections;
+use crate::models::DocumentType;
+use crate::profile::{self, Phase};
+
+/// A reference from running text to a section or another document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossReference {
+    /// The reference as written (e.g., "Section 4.1", "[RFC8446]")
+    pub label: String,
+    /// Referenced section number, if any
+    pub section: Option<String>,
+    /// Referenced document; None for sections of the same document
+    pub document: Option<DocumentType>,
+    /// Title of a referenced section of the same document, filled in by
+    /// [`ParsedDocument::excerpt`](super::ParsedDocument::excerpt)
+    pub title: Option<String>,
+}
+
+/// A citation of another document in the body of a document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Citation {
+    /// Citation anchor without brackets (e.g., "RFC8446")
+    pub anchor: String,
+    pub document: DocumentType,
+    /// Line of the citation (0-based)
+    pub line: usize,
+}
+
+/// Find the `[RFCxxxx]` and `[I-D.x]` citations outside of the References sections
+pub fn citations(text: &str) -> Vec<Citation> {
+    profile::time(Phase::Parse, || find_citations(text))
+}
+
+fn find_citations(text: &str) -> Vec<Citation> {
+    let reference_lines: Vec<(usize, usize)> = sections(text)
+        .iter()
+        .filter(|s| s.title.contains("References"))
+        .map(|s| (s.start_line, s.end_line))
+        .collect();
+
+    text.lines()
+        .enumerate()
+        .filter(|(i, _)| !reference_lines.iter().any(|(s, e)| (s..e).contains(&i)))
+        .flat_map(|(i, line)| {
+            cross_references(line).into_iter().filter_map(move |r| {
+                let document = r.document?;
+                let anchor = r.label.rsplit('[').next()?.trim_end_matches(']');
+                Some(Citation {
+                    anchor: anchor.to_string(),
+                    document,
+                    line: i,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Find section references ("Section 4.1", "Appendix A", "Section 3 of [RFC8446]")
+/// and citations ("[RFC8446]", "[I-D.ietf-quic-tls]") in running text
+pub fn cross_references(text: &str) -> Vec<CrossReference> {
+    profile::time(Phase::Parse, || find_cross_references(text))
+}
+
+fn find_cross_references(text: &str) -> Vec<CrossReference> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut refs: Vec<CrossReference> = Vec::new();
+    let mut i = 0;
+
+    while i < words.len() {
+        let word = words[i];
+
+        if matches!(word, "Section" | "Sections" | "Appendix") && i + 1 < words.len() {
+            if let Some(number) = section_number(words[i + 1]) {
+                let mut reference = CrossReference {
+                    label: format!("{} {}", word.trim_end_matches('s'), number),
+                    section: Some(number),
+                    document: None,
+                    title: None,
+                };
+                i += 2;
+                // "Section 3 of [RFC8446]" refers to another document
+                if words.get(i) == Some(&"of") {
+                    if let Some(doc) = words.get(i + 1).and_then(|w| citation(w)) {
+                        reference.label = format!(
+                            "{} of {}",
+                            reference.label,
+                            words[i + 1].trim_end_matches(|c: char| c != ']')
+                        );
+                        reference.document = Some(doc);
+                        i += 2;
+                    }
+                }
+                push_unique(&mut refs, reference);
+                continue;
+            }
+        }
+
+        if let Some(doc) = citation(word) {
+            let label = word
+                .trim_start_matches(|c: char| c != '[')
+                .trim_end_matches(|c: char| c != ']')
+                .to_string();
+            push_unique(
+                &mut refs,
+                CrossReference {
+                    label,
+                    section: None,
+                    document: Some(doc),
+                    title: None,
+                },
+            );
+        }
+        i += 1;
+    }
+
+    refs
+}
+
+/// Parse a section number following "Section", stripping trailing punctuation
+fn section_number(word: &str) -> Option<String> {
+    let number = word.trim_end_matches([',', '.', ';', ':', ')']);
+    let valid = !number.is_empty()
+        && number.split('.').enumerate().all(|(i, part)| {
+            !part.is_empty()
+                && (part.chars().all(|c| c.is_ascii_digit())
+                    || (i == 0 && part.len() == 1 && part.chars().all(|c| c.is_ascii_uppercase())))
+        });
+    valid.then(|| number.to_string())
+}
+
+/// Parse a citation like "[RFC8446]" or "[I-D.ietf-quic-tls]" into a document
+pub(super) fn citation(word: &str) -> Option<DocumentType> {
+    let start = word.find('[')?;
+    let end = word[start..].find(']')? + start;
+    let anchor = &word[start + 1..end];
+
+    if let Some(number) = anchor.strip_prefix("RFC") {
+        return number.parse().ok().map(DocumentType::Rfc);
+    }
+    if let Some(name) = anchor.strip_prefix("I-D.") {
+        return (!name.is_empty()).then(|| DocumentType::Draft(format!("draft-{}", name)));
+    }
+    None
+}
+
+/// Add a reference unless an identical label is already present
+fn push_unique(refs: &mut Vec<CrossReference>, reference: CrossReference) {
+    if !refs.iter().any(|r| r.label == reference.label) {
+        refs.push(reference);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_section_references() {
+        let refs = cross_references(
+            "See Section 21.3 and Appendix A.1, as well as Sections 4, and 5 of this document.",
+        );
+        let labels: Vec<&str> = refs.iter().map(|r| r.label.as_str()).collect();
+
+        assert_eq!(labels, vec!["Section 21.3", "Appendix A.1", "Section 4"]);
+        assert!(refs.iter().all(|r| r.document.is_none()));
+        assert_eq!(refs[1].section.as_deref(), Some("A.1"));
+    }
+
+    #[test]
+    fn test_citations() {
+        let refs = cross_references(
+            "Handshake security relies on [RFC8446] and Section 5 of [RFC9001]; see also\n[I-D.ietf-quic-manageability] and [RFC8446].",
+        );
+
+        assert_eq!(refs.len(), 3);
+        assert_eq!(refs[0].document, Some(DocumentType::Rfc(8446)));
+        assert_eq!(refs[1].label, "Section 5 of [RFC9001]");
+        assert_eq!(refs[1].section.as_deref(), Some("5"));
+        assert_eq!(refs[1].document, Some(DocumentType::Rfc(9001)));
+        assert_eq!(
+            refs[2].document,
+            Some(DocumentType::Draft(
+                "draft-ietf-quic-manageability".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_body_citations() {
+        let text = "1.  Introduction\n\n   Uses [RFC8446] and Section 4 of [RFC9001].\n\n\
+2.  References\n\n   [RFC8446]  Rescorla, E., \"TLS 1.3\", RFC 8446.\n";
+        let found = citations(text);
+
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].anchor, "RFC8446");
+        assert_eq!(found[0].line, 2);
+        assert_eq!(found[1].document, DocumentType::Rfc(9001));
+    }
+
+    #[test]
+    fn test_not_references() {
+        assert!(cross_references("The Section heading and [TLS13] and [RFCfoo].").is_empty());
+    }
+}
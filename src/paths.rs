@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use directories::ProjectDirs;
+
+use crate::Result;
+
+/// Get the platform-specific directory for user data like bookmarks and the reading list
+pub fn data_dir() -> Result<PathBuf> {
+    if let Some(proj_dirs) = ProjectDirs::from("", "", "rfc") {
+        Ok(proj_dirs.data_dir().to_path_buf())
+    } else {
+        // Fallback to home directory
+        let home = std::env::var("HOME").context("HOME not set")?;
+        Ok(PathBuf::from(home).join(".local").join("share").join("rfc"))
+    }
+}
+
+/// Get the platform-specific directory for cached documents
+pub fn cache_dir() -> Result<PathBuf> {
+    if let Some(proj_dirs) = ProjectDirs::from("", "", "rfc") {
+        Ok(proj_dirs.cache_dir().to_path_buf())
+    } else {
+        // Fallback to home directory
+        let home = std::env::var("HOME").context("HOME not set")?;
+        Ok(PathBuf::from(home).join(".cache").join("rfc"))
+    }
+}
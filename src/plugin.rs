@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+
+use crate::api::{BoxFuture, DocumentFetcher, DocumentSource};
+use crate::models::{DocumentType, Format};
+use crate::render::{render_accessible, to_text};
+
+/// A custom output renderer, keyed by name (e.g., "accessible")
+pub trait RendererPlugin: Send + Sync {
+    /// Name under which the renderer is registered
+    fn name(&self) -> &str;
+
+    /// Render document content in the given format to displayable text
+    fn render(&self, content: &str, format: Format) -> crate::Result<String>;
+}
+
+/// Registry of renderers and document sources
+///
+/// Sources are [`DocumentSource`]s keyed by scheme (e.g., "3gpp" for
+/// "3gpp:23.501"): they serve the [`DocumentType::External`] documents of
+/// their scheme, and every other document goes to the default source. The
+/// registry is itself a `DocumentSource` routing each document this way.
+#[derive(Default)]
+pub struct PluginRegistry {
+    renderers: HashMap<String, Box<dyn RendererPlugin>>,
+    sources: HashMap<String, Arc<dyn DocumentSource>>,
+    /// Serves the documents of no registered scheme
+    default: Option<Arc<dyn DocumentSource>>,
+}
+
+impl PluginRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a registry with the built-in renderers and the IETF fetcher as default source
+    pub fn with_builtins() -> crate::Result<Self> {
+        Ok(Self::with_builtin_renderers().with_default_source(DocumentFetcher::new()?))
+    }
+
+    /// Create a registry with the built-in renderers and no sources
+    pub fn with_builtin_renderers() -> Self {
+        let mut registry = Self::new();
+        registry.register_renderer(PlainRenderer);
+        registry.register_renderer(AccessibleRenderer);
+        registry
+    }
+
+    /// Serve documents of no registered scheme from `source`
+    pub fn with_default_source(mut self, source: impl DocumentSource + 'static) -> Self {
+        self.default = Some(Arc::new(source));
+        self
+    }
+
+    /// Register a renderer, replacing any renderer with the same name
+    pub fn register_renderer(&mut self, renderer: impl RendererPlugin + 'static) {
+        self.renderers
+            .insert(renderer.name().to_lowercase(), Box::new(renderer));
+    }
+
+    /// Register a source for a scheme, replacing any source of the same scheme
+    pub fn register_source(&mut self, scheme: &str, source: impl DocumentSource + 'static) {
+        self.sources.insert(scheme.to_lowercase(), Arc::new(source));
+    }
+
+    /// Look up a renderer by name
+    pub fn renderer(&self, name: &str) -> Option<&dyn RendererPlugin> {
+        self.renderers.get(&name.to_lowercase()).map(|r| r.as_ref())
+    }
+
+    /// Look up a source by scheme
+    pub fn source(&self, scheme: &str) -> Option<&dyn DocumentSource> {
+        self.sources.get(&scheme.to_lowercase()).map(|s| s.as_ref())
+    }
+
+    /// Names of all registered renderers, sorted
+    pub fn renderer_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.renderers.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Schemes of all registered sources, sorted
+    pub fn source_schemes(&self) -> Vec<&str> {
+        let mut schemes: Vec<&str> = self.sources.keys().map(String::as_str).collect();
+        schemes.sort_unstable();
+        schemes
+    }
+
+    /// Parse a document identifier, including "scheme:id" of the registered schemes
+    ///
+    /// Identifiers of a registered scheme resolve to [`DocumentType::External`];
+    /// anything else is parsed as by [`DocumentType::parse`].
+    pub fn resolve(&self, identifier: &str) -> Option<DocumentType> {
+        let identifier = identifier.trim();
+        if let Some((scheme, id)) = identifier.split_once(':') {
+            let (scheme, id) = (scheme.to_lowercase(), id.trim());
+            if self.sources.contains_key(&scheme) && !id.is_empty() {
+                return Some(DocumentType::External {
+                    source: scheme,
+                    name: id.to_string(),
+                });
+            }
+        }
+        DocumentType::parse(identifier)
+    }
+
+    /// Fetch a document by identifier through the source serving it
+    pub async fn fetch(&self, identifier: &str) -> crate::Result<(String, Format)> {
+        let doc = self
+            .resolve(identifier)
+            .with_context(|| format!("Unrecognized document identifier: {}", identifier))?;
+        DocumentSource::fetch(self, &doc).await
+    }
+
+    /// Get the source serving a document: its scheme's, or the default
+    pub fn source_for(&self, doc: &DocumentType) -> Option<&dyn DocumentSource> {
+        let scheme = match doc {
+            DocumentType::External { source, .. } => self.sources.get(source),
+            _ => None,
+        };
+        scheme.or(self.default.as_ref()).map(|s| s.as_ref())
+    }
+
+    /// Render content through the named renderer
+    pub fn render(&self, name: &str, content: &str, format: Format) -> crate::Result<String> {
+        let renderer = self
+            .renderer(name)
+            .with_context(|| format!("No renderer registered with name: {}", name))?;
+        renderer.render(content, format)
+    }
+}
+
+impl DocumentSource for PluginRegistry {
+    fn fetch<'a>(
+        &'a self,
+        doc: &'a DocumentType,
+    ) -> BoxFuture<'a, crate::Result<(String, Format)>> {
+        match self.source_for(doc) {
+            Some(source) => source.fetch(doc),
+            None => Box::pin(async move { Err(no_source(doc)) }),
+        }
+    }
+
+    fn fetch_bytes<'a>(
+        &'a self,
+        doc: &'a DocumentType,
+        format: Format,
+    ) -> BoxFuture<'a, crate::Result<Vec<u8>>> {
+        match self.source_for(doc) {
+            Some(source) => source.fetch_bytes(doc, format),
+            None => Box::pin(async move { Err(no_source(doc)) }),
+        }
+    }
+
+    fn fetch_format<'a>(
+        &'a self,
+        doc: &'a DocumentType,
+        format: Format,
+    ) -> BoxFuture<'a, crate::Result<String>> {
+        match self.source_for(doc) {
+            Some(source) => source.fetch_format(doc, format),
+            None => Box::pin(async move { Err(no_source(doc)) }),
+        }
+    }
+}
+
+fn no_source(doc: &DocumentType) -> crate::Error {
+    anyhow!("No source registered for {}", doc).into()
+}
+
+/// Renders documents as plain text, converting HTML if needed
+struct PlainRenderer;
+
+impl RendererPlugin for PlainRenderer {
+    fn name(&self) -> &str {
+        "plain"
+    }
+
+    fn render(&self, content: &str, format: Format) -> crate::Result<String> {
+        to_text(content.to_string(), format, 80)
+    }
+}
+
+/// Renders documents for screen readers
+struct AccessibleRenderer;
+
+impl RendererPlugin for AccessibleRenderer {
+    fn name(&self) -> &str {
+        "accessible"
+    }
+
+    fn render(&self, content: &str, format: Format) -> crate::Result<String> {
+        let text = PlainRenderer.render(content, format)?;
+        Ok(render_accessible(&text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::FixtureSource;
+
+    struct UpperRenderer;
+
+    impl RendererPlugin for UpperRenderer {
+        fn name(&self) -> &str {
+            "Upper"
+        }
+
+        fn render(&self, content: &str, _format: Format) -> crate::Result<String> {
+            Ok(content.to_uppercase())
+        }
+    }
+
+    #[test]
+    fn test_builtins() {
+        let registry = PluginRegistry::with_builtins().unwrap();
+
+        assert_eq!(registry.renderer_names(), vec!["accessible", "plain"]);
+        assert!(registry.source_schemes().is_empty());
+        assert!(registry.source_for(&DocumentType::Rfc(9000)).is_some());
+    }
+
+    #[test]
+    fn test_custom_renderer() {
+        let mut registry = PluginRegistry::new();
+        registry.register_renderer(UpperRenderer);
+
+        assert!(registry.renderer("upper").is_some());
+        assert_eq!(
+            registry.render("UPPER", "quic", Format::Text).unwrap(),
+            "QUIC"
+        );
+        assert!(registry.render("missing", "quic", Format::Text).is_err());
+    }
+
+    #[test]
+    fn test_custom_source() {
+        let spec = DocumentType::External {
+            source: "3gpp".to_string(),
+            name: "23.501".to_string(),
+        };
+        let mut registry = PluginRegistry::new().with_default_source(
+            FixtureSource::new().with_document(DocumentType::Rfc(9000), Format::Text, "QUIC"),
+        );
+        registry.register_source(
+            "3GPP",
+            FixtureSource::new().with_document(spec.clone(), Format::Text, "5G System"),
+        );
+
+        assert_eq!(registry.resolve("3gpp:23.501"), Some(spec));
+        assert_eq!(registry.resolve("rfc9000"), Some(DocumentType::Rfc(9000)));
+        let (content, format) = tokio_test::block_on(registry.fetch("3gpp:23.501")).unwrap();
+        assert_eq!((content.as_str(), format), ("5G System", Format::Text));
+        let (content, _) = tokio_test::block_on(registry.fetch("9000")).unwrap();
+        assert_eq!(content, "QUIC");
+
+        // Unregistered schemes aren't resolved or fetched
+        assert_eq!(registry.resolve("etsi:ts-103"), None);
+        assert!(tokio_test::block_on(registry.fetch("etsi:ts-103")).is_err());
+        assert!(tokio_test::block_on(PluginRegistry::new().fetch("9000")).is_err());
+    }
+}
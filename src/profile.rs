@@ -0,0 +1,266 @@
+use std::cell::Cell;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Set by [`enable`] for the rest of the process
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Nanoseconds spent in each phase by one operation, indexed by `Phase::index`
+type Counters = [AtomicU64; Phase::ALL.len()];
+
+tokio::task_local! {
+    /// Operations the current task is measured for, outermost first
+    static SCOPES: Vec<Arc<Counters>>;
+}
+
+thread_local! {
+    /// Phases being timed on this thread, so nested timings aren't counted twice
+    static ACTIVE: Cell<[bool; Phase::ALL.len()]> = const { Cell::new([false; Phase::ALL.len()]) };
+}
+
+/// Where time is spent while handling a document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    /// Requests and response bodies, including rate limiting and retries
+    Network,
+    /// Parsing plain text documents (sections, references, ...)
+    Parse,
+    /// Converting and rendering documents for display
+    Render,
+    /// Reading and writing the cache
+    CacheIo,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 4] = [Phase::Network, Phase::Parse, Phase::Render, Phase::CacheIo];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Check if profiling is on, because of [`enable`] or `RFC_PROFILE` ("1", "true" or "yes")
+pub fn is_enabled() -> bool {
+    static FROM_ENV: OnceLock<bool> = OnceLock::new();
+    ENABLED.load(Ordering::Relaxed)
+        || *FROM_ENV.get_or_init(|| {
+            std::env::var("RFC_PROFILE").is_ok_and(|v| {
+                matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes")
+            })
+        })
+}
+
+/// Turn profiling on for the rest of the process, e.g. for a `--profile` flag
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Check if the current task is being measured
+fn is_measuring() -> bool {
+    SCOPES
+        .try_with(|scopes| !scopes.is_empty())
+        .unwrap_or(false)
+}
+
+/// Add time spent in a phase to the operations the current task is measured for
+pub fn record(phase: Phase, elapsed: Duration) {
+    let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+    let _ = SCOPES.try_with(|scopes| {
+        for counters in scopes {
+            counters[phase.index()].fetch_add(nanos, Ordering::Relaxed);
+        }
+    });
+}
+
+/// Run a function, counting its time towards a phase if the task is measured
+///
+/// Time spent in a nested call for the same phase is only counted once.
+pub fn time<T>(phase: Phase, f: impl FnOnce() -> T) -> T {
+    if !is_measuring() {
+        return f();
+    }
+    let mut active = ACTIVE.get();
+    if active[phase.index()] {
+        return f();
+    }
+    active[phase.index()] = true;
+    ACTIVE.set(active);
+
+    let started = Instant::now();
+    let result = f();
+    record(phase, started.elapsed());
+
+    let mut active = ACTIVE.get();
+    active[phase.index()] = false;
+    ACTIVE.set(active);
+    result
+}
+
+/// Await a future, counting the time until it completes towards a phase if the task is measured
+pub async fn time_async<F: Future>(phase: Phase, future: F) -> F::Output {
+    if !is_measuring() {
+        return future.await;
+    }
+    let started = Instant::now();
+    let output = future.await;
+    record(phase, started.elapsed());
+    output
+}
+
+/// Run a future as one measured operation if profiling is on, returning its timings
+///
+/// Only time the future itself spends is counted, so operations running
+/// concurrently in other tasks don't get each other's time; work it spawns
+/// onto other tasks is not counted. Measured operations may be nested.
+pub async fn measured<F: Future>(future: F) -> (F::Output, Option<Timings>) {
+    if !is_enabled() {
+        return (future.await, None);
+    }
+    let (output, timings) = measure(future).await;
+    (output, Some(timings))
+}
+
+/// Run a future as one measured operation
+async fn measure<F: Future>(future: F) -> (F::Output, Timings) {
+    let counters: Arc<Counters> = Arc::new(std::array::from_fn(|_| AtomicU64::new(0)));
+    let mut scopes = SCOPES.try_with(Vec::clone).unwrap_or_default();
+    scopes.push(counters.clone());
+
+    let started = Instant::now();
+    let output = SCOPES.scope(scopes, future).await;
+    let phase =
+        |phase: Phase| Duration::from_nanos(counters[phase.index()].load(Ordering::Relaxed));
+    let timings = Timings {
+        total: started.elapsed(),
+        network: phase(Phase::Network),
+        parse: phase(Phase::Parse),
+        render: phase(Phase::Render),
+        cache_io: phase(Phase::CacheIo),
+    };
+    (output, timings)
+}
+
+/// Time spent by one operation, in total and per phase (serialized in milliseconds)
+///
+/// Phases may overlap when work runs concurrently, so they don't need to
+/// add up to the total.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timings {
+    #[serde(with = "millis")]
+    pub total: Duration,
+    #[serde(with = "millis")]
+    pub network: Duration,
+    #[serde(with = "millis")]
+    pub parse: Duration,
+    #[serde(with = "millis")]
+    pub render: Duration,
+    #[serde(with = "millis")]
+    pub cache_io: Duration,
+}
+
+impl Timings {
+    /// Get the time spent in a phase
+    pub fn get(&self, phase: Phase) -> Duration {
+        match phase {
+            Phase::Network => self.network,
+            Phase::Parse => self.parse,
+            Phase::Render => self.render,
+            Phase::CacheIo => self.cache_io,
+        }
+    }
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        write!(
+            f,
+            "total {:.1} ms (network {:.1} ms, parse {:.1} ms, render {:.1} ms, cache {:.1} ms)",
+            ms(self.total),
+            ms(self.network),
+            ms(self.parse),
+            ms(self.render),
+            ms(self.cache_io)
+        )
+    }
+}
+
+mod millis {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let ms = f64::deserialize(deserializer)?;
+        Ok(Duration::try_from_secs_f64(ms / 1000.0).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_measure_phases() {
+        let (_, timings) = measure(async {
+            time(Phase::Parse, || {
+                // Nested timings of the same phase count once
+                time(Phase::Parse, || {
+                    record(Phase::Render, Duration::from_millis(5))
+                });
+                std::thread::sleep(Duration::from_millis(2));
+            });
+        })
+        .await;
+
+        assert!(timings.parse >= Duration::from_millis(2));
+        assert!(timings.parse < Duration::from_millis(500));
+        assert!(timings.render >= Duration::from_millis(5));
+        assert!(timings.total >= timings.parse);
+
+        let json = serde_json::to_value(timings).unwrap();
+        assert!(json["render"].as_f64().unwrap() >= 5.0);
+        let back: Timings = serde_json::from_value(json).unwrap();
+        assert_eq!(back.render.as_millis(), timings.render.as_millis());
+        assert!(timings.to_string().starts_with("total "));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_measurements() {
+        let spend = |ms| async move {
+            time_async(
+                Phase::Network,
+                tokio::time::sleep(Duration::from_millis(ms)),
+            )
+            .await;
+        };
+        let (((_, first), (_, second)), outer) = measure(async {
+            tokio::join!(
+                measure(spend(50)),
+                measure(async {
+                    record(Phase::Parse, Duration::from_millis(7));
+                })
+            )
+        })
+        .await;
+
+        // Concurrent operations keep their own time; the enclosing one gets both
+        assert!(first.network >= Duration::from_millis(50));
+        assert_eq!(first.parse, Duration::ZERO);
+        assert_eq!(second.network, Duration::ZERO);
+        assert_eq!(second.parse, Duration::from_millis(7));
+        assert!(outer.network >= Duration::from_millis(50));
+        assert_eq!(outer.parse, Duration::from_millis(7));
+
+        // Nothing is counted outside of a measurement
+        record(Phase::Parse, Duration::from_millis(1));
+    }
+}
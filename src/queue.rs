@@ -0,0 +1,337 @@
+use std::fmt;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::DocumentType;
+use crate::paths::data_dir;
+
+/// How far reading of a queued document has progressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReadingState {
+    ToRead,
+    InProgress,
+    Done,
+}
+
+impl ReadingState {
+    /// All states, in reading order
+    pub const ALL: [ReadingState; 3] = [
+        ReadingState::ToRead,
+        ReadingState::InProgress,
+        ReadingState::Done,
+    ];
+
+    /// Parse a state name ("to-read", "in-progress" or "reading", "done" or "read")
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "to-read" | "todo" | "queued" => Some(ReadingState::ToRead),
+            "in-progress" | "reading" | "started" => Some(ReadingState::InProgress),
+            "done" | "read" | "finished" => Some(ReadingState::Done),
+            _ => None,
+        }
+    }
+
+    /// Get the canonical name (e.g., "in-progress")
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReadingState::ToRead => "to-read",
+            ReadingState::InProgress => "in-progress",
+            ReadingState::Done => "done",
+        }
+    }
+}
+
+impl fmt::Display for ReadingState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A document on the reading list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueEntry {
+    pub doc: DocumentType,
+    pub state: ReadingState,
+    /// When the document was queued
+    pub added: DateTime<Utc>,
+    /// When reading started, if it has
+    #[serde(default)]
+    pub started: Option<DateTime<Utc>>,
+    /// When reading finished, if it has
+    #[serde(default)]
+    pub finished: Option<DateTime<Utc>>,
+    /// Last change of the entry; the newest change wins when importing
+    pub updated: DateTime<Utc>,
+}
+
+impl QueueEntry {
+    pub fn new(doc: DocumentType, now: DateTime<Utc>) -> Self {
+        Self {
+            doc,
+            state: ReadingState::ToRead,
+            added: now,
+            started: None,
+            finished: None,
+            updated: now,
+        }
+    }
+
+    /// Move the entry to a state, recording when reading started and finished
+    fn set_state(&mut self, state: ReadingState, now: DateTime<Utc>) {
+        match state {
+            ReadingState::ToRead => {
+                self.started = None;
+                self.finished = None;
+            }
+            ReadingState::InProgress => {
+                self.started.get_or_insert(now);
+                self.finished = None;
+            }
+            ReadingState::Done => {
+                self.started.get_or_insert(now);
+                self.finished.get_or_insert(now);
+            }
+        }
+        self.state = state;
+        self.updated = now;
+    }
+}
+
+/// A reading list of documents and how far each has been read, kept in a JSON file
+#[derive(Debug, Clone, Default)]
+pub struct ReadingQueue {
+    path: PathBuf,
+    entries: Vec<QueueEntry>,
+}
+
+impl ReadingQueue {
+    /// Get the default queue file in the platform-specific data directory
+    pub fn default_path() -> crate::Result<PathBuf> {
+        Ok(data_dir()?.join("queue.json"))
+    }
+
+    /// Load the queue from the default queue file
+    pub fn open() -> crate::Result<Self> {
+        Self::load(&Self::default_path()?)
+    }
+
+    /// Load a queue from a file; a missing file holds an empty queue
+    pub fn load(path: &Path) -> crate::Result<Self> {
+        let entries = match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse reading queue {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display()))?,
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    /// Write the queue back to its file
+    pub fn save(&self) -> crate::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let json =
+            serde_json::to_string_pretty(&self.entries).context("Failed to serialize queue")?;
+        fs::write(&self.path, json)
+            .with_context(|| format!("Failed to write {}", self.path.display()))?;
+        Ok(())
+    }
+
+    /// Get the queued documents in the order they were added
+    pub fn entries(&self) -> &[QueueEntry] {
+        &self.entries
+    }
+
+    /// Get the entry of a document
+    pub fn get(&self, doc: &DocumentType) -> Option<&QueueEntry> {
+        self.entries.iter().find(|e| e.doc == *doc)
+    }
+
+    /// Get the documents in a state, in the order they were added
+    pub fn in_state(&self, state: ReadingState) -> impl Iterator<Item = &QueueEntry> {
+        self.entries.iter().filter(move |e| e.state == state)
+    }
+
+    /// Queue a document to read; returns false if it was already queued
+    pub fn add(&mut self, doc: DocumentType) -> bool {
+        if self.get(&doc).is_some() {
+            return false;
+        }
+        self.entries.push(QueueEntry::new(doc, Utc::now()));
+        true
+    }
+
+    /// Set the reading state of a document, queueing it if needed
+    pub fn set_state(&mut self, doc: &DocumentType, state: ReadingState) -> &QueueEntry {
+        self.set_state_at(doc, state, Utc::now())
+    }
+
+    fn set_state_at(
+        &mut self,
+        doc: &DocumentType,
+        state: ReadingState,
+        now: DateTime<Utc>,
+    ) -> &QueueEntry {
+        let index = match self.entries.iter().position(|e| e.doc == *doc) {
+            Some(index) => index,
+            None => {
+                self.entries.push(QueueEntry::new(doc.clone(), now));
+                self.entries.len() - 1
+            }
+        };
+        let entry = &mut self.entries[index];
+        entry.set_state(state, now);
+        entry
+    }
+
+    /// Remove a document from the queue
+    pub fn remove(&mut self, doc: &DocumentType) -> Option<QueueEntry> {
+        let index = self.entries.iter().position(|e| e.doc == *doc)?;
+        Some(self.entries.remove(index))
+    }
+
+    /// Write the queue as JSON, for importing on another device
+    pub fn export(&self, out: impl Write) -> crate::Result<()> {
+        serde_json::to_writer_pretty(out, &self.entries)
+            .context("Failed to export reading queue")?;
+        Ok(())
+    }
+
+    /// Merge an exported queue into this one
+    ///
+    /// Documents not yet queued are added; for documents in both queues the
+    /// more recently updated entry wins. Returns the number of entries changed.
+    pub fn import(&mut self, input: impl Read) -> crate::Result<usize> {
+        let imported: Vec<QueueEntry> =
+            serde_json::from_reader(input).context("Failed to parse exported reading queue")?;
+        let mut changed = 0;
+        for entry in imported {
+            match self.entries.iter_mut().find(|e| e.doc == entry.doc) {
+                Some(existing) if existing.updated >= entry.updated => {}
+                Some(existing) => {
+                    *existing = entry;
+                    changed += 1;
+                }
+                None => {
+                    self.entries.push(entry);
+                    changed += 1;
+                }
+            }
+        }
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_state() {
+        assert_eq!(ReadingState::parse("to-read"), Some(ReadingState::ToRead));
+        assert_eq!(
+            ReadingState::parse("In_Progress"),
+            Some(ReadingState::InProgress)
+        );
+        assert_eq!(ReadingState::parse("read"), Some(ReadingState::Done));
+        assert_eq!(ReadingState::parse("later"), None);
+        for state in ReadingState::ALL {
+            assert_eq!(ReadingState::parse(state.name()), Some(state));
+        }
+    }
+
+    #[test]
+    fn test_state_timestamps() {
+        let mut queue = ReadingQueue::default();
+        let doc = DocumentType::Rfc(9110);
+        let started = at("2026-10-01T08:00:00Z");
+        let finished = at("2026-10-14T18:00:00Z");
+
+        queue.set_state_at(&doc, ReadingState::InProgress, started);
+        let entry = queue.set_state_at(&doc, ReadingState::Done, finished);
+        assert_eq!(entry.added, started);
+        assert_eq!(entry.started, Some(started));
+        assert_eq!(entry.finished, Some(finished));
+
+        // Re-queueing forgets the progress
+        let entry = queue.set_state_at(&doc, ReadingState::ToRead, finished);
+        assert_eq!((entry.started, entry.finished), (None, None));
+    }
+
+    #[test]
+    fn test_queue_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("rfc").join("queue.json");
+        let mut queue = ReadingQueue::load(&path).unwrap();
+        assert!(queue.add(DocumentType::Rfc(9110)));
+        assert!(!queue.add(DocumentType::Rfc(9110)));
+        queue.set_state(&DocumentType::Rfc(9111), ReadingState::InProgress);
+        queue.save().unwrap();
+
+        let mut loaded = ReadingQueue::load(&path).unwrap();
+        assert_eq!(loaded.entries(), queue.entries());
+        let reading: Vec<_> = loaded.in_state(ReadingState::InProgress).collect();
+        assert_eq!(reading.len(), 1);
+        assert_eq!(reading[0].doc, DocumentType::Rfc(9111));
+
+        assert!(loaded.remove(&DocumentType::Rfc(9110)).is_some());
+        assert!(loaded.get(&DocumentType::Rfc(9110)).is_none());
+    }
+
+    #[test]
+    fn test_import_keeps_newest() {
+        let mut laptop = ReadingQueue::default();
+        laptop.set_state_at(
+            &DocumentType::Rfc(9110),
+            ReadingState::Done,
+            at("2026-10-14T18:00:00Z"),
+        );
+        laptop.set_state_at(
+            &DocumentType::Rfc(9111),
+            ReadingState::ToRead,
+            at("2026-10-01T08:00:00Z"),
+        );
+
+        let mut desktop = ReadingQueue::default();
+        desktop.set_state_at(
+            &DocumentType::Rfc(9110),
+            ReadingState::InProgress,
+            at("2026-10-10T08:00:00Z"),
+        );
+        desktop.set_state_at(
+            &DocumentType::Rfc(9111),
+            ReadingState::InProgress,
+            at("2026-10-12T08:00:00Z"),
+        );
+        desktop.set_state_at(
+            &DocumentType::Rfc(9112),
+            ReadingState::ToRead,
+            at("2026-10-12T08:00:00Z"),
+        );
+
+        let mut exported = Vec::new();
+        desktop.export(&mut exported).unwrap();
+        assert_eq!(laptop.import(exported.as_slice()).unwrap(), 2);
+
+        let state = |n| laptop.get(&DocumentType::Rfc(n)).unwrap().state;
+        assert_eq!(state(9110), ReadingState::Done);
+        assert_eq!(state(9111), ReadingState::InProgress);
+        assert_eq!(state(9112), ReadingState::ToRead);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::parse::{is_art, is_page_artifact, sections};
+use crate::profile::{self, Phase};
+
+/// Render a plain text document for consumption with assistive technology
+///
+/// Page headers and footers are dropped, headings are prefixed with an
+/// explicit "Section 5.2:" label, diagrams are replaced by their caption,
+/// and indentation is removed so screen readers don't announce it.
+pub fn render_accessible(text: &str) -> String {
+    profile::time(Phase::Render, || render(text))
+}
+
+fn render(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let headings = sections(text);
+    let mut headings = headings.iter().peekable();
+
+    let mut out: Vec<String> = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        if let Some(heading) = headings.next_if(|h| h.start_line == i) {
+            match &heading.number {
+                Some(_) => out.push(format!("{}: {}", heading.label(), heading.title)),
+                None => out.push(heading.title.clone()),
+            }
+            i += 1;
+            continue;
+        }
+
+        let line = lines[i];
+        if is_page_artifact(line) {
+            i += 1;
+            continue;
+        }
+
+        if line.trim().is_empty() {
+            out.push(String::new());
+            i += 1;
+            continue;
+        }
+
+        let block_end = lines[i..]
+            .iter()
+            .position(|l| l.trim().is_empty() || is_page_artifact(l))
+            .map_or(lines.len(), |n| i + n);
+
+        if is_art(&lines[i..block_end]) {
+            let caption = find_caption(&lines[block_end..]);
+            match caption {
+                Some((offset, caption)) => {
+                    out.push(format!("[Diagram omitted. {}]", caption));
+                    i = block_end + offset + 1;
+                }
+                None => {
+                    out.push("[Diagram omitted]".to_string());
+                    i = block_end;
+                }
+            }
+            continue;
+        }
+
+        for line in &lines[i..block_end] {
+            out.push(line.trim().to_string());
+        }
+        i = block_end;
+    }
+
+    collapse_blank_lines(&out)
+}
+
+/// Find a "Figure N: ..." caption following a block, skipping blank lines
+fn find_caption(rest: &[&str]) -> Option<(usize, String)> {
+    let (offset, line) = rest
+        .iter()
+        .enumerate()
+        .find(|(_, l)| !l.trim().is_empty())?;
+    let line = line.trim();
+    (line.starts_with("Figure ") && line.contains(':')).then(|| (offset, line.to_string()))
+}
+
+/// Collapse runs of blank lines into a single blank line
+fn collapse_blank_lines(lines: &[String]) -> String {
+    let mut out = String::new();
+    let mut previous_blank = true;
+
+    for line in lines {
+        let blank = line.is_empty();
+        if blank && previous_blank {
+            continue;
+        }
+        out.push_str(line);
+        out.push('\n');
+        previous_blank = blank;
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_section_prefixes() {
+        let text = "1.  Introduction\n\n   QUIC is a transport.\n\n1.1.  Terms\n\n   Some terms.\n";
+        let rendered = render_accessible(text);
+
+        assert_eq!(
+            rendered,
+            "Section 1: Introduction\n\nQUIC is a transport.\n\nSection 1.1: Terms\n\nSome terms.\n"
+        );
+    }
+
+    #[test]
+    fn test_diagram_replaced_with_caption() {
+        let text = "\
+2.  Packets
+
+   +--------+--------+
+   |  Type  | Length |
+   +--------+--------+
+
+                Figure 1: Packet Layout
+
+   Packets carry frames.
+";
+        let rendered = render_accessible(text);
+
+        assert!(rendered.contains("[Diagram omitted. Figure 1: Packet Layout]"));
+        assert!(!rendered.contains("+--------+"));
+        assert!(rendered.contains("Packets carry frames."));
+    }
+
+    #[test]
+    fn test_drops_pagination() {
+        let text = "\
+   Body text.
+
+Iyengar & Thomson       Standards Track                   [Page 12]
+\u{c}
+RFC 9000            QUIC Transport Protocol              May 2021
+
+
+   More body text.
+";
+        let rendered = render_accessible(text);
+
+        assert_eq!(rendered, "Body text.\n\nMore body text.\n");
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+
+use crate::lint::BCP14_KEYWORDS;
+use crate::parse::{headings, is_page_artifact};
+use crate::profile::{self, Phase};
+
+/// Schemes of the links underlined in running text
+const LINK_SCHEMES: &[&str] = &["https://", "http://"];
+
+/// Terminal foreground colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    /// Get the SGR parameter selecting the color as foreground
+    fn code(&self) -> u8 {
+        30 + *self as u8
+    }
+}
+
+/// Text attributes applied to one kind of element
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Style {
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub color: Option<Color>,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Check if the style leaves text unchanged
+    pub fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Wrap text in the escape sequences of this style
+    pub fn paint(&self, text: &str) -> String {
+        if self.is_plain() || text.is_empty() {
+            return text.to_string();
+        }
+        let mut codes = Vec::new();
+        for (on, code) in [
+            (self.bold, 1),
+            (self.dim, 2),
+            (self.italic, 3),
+            (self.underline, 4),
+        ] {
+            if on {
+                codes.push(code.to_string());
+            }
+        }
+        if let Some(color) = self.color {
+            codes.push(color.code().to_string());
+        }
+        format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+    }
+}
+
+/// Styles of the elements highlighted by `render_ansi`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Section headings
+    pub heading: Style,
+    /// URLs in running text
+    pub link: Style,
+    /// Running page headers and footers
+    pub page_header: Style,
+    /// BCP 14 requirement keywords (MUST, SHOULD NOT, ...)
+    pub keyword: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            heading: Style::new().bold(),
+            link: Style::new().underline(),
+            page_header: Style::new().dim(),
+            keyword: Style::new().bold().with_color(Color::Yellow),
+        }
+    }
+}
+
+impl Theme {
+    /// A theme that styles nothing, for terminals without escape sequences
+    pub fn plain() -> Self {
+        Self {
+            heading: Style::new(),
+            link: Style::new(),
+            page_header: Style::new(),
+            keyword: Style::new(),
+        }
+    }
+}
+
+/// Render a plain text document for a terminal, styled with ANSI escape sequences
+///
+/// Headings, page headers and footers are styled as whole lines; links and
+/// BCP 14 keywords are styled where they appear. The text is otherwise
+/// unchanged, so stripping the escapes gives back the input.
+pub fn render_ansi(text: &str, theme: &Theme) -> String {
+    profile::time(Phase::Render, || paint(text, theme))
+}
+
+fn paint(text: &str, theme: &Theme) -> String {
+    let heading_lines: HashSet<usize> = headings(text).map(|h| h.line).collect();
+    let mut out = String::with_capacity(text.len() + text.len() / 8);
+
+    for (i, line) in text.split_inclusive('\n').enumerate() {
+        let (line, newline) = match line.strip_suffix('\n') {
+            Some(line) => (line, "\n"),
+            None => (line, ""),
+        };
+        if heading_lines.contains(&i) {
+            paint_trimmed(&mut out, line, &theme.heading);
+        } else if is_page_artifact(line) {
+            paint_trimmed(&mut out, line, &theme.page_header);
+        } else {
+            paint_inline(&mut out, line, theme);
+        }
+        out.push_str(newline);
+    }
+    out
+}
+
+/// Style a whole line, leaving form feeds and surrounding whitespace unstyled
+fn paint_trimmed(out: &mut String, line: &str, style: &Style) {
+    let content = line.trim_start_matches(|c: char| c == '\u{c}' || c.is_whitespace());
+    let lead = &line[..line.len() - content.len()];
+    let content = content.trim_end();
+    out.push_str(lead);
+    out.push_str(&style.paint(content));
+    out.push_str(&line[lead.len() + content.len()..]);
+}
+
+/// Style the links and BCP 14 keywords within a line
+fn paint_inline(out: &mut String, line: &str, theme: &Theme) {
+    let mut rest = line;
+    let mut at_boundary = true;
+    while let Some(c) = rest.chars().next() {
+        if at_boundary {
+            let matched = link_len(rest)
+                .map(|len| (len, &theme.link))
+                .or_else(|| keyword_len(rest).map(|len| (len, &theme.keyword)));
+            if let Some((len, style)) = matched {
+                out.push_str(&style.paint(&rest[..len]));
+                rest = &rest[len..];
+                at_boundary = false;
+                continue;
+            }
+        }
+        out.push(c);
+        at_boundary = !c.is_alphanumeric() && c != '_' && c != '-';
+        rest = &rest[c.len_utf8()..];
+    }
+}
+
+/// Get the length of the URL at the start of the text, without trailing punctuation
+fn link_len(text: &str) -> Option<usize> {
+    LINK_SCHEMES
+        .iter()
+        .find(|scheme| text.starts_with(*scheme))?;
+    let end = text
+        .find(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | '"'))
+        .unwrap_or(text.len());
+    let url = text[..end].trim_end_matches(['.', ',', ';', ':', ')', ']']);
+    Some(url.len())
+}
+
+/// Get the length of the longest BCP 14 keyword at the start of the text
+fn keyword_len(text: &str) -> Option<usize> {
+    BCP14_KEYWORDS
+        .iter()
+        .filter(|keyword| {
+            text.strip_prefix(**keyword).is_some_and(|after| {
+                after
+                    .chars()
+                    .next()
+                    .is_none_or(|c| !c.is_alphanumeric() && c != '_' && c != '-')
+            })
+        })
+        .map(|keyword| keyword.len())
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "\
+RFC 9000                    QUIC Transport                      May 2021
+
+1.  Overview
+
+   Endpoints MUST NOT send frames, see https://example.com/quic.
+   MUSTARD is not a keyword and NOT-RECOMMENDED neither.
+
+Iyengar & Thomson            Standards Track                    [Page 5]
+\u{c}
+";
+
+    fn strip(text: &str) -> String {
+        let mut out = String::new();
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_render_ansi() {
+        let out = render_ansi(TEXT, &Theme::default());
+        let lines: Vec<&str> = out.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "\x1b[2mRFC 9000                    QUIC Transport                      May 2021\x1b[0m"
+        );
+        assert_eq!(lines[2], "\x1b[1m1.  Overview\x1b[0m");
+        assert_eq!(
+            lines[4],
+            "   Endpoints \x1b[1;33mMUST NOT\x1b[0m send frames, see \
+             \x1b[4mhttps://example.com/quic\x1b[0m."
+        );
+        assert!(!lines[5].contains('\x1b'));
+        assert!(lines[7].starts_with("\x1b[2mIyengar"));
+        assert_eq!(strip(&out), TEXT);
+    }
+
+    #[test]
+    fn test_plain_theme() {
+        assert_eq!(render_ansi(TEXT, &Theme::plain()), TEXT);
+
+        let theme = Theme {
+            keyword: Style::new().underline().with_color(Color::Red),
+            ..Theme::plain()
+        };
+        assert_eq!(render_ansi("MAY", &theme), "\x1b[4;31mMAY\x1b[0m");
+    }
+}
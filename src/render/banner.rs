@@ -0,0 +1,214 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{DocumentMetadata, DocumentType};
+
+/// First line of every banner, for tools that strip it
+pub const BANNER_MARKER: &str = "==== STALE DRAFT ";
+
+/// Width of the banner's top and bottom rules
+const WIDTH: usize = 72;
+
+/// Why a draft is no longer the document to read
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "kebab-case")]
+pub enum DraftStatus {
+    /// Published as an RFC
+    Published { rfc: u32 },
+    /// Replaced by other drafts, e.g. when adopted by a working group; `by`
+    /// is empty when the replacements aren't known
+    Replaced { by: Vec<DocumentType> },
+    /// Expired without being published or replaced
+    Expired { expired: Option<DateTime<Utc>> },
+}
+
+impl DraftStatus {
+    /// Get the keyword used on the banner's `Status:` line
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            DraftStatus::Published { .. } => "published",
+            DraftStatus::Replaced { .. } => "replaced",
+            DraftStatus::Expired { .. } => "expired",
+        }
+    }
+}
+
+/// Status block prepended to a rendered draft that is out of date
+///
+/// The block is a few `Key: value` lines between two marker rules, followed
+/// by a sentence for human readers, so it can be read by people and scripts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaleBanner {
+    /// The draft being rendered
+    pub doc: DocumentType,
+    pub status: DraftStatus,
+}
+
+impl StaleBanner {
+    pub fn new(doc: DocumentType, status: DraftStatus) -> Self {
+        Self { doc, status }
+    }
+
+    /// Build the banner for a draft from the Datatracker metadata of its latest revision
+    ///
+    /// The Datatracker state tells replaced and expired drafts apart; drafts
+    /// published as RFCs are recognized from the RFC index instead (see
+    /// [`crate::info::stale_banner`]). Returns `None` for drafts that are still
+    /// active, and for other documents.
+    pub fn for_draft(
+        doc: &DocumentType,
+        meta: &DocumentMetadata,
+        now: DateTime<Utc>,
+    ) -> Option<Self> {
+        if !matches!(doc, DocumentType::Draft(_)) {
+            return None;
+        }
+        let state = meta.state.as_deref().map(str::to_ascii_lowercase);
+        let status = if state.as_deref() == Some("replaced") {
+            DraftStatus::Replaced { by: Vec::new() }
+        } else if state.as_deref() == Some("expired")
+            || (meta.expires.is_some_and(|expires| expires <= now)
+                && state.as_deref().is_none_or(|s| s == "active"))
+        {
+            DraftStatus::Expired {
+                expired: meta.expires,
+            }
+        } else {
+            return None;
+        };
+        Some(Self::new(doc.clone(), status))
+    }
+
+    /// Get the document to read instead, if there is one
+    pub fn recommended(&self) -> Option<DocumentType> {
+        match &self.status {
+            DraftStatus::Published { rfc } => Some(DocumentType::Rfc(*rfc)),
+            DraftStatus::Replaced { by } => by.first().cloned(),
+            DraftStatus::Expired { .. } => None,
+        }
+    }
+
+    /// Describe the status in a sentence
+    pub fn summary(&self) -> String {
+        match &self.status {
+            DraftStatus::Published { rfc } => format!(
+                "This draft was published as RFC {}; read the RFC instead.",
+                rfc
+            ),
+            DraftStatus::Replaced { by } if by.is_empty() => {
+                "This draft was replaced by another document.".to_string()
+            }
+            DraftStatus::Replaced { by } => {
+                let by: Vec<String> = by.iter().map(|d| d.to_string()).collect();
+                format!(
+                    "This draft was replaced by {}; read that instead.",
+                    by.join(", ")
+                )
+            }
+            DraftStatus::Expired { expired } => match expired {
+                Some(date) => format!(
+                    "This draft expired on {} and is no longer an active document.",
+                    date.format("%Y-%m-%d")
+                ),
+                None => "This draft expired and is no longer an active document.".to_string(),
+            },
+        }
+    }
+
+    /// Render the banner as a block of plain text lines
+    pub fn render(&self) -> String {
+        let mut lines = vec![format!("{:=<WIDTH$}", BANNER_MARKER)];
+        lines.push(format!("Document:    {}", self.doc));
+        lines.push(format!("Status:      {}", self.status.keyword()));
+        if let Some(doc) = self.recommended() {
+            lines.push(format!("Recommended: {}", doc));
+        }
+        lines.push(self.summary());
+        lines.push("=".repeat(WIDTH));
+        lines.join("\n")
+    }
+
+    /// Prepend the banner to a rendered document
+    pub fn apply(&self, text: &str) -> String {
+        format!("{}\n\n{}", self.render(), text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn metadata(state: &str) -> DocumentMetadata {
+        DocumentMetadata {
+            name: "draft-ietf-quic-transport".to_string(),
+            doc_type: DocumentType::Draft("draft-ietf-quic-transport".to_string()),
+            title: "QUIC".to_string(),
+            authors: Vec::new(),
+            abstract_text: None,
+            published: None,
+            expires: Some(Utc.with_ymd_and_hms(2021, 7, 17, 0, 0, 0).unwrap()),
+            pages: None,
+            rev: Some("34".to_string()),
+            stream: None,
+            std_level: None,
+            intended_std_level: None,
+            state: Some(state.to_string()),
+            wg: None,
+            wg_name: None,
+        }
+    }
+
+    #[test]
+    fn test_draft_status() {
+        let doc = DocumentType::Draft("draft-ietf-quic-transport-34".to_string());
+        let before = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+        let after = Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap();
+
+        assert_eq!(
+            StaleBanner::for_draft(&doc, &metadata("Active"), before),
+            None
+        );
+        let expired = StaleBanner::for_draft(&doc, &metadata("Active"), after).unwrap();
+        assert_eq!(expired.status.keyword(), "expired");
+        assert_eq!(expired.recommended(), None);
+
+        let meta = metadata("Replaced");
+        let replaced = StaleBanner::for_draft(&doc, &meta, before).unwrap();
+        assert_eq!(replaced.status, DraftStatus::Replaced { by: Vec::new() });
+        assert_eq!(replaced.recommended(), None);
+        assert_eq!(
+            replaced.summary(),
+            "This draft was replaced by another document."
+        );
+
+        // The publication of a draft is read from the RFC index instead
+        assert_eq!(StaleBanner::for_draft(&doc, &metadata("RFC"), before), None);
+        assert_eq!(
+            StaleBanner::for_draft(&DocumentType::Rfc(9000), &meta, before),
+            None
+        );
+    }
+
+    #[test]
+    fn test_render_banner() {
+        let banner = StaleBanner::new(
+            DocumentType::Draft("draft-thomson-quic-tls-00".to_string()),
+            DraftStatus::Replaced {
+                by: vec![DocumentType::Draft("draft-ietf-quic-tls".to_string())],
+            },
+        );
+        let text = banner.apply("Abstract\n");
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].starts_with(BANNER_MARKER));
+        assert_eq!(lines[0].len(), WIDTH);
+        assert_eq!(lines[1], "Document:    draft-thomson-quic-tls-00");
+        assert_eq!(lines[2], "Status:      replaced");
+        assert_eq!(lines[3], "Recommended: draft-ietf-quic-tls");
+        assert_eq!(lines[6], "");
+        assert_eq!(lines[7], "Abstract");
+
+        let json = serde_json::to_value(&banner).unwrap();
+        assert_eq!(json["status"]["status"], "replaced");
+    }
+}
@@ -0,0 +1,225 @@
+use anyhow::{Context, Result};
+use html2text::render::TrivialDecorator;
+use html2text::{Element, Handle};
+
+use crate::models::Format;
+use crate::profile::{self, Phase};
+
+/// Elements that only make up the page around the document
+const CHROME_ELEMENTS: &[&str] = &[
+    "script", "style", "noscript", "template", "header", "footer", "nav", "form", "button",
+];
+
+/// Classes of RFC Editor and Datatracker markup that are not document content:
+/// paragraph anchors, "back to top" links, running page headers and site chrome
+const CHROME_CLASSES: &[&str] = &["pilcrow", "toplink", "ears", "navbar", "d-print-none"];
+
+/// Delimits the placeholder of a `pre` block while the rest is rendered
+const PRE_MARKER: char = '\u{E000}';
+
+/// Convert HTML to plain text wrapped at the given width
+///
+/// Navigation, scripts, paragraph anchors and page headers are dropped
+/// before rendering; headings stay on lines of their own and `pre` blocks
+/// (artwork, source code) keep their layout, even if wider than `width`.
+pub fn html_to_text(html: &str, width: usize) -> crate::Result<String> {
+    profile::time(Phase::Render, || {
+        let (html, blocks) = take_preformatted(html, width)?;
+        let text = render(&html, width)?;
+        Ok(tidy(&restore_preformatted(&text, &blocks)))
+    })
+}
+
+fn render(html: &str, width: usize) -> Result<String> {
+    let config = html2text::config::with_decorator(TrivialDecorator::new());
+    let dom = config
+        .parse_html(html.as_bytes())
+        .context("Failed to parse HTML")?;
+    strip_chrome(&dom.document);
+    let tree = config
+        .dom_to_render_tree(&dom)
+        .context("Failed to convert HTML to text")?;
+    config
+        .render_to_string(tree, width)
+        .context("Failed to convert HTML to text")
+}
+
+/// Replace the content of every `pre` element by a numbered placeholder
+///
+/// Returns the HTML with placeholders and the blocks, each rendered wide
+/// enough that none of its lines is wrapped.
+fn take_preformatted(html: &str, width: usize) -> Result<(String, Vec<String>)> {
+    // ASCII lowercasing keeps byte offsets, so matches index into `html`
+    let lower = html.to_ascii_lowercase();
+    let mut out = String::with_capacity(html.len());
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = find_pre(&lower, pos) {
+        let Some(content) = lower[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(end) = lower[content..].find("</pre").map(|i| content + i) else {
+            break;
+        };
+        let inner = &html[content..end];
+        let widest = inner.lines().map(str::len).max().unwrap_or(0) + 1;
+        let block = render(&format!("<pre>{}</pre>", inner), widest.max(width))?;
+        out.push_str(&html[pos..content]);
+        out.push_str(&format!("{0}{1}{0}", PRE_MARKER, blocks.len()));
+        blocks.push(block.trim_end().to_string());
+        pos = end;
+    }
+    out.push_str(&html[pos..]);
+    Ok((out, blocks))
+}
+
+/// Find the next `<pre>` start tag, skipping elements like `<preface>`
+fn find_pre(lower: &str, from: usize) -> Option<usize> {
+    lower[from..]
+        .match_indices("<pre")
+        .map(|(i, _)| from + i)
+        .find(|&i| {
+            lower[i + 4..]
+                .chars()
+                .next()
+                .is_some_and(|c| c == '>' || c == '/' || c.is_ascii_whitespace())
+        })
+}
+
+/// Put the `pre` blocks back in place of their placeholders, keeping the indentation
+fn restore_preformatted(text: &str, blocks: &[String]) -> String {
+    let mut out = String::with_capacity(text.len() + blocks.iter().map(String::len).sum::<usize>());
+    for line in text.lines() {
+        let block = line.split_once(PRE_MARKER).and_then(|(indent, rest)| {
+            let (number, _) = rest.split_once(PRE_MARKER)?;
+            Some((indent, blocks.get(number.parse::<usize>().ok()?)?))
+        });
+        match block {
+            Some((indent, block)) => {
+                for block_line in block.lines() {
+                    out.push_str(indent);
+                    out.push_str(block_line);
+                    out.push('\n');
+                }
+            }
+            None => {
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+/// Remove the elements around the document content, descending into the rest
+fn strip_chrome(node: &Handle) {
+    node.children.borrow_mut().retain(|child| !is_chrome(child));
+    for child in node.children.borrow().iter() {
+        strip_chrome(child);
+    }
+}
+
+fn is_chrome(node: &Handle) -> bool {
+    let Element { name, attrs, .. } = &node.data else {
+        return false;
+    };
+    let attrs = attrs.borrow();
+    let classes: Vec<&str> = attrs
+        .iter()
+        .filter(|attr| &*attr.name.local == "class")
+        .flat_map(|attr| attr.value.split_whitespace())
+        .collect();
+    let role = attrs
+        .iter()
+        .find(|attr| &*attr.name.local == "role")
+        .map(|attr| &*attr.value);
+
+    // The table of contents is a `nav` too, but belongs to the document
+    if &*name.local == "nav" && classes.contains(&"toc") {
+        return false;
+    }
+    CHROME_ELEMENTS.contains(&&*name.local)
+        || role == Some("navigation")
+        || classes.iter().any(|class| CHROME_CLASSES.contains(class))
+}
+
+/// Drop trailing whitespace and blank lines around the text
+fn tidy(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    let start = lines.iter().position(|line| !line.is_empty());
+    let end = lines.iter().rposition(|line| !line.is_empty());
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].join("\n") + "\n",
+        _ => String::new(),
+    }
+}
+
+/// Convert fetched content to plain text wrapped at the given width
+pub fn to_text(content: String, format: Format, width: usize) -> crate::Result<String> {
+    match format {
+        Format::Text => Ok(content),
+        Format::Html => html_to_text(&content, width),
+        Format::Xml => {
+            Err(anyhow::anyhow!("Converting xml2rfc sources to text is not supported").into())
+        }
+        Format::Pdf => {
+            Err(anyhow::anyhow!("Converting PDF documents to text is not supported").into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_to_text() {
+        let html = "<html><body><h1>Introduction</h1><p>QUIC is a <a href=\"#x\">transport</a>.</p></body></html>";
+        let text = html_to_text(html, 80).unwrap();
+
+        assert!(text.contains("Introduction"));
+        assert!(text.contains("QUIC is a transport."));
+        assert!(!text.contains("#x"));
+    }
+
+    #[test]
+    fn test_strip_page_chrome() {
+        let html = r##"<html><head><script>var x;</script></head><body>
+<nav class="navbar"><a href="/">Datatracker</a></nav>
+<table class="ears"><thead><tr><td>RFC 9000</td><td>QUIC</td></tr></thead></table>
+<h2 id="abstract"><a href="#abstract" class="selfRef">Abstract</a></h2>
+<p>This document defines QUIC.<a href="#section-abstract-1" class="pilcrow">¶</a></p>
+<div id="toc"><a href="#" class="toplink">▲</a><h2>Table of Contents</h2>
+<nav class="toc"><ul><li><a href="#section-1">1</a>. Overview</li></ul></nav></div>
+<footer>About</footer></body></html>"##;
+        let text = html_to_text(html, 80).unwrap();
+
+        assert_eq!(
+            text,
+            "Abstract\n\nThis document defines QUIC.\n\nTable of Contents\n1. Overview\n"
+        );
+    }
+
+    #[test]
+    fn test_preformatted_blocks_keep_their_layout() {
+        let wide = format!("+{}+", "-".repeat(90));
+        let html = format!(
+            "<p>Figure 1:</p><pre>\n{}\n| A &lt;-&gt; B |\n\n\n| end |</pre>",
+            wide
+        );
+        let text = html_to_text(&html, 80).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[2], wide);
+        assert_eq!(lines[3], "| A <-> B |");
+        assert_eq!(&lines[4..7], ["", "", "| end |"]);
+        assert_eq!(find_pre("<preface><pre class=\"x\">", 0), Some(9));
+    }
+
+    #[test]
+    fn test_to_text() {
+        let text = to_text("plain".to_string(), Format::Text, 80).unwrap();
+        assert_eq!(text, "plain");
+        assert!(to_text("<rfc/>".to_string(), Format::Xml, 80).is_err());
+    }
+}
@@ -0,0 +1,394 @@
+use std::collections::{BTreeMap, HashMap};
+
+use super::html::to_text;
+use crate::models::Format;
+use crate::parse::{
+    classify_block, code_block, cross_references, headings, is_page_artifact, is_rule, ArtworkKind,
+    Heading,
+};
+use crate::profile::{self, Phase};
+
+/// Width HTML documents are converted to text at before conversion
+const MARKDOWN_WIDTH: usize = 72;
+
+/// Bullets of plain text lists, as used by xml2rfc v2 and v3
+const BULLETS: &[&str] = &["o ", "* ", "- ", "+ "];
+
+/// A block of the Markdown output
+enum Block {
+    Heading(String),
+    Paragraph(String),
+    List(Vec<String>),
+    Code {
+        lang: Option<String>,
+        lines: Vec<String>,
+    },
+}
+
+/// Convert a plain text or HTML document to Markdown
+///
+/// Section headings become Markdown headings, artwork, ABNF and
+/// `<CODE BEGINS>` blocks become fenced code blocks, and citations like
+/// `[RFC9110]` become reference links to the Datatracker. Page headers,
+/// footers and the table of contents are dropped.
+pub fn to_markdown(content: &str, format: Format) -> crate::Result<String> {
+    let text = to_text(content.to_string(), format, MARKDOWN_WIDTH)?;
+    Ok(profile::time(Phase::Render, || convert(&text)))
+}
+
+fn convert(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let headings: HashMap<usize, Heading> = headings(text).map(|h| (h.line, h)).collect();
+    let first_heading = headings.keys().min().copied().unwrap_or(lines.len());
+
+    let mut blocks = front_matter(&lines[..first_heading]);
+    let mut in_toc = false;
+    let mut i = first_heading;
+    while i < lines.len() {
+        if let Some(heading) = headings.get(&i) {
+            in_toc = heading.number.is_none() && heading.title == "Table of Contents";
+            if !in_toc {
+                blocks.push(Block::Heading(heading_line(heading)));
+            }
+            i += 1;
+            continue;
+        }
+        let line = lines[i];
+        if in_toc || is_blank(line) {
+            i += 1;
+            continue;
+        }
+
+        if let Some(code) = code_block(&lines, i) {
+            let body: Vec<&str> = lines[code.body.clone()]
+                .iter()
+                .copied()
+                .filter(|l| !is_page_artifact(l) && l.trim() != "\u{c}")
+                .collect();
+            push_code(&mut blocks, code.extension(), &body);
+            i = code.body.end + 1;
+            continue;
+        }
+
+        let end = lines[i..]
+            .iter()
+            .position(|l| is_blank(l))
+            .map_or(lines.len(), |n| i + n);
+        let block = &lines[i..end];
+        match code_lang(block) {
+            Some(lang) => push_code(&mut blocks, lang, block),
+            None => push_prose(&mut blocks, block),
+        }
+        i = end;
+    }
+
+    let mut out = blocks
+        .iter()
+        .map(render_block)
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    out.push('\n');
+
+    let links = reference_links(text);
+    if !links.is_empty() {
+        out.push('\n');
+        for (anchor, url) in links {
+            out.push_str(&format!("[{}]: {}\n", anchor, url));
+        }
+    }
+    out
+}
+
+/// Check for lines that separate blocks: blank lines, form feeds and page headers and footers
+fn is_blank(line: &str) -> bool {
+    line.trim().trim_matches('\u{c}').is_empty() || is_page_artifact(line)
+}
+
+/// Convert the lines before the first section: the title and the header of the first page
+fn front_matter(lines: &[&str]) -> Vec<Block> {
+    let blocks: Vec<&[&str]> = lines
+        .split(|l| is_blank(l))
+        .filter(|b| !b.is_empty())
+        .collect();
+    // The title is the last block, centered
+    let title = blocks
+        .last()
+        .filter(|b| b.iter().all(|l| l.starts_with(char::is_whitespace)));
+
+    let mut out = Vec::new();
+    for block in &blocks {
+        if Some(block) == title {
+            let (names, title): (Vec<&str>, Vec<&str>) = block
+                .iter()
+                .map(|l| l.trim())
+                .partition(|l| l.starts_with("draft-"));
+            out.push(Block::Heading(format!("# {}", escape(&title.join(" ")))));
+            if !names.is_empty() {
+                out.push(Block::Paragraph(escape(&names.join(" "))));
+            }
+        } else {
+            push_code(&mut out, None, block);
+        }
+    }
+    out
+}
+
+fn heading_line(heading: &Heading) -> String {
+    let marks = "#".repeat((heading.level() + 1).min(6));
+    match heading.number {
+        Some(number) => format!("{} {}. {}", marks, number, escape(heading.title)),
+        None => format!("{} {}", marks, escape(heading.title)),
+    }
+}
+
+/// Get the language of a block that is artwork, ABNF or an example rather
+/// than prose; None for prose, Some(None) for code without a known language
+fn code_lang(block: &[&str]) -> Option<Option<String>> {
+    if is_rule(block[0].trim_start()) {
+        return Some(Some("abnf".to_string()));
+    }
+    let (kind, content_type) = classify_block(block)?;
+    Some(content_type.filter(|_| kind == ArtworkKind::SourceCode))
+}
+
+/// Add a code block without its common indentation, continuing the previous one if it has the same language
+fn push_code(blocks: &mut Vec<Block>, lang: Option<String>, lines: &[&str]) {
+    let common = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| indent(l))
+        .min()
+        .unwrap_or(0);
+    let code: Vec<String> = lines
+        .iter()
+        .map(|l| l.get(common..).unwrap_or("").trim_end().to_string())
+        .collect();
+    if let Some(Block::Code {
+        lang: previous,
+        lines,
+    }) = blocks.last_mut()
+    {
+        if *previous == lang {
+            lines.push(String::new());
+            lines.extend(code);
+            return;
+        }
+    }
+    blocks.push(Block::Code { lang, lines: code });
+}
+
+/// Add a paragraph, or list items if the block starts with a bullet or number
+fn push_prose(blocks: &mut Vec<Block>, lines: &[&str]) {
+    let Some(marker) = bullet(lines[0]) else {
+        let text: Vec<&str> = lines.iter().map(|l| l.trim()).collect();
+        blocks.push(Block::Paragraph(escape(&text.join(" "))));
+        return;
+    };
+    let mut items: Vec<String> = Vec::new();
+    for line in lines {
+        match bullet(line) {
+            Some((marker, rest)) => items.push(format!("{} {}", marker, escape(rest))),
+            None => {
+                let item = items.last_mut().expect("the first line has a bullet");
+                item.push(' ');
+                item.push_str(&escape(line.trim()));
+            }
+        }
+    }
+    // Items of the same list are often separated by blank lines
+    if let Some(Block::List(previous)) = blocks.last_mut() {
+        let same_kind = previous
+            .first()
+            .is_some_and(|p| p.starts_with('-') == (marker.0 == "-"));
+        if same_kind {
+            previous.extend(items);
+            return;
+        }
+    }
+    blocks.push(Block::List(items));
+}
+
+/// Split a list item line into its Markdown marker ("-" or "1.") and text
+fn bullet(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    if let Some(rest) = BULLETS.iter().find_map(|b| trimmed.strip_prefix(b)) {
+        return Some(("-", rest.trim_start()));
+    }
+    let (number, rest) = trimmed.split_once(". ")?;
+    (!number.is_empty() && number.len() <= 3 && number.chars().all(|c| c.is_ascii_digit()))
+        .then(|| (&trimmed[..number.len() + 1], rest.trim_start()))
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Escape the characters Markdown would take as emphasis, code or HTML
+///
+/// Square brackets are kept, so citations like `[RFC9110]` become
+/// reference links.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for (i, c) in text.char_indices() {
+        match c {
+            '\\' | '*' | '_' | '`' => out.push('\\'),
+            '<' if !text[i..].starts_with("<http") => out.push('\\'),
+            '#' | '>' if i == 0 => out.push('\\'),
+            _ => {}
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn render_block(block: &Block) -> String {
+    match block {
+        Block::Heading(line) | Block::Paragraph(line) => line.clone(),
+        Block::List(items) => items.join("\n"),
+        Block::Code { lang, lines } => {
+            let fence = if lines.iter().any(|l| l.contains("```")) {
+                "~~~"
+            } else {
+                "```"
+            };
+            format!(
+                "{}{}\n{}\n{}",
+                fence,
+                lang.as_deref().unwrap_or(""),
+                lines.join("\n"),
+                fence
+            )
+        }
+    }
+}
+
+/// Get the link target of every cited document, by citation anchor
+fn reference_links(text: &str) -> BTreeMap<String, String> {
+    cross_references(text)
+        .into_iter()
+        .filter(|r| r.section.is_none())
+        .filter_map(|r| {
+            let url = r.document?.datatracker_url();
+            let anchor = r.label.trim_start_matches('[').trim_end_matches(']');
+            (!url.is_empty()).then(|| (anchor.to_string(), url))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "\
+Internet Engineering Task Force (IETF)                   J. Iyengar, Ed.
+Request for Comments: 9000                                        Fastly
+
+     QUIC: A UDP-Based Multiplexed and Secure Transport
+
+Abstract
+
+   This document defines the core of the QUIC transport protocol, see
+   [RFC9001] and <https://quicwg.org>.
+
+Table of Contents
+
+   1.  Overview  . . . . . . . . . . . . . . . . . . . . . . . . . .   4
+
+1.  Overview
+
+   Endpoints *MUST* support:
+
+   o  the version negotiation
+      mechanism;
+
+   o  retry.
+
+Iyengar & Thomson            Standards Track                    [Page 5]
+\u{c}
+RFC 9000                    QUIC Transport                      May 2021
+
+
+1.1.  Packets
+
+   +--------+
+   |  Type  |
+   +--------+
+
+   +--------+
+   | Length |
+   +--------+
+
+     Packet = Header
+              Payload
+
+   <CODE BEGINS> file \"quic.yang\"
+   module quic {
+   }
+   <CODE ENDS>
+";
+
+    #[test]
+    fn test_text_to_markdown() {
+        let markdown = to_markdown(TEXT, Format::Text).unwrap();
+
+        assert_eq!(
+            markdown,
+            "```
+Internet Engineering Task Force (IETF)                   J. Iyengar, Ed.
+Request for Comments: 9000                                        Fastly
+```
+
+# QUIC: A UDP-Based Multiplexed and Secure Transport
+
+## Abstract
+
+This document defines the core of the QUIC transport protocol, see [RFC9001] and <https://quicwg.org>.
+
+## 1. Overview
+
+Endpoints \\*MUST\\* support:
+
+- the version negotiation mechanism;
+- retry.
+
+### 1.1. Packets
+
+```
++--------+
+|  Type  |
++--------+
+
++--------+
+| Length |
++--------+
+```
+
+```abnf
+Packet = Header
+         Payload
+```
+
+```yang
+module quic {
+}
+```
+
+[RFC9001]: https://datatracker.ietf.org/doc/rfc9001/
+"
+        );
+    }
+
+    #[test]
+    fn test_html_to_markdown() {
+        let html = "<h2>1. Introduction</h2><p>See [RFC9110] for <b>HTTP</b>.</p>\
+                    <pre>  a = b\n  c = d</pre>";
+        let markdown = to_markdown(html, Format::Html).unwrap();
+
+        assert_eq!(
+            markdown,
+            "## 1. Introduction\n\nSee [RFC9110] for HTTP.\n\n```abnf\na = b\nc = d\n```\n\n\
+             [RFC9110]: https://datatracker.ietf.org/doc/rfc9110/\n"
+        );
+        assert!(to_markdown("<rfc/>", Format::Xml).is_err());
+    }
+}
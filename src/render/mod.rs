@@ -0,0 +1,13 @@
+mod accessible;
+mod ansi;
+mod banner;
+mod html;
+mod markdown;
+mod options;
+
+pub use accessible::render_accessible;
+pub use ansi::{render_ansi, Color, Style, Theme};
+pub use banner::{DraftStatus, StaleBanner, BANNER_MARKER};
+pub use html::{html_to_text, to_text};
+pub use markdown::to_markdown;
+pub use options::{BoilerplateMode, RenderOptions};
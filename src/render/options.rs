@@ -0,0 +1,120 @@
+use super::{render_ansi, StaleBanner, Theme};
+use crate::parse::boilerplate;
+use crate::profile::{self, Phase};
+
+/// How renderers treat standard boilerplate
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BoilerplateMode {
+    /// Render boilerplate as-is
+    #[default]
+    Show,
+    /// Replace each block with a one-line marker
+    Fold,
+    /// Drop boilerplate entirely
+    Skip,
+}
+
+impl BoilerplateMode {
+    /// Parse a mode name ("show", "fold", "skip")
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "show" => Some(BoilerplateMode::Show),
+            "fold" => Some(BoilerplateMode::Fold),
+            "skip" => Some(BoilerplateMode::Skip),
+            _ => None,
+        }
+    }
+}
+
+/// Options shared by the text renderers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    pub boilerplate: BoilerplateMode,
+    /// Status block prepended to drafts that are out of date
+    pub banner: Option<StaleBanner>,
+    /// Terminal styling, applied after everything else
+    pub theme: Option<Theme>,
+}
+
+impl RenderOptions {
+    /// Apply the options to a plain text document
+    pub fn apply(&self, text: &str) -> String {
+        profile::time(Phase::Render, || self.render(text))
+    }
+
+    fn render(&self, text: &str) -> String {
+        let text = self.apply_boilerplate(text);
+        let text = match &self.banner {
+            Some(banner) => banner.apply(&text),
+            None => text,
+        };
+        match &self.theme {
+            Some(theme) => render_ansi(&text, theme),
+            None => text,
+        }
+    }
+
+    fn apply_boilerplate(&self, text: &str) -> String {
+        if self.boilerplate == BoilerplateMode::Show {
+            return text.to_string();
+        }
+
+        let lines: Vec<&str> = text.lines().collect();
+        let mut out: Vec<String> = Vec::with_capacity(lines.len());
+        let mut i = 0;
+
+        for block in boilerplate(text) {
+            if block.start_line < i {
+                continue;
+            }
+            out.extend(lines[i..block.start_line].iter().map(|l| l.to_string()));
+            if self.boilerplate == BoilerplateMode::Fold {
+                let folded = block.end_line - block.start_line;
+                out.push(format!("[{}: {} lines folded]", block.kind.label(), folded));
+                out.push(String::new());
+            }
+            i = block.end_line;
+        }
+        out.extend(lines[i..].iter().map(|l| l.to_string()));
+
+        out.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "Abstract\n\n   A protocol.\n\n\
+Status of This Memo\n\n   Standards Track.\n\n\
+1.  Introduction\n\n   Text.\n";
+
+    #[test]
+    fn test_fold_and_skip() {
+        let fold = RenderOptions {
+            boilerplate: BoilerplateMode::Fold,
+            ..Default::default()
+        };
+        let skip = RenderOptions {
+            boilerplate: BoilerplateMode::Skip,
+            ..Default::default()
+        };
+
+        let folded = fold.apply(TEXT);
+        assert!(folded.contains("[Status of This Memo: 4 lines folded]"));
+        assert!(!folded.contains("Standards Track."));
+        assert!(folded.contains("1.  Introduction"));
+
+        let skipped = skip.apply(TEXT);
+        assert!(!skipped.contains("Status of This Memo"));
+        assert!(skipped.contains("A protocol."));
+
+        assert_eq!(RenderOptions::default().apply(TEXT), TEXT);
+    }
+
+    #[test]
+    fn test_parse_mode() {
+        assert_eq!(BoilerplateMode::parse("Fold"), Some(BoilerplateMode::Fold));
+        assert_eq!(BoilerplateMode::parse("hide"), None);
+    }
+}
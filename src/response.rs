@@ -0,0 +1,336 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::models::{DocumentInfo, DocumentType, Format, SearchResult};
+use crate::profile::Timings;
+
+/// Version of the envelope layout; bump on incompatible changes
+pub const RESPONSE_VERSION: u32 = 1;
+
+/// JSON Schema (draft 2020-12) of the envelope, for consumers of `--json` output
+pub const RESPONSE_SCHEMA: &str = include_str!("response.schema.json");
+
+/// A fetched document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FetchResponse {
+    pub document: DocumentType,
+    /// Document name (e.g., "rfc9000")
+    pub name: String,
+    pub format: Format,
+    /// Whether the content was served from the cache
+    pub cached: bool,
+    pub content: String,
+    /// Time spent fetching and rendering, if profiling is on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<Timings>,
+}
+
+/// The cache contents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheListing {
+    pub directory: PathBuf,
+    pub documents: Vec<DocumentType>,
+    /// Total size of the cache in bytes
+    pub size: u64,
+}
+
+/// Result of a cache removal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheRemoval {
+    pub document: DocumentType,
+    /// Whether the document was cached
+    pub removed: bool,
+}
+
+/// Typed result of a CLI operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "command", content = "data", rename_all = "snake_case")]
+pub enum Response {
+    Fetch(FetchResponse),
+    Search(SearchResult),
+    Info(Box<DocumentInfo>),
+    CacheList(CacheListing),
+    CacheRemove(CacheRemoval),
+    CacheClear,
+}
+
+/// Machine-readable envelope around a response or an error
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope {
+    pub version: u32,
+    pub ok: bool,
+    #[serde(flatten)]
+    pub response: Option<Response>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Envelope {
+    /// Wrap a successful response
+    pub fn ok(response: Response) -> Self {
+        Self {
+            version: RESPONSE_VERSION,
+            ok: true,
+            response: Some(response),
+            error: None,
+        }
+    }
+
+    /// Wrap an error, including its context chain
+    pub fn error(error: &anyhow::Error) -> Self {
+        Self {
+            version: RESPONSE_VERSION,
+            ok: false,
+            response: None,
+            error: Some(format!("{:#}", error)),
+        }
+    }
+
+    /// Serialize the envelope as a single line of JSON
+    pub fn to_json(&self) -> crate::Result<String> {
+        Ok(serde_json::to_string(self).context("Failed to serialize response")?)
+    }
+
+    /// Parse an envelope printed by `--json`
+    pub fn from_json(json: &str) -> crate::Result<Self> {
+        let envelope: Self = serde_json::from_str(json).context("Failed to parse response")?;
+        if envelope.version != RESPONSE_VERSION {
+            return Err(anyhow::anyhow!(
+                "Unsupported response version {} (expected {})",
+                envelope.version,
+                RESPONSE_VERSION
+            )
+            .into());
+        }
+        Ok(envelope)
+    }
+}
+
+impl From<Result<Response>> for Envelope {
+    fn from(result: Result<Response>) -> Self {
+        match result {
+            Ok(response) => Envelope::ok(response),
+            Err(e) => Envelope::error(&e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        Author, Document, License, RfcMetadata, SearchFilter, SectionMatch, Stream, Subseries,
+    };
+    use crate::parse::SectionAnchor;
+    use serde_json::Value;
+
+    #[test]
+    fn test_ok_envelope() {
+        let envelope = Envelope::ok(Response::CacheRemove(CacheRemoval {
+            document: DocumentType::Rfc(9000),
+            removed: true,
+        }));
+        let value: serde_json::Value = serde_json::from_str(&envelope.to_json().unwrap()).unwrap();
+
+        assert_eq!(value["version"], RESPONSE_VERSION);
+        assert_eq!(value["ok"], true);
+        assert_eq!(value["command"], "cache_remove");
+        assert_eq!(value["data"]["removed"], true);
+        assert!(value.get("error").is_none());
+
+        let search = Envelope::ok(Response::Search(SearchResult::empty(
+            "quic".to_string(),
+            SearchFilter::RfcsOnly,
+        )));
+        let value: serde_json::Value = serde_json::from_str(&search.to_json().unwrap()).unwrap();
+        assert_eq!(value["data"]["query"], "quic");
+    }
+
+    #[test]
+    fn test_error_envelope() {
+        let result: Result<Response> =
+            Err(anyhow::anyhow!("HTTP 404").context("Failed to fetch RFC 99999"));
+        let json = Envelope::from(result).to_json().unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"version":1,"ok":false,"error":"Failed to fetch RFC 99999: HTTP 404"}"#
+        );
+    }
+
+    /// Check a value against the subset of JSON Schema used by `RESPONSE_SCHEMA`,
+    /// rejecting properties the schema doesn't declare
+    fn check(root: &Value, schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.strip_prefix("#/$defs/").unwrap();
+            return check(root, &root["$defs"][name], value, path);
+        }
+        if let Some(options) = schema["oneOf"].as_array() {
+            let matched = options
+                .iter()
+                .filter(|o| check(root, o, value, path).is_ok())
+                .count();
+            if matched != 1 {
+                return Err(format!("{}: {} of oneOf match {}", path, matched, value));
+            }
+        }
+        if let Some(constant) = schema.get("const") {
+            if constant != value {
+                return Err(format!("{}: {} is not {}", path, value, constant));
+            }
+        }
+        if let Some(options) = schema["enum"].as_array() {
+            if !options.contains(value) {
+                return Err(format!("{}: {} is not in enum", path, value));
+            }
+        }
+        let types: Vec<&str> = match &schema["type"] {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        let type_matches = |t: &str| match t {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "integer" => value.is_u64() || value.is_i64(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            "null" => value.is_null(),
+            _ => false,
+        };
+        if !types.is_empty() && !types.into_iter().any(type_matches) {
+            return Err(format!("{}: {} has the wrong type", path, value));
+        }
+        if let (Some(minimum), Some(n)) = (schema["minimum"].as_f64(), value.as_f64()) {
+            if n < minimum {
+                return Err(format!("{}: {} is below {}", path, n, minimum));
+            }
+        }
+
+        if let (Some(object), Some(properties)) =
+            (value.as_object(), schema["properties"].as_object())
+        {
+            for required in schema["required"].as_array().into_iter().flatten() {
+                if !object.contains_key(required.as_str().unwrap()) {
+                    return Err(format!("{}: missing {}", path, required));
+                }
+            }
+            for (key, field) in object {
+                let property = properties
+                    .get(key)
+                    .ok_or_else(|| format!("{}: undeclared property {}", path, key))?;
+                check(root, property, field, &format!("{}.{}", path, key))?;
+            }
+        }
+        if let Some(items) = value.as_array() {
+            let prefix = schema["prefixItems"].as_array();
+            for (i, item) in items.iter().enumerate() {
+                let item_schema = prefix.and_then(|p| p.get(i)).unwrap_or(&schema["items"]);
+                check(root, item_schema, item, &format!("{}[{}]", path, i))?;
+            }
+            if schema["minItems"]
+                .as_u64()
+                .is_some_and(|n| (items.len() as u64) < n)
+                || schema["maxItems"]
+                    .as_u64()
+                    .is_some_and(|n| (items.len() as u64) > n)
+            {
+                return Err(format!("{}: wrong number of items", path));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_envelopes_match_schema() {
+        let schema: Value = serde_json::from_str(RESPONSE_SCHEMA).unwrap();
+        assert_eq!(
+            schema["$defs"]["FetchEnvelope"]["properties"]["version"]["const"],
+            RESPONSE_VERSION
+        );
+
+        let bad = serde_json::json!({"version": 1, "ok": true, "error": "HTTP 404"});
+        assert!(check(&schema, &schema, &bad, "$").is_err());
+
+        let timings = Some(crate::profile::Timings::default());
+        let mut document = Document::new(
+            "rfc9000".to_string(),
+            "QUIC".to_string(),
+            DocumentType::Rfc(9000),
+        );
+        document.authors = vec![
+            Author::new("M. Thomson"),
+            Author::with_ascii("Jürgen Schönwälder", "Juergen Schoenwaelder"),
+        ];
+        document.published = Some(chrono::Utc::now());
+        document.pages = Some(151);
+        document.stream = Some(Stream::Ietf);
+        document.matches = vec![SectionMatch {
+            anchor: SectionAnchor::parse("5.2").unwrap(),
+            snippet: Some("streams".to_string()),
+        }];
+        let mut search = SearchResult::empty("quic".to_string(), SearchFilter::RfcsOnly);
+        search.documents.push(document.clone());
+        search.next_offset = Some(20);
+        search.timings = timings;
+
+        let mut info = DocumentInfo::new(DocumentType::Rfc(9000));
+        info.document = Some(document);
+        info.rfc = Some(RfcMetadata {
+            number: 9000,
+            obsoletes: vec![DocumentType::Draft("draft-ietf-quic-transport".to_string())],
+            license: Some(License {
+                code_license: Some(crate::models::CodeLicense::RevisedBsd),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        info.cached_formats = vec![Format::Text, Format::Html];
+
+        let responses = vec![
+            Response::Fetch(FetchResponse {
+                document: DocumentType::Rfc(9000),
+                name: "rfc9000".to_string(),
+                format: Format::Xml,
+                cached: true,
+                content: "QUIC".to_string(),
+                timings,
+            }),
+            Response::Search(search),
+            Response::Info(Box::new(info)),
+            Response::CacheList(CacheListing {
+                directory: PathBuf::from("/tmp/rfc"),
+                documents: vec![
+                    DocumentType::Subseries(Subseries::Bcp, 14),
+                    DocumentType::External {
+                        source: "w3c".to_string(),
+                        name: "webauthn-2".to_string(),
+                    },
+                ],
+                size: 4096,
+            }),
+            Response::CacheRemove(CacheRemoval {
+                document: DocumentType::Draft("draft-foo-00".to_string()),
+                removed: false,
+            }),
+            Response::CacheClear,
+        ];
+
+        let mut envelopes: Vec<Envelope> = responses.into_iter().map(Envelope::ok).collect();
+        envelopes.push(Envelope::error(&anyhow::anyhow!("HTTP 404")));
+        for envelope in envelopes {
+            let json = envelope.to_json().unwrap();
+            let value: Value = serde_json::from_str(&json).unwrap();
+            if let Err(e) = check(&schema, &schema, &value, "$") {
+                panic!("{}\n{}", e, json);
+            }
+
+            // Every envelope reads back into the same JSON
+            let parsed = Envelope::from_json(&json).unwrap();
+            assert_eq!(parsed.to_json().unwrap(), json);
+        }
+    }
+}
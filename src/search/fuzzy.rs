@@ -0,0 +1,105 @@
+use crate::models::DocumentType;
+
+/// Count the single-character edits turning `a` into `b`
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substituted = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substituted.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Most edits a word of this length may be off by and still match
+///
+/// Roughly one typo per four characters, so "qic" matches "quic" but "tls"
+/// doesn't match "ssh".
+pub fn max_distance(len: usize) -> usize {
+    (len / 4).max(1)
+}
+
+/// Check whether `candidate` is within typo distance of `word`
+pub fn is_close(word: &str, candidate: &str) -> bool {
+    let len = word.chars().count();
+    len.abs_diff(candidate.chars().count()) <= max_distance(len)
+        && levenshtein(word, candidate) <= max_distance(len)
+}
+
+/// Name used to compare documents, without a draft's revision number
+fn comparable_name(doc: &DocumentType) -> String {
+    match doc.draft_revision() {
+        Some((base, _)) => base.to_string(),
+        None => doc.name(),
+    }
+}
+
+/// Suggest the known documents whose names are closest to a misspelled one
+///
+/// Closest first; the input itself is never suggested.
+pub fn suggest_documents<'a>(
+    input: &DocumentType,
+    known: impl IntoIterator<Item = &'a DocumentType>,
+    limit: usize,
+) -> Vec<DocumentType> {
+    let name = comparable_name(input);
+    let mut close: Vec<(usize, &DocumentType)> = known
+        .into_iter()
+        .filter(|doc| *doc != input)
+        .filter_map(|doc| {
+            let candidate = comparable_name(doc);
+            is_close(&name, &candidate).then(|| (levenshtein(&name, &candidate), doc))
+        })
+        .collect();
+    // Among revisions of the same draft, keep the newest
+    close.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1.name().cmp(&a.1.name())));
+    close.dedup_by(|a, b| comparable_name(a.1) == comparable_name(b.1));
+    close
+        .into_iter()
+        .take(limit)
+        .map(|(_, doc)| doc.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("quic", "quic"), 0);
+        assert_eq!(levenshtein("qic", "quic"), 1);
+        assert_eq!(levenshtein("trnsport", "transport"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "tls"), 3);
+        assert!(is_close("qic", "quic"));
+        assert!(!is_close("tls", "ssh"));
+    }
+
+    #[test]
+    fn test_suggest_documents() {
+        let known = [
+            DocumentType::Draft("draft-ietf-quic-transport-34".to_string()),
+            DocumentType::Draft("draft-ietf-quic-transport-33".to_string()),
+            DocumentType::Draft("draft-ietf-quic-recovery-34".to_string()),
+            DocumentType::Rfc(9000),
+        ];
+        let typo = DocumentType::parse("draft-ietf-quic-trnsport").unwrap();
+        assert_eq!(
+            suggest_documents(&typo, &known, 5),
+            vec![DocumentType::Draft(
+                "draft-ietf-quic-transport-34".to_string()
+            )]
+        );
+        assert_eq!(
+            suggest_documents(&DocumentType::Rfc(900), &known, 5),
+            vec![DocumentType::Rfc(9000)]
+        );
+        assert!(suggest_documents(&DocumentType::Rfc(9000), &known, 5).is_empty());
+    }
+}
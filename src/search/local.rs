@@ -0,0 +1,488 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::fuzzy;
+use crate::cache::CacheManager;
+use crate::models::{DocumentType, Format, SectionMatch};
+use crate::parse::{is_page_artifact, sections, SectionAnchor};
+
+/// BM25 term frequency saturation
+const K1: f64 = 1.2;
+/// BM25 document length normalization
+const B: f64 = 0.75;
+/// Maximum length of a snippet in characters
+const SNIPPET_LEN: usize = 160;
+/// Score weight of terms matched by prefix or despite a typo
+const FUZZY_WEIGHT: f64 = 0.5;
+/// Shortest query term that is matched by prefix
+const MIN_PREFIX_LEN: usize = 3;
+
+/// A document matching a local search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalHit {
+    pub doc: DocumentType,
+    /// BM25 relevance; higher is better
+    pub score: f64,
+    /// Line with the most query terms
+    pub snippet: String,
+    /// Label of the section containing the snippet (e.g., "Section 5.2")
+    pub section: Option<String>,
+}
+
+/// An indexed document
+#[derive(Debug, Clone)]
+struct Entry {
+    doc: DocumentType,
+    text: String,
+    /// Number of terms in the document
+    length: usize,
+}
+
+/// An inverted index over document text, for ranked keyword queries
+#[derive(Debug, Clone, Default)]
+pub struct LocalIndex {
+    entries: Vec<Entry>,
+    /// Term to (entry, term frequency) pairs, in entry order
+    postings: HashMap<String, Vec<(usize, u32)>>,
+}
+
+impl LocalIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index the plain text of every cached document
+    ///
+    /// Building doesn't count as reading the documents.
+    pub fn build(cache: &CacheManager) -> Self {
+        let mut index = Self::new();
+        index.add_cached(cache, &cache.list_cached());
+        index
+    }
+
+    /// Index the plain text of cached documents, on several threads
+    ///
+    /// Documents without a cached text are skipped; reading them doesn't
+    /// count as an access.
+    pub fn add_cached(&mut self, cache: &CacheManager, docs: &[DocumentType]) {
+        let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let chunk = docs.len().div_ceil(workers).max(1);
+        let parts: Vec<Self> = std::thread::scope(|scope| {
+            let handles: Vec<_> = docs
+                .chunks(chunk)
+                .map(|docs| {
+                    scope.spawn(move || {
+                        let mut part = Self::new();
+                        for doc in docs {
+                            if let Some(text) = cache.peek_document(doc, Format::Text) {
+                                part.add(doc.clone(), text);
+                            }
+                        }
+                        part
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|e| std::panic::resume_unwind(e))
+                })
+                .collect()
+        });
+        // Merged in document order, so ties rank the same as a sequential build
+        for part in parts {
+            self.merge(part);
+        }
+    }
+
+    /// Append the entries of another index after this one's
+    fn merge(&mut self, other: Self) {
+        let offset = self.entries.len();
+        for (term, postings) in other.postings {
+            let merged = self.postings.entry(term).or_default();
+            merged.extend(postings.into_iter().map(|(id, f)| (id + offset, f)));
+        }
+        self.entries.extend(other.entries);
+    }
+
+    /// Add a document to the index
+    pub fn add(&mut self, doc: DocumentType, text: String) {
+        let id = self.entries.len();
+        let mut frequencies: HashMap<String, u32> = HashMap::new();
+        let mut length = 0;
+        for term in terms(&text) {
+            *frequencies.entry(term).or_default() += 1;
+            length += 1;
+        }
+        for (term, frequency) in frequencies {
+            self.postings.entry(term).or_default().push((id, frequency));
+        }
+        self.entries.push(Entry { doc, text, length });
+    }
+
+    /// Remove a document from the index, returning whether it was indexed
+    pub fn remove(&mut self, doc: &DocumentType) -> bool {
+        let Some(id) = self.entries.iter().position(|e| &e.doc == doc) else {
+            return false;
+        };
+        self.entries.remove(id);
+        self.postings.retain(|_, postings| {
+            postings.retain(|&(entry, _)| entry != id);
+            for (entry, _) in postings.iter_mut() {
+                if *entry > id {
+                    *entry -= 1;
+                }
+            }
+            !postings.is_empty()
+        });
+        true
+    }
+
+    /// Number of indexed documents
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Find the documents most relevant to a keyword query, best first
+    ///
+    /// Documents need not contain every keyword, but those containing more
+    /// (and rarer) keywords rank higher. Keywords not in the index match
+    /// indexed terms they are a prefix of, or else terms within a typo or two
+    /// ("qic" finds "quic"), at a lower weight.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<LocalHit> {
+        let mut query_terms: Vec<String> = terms(query).collect();
+        query_terms.sort();
+        query_terms.dedup();
+        if query_terms.is_empty() || self.entries.is_empty() {
+            return Vec::new();
+        }
+
+        let mut weighted: Vec<(String, f64)> = Vec::new();
+        for term in query_terms {
+            if self.postings.contains_key(&term) {
+                weighted.push((term, 1.0));
+            } else {
+                let expanded = self.expand(&term);
+                weighted.extend(expanded.into_iter().map(|t| (t, FUZZY_WEIGHT)));
+            }
+        }
+
+        let count = self.entries.len() as f64;
+        let average = self.entries.iter().map(|e| e.length).sum::<usize>() as f64 / count;
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+        for (term, weight) in &weighted {
+            let Some(postings) = self.postings.get(term) else {
+                continue;
+            };
+            let matching = postings.len() as f64;
+            let idf = ((count - matching + 0.5) / (matching + 0.5) + 1.0).ln();
+            for &(id, frequency) in postings {
+                let tf = frequency as f64;
+                let norm = 1.0 - B + B * self.entries[id].length as f64 / average.max(1.0);
+                *scores.entry(id).or_default() += weight * idf * tf * (K1 + 1.0) / (tf + K1 * norm);
+            }
+        }
+        let query_terms: Vec<String> = weighted.into_iter().map(|(term, _)| term).collect();
+
+        let mut ranked: Vec<(usize, f64)> = scores.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        ranked
+            .into_iter()
+            .take(limit)
+            .map(|(id, score)| {
+                let entry = &self.entries[id];
+                let (snippet, section) = snippet(&entry.text, &query_terms);
+                LocalHit {
+                    doc: entry.doc.clone(),
+                    score,
+                    snippet,
+                    section,
+                }
+            })
+            .collect()
+    }
+
+    /// Find indexed terms a query term is a prefix of, or else is a typo of
+    fn expand(&self, term: &str) -> Vec<String> {
+        let prefixed: Vec<String> = if term.chars().count() >= MIN_PREFIX_LEN {
+            self.postings
+                .keys()
+                .filter(|t| t.starts_with(term))
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
+        };
+        if !prefixed.is_empty() {
+            return prefixed;
+        }
+        self.postings
+            .keys()
+            .filter(|t| fuzzy::is_close(term, t))
+            .cloned()
+            .collect()
+    }
+
+    /// Suggest indexed documents whose names are close to a misspelled one
+    pub fn suggest(&self, doc: &DocumentType, limit: usize) -> Vec<DocumentType> {
+        fuzzy::suggest_documents(doc, self.entries.iter().map(|e| &e.doc), limit)
+    }
+}
+
+/// Find the numbered sections of a document's text containing words of a query, best first
+///
+/// Sections rank by how many distinct query words they contain, then by
+/// position; words in subsections count towards the subsection only. Each
+/// match carries the section's line with the most query words as snippet.
+pub fn section_matches(text: &str, query: &str) -> Vec<SectionMatch> {
+    let mut query_terms: Vec<String> = terms(query).collect();
+    query_terms.sort();
+    query_terms.dedup();
+    if query_terms.is_empty() {
+        return Vec::new();
+    }
+
+    let lines: Vec<&str> = text.lines().collect();
+    let sections = sections(text);
+    let mut ranked: Vec<(usize, SectionMatch)> = Vec::new();
+    for section in &sections {
+        let Some(anchor) = section.number.as_deref().and_then(SectionAnchor::parse) else {
+            continue;
+        };
+        // The section's own lines, up to its first subsection
+        let end = sections
+            .iter()
+            .map(|s| s.start_line)
+            .filter(|&start| start > section.start_line && start < section.end_line)
+            .min()
+            .unwrap_or(section.end_line)
+            .min(lines.len());
+        let mut found = vec![false; query_terms.len()];
+        let mut best: Option<(usize, &str)> = None;
+        for line in lines[section.start_line.min(end)..end]
+            .iter()
+            .filter(|line| !is_page_artifact(line))
+        {
+            let line_terms: Vec<String> = terms(line).collect();
+            let mut matched = 0;
+            for (i, term) in query_terms.iter().enumerate() {
+                if line_terms.contains(term) {
+                    found[i] = true;
+                    matched += 1;
+                }
+            }
+            if matched > best.map_or(0, |(m, _)| m) {
+                best = Some((matched, line));
+            }
+        }
+        if let Some((_, line)) = best {
+            let found = found.iter().filter(|f| **f).count();
+            let snippet = line.trim().chars().take(SNIPPET_LEN).collect();
+            ranked.push((
+                found,
+                SectionMatch {
+                    anchor,
+                    snippet: Some(snippet),
+                },
+            ));
+        }
+    }
+    // Stable, so equally good sections stay in document order
+    ranked.sort_by_key(|(found, _)| std::cmp::Reverse(*found));
+    ranked.into_iter().map(|(_, m)| m).collect()
+}
+
+/// Split text into lowercase alphanumeric terms, skipping single characters
+fn terms(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().nth(1).is_some())
+        .map(str::to_lowercase)
+}
+
+/// Find the line with the most distinct query terms, and the section it is in
+fn snippet(text: &str, query_terms: &[String]) -> (String, Option<String>) {
+    let best = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !is_page_artifact(line))
+        .map(|(i, line)| {
+            let line_terms: Vec<String> = terms(line).collect();
+            let matched = query_terms
+                .iter()
+                .filter(|t| line_terms.contains(t))
+                .count();
+            (i, line, matched)
+        })
+        .filter(|(_, _, matched)| *matched > 0)
+        // Keep the first of equally good lines (max_by_key would keep the last)
+        .fold(
+            None,
+            |best: Option<(usize, &str, usize)>, candidate| match best {
+                Some(b) if b.2 >= candidate.2 => Some(b),
+                _ => Some(candidate),
+            },
+        );
+    let Some((line_index, line, _)) = best else {
+        return (String::new(), None);
+    };
+
+    let snippet: String = line.trim().chars().take(SNIPPET_LEN).collect();
+    let section = sections(text)
+        .into_iter()
+        .filter(|s| s.start_line <= line_index && line_index < s.end_line)
+        .max_by_key(|s| s.level)
+        .map(|s| s.label());
+    (snippet, section)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUIC: &str = "\
+1.  Introduction
+
+   QUIC is a secure general-purpose transport protocol.
+
+2.  Streams
+
+   Streams in QUIC provide a lightweight, ordered byte-stream
+   abstraction to an application.
+";
+
+    const TLS: &str = "\
+1.  Introduction
+
+   TLS allows client/server applications to communicate securely.
+   This document describes the TLS handshake protocol.
+";
+
+    fn index() -> LocalIndex {
+        let mut index = LocalIndex::new();
+        index.add(DocumentType::Rfc(9000), QUIC.to_string());
+        index.add(DocumentType::Rfc(8446), TLS.to_string());
+        index
+    }
+
+    #[test]
+    fn test_ranked_search() {
+        let index = index();
+        assert_eq!(index.len(), 2);
+
+        let hits = index.search("QUIC streams", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].doc, DocumentType::Rfc(9000));
+        assert_eq!(
+            hits[0].snippet,
+            "Streams in QUIC provide a lightweight, ordered byte-stream"
+        );
+        assert_eq!(hits[0].section.as_deref(), Some("Section 2"));
+
+        let hits = index.search("tls protocol", 10);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].doc, DocumentType::Rfc(8446));
+        assert!(hits[0].score > hits[1].score);
+        assert_eq!(
+            hits[0].snippet,
+            "This document describes the TLS handshake protocol."
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_search() {
+        let index = index();
+        let hits = index.search("qic transport", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].doc, DocumentType::Rfc(9000));
+        assert_eq!(
+            hits[0].snippet,
+            "QUIC is a secure general-purpose transport protocol."
+        );
+
+        // Prefixes match whole terms, at a lower weight than exact terms
+        let prefixed = index.search("handsh", 10);
+        assert_eq!(prefixed[0].doc, DocumentType::Rfc(8446));
+        assert!(prefixed[0].score < index.search("handshake", 10)[0].score);
+
+        let typo = DocumentType::Rfc(900);
+        assert_eq!(index.suggest(&typo, 5), vec![DocumentType::Rfc(9000)]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut index = index();
+        assert!(index.remove(&DocumentType::Rfc(9000)));
+        assert!(!index.remove(&DocumentType::Rfc(9000)));
+        assert_eq!(index.len(), 1);
+        assert!(index.search("quic", 10).is_empty());
+        assert_eq!(index.search("protocol", 10)[0].doc, DocumentType::Rfc(8446));
+
+        // Entries after the removed one keep their postings
+        index.add(DocumentType::Rfc(9000), QUIC.to_string());
+        assert!(index.remove(&DocumentType::Rfc(8446)));
+        assert_eq!(index.search("streams", 10)[0].doc, DocumentType::Rfc(9000));
+        assert!(index.search("handshake", 10).is_empty());
+    }
+
+    #[test]
+    fn test_build_from_cache() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        let quic = DocumentType::Rfc(9000);
+        cache.store_document(&quic, Format::Text, QUIC).unwrap();
+        let tls = DocumentType::Rfc(8446);
+        cache.store_document(&tls, Format::Text, TLS).unwrap();
+        let stored = cache.access_count(&quic);
+
+        let index = LocalIndex::build(&cache);
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.search("quic", 10)[0].doc, quic);
+        assert_eq!(index.search("handshake", 10)[0].doc, tls);
+        // Built on several threads, but ranked as if documents were added in order
+        let mut sequential = LocalIndex::new();
+        sequential.add(tls.clone(), TLS.to_string());
+        sequential.add(quic.clone(), QUIC.to_string());
+        assert_eq!(
+            index.search("introduction", 10),
+            sequential.search("introduction", 10)
+        );
+        // Indexing doesn't make documents look recently used
+        assert_eq!(cache.access_count(&quic), stored);
+    }
+
+    #[test]
+    fn test_section_matches() {
+        let matches = section_matches(QUIC, "QUIC streams");
+        let anchors: Vec<String> = matches
+            .iter()
+            .map(|m| m.anchor.number().to_string())
+            .collect();
+        assert_eq!(anchors, vec!["2", "1"]);
+        assert_eq!(
+            matches[0].snippet.as_deref(),
+            Some("Streams in QUIC provide a lightweight, ordered byte-stream")
+        );
+        assert_eq!(
+            matches[1].snippet.as_deref(),
+            Some("QUIC is a secure general-purpose transport protocol.")
+        );
+
+        assert!(section_matches(QUIC, "bgp").is_empty());
+        assert!(section_matches(QUIC, "").is_empty());
+    }
+
+    #[test]
+    fn test_no_matches() {
+        let index = index();
+        assert!(index.search("bgp", 10).is_empty());
+        assert!(index.search("", 10).is_empty());
+        assert!(LocalIndex::new().search("quic", 10).is_empty());
+    }
+}
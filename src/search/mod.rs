@@ -0,0 +1,4 @@
+pub mod fuzzy;
+pub mod local;
+
+pub use local::{section_matches, LocalHit, LocalIndex};
@@ -0,0 +1,447 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::analyze::define;
+use crate::api::DocumentSource;
+use crate::cache::CacheManager;
+use crate::models::{DocumentType, Format};
+use crate::parse::{cross_references, section_text, ParsedDocument};
+use crate::search::LocalIndex;
+
+/// JSON-RPC error code for malformed requests
+const PARSE_ERROR: i64 = -32700;
+/// JSON-RPC error code for unknown methods
+const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC error code for missing or malformed parameters
+const INVALID_PARAMS: i64 = -32602;
+/// Error code for operations that failed (e.g., a fetch error)
+const OPERATION_FAILED: i64 = -32000;
+
+/// A JSON-RPC 2.0 request
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcRequest {
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// A JSON-RPC 2.0 error object
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+/// A JSON-RPC 2.0 response
+#[derive(Debug, Clone, Serialize)]
+pub struct RpcResponse {
+    pub jsonrpc: &'static str,
+    pub id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    fn new(id: Value, result: std::result::Result<Value, RpcError>) -> Self {
+        let (result, error) = match result {
+            Ok(value) => (Some(value), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+/// Long-running lookup service for editor integrations
+///
+/// Reads one JSON-RPC request per line and answers `resolve`, `fetch`,
+/// `section`, `citation`, `define` and `search` calls. Documents stay in memory once loaded, so
+/// repeated lookups don't touch the disk or network. Searches go to the
+/// [`Daemon`](crate::daemon::Daemon) owning the cache when one is running,
+/// instead of indexing the cache in this process.
+pub struct LookupService {
+    cache: CacheManager,
+    /// Where documents missing from the cache are fetched
+    source: Arc<dyn DocumentSource>,
+    loaded: Mutex<HashMap<DocumentType, (String, ParsedDocument)>>,
+    /// Full-text index of the cache, built on the first search
+    index: RwLock<Option<Arc<LocalIndex>>>,
+    /// Ask a running daemon before building an index
+    delegate: bool,
+}
+
+impl LookupService {
+    /// Create a service fetching documents missing from `cache` from `source`
+    /// (e.g., a [`DocumentFetcher`](crate::DocumentFetcher))
+    pub fn new(cache: CacheManager, source: impl DocumentSource + 'static) -> Self {
+        Self {
+            cache,
+            source: Arc::new(source),
+            loaded: Mutex::new(HashMap::new()),
+            index: RwLock::new(None),
+            delegate: true,
+        }
+    }
+
+    /// Never delegate searches, for the daemon's own service
+    pub(crate) fn without_delegation(mut self) -> Self {
+        self.delegate = false;
+        self
+    }
+
+    /// Replace the full-text index searched by `search` calls
+    pub fn set_index(&self, index: LocalIndex) {
+        *self.index.write().unwrap() = Some(Arc::new(index));
+    }
+
+    /// Serve requests until the reader is exhausted
+    pub async fn run<R, W>(&self, reader: R, mut writer: W) -> crate::Result<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut lines = reader.lines();
+        while let Some(line) = lines.next_line().await.context("Failed to read request")? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = self.handle_line(&line).await;
+            let mut json =
+                serde_json::to_string(&response).context("Failed to serialize response")?;
+            json.push('\n');
+            writer
+                .write_all(json.as_bytes())
+                .await
+                .context("Failed to write response")?;
+            writer.flush().await.context("Failed to write response")?;
+        }
+        Ok(())
+    }
+
+    /// Answer a single request line
+    pub async fn handle_line(&self, line: &str) -> RpcResponse {
+        match serde_json::from_str::<RpcRequest>(line) {
+            Ok(request) => self.handle(request).await,
+            Err(e) => RpcResponse::new(
+                Value::Null,
+                Err(RpcError {
+                    code: PARSE_ERROR,
+                    message: format!("Invalid request: {}", e),
+                }),
+            ),
+        }
+    }
+
+    /// Answer a parsed request
+    pub async fn handle(&self, request: RpcRequest) -> RpcResponse {
+        let result = match request.method.as_str() {
+            "resolve" => self.resolve(&request.params),
+            "fetch" => self.fetch(&request.params).await,
+            "section" => self.section(&request.params).await,
+            "citation" => self.citation(&request.params).await,
+            "define" => self.define(&request.params).await,
+            "search" => self.search(&request.params).await,
+            method => Err(RpcError {
+                code: METHOD_NOT_FOUND,
+                message: format!("Unknown method '{}'", method),
+            }),
+        };
+        RpcResponse::new(request.id, result)
+    }
+
+    /// Normalize a document identifier and report where it can be found
+    fn resolve(&self, params: &Value) -> std::result::Result<Value, RpcError> {
+        let doc = document_param(params)?;
+        let formats: Vec<&str> = self
+            .cache
+            .cached_formats(&doc)
+            .iter()
+            .map(|f| f.extension())
+            .collect();
+        Ok(json!({
+            "name": doc.name(),
+            "label": doc.to_string(),
+            "url": doc.datatracker_url(),
+            "cached": formats,
+        }))
+    }
+
+    /// Get the full text of a document
+    async fn fetch(&self, params: &Value) -> std::result::Result<Value, RpcError> {
+        let doc = document_param(params)?;
+        let (text, _) = self.load(&doc).await.map_err(failed)?;
+        Ok(json!({ "name": doc.name(), "content": text }))
+    }
+
+    /// Get a single section of a document by number
+    async fn section(&self, params: &Value) -> std::result::Result<Value, RpcError> {
+        let doc = document_param(params)?;
+        let number = string_param(params, "section")?;
+        let (text, parsed) = self.load(&doc).await.map_err(failed)?;
+        let section = parsed.section(&number).ok_or_else(|| RpcError {
+            code: OPERATION_FAILED,
+            message: format!("{} has no Section {}", doc, number),
+        })?;
+        Ok(json!({
+            "number": section.number,
+            "title": section.title,
+            "text": section_text(&text, section),
+        }))
+    }
+
+    /// Resolve citation text like "Section 4 of [RFC9001]" to its target
+    async fn citation(&self, params: &Value) -> std::result::Result<Value, RpcError> {
+        let text = string_param(params, "text")?;
+        let (reference, doc) = cross_references(&text)
+            .into_iter()
+            .find_map(|r| r.document.clone().map(|doc| (r, doc)))
+            .ok_or_else(|| RpcError {
+                code: INVALID_PARAMS,
+                message: format!("No citation found in '{}'", text),
+            })?;
+
+        let mut result = json!({
+            "label": reference.label,
+            "name": doc.name(),
+            "document": doc.to_string(),
+            "url": doc.datatracker_url(),
+        });
+        if let Some(number) = &reference.section {
+            let (text, parsed) = self.load(&doc).await.map_err(failed)?;
+            if let Some(section) = parsed.section(number) {
+                result["section"] = json!({
+                    "number": section.number,
+                    "title": section.title,
+                    "text": section_text(&text, section),
+                });
+            }
+        }
+        Ok(result)
+    }
+
+    /// Look up a term's definition in the given documents, or in all cached ones
+    async fn define(&self, params: &Value) -> std::result::Result<Value, RpcError> {
+        let term = string_param(params, "term")?;
+        let docs = match params.get("documents").and_then(Value::as_array) {
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    let name = name.as_str().unwrap_or_default();
+                    DocumentType::parse(name).ok_or_else(|| RpcError {
+                        code: INVALID_PARAMS,
+                        message: format!("Invalid document identifier '{}'", name),
+                    })
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()?,
+            None => self.cache.list_cached(),
+        };
+
+        let mut texts = Vec::with_capacity(docs.len());
+        for doc in docs {
+            let (text, _) = self.load(&doc).await.map_err(failed)?;
+            texts.push((doc, text));
+        }
+        let definitions = define(&term, texts.iter().map(|(d, t)| (d, t.as_str())));
+        serde_json::to_value(definitions).map_err(|e| failed(e.into()))
+    }
+
+    /// Rank cached documents for a keyword query
+    async fn search(&self, params: &Value) -> std::result::Result<Value, RpcError> {
+        let query = string_param(params, "query")?;
+        let limit = params.get("limit").and_then(Value::as_u64).unwrap_or(20) as usize;
+        let index = self.index.read().unwrap().clone();
+        #[cfg(unix)]
+        if index.is_none() && self.delegate {
+            if let Some(mut client) = crate::daemon::DaemonClient::connect(&self.cache).await {
+                if let Ok(hits) = client.search(&query, limit).await {
+                    return serde_json::to_value(hits).map_err(|e| failed(e.into()));
+                }
+            }
+        }
+        let index = match index {
+            Some(index) => index,
+            None => {
+                let cache = self.cache.clone();
+                let index = tokio::task::spawn_blocking(move || LocalIndex::build(&cache))
+                    .await
+                    .map_err(|e| failed(e.into()))?;
+                let index = Arc::new(index);
+                *self.index.write().unwrap() = Some(index.clone());
+                index
+            }
+        };
+        serde_json::to_value(index.search(&query, limit)).map_err(|e| failed(e.into()))
+    }
+
+    /// Get a document's text and structure from memory, the cache or the network
+    async fn load(&self, doc: &DocumentType) -> Result<(String, ParsedDocument)> {
+        if let Some(loaded) = self.loaded.lock().unwrap().get(doc) {
+            return Ok(loaded.clone());
+        }
+
+        let text = match self.cache.get_fresh(doc, Format::Text) {
+            Some(text) => text,
+            None => {
+                let (content, format) = self.source.fetch(doc).await?;
+                let text = crate::render::to_text(content, format, 80)?;
+                self.cache.store_document(doc, Format::Text, &text)?;
+                text
+            }
+        };
+        let parsed = match self.cache.parsed_document(doc)? {
+            Some(parsed) => parsed,
+            None => ParsedDocument::parse(&text),
+        };
+
+        let loaded = (text, parsed);
+        self.loaded
+            .lock()
+            .unwrap()
+            .insert(doc.clone(), loaded.clone());
+        Ok(loaded)
+    }
+}
+
+fn string_param(params: &Value, key: &str) -> std::result::Result<String, RpcError> {
+    params
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| RpcError {
+            code: INVALID_PARAMS,
+            message: format!("Missing string parameter '{}'", key),
+        })
+}
+
+fn document_param(params: &Value) -> std::result::Result<DocumentType, RpcError> {
+    let document = string_param(params, "document")?;
+    DocumentType::parse(&document).ok_or_else(|| RpcError {
+        code: INVALID_PARAMS,
+        message: format!("Invalid document identifier '{}'", document),
+    })
+}
+
+fn failed(error: anyhow::Error) -> RpcError {
+    RpcError {
+        code: OPERATION_FAILED,
+        message: format!("{:#}", error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::FixtureSource;
+    use tempfile::TempDir;
+
+    const TEXT: &str = "1.  Introduction\n\n   Text.\n\n\
+4.  Key Update\n\n   Keys are updated.\n\n5.  References\n";
+
+    fn service() -> (LookupService, TempDir) {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheManager::with_dir(temp.path().to_path_buf()).unwrap();
+        cache
+            .store_document(&DocumentType::Rfc(9001), Format::Text, TEXT)
+            .unwrap();
+        // Documents missing from the cache come from fixtures, not the network
+        let source = FixtureSource::new().with_document(
+            DocumentType::Rfc(9000),
+            Format::Text,
+            "1.  Introduction\n\n   QUIC.\n",
+        );
+        (LookupService::new(cache, source), temp)
+    }
+
+    #[test]
+    fn test_requests() {
+        let (service, _temp) = service();
+        let call = |line: &str| {
+            let response = tokio_test::block_on(service.handle_line(line));
+            serde_json::to_value(response).unwrap()
+        };
+
+        let resolved =
+            call(r#"{"jsonrpc":"2.0","id":1,"method":"resolve","params":{"document":"rfc9001"}}"#);
+        assert_eq!(resolved["id"], 1);
+        assert_eq!(resolved["result"]["label"], "RFC 9001");
+        assert_eq!(resolved["result"]["cached"], json!(["txt"]));
+
+        let section =
+            call(r#"{"id":2,"method":"section","params":{"document":"9001","section":"4"}}"#);
+        assert_eq!(section["result"]["title"], "Key Update");
+        assert_eq!(
+            section["result"]["text"],
+            "4.  Key Update\n\n   Keys are updated."
+        );
+
+        let citation =
+            call(r#"{"id":3,"method":"citation","params":{"text":"see Section 4 of [RFC9001]"}}"#);
+        assert_eq!(citation["result"]["document"], "RFC 9001");
+        assert_eq!(citation["result"]["section"]["title"], "Key Update");
+
+        let define = call(r#"{"id":4,"method":"define","params":{"term":"key"}}"#);
+        assert_eq!(define["result"], json!([]));
+
+        let search = call(r#"{"id":5,"method":"search","params":{"query":"keys updated"}}"#);
+        assert_eq!(search["result"][0]["doc"], json!({ "Rfc": 9001 }));
+        assert_eq!(search["result"][0]["section"], "Section 4");
+
+        let fetched = call(r#"{"id":6,"method":"fetch","params":{"document":"9000"}}"#);
+        assert_eq!(
+            fetched["result"]["content"],
+            "1.  Introduction\n\n   QUIC.\n"
+        );
+        let missing = call(r#"{"id":7,"method":"fetch","params":{"document":"9002"}}"#);
+        assert_eq!(missing["error"]["message"], "RFC 9002 not found");
+    }
+
+    #[test]
+    fn test_errors() {
+        let (service, _temp) = service();
+        let call = |line: &str| {
+            let response = tokio_test::block_on(service.handle_line(line));
+            serde_json::to_value(response).unwrap()
+        };
+
+        assert_eq!(call("not json")["error"]["code"], PARSE_ERROR);
+        assert_eq!(
+            call(r#"{"id":1,"method":"hover"}"#)["error"]["code"],
+            METHOD_NOT_FOUND
+        );
+        assert_eq!(
+            call(r#"{"id":1,"method":"fetch","params":{}}"#)["error"]["code"],
+            INVALID_PARAMS
+        );
+        let missing =
+            call(r#"{"id":1,"method":"section","params":{"document":"9001","section":"9"}}"#);
+        assert_eq!(missing["error"]["message"], "RFC 9001 has no Section 9");
+        assert!(missing.get("result").is_none());
+    }
+
+    #[test]
+    fn test_run() {
+        let (service, _temp) = service();
+        let input = b"{\"id\":1,\"method\":\"fetch\",\"params\":{\"document\":\"9001\"}}\n\n{\"id\":2,\"method\":\"x\"}\n";
+        let mut output = Vec::new();
+
+        tokio_test::block_on(service.run(&input[..], &mut output)).unwrap();
+        let lines: Vec<&str> = std::str::from_utf8(&output).unwrap().lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        let first: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["result"]["content"], TEXT);
+    }
+}
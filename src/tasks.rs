@@ -0,0 +1,315 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+
+use crate::hooks::{Hook, HookEvent};
+use crate::models::DocumentType;
+use crate::queue::{QueueEntry, ReadingQueue, ReadingState};
+
+/// Formats of personal task managers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskFormat {
+    /// One task per line, see <https://github.com/todotxt/todo.txt>
+    TodoTxt,
+    /// Org-mode headings with TODO keywords
+    Org,
+    /// Markdown checklist items
+    Markdown,
+}
+
+impl TaskFormat {
+    /// Parse a format name ("todo.txt", "org", "markdown")
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "todo" | "todo.txt" | "todotxt" | "txt" => Some(TaskFormat::TodoTxt),
+            "org" | "org-mode" => Some(TaskFormat::Org),
+            "md" | "markdown" => Some(TaskFormat::Markdown),
+            _ => None,
+        }
+    }
+
+    /// Pick the format from a file extension, falling back to todo.txt
+    pub fn from_path(path: &Path) -> Self {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| match ext {
+                "org" => Some(TaskFormat::Org),
+                "md" | "markdown" => Some(TaskFormat::Markdown),
+                _ => None,
+            })
+            .unwrap_or(TaskFormat::TodoTxt)
+    }
+}
+
+/// Something to do about a document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Task {
+    pub doc: DocumentType,
+    /// What to do (e.g., "Read RFC 9110")
+    pub description: String,
+    pub state: ReadingState,
+    pub created: DateTime<Utc>,
+    /// When the task was done, if it is
+    pub completed: Option<DateTime<Utc>>,
+}
+
+impl From<&QueueEntry> for Task {
+    fn from(entry: &QueueEntry) -> Self {
+        Self {
+            doc: entry.doc.clone(),
+            description: format!("Read {}", entry.doc),
+            state: entry.state,
+            created: entry.added,
+            completed: entry.finished.filter(|_| entry.state == ReadingState::Done),
+        }
+    }
+}
+
+impl Task {
+    /// Turn an event worth acting on into a task
+    ///
+    /// New draft revisions are to be read and changed RFCs to be checked;
+    /// downloads and evictions need nothing done.
+    pub fn from_event(event: &HookEvent, now: DateTime<Utc>) -> Option<Self> {
+        let (doc, description) = match event {
+            HookEvent::NewRevision {
+                doc,
+                previous,
+                revision,
+            } => {
+                let latest = DocumentType::Draft(format!("{}-{:02}", doc.name(), revision));
+                let description = format!("Read {} (changed since -{:02})", latest, previous);
+                (latest, description)
+            }
+            HookEvent::ContentChanged { change } => (
+                change.doc.clone(),
+                format!("Check {}, which changed at {}", change.doc, change.url),
+            ),
+            HookEvent::Fetched { .. } | HookEvent::Evicted { .. } => return None,
+        };
+        Some(Self {
+            doc,
+            description,
+            state: ReadingState::ToRead,
+            created: now,
+            completed: None,
+        })
+    }
+
+    /// Write the task as one entry of a task list
+    pub fn write(&self, format: TaskFormat, mut out: impl Write) -> crate::Result<()> {
+        let url = self.doc.datatracker_url();
+        let entry = match format {
+            TaskFormat::TodoTxt => {
+                let prefix = match (self.state, self.completed) {
+                    (ReadingState::Done, Some(completed)) => {
+                        format!("x {} ", completed.format("%Y-%m-%d"))
+                    }
+                    (ReadingState::Done, None) => "x ".to_string(),
+                    (ReadingState::InProgress, _) => "(A) ".to_string(),
+                    (ReadingState::ToRead, _) => String::new(),
+                };
+                format!(
+                    "{}{} {} +rfc doc:{} url:{}\n",
+                    prefix,
+                    self.created.format("%Y-%m-%d"),
+                    self.description,
+                    self.doc.name(),
+                    url
+                )
+            }
+            TaskFormat::Org => {
+                let keyword = match self.state {
+                    ReadingState::ToRead => "TODO",
+                    ReadingState::InProgress => "STARTED",
+                    ReadingState::Done => "DONE",
+                };
+                let mut entry = format!("* {} {}\n", keyword, self.description);
+                if let Some(completed) = self.completed {
+                    entry.push_str(&format!("  CLOSED: {}\n", org_timestamp(completed)));
+                }
+                entry.push_str(&format!(
+                    "  :PROPERTIES:\n  :DOCUMENT: {}\n  :CREATED:  {}\n  :URL:      {}\n  :END:\n",
+                    self.doc.name(),
+                    org_timestamp(self.created),
+                    url
+                ));
+                entry
+            }
+            TaskFormat::Markdown => {
+                let (mark, note) = match self.state {
+                    ReadingState::ToRead => (' ', String::new()),
+                    ReadingState::InProgress => (' ', " (in progress)".to_string()),
+                    ReadingState::Done => match self.completed {
+                        Some(completed) => {
+                            ('x', format!(" (done {})", completed.format("%Y-%m-%d")))
+                        }
+                        None => ('x', String::new()),
+                    },
+                };
+                format!("- [{}] [{}]({}){}\n", mark, self.description, url, note)
+            }
+        };
+        out.write_all(entry.as_bytes())
+            .context("Failed to write task")?;
+        Ok(())
+    }
+}
+
+/// Write a task list, with the header the format needs
+pub fn export_tasks<W: Write>(tasks: &[Task], format: TaskFormat, mut out: W) -> crate::Result<()> {
+    match format {
+        TaskFormat::Org => writeln!(out, "#+TODO: TODO STARTED | DONE\n"),
+        TaskFormat::Markdown => writeln!(out, "# Reading list\n"),
+        TaskFormat::TodoTxt => Ok(()),
+    }
+    .context("Failed to write task list")?;
+    for task in tasks {
+        task.write(format, &mut out)?;
+    }
+    Ok(out.flush().context("Failed to write task list")?)
+}
+
+impl ReadingQueue {
+    /// Get a task for every queued document, in the order they were added
+    pub fn tasks(&self) -> Vec<Task> {
+        self.entries().iter().map(Task::from).collect()
+    }
+}
+
+/// Appends a task to a task list for every event worth acting on
+///
+/// Configured with `RFC_TASKS`, e.g. `~/todo.txt` or `~/notes/rfc.org`; the
+/// format follows the file extension.
+#[derive(Debug, Clone)]
+pub struct TaskHook {
+    path: PathBuf,
+    format: TaskFormat,
+}
+
+impl TaskHook {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let format = TaskFormat::from_path(&path);
+        Self { path, format }
+    }
+
+    pub fn with_format(mut self, format: TaskFormat) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+impl Hook for TaskHook {
+    fn on_event(&self, event: &HookEvent) -> Result<()> {
+        let Some(task) = Task::from_event(event, Utc::now()) else {
+            return Ok(());
+        };
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open {}", self.path.display()))?;
+        Ok(task.write(self.format, file)?)
+    }
+}
+
+/// Format a timestamp as an inactive org-mode timestamp
+fn org_timestamp(time: DateTime<Utc>) -> String {
+    time.format("[%Y-%m-%d %a %H:%M]").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn tasks() -> Vec<Task> {
+        let mut reading = QueueEntry::new(DocumentType::Rfc(9110), at("2026-10-01T08:00:00Z"));
+        reading.state = ReadingState::InProgress;
+        let mut read = QueueEntry::new(DocumentType::Rfc(9111), at("2026-10-01T08:00:00Z"));
+        read.state = ReadingState::Done;
+        read.finished = Some(at("2026-10-14T18:00:00Z"));
+        vec![Task::from(&reading), Task::from(&read)]
+    }
+
+    fn export(format: TaskFormat) -> String {
+        let mut out = Vec::new();
+        export_tasks(&tasks(), format, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_todo_txt() {
+        assert_eq!(
+            export(TaskFormat::TodoTxt),
+            "(A) 2026-10-01 Read RFC 9110 +rfc doc:rfc9110 \
+             url:https://datatracker.ietf.org/doc/rfc9110/\n\
+             x 2026-10-14 2026-10-01 Read RFC 9111 +rfc doc:rfc9111 \
+             url:https://datatracker.ietf.org/doc/rfc9111/\n"
+        );
+    }
+
+    #[test]
+    fn test_org() {
+        let org = export(TaskFormat::Org);
+        let lines: Vec<&str> = org.lines().collect();
+        assert_eq!(lines[0], "#+TODO: TODO STARTED | DONE");
+        assert_eq!(lines[2], "* STARTED Read RFC 9110");
+        assert_eq!(lines[5], "  :CREATED:  [2026-10-01 Thu 08:00]");
+        assert_eq!(lines[8], "* DONE Read RFC 9111");
+        assert_eq!(lines[9], "  CLOSED: [2026-10-14 Wed 18:00]");
+    }
+
+    #[test]
+    fn test_markdown() {
+        let markdown = export(TaskFormat::Markdown);
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert_eq!(
+            lines[2],
+            "- [ ] [Read RFC 9110](https://datatracker.ietf.org/doc/rfc9110/) (in progress)"
+        );
+        assert_eq!(
+            lines[3],
+            "- [x] [Read RFC 9111](https://datatracker.ietf.org/doc/rfc9111/) (done 2026-10-14)"
+        );
+    }
+
+    #[test]
+    fn test_hook_appends_events() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("todo.txt");
+        let hook = TaskHook::new(&path);
+        let draft = DocumentType::Draft("draft-ietf-quic-qlog".to_string());
+
+        hook.on_event(&HookEvent::Evicted { doc: draft.clone() })
+            .unwrap();
+        assert!(!path.exists());
+        for revision in [4, 5] {
+            let event = HookEvent::NewRevision {
+                doc: draft.clone(),
+                previous: revision - 1,
+                revision,
+            };
+            hook.on_event(&event).unwrap();
+        }
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].contains(" Read draft-ietf-quic-qlog-05 (changed since -04) +rfc "));
+
+        assert_eq!(TaskFormat::from_path(Path::new("rfc.org")), TaskFormat::Org);
+        assert_eq!(TaskFormat::parse("Markdown"), Some(TaskFormat::Markdown));
+    }
+}
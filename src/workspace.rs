@@ -0,0 +1,190 @@
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+use serde::Deserialize;
+
+use crate::cache::{Manifest, ManifestEntry};
+
+/// Name of the workspace configuration file
+pub const WORKSPACE_FILE: &str = ".rfc.toml";
+
+/// Project-local configuration, discovered by walking up from the working directory
+///
+/// ```toml
+/// # Relative paths are resolved against the directory of .rfc.toml; the
+/// # cache directory must be a subdirectory of it
+/// cache_dir = ".rfc-cache"
+/// manifest = "specs/manifest.toml"
+///
+/// # Preferred documents, merged into the manifest
+/// documents = ["9000", "9001", "draft-ietf-quic-qlog-main-schema"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Workspace {
+    /// Directory holding `.rfc.toml`
+    #[serde(skip)]
+    root: PathBuf,
+    /// Cache directory used instead of the user's cache
+    #[serde(default)]
+    cache_dir: Option<PathBuf>,
+    /// Manifest file extending the inline documents and collections
+    #[serde(default)]
+    manifest: Option<PathBuf>,
+    /// Documents and collections listed inline
+    #[serde(flatten)]
+    inline: Manifest,
+}
+
+impl Workspace {
+    /// Parse a workspace configuration whose file lives in `root`
+    pub fn from_toml(s: &str, root: &Path) -> crate::Result<Self> {
+        let mut workspace: Self =
+            toml::from_str(s).with_context(|| format!("Failed to parse {}", WORKSPACE_FILE))?;
+        if let Some(dir) = &workspace.cache_dir {
+            if !is_subdirectory(dir) {
+                return Err(anyhow!(
+                    "cache_dir '{}' must be a subdirectory of the workspace",
+                    dir.display()
+                )
+                .into());
+            }
+        }
+        workspace.root = root.to_path_buf();
+        Ok(workspace)
+    }
+
+    /// Load a workspace configuration file
+    pub fn load(path: &Path) -> crate::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let root = path.parent().unwrap_or(Path::new("."));
+        Ok(Self::from_toml(&content, root)
+            .with_context(|| format!("Invalid workspace configuration {}", path.display()))?)
+    }
+
+    /// Find the nearest `.rfc.toml` in `start` or one of its parents
+    pub fn discover(start: &Path) -> crate::Result<Option<Self>> {
+        start
+            .ancestors()
+            .map(|dir| dir.join(WORKSPACE_FILE))
+            .find(|path| path.is_file())
+            .map(|path| Self::load(&path))
+            .transpose()
+    }
+
+    /// Find the workspace of the current working directory, if any
+    pub fn current() -> crate::Result<Option<Self>> {
+        let cwd = std::env::current_dir().context("Failed to get working directory")?;
+        Self::discover(&cwd)
+    }
+
+    /// Get the cache directory pinned by the workspace of `start`, if any
+    pub fn pinned_cache_dir(start: &Path) -> crate::Result<Option<PathBuf>> {
+        Ok(Self::discover(start)?.and_then(|w| w.cache_dir()))
+    }
+
+    /// Get the directory holding `.rfc.toml`
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Get the pinned cache directory, resolved against the workspace root
+    pub fn cache_dir(&self) -> Option<PathBuf> {
+        self.cache_dir.as_ref().map(|dir| self.root.join(dir))
+    }
+
+    /// Get the preferred documents listed inline
+    pub fn documents(&self) -> &[ManifestEntry] {
+        &self.inline.documents
+    }
+
+    /// Get the workspace manifest: the manifest file, if any, plus the inline entries
+    pub fn manifest(&self) -> crate::Result<Manifest> {
+        let mut manifest = match &self.manifest {
+            Some(path) => Manifest::load(&self.root.join(path))?,
+            None => Manifest::default(),
+        };
+        manifest.documents.extend(self.inline.documents.clone());
+        manifest.collections.extend(self.inline.collections.clone());
+        Ok(manifest)
+    }
+}
+
+/// Check that a relative path names a directory below the one it is relative to
+fn is_subdirectory(path: &Path) -> bool {
+    let mut depth = 0;
+    for component in path.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => return false,
+        }
+    }
+    depth > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DocumentType, Format};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_discover_walks_up() {
+        let temp = TempDir::new().unwrap();
+        let nested = temp.path().join("src").join("quic");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(Workspace::discover(&nested).unwrap(), None);
+
+        std::fs::write(
+            temp.path().join(WORKSPACE_FILE),
+            "cache_dir = \".rfc-cache\"\ndocuments = [\"9000\"]\n",
+        )
+        .unwrap();
+        let workspace = Workspace::discover(&nested).unwrap().unwrap();
+        assert_eq!(workspace.root(), temp.path());
+        assert_eq!(workspace.cache_dir(), Some(temp.path().join(".rfc-cache")));
+        assert_eq!(
+            workspace.documents(),
+            &[ManifestEntry::Name("9000".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_manifest_merges_inline_entries() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("specs.toml"), "documents = [\"9001\"]\n").unwrap();
+        let workspace = Workspace::from_toml(
+            "manifest = \"specs.toml\"\ndocuments = [\"9000\"]",
+            temp.path(),
+        )
+        .unwrap();
+        assert_eq!(workspace.cache_dir(), None);
+        assert_eq!(
+            workspace.manifest().unwrap().entries().unwrap(),
+            vec![
+                (DocumentType::Rfc(9001), Format::Text),
+                (DocumentType::Rfc(9000), Format::Text),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_configuration() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join(WORKSPACE_FILE), "cache_dir = 5").unwrap();
+        assert!(Workspace::discover(temp.path()).is_err());
+        assert!(Workspace::pinned_cache_dir(temp.path()).is_err());
+    }
+
+    #[test]
+    fn test_cache_dir_stays_in_workspace() {
+        let root = Path::new("/project");
+        for dir in [".", "./", "..", "cache/../..", "/var/cache/rfc", ""] {
+            let toml = format!("cache_dir = {:?}", dir);
+            assert!(Workspace::from_toml(&toml, root).is_err(), "{}", dir);
+        }
+        let workspace = Workspace::from_toml("cache_dir = \"./build/rfc\"", root).unwrap();
+        assert_eq!(workspace.cache_dir(), Some(root.join("./build/rfc")));
+    }
+}